@@ -0,0 +1,113 @@
+//! ストアのバックエンドごとのエンジン処理のベンチマーク
+//!
+//! `cargo bench --features test-util` で実行する。データは `fixtures` でシードから生成するため、
+//! マシンをまたいでも同じデータで比較できる
+
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use norimaki_db::fixtures::{generate_schedule, seed_engine, seed_months, DirStore, SyntheticRace, TempDir, SEED_START_MONTH};
+use norimaki_db::{generate_tournament_id, BoatRaceEngine, MemoryStore};
+
+const SEED: u64 = 2025;
+/// 読み取り系のベンチマークで事前に書き込むデータの規模
+const MONTHS: usize = 6;
+const TOURNAMENTS_PER_MONTH: usize = 30;
+const RACES_PER_TOURNAMENT: usize = 48;
+/// 一括取り込みで書き込む月数
+const IMPORT_MONTHS: usize = 12;
+
+/// ベンチマークするバックエンドごとに `$bench::<ストア>(group)` を呼ぶ
+///
+/// バックエンドを追加する場合は、`DirStore` を実装してここに1行足す
+macro_rules! for_each_backend {
+    ($bench:ident, $group:expr) => {
+        $bench::<MemoryStore>($group);
+        $bench::<norimaki_db::CompactMemoryStore>($group);
+        #[cfg(feature = "fs")]
+        $bench::<norimaki_db::FileStore>($group);
+    };
+}
+
+/// 一時ディレクトリに作ったストアに読み取り用のデータを書き込んだエンジン
+fn seeded<S: DirStore>() -> (TempDir, BoatRaceEngine<S>) {
+    let dir = TempDir::new(S::NAME).expect("temp dir");
+    let mut engine = BoatRaceEngine::new(dir.create_store::<S>().expect("store")).with_clock(|| 0);
+    seed_engine(&mut engine, MONTHS, TOURNAMENTS_PER_MONTH, RACES_PER_TOURNAMENT, SEED).expect("seed");
+    (dir, engine)
+}
+
+/// 月別スケジュールの一括取り込み (1か月ずつ `put_monthly_schedule`)
+fn bulk_schedule_import<S: DirStore>(group: &mut BenchmarkGroup<WallTime>) {
+    let schedules: Vec<_> = seed_months(IMPORT_MONTHS)
+        .into_iter()
+        .map(|year_month| generate_schedule(year_month, TOURNAMENTS_PER_MONTH, SEED))
+        .collect();
+    group.bench_function(BenchmarkId::from_parameter(S::NAME), |b| {
+        b.iter_batched(
+            || {
+                let dir = TempDir::new(S::NAME).expect("temp dir");
+                let engine = BoatRaceEngine::new(dir.create_store::<S>().expect("store")).with_clock(|| 0);
+                (dir, engine)
+            },
+            |(dir, mut engine)| {
+                for schedule in &schedules {
+                    engine.put_monthly_schedule(schedule).expect("import");
+                }
+                (dir, engine)
+            },
+            BatchSize::PerIteration,
+        )
+    });
+}
+
+/// 1か月分のスケジュールの読み取り
+fn single_month_read<S: DirStore>(group: &mut BenchmarkGroup<WallTime>) {
+    let (_dir, mut engine) = seeded::<S>();
+    group.bench_function(BenchmarkId::from_parameter(S::NAME), |b| {
+        b.iter(|| engine.get_monthly_schedule(SEED_START_MONTH).expect("read"))
+    });
+}
+
+/// 1大会分のレースデータのスキャン
+fn tournament_race_scan<S: DirStore>(group: &mut BenchmarkGroup<WallTime>) {
+    let (_dir, mut engine) = seeded::<S>();
+    let event = engine.get_monthly_schedule(SEED_START_MONTH).expect("read").events.remove(0);
+    let tournament_id = generate_tournament_id(&event.venue_name, &event.event_name);
+    group.bench_function(BenchmarkId::from_parameter(S::NAME), |b| {
+        b.iter(|| engine.get_tournament_races::<SyntheticRace>(&tournament_id).expect("scan"))
+    });
+}
+
+/// 全件を走査する統計の集計
+fn statistics<S: DirStore>(group: &mut BenchmarkGroup<WallTime>) {
+    let (_dir, engine) = seeded::<S>();
+    group.bench_function(BenchmarkId::from_parameter(S::NAME), |b| {
+        b.iter(|| engine.get_detailed_statistics().expect("statistics"))
+    });
+}
+
+fn engine_workloads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk_schedule_import");
+    group.throughput(Throughput::Elements((IMPORT_MONTHS * TOURNAMENTS_PER_MONTH) as u64));
+    group.sample_size(10);
+    for_each_backend!(bulk_schedule_import, &mut group);
+    group.finish();
+
+    let mut group = c.benchmark_group("single_month_read");
+    group.throughput(Throughput::Elements(TOURNAMENTS_PER_MONTH as u64));
+    for_each_backend!(single_month_read, &mut group);
+    group.finish();
+
+    let mut group = c.benchmark_group("tournament_race_scan");
+    group.throughput(Throughput::Elements(RACES_PER_TOURNAMENT as u64));
+    for_each_backend!(tournament_race_scan, &mut group);
+    group.finish();
+
+    let mut group = c.benchmark_group("statistics");
+    group.sample_size(20);
+    for_each_backend!(statistics, &mut group);
+    group.finish();
+}
+
+criterion_group!(benches, engine_workloads);
+criterion_main!(benches);
@@ -1,9 +1,9 @@
-/// 競艇データエンジンのデモンストレーション
-/// 
-/// 使用方法: cargo run --example boat_race_demo
+//! 競艇データエンジンのデモンストレーション
+//! 
+//! 使用方法: cargo run --example boat_race_demo
 
 use norimaki_db::{
-    BoatRaceEngine, MemoryStore, FileStore, MonthlySchedule, RaceEvent, 
+    BoatRaceEngine, MemoryStore, FileStore, MonthlySchedule, RaceEvent, Grade,
     Result, generate_tournament_id
 };
 use serde::{Serialize, Deserialize};
@@ -57,12 +57,8 @@ fn demo_memory_operations() -> Result<()> {
     // 2. 月別スケジュールの取得
     println!("📋 2025年9月のスケジュールを取得中...");
     let retrieved = engine.get_monthly_schedule(202509)?;
-    println!("✅ 取得完了: {} ({} 大会)", retrieved.year_month, retrieved.events.len());
-    
-    for (i, event) in retrieved.events.iter().enumerate() {
-        println!("  {}. {} - {} ({}日間, {})",
-            i + 1, event.venue_name, event.event_name, event.duration_days, event.grade);
-    }
+    println!("✅ 取得完了");
+    print!("{}", retrieved.summary());
 
     // 3. 個別レースデータの保存
     println!("\n🏁 個別レースデータの保存...");
@@ -151,23 +147,19 @@ fn demo_cross_month_tournament() -> Result<()> {
     let mut engine = BoatRaceEngine::new(store);
 
     // 年末年始に跨る大会を作成
-    let year_end_tournament = RaceEvent {
-        venue_id: 24,
-        venue_name: "大村".to_string(),
-        event_name: "年末年始特別競走".to_string(),
-        grade: "SG".to_string(),
-        start_date: "2025-12-28".to_string(),
-        duration_days: 8, // 2026-01-04まで
-    };
+    let year_end_tournament = RaceEvent::builder()
+        .venue_id(24)
+        .venue_name("大村")
+        .event_name("年末年始特別競走")
+        .grade(Grade::SG)
+        .start_date("2025-12-28")
+        .duration_days(8) // 2026-01-04まで
+        .build()?;
 
     println!("🎊 年末年始大会を複数月に登録中...");
     println!("  期間: {} ～ {} ({} 日間)",
         year_end_tournament.start_date,
-        chrono::NaiveDate::parse_from_str(&year_end_tournament.start_date, "%Y-%m-%d")
-            .unwrap()
-            .checked_add_signed(chrono::Duration::days(year_end_tournament.duration_days as i64 - 1))
-            .unwrap()
-            .format("%Y-%m-%d"),
+        year_end_tournament.end()?.format("%Y-%m-%d"),
         year_end_tournament.duration_days
     );
 
@@ -177,16 +169,14 @@ fn demo_cross_month_tournament() -> Result<()> {
     println!("\n📅 2025年12月のスケジュール:");
     let dec_schedule = engine.get_monthly_schedule(202512)?;
     for event in &dec_schedule.events {
-        println!("  • {} - {} ({})",
-            event.venue_name, event.event_name, event.grade);
+        println!("  • {}", event);
     }
 
     // 1月のスケジュールを確認
     println!("\n📅 2026年1月のスケジュール:");
     let jan_schedule = engine.get_monthly_schedule(202601)?;
     for event in &jan_schedule.events {
-        println!("  • {} - {} ({})",
-            event.venue_name, event.event_name, event.grade);
+        println!("  • {}", event);
     }
 
     println!("\n✅ 月跨ぎ大会が両方の月に正しく登録されました");
@@ -196,35 +186,23 @@ fn demo_cross_month_tournament() -> Result<()> {
 }
 
 fn create_sample_schedule() -> MonthlySchedule {
-    MonthlySchedule {
-        year_month: "2025-09".to_string(),
-        events: vec![
-            RaceEvent {
-                venue_id: 1,
-                venue_name: "桐生".to_string(),
-                event_name: "バスケで群馬を熱くする群馬クレインサンダーズカップ".to_string(),
-                grade: "一般".to_string(),
-                start_date: "2025-09-11".to_string(),
-                duration_days: 6,
-            },
-            RaceEvent {
-                venue_id: 4,
-                venue_name: "平和島".to_string(),
-                event_name: "開設７１周年記念トーキョー・ベイ・カップ".to_string(),
-                grade: "G1".to_string(),
-                start_date: "2025-09-10".to_string(),
-                duration_days: 7,
-            },
-            RaceEvent {
-                venue_id: 12,
-                venue_name: "住之江".to_string(),
-                event_name: "第５３回高松宮記念特別競走".to_string(),
-                grade: "G1".to_string(),
-                start_date: "2025-09-13".to_string(),
-                duration_days: 6,
-            },
-        ],
-    }
+    let event = |venue_id, venue_name: &str, event_name: &str, grade, start_date: &str, duration_days| {
+        RaceEvent::builder()
+            .venue_id(venue_id)
+            .venue_name(venue_name)
+            .event_name(event_name)
+            .grade(grade)
+            .start_date(start_date)
+            .duration_days(duration_days)
+            .build()
+            .expect("サンプルのイベントは検証を通る")
+    };
+    MonthlySchedule::builder(202509)
+        .event(event(1, "桐生", "バスケで群馬を熱くする群馬クレインサンダーズカップ", Grade::Ippan, "2025-09-11", 6))
+        .event(event(4, "平和島", "開設７１周年記念トーキョー・ベイ・カップ", Grade::G1, "2025-09-10", 7))
+        .event(event(12, "住之江", "第５３回高松宮記念特別競走", Grade::G1, "2025-09-13", 6))
+        .build()
+        .expect("サンプルのスケジュールは検証を通る")
 }
 
 fn create_sample_race_data(race_number: u32) -> RaceData {
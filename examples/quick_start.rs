@@ -1,10 +1,10 @@
-/// Quick Start Example for Norimaki DB
-/// 
-/// This example shows the most basic usage patterns.
-/// Run with: cargo run --example quick_start
+//! Quick Start Example for Norimaki DB
+//! 
+//! This example shows the most basic usage patterns.
+//! Run with: cargo run --example quick_start
 
 use norimaki_db::{
-    BoatRaceEngine, MemoryStore, MonthlySchedule, RaceEvent, 
+    BoatRaceEngine, MemoryStore, MonthlySchedule, RaceEvent, Grade,
     generate_tournament_id, Result
 };
 use serde::{Serialize, Deserialize};
@@ -27,19 +27,18 @@ fn main() -> Result<()> {
     println!("✅ Created engine with in-memory storage");
 
     // 2. Create and save monthly schedule
-    let schedule = MonthlySchedule {
-        year_month: "2025-09".to_string(),
-        events: vec![
-            RaceEvent {
-                venue_id: 4,
-                venue_name: "平和島".to_string(),
-                event_name: "トーキョー・ベイ・カップ".to_string(),
-                grade: "G1".to_string(),
-                start_date: "2025-09-10".to_string(),
-                duration_days: 3,
-            },
-        ],
-    };
+    let schedule = MonthlySchedule::builder(202509)
+        .event(
+            RaceEvent::builder()
+                .venue_id(4)
+                .venue_name("平和島")
+                .event_name("トーキョー・ベイ・カップ")
+                .grade(Grade::G1)
+                .start_date("2025-09-10")
+                .duration_days(3)
+                .build()?,
+        )
+        .build()?;
     
     engine.put_monthly_schedule(&schedule)?;
     println!("✅ Saved monthly schedule for September 2025");
@@ -50,7 +49,7 @@ fn main() -> Result<()> {
              retrieved.year_month, retrieved.events.len());
     
     for event in &retrieved.events {
-        println!("   • {} - {}", event.venue_name, event.event_name);
+        println!("   • {}", event.short_label());
     }
 
     // 4. Add race data
@@ -0,0 +1,422 @@
+//! 非同期 API モジュール (`async` フィーチャー)
+//!
+//! 同期ストアと同期エンジンの呼び出しを tokio の `spawn_blocking` でブロッキング用スレッドプールに送り、
+//! 結果を Future として返す。FileStore の fsync などで executor が止まらない。
+//!
+//! `AsyncBoatRaceEngine` は同期エンジンを直列化するラッパーで、並列には実行しない。
+//! エンジンは非同期の `Mutex` で共有し、呼び出しはロックを得た順に1件ずつ実行される
+//! (待っている呼び出しはブロッキング用スレッドを占有しない。同じタスクから順に await した呼び出しはその順で実行される)。
+//! Future は tokio のランタイム上で await すること。
+//! 同期 API (`KeyValueStore` / `BoatRaceEngine`) はそのまま既定の API として残る
+
+use std::future::Future;
+use std::io::Write;
+use std::panic::resume_unwind;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{
+    BoatRaceEngine, DatabaseStatistics, DeleteReport, KeyValueStore, MonthlySchedule, RaceEvent, Result,
+    TournamentStatus, TournamentSummary, YearOverview,
+};
+
+/// 同期ストアを非同期に使うためのアダプター
+///
+/// 各操作は `spawn_blocking` で1件ずつ実行される。メソッドは `KeyValueStore` と同じ意味を持ち
+/// (`keys` はキーの辞書順で返す)、複数タスクから共有できるよう `&self` で呼び出す。
+/// クローンしたハンドルは同じストアを共有する
+pub struct SyncAsAsync<S: KeyValueStore + Send> {
+    store: Arc<Mutex<S>>,
+}
+
+impl<S: KeyValueStore + Send> Clone for SyncAsAsync<S> {
+    fn clone(&self) -> Self {
+        Self { store: Arc::clone(&self.store) }
+    }
+}
+
+impl<S: KeyValueStore + Send + 'static> SyncAsAsync<S> {
+    pub fn new(store: S) -> Self {
+        Self { store: Arc::new(Mutex::new(store)) }
+    }
+
+    pub async fn put(&self, key: String, value: String) -> Result<()> {
+        run_blocking(&self.store, move |store| store.put(key, value)).await
+    }
+
+    pub async fn get(&self, key: String) -> Result<Option<String>> {
+        run_blocking(&self.store, move |store| store.get(&key)).await
+    }
+
+    pub async fn delete(&self, key: String) -> Result<()> {
+        run_blocking(&self.store, move |store| store.delete(&key)).await
+    }
+
+    pub async fn keys(&self) -> Result<Vec<String>> {
+        run_blocking(&self.store, |store| store.keys()).await
+    }
+
+    pub async fn clear(&self) -> Result<()> {
+        run_blocking(&self.store, |store| store.clear()).await
+    }
+
+    pub async fn scan(&self, start: String, end: String) -> Result<Vec<(String, String)>> {
+        run_blocking(&self.store, move |store| store.scan(&start, &end)).await
+    }
+}
+
+/// `AsyncBoatRaceEngine` のストアを非同期に扱うハンドル (`AsyncBoatRaceEngine::store` で取得)
+///
+/// メソッドは `SyncAsAsync` と同じで、エンジンの呼び出しと同じロックの下で実行される。
+/// 書き込みは `BoatRaceEngine::store_mut` と同じくストアに直接行うため、統計カウンタやインデックスには
+/// 反映されない (書き込んだ後は `rebuild_statistics` / `rebuild_indexes` を呼ぶこと)
+pub struct AsyncEngineStore<K: KeyValueStore + Send> {
+    engine: Arc<Mutex<BoatRaceEngine<K>>>,
+}
+
+impl<K: KeyValueStore + Send> Clone for AsyncEngineStore<K> {
+    fn clone(&self) -> Self {
+        Self { engine: Arc::clone(&self.engine) }
+    }
+}
+
+impl<K: KeyValueStore + Send + 'static> AsyncEngineStore<K> {
+    pub async fn put(&self, key: String, value: String) -> Result<()> {
+        run_blocking(&self.engine, move |engine| engine.store_mut().put(key, value)).await
+    }
+
+    pub async fn get(&self, key: String) -> Result<Option<String>> {
+        run_blocking(&self.engine, move |engine| engine.store().get(&key)).await
+    }
+
+    pub async fn delete(&self, key: String) -> Result<()> {
+        run_blocking(&self.engine, move |engine| engine.store_mut().delete(&key)).await
+    }
+
+    pub async fn keys(&self) -> Result<Vec<String>> {
+        run_blocking(&self.engine, |engine| engine.store().keys()).await
+    }
+
+    pub async fn clear(&self) -> Result<()> {
+        run_blocking(&self.engine, |engine| engine.store_mut().clear()).await
+    }
+
+    pub async fn scan(&self, start: String, end: String) -> Result<Vec<(String, String)>> {
+        run_blocking(&self.engine, move |engine| engine.store_mut().scan(&start, &end)).await
+    }
+}
+
+/// 非同期版の競艇データエンジン
+///
+/// 同期エンジンを直列化するラッパー。呼び出しは `spawn_blocking` で1件ずつ実行され、読み込み同士も並列にはならない。
+/// 主要な操作は同名の async メソッドとして、それ以外の操作は `call` で同期 API をそのまま呼び出せる。
+/// クローンしたハンドルは同じエンジンを共有する。呼び出し内のパニックは待機側で再開され、
+/// エンジンはその後も使える (値の状態は各操作のバッチ単位で一貫している)
+pub struct AsyncBoatRaceEngine<K: KeyValueStore + Send> {
+    engine: Arc<Mutex<BoatRaceEngine<K>>>,
+}
+
+impl<K: KeyValueStore + Send> Clone for AsyncBoatRaceEngine<K> {
+    fn clone(&self) -> Self {
+        Self { engine: Arc::clone(&self.engine) }
+    }
+}
+
+impl<K: KeyValueStore + Send + 'static> AsyncBoatRaceEngine<K> {
+    /// 同期エンジンを非同期エンジンに包む
+    pub fn new(engine: BoatRaceEngine<K>) -> Self {
+        Self { engine: Arc::new(Mutex::new(engine)) }
+    }
+
+    /// エンジンのストアを非同期に扱うハンドルを取得
+    pub fn store(&self) -> AsyncEngineStore<K> {
+        AsyncEngineStore { engine: Arc::clone(&self.engine) }
+    }
+
+    /// 任意の同期 API を `spawn_blocking` で呼び出す
+    ///
+    /// # Arguments
+    /// * `f` - エンジンを受け取る処理
+    ///
+    /// # Returns
+    /// 処理の戻り値
+    pub fn call<T, F>(&self, f: F) -> impl Future<Output = T> + Send
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut BoatRaceEngine<K>) -> T + Send + 'static,
+    {
+        run_blocking(&self.engine, f)
+    }
+
+    /// 月別スケジュールを保存 (`BoatRaceEngine::put_monthly_schedule`)
+    pub async fn put_monthly_schedule(&self, schedule: MonthlySchedule) -> Result<()> {
+        self.call(move |engine| engine.put_monthly_schedule(&schedule)).await
+    }
+
+    /// 月別スケジュールを取得 (`BoatRaceEngine::get_monthly_schedule`)
+    pub async fn get_monthly_schedule(&self, year_month: u32) -> Result<MonthlySchedule> {
+        self.call(move |engine| engine.get_monthly_schedule(year_month)).await
+    }
+
+    /// 取り込み済みの月別スケジュールを取得 (`BoatRaceEngine::try_get_monthly_schedule`)
+    pub async fn try_get_monthly_schedule(&self, year_month: u32) -> Result<Option<MonthlySchedule>> {
+        self.call(move |engine| engine.try_get_monthly_schedule(year_month)).await
+    }
+
+    /// 期間内の月別スケジュールを取得 (`BoatRaceEngine::get_schedules_between`)
+    pub async fn get_schedules_between(&self, from: u32, to: u32) -> Result<Vec<MonthlySchedule>> {
+        self.call(move |engine| engine.get_schedules_between(from, to)).await
+    }
+
+    /// 1年分の月別スケジュールを取得 (`BoatRaceEngine::get_yearly_schedule`)
+    pub async fn get_yearly_schedule(&self, year: u32) -> Result<Vec<MonthlySchedule>> {
+        self.call(move |engine| engine.get_yearly_schedule(year)).await
+    }
+
+    /// 年間の集計を取得 (`BoatRaceEngine::year_overview`)
+    pub async fn year_overview(&self, year: u32) -> Result<YearOverview> {
+        self.call(move |engine| engine.year_overview(year)).await
+    }
+
+    /// 月別スケジュールを削除 (`BoatRaceEngine::delete_monthly_schedule`)
+    pub async fn delete_monthly_schedule(&self, year_month: u32) -> Result<usize> {
+        self.call(move |engine| engine.delete_monthly_schedule(year_month)).await
+    }
+
+    /// 月のスケジュールを CSV で書き出す (`BoatRaceEngine::export_month_csv`)
+    ///
+    /// # Returns
+    /// 書き出した行数と、書き込み先
+    pub async fn export_month_csv<W: Write + Send + 'static>(&self, year_month: u32, mut writer: W) -> Result<(usize, W)> {
+        self.call(move |engine| engine.export_month_csv(year_month, &mut writer).map(|rows| (rows, writer))).await
+    }
+
+    /// 大会を登録 (`BoatRaceEngine::register_tournament`)
+    pub async fn register_tournament(&self, event: RaceEvent) -> Result<String> {
+        self.call(move |engine| engine.register_tournament(&event)).await
+    }
+
+    /// 大会を検索 (`BoatRaceEngine::find_race_event`)
+    pub async fn find_race_event(&self, tournament_id: String) -> Result<Option<(u32, RaceEvent)>> {
+        self.call(move |engine| engine.find_race_event(&tournament_id)).await
+    }
+
+    /// 大会の開催状況を取得 (`BoatRaceEngine::get_tournament_status`)
+    pub async fn get_tournament_status(&self, tournament_id: String, today: NaiveDate) -> Result<TournamentStatus> {
+        self.call(move |engine| engine.get_tournament_status(&tournament_id, today)).await
+    }
+
+    /// 大会の一覧を取得 (`BoatRaceEngine::list_tournaments`)
+    pub async fn list_tournaments(&self) -> Result<Vec<TournamentSummary>> {
+        self.call(|engine| engine.list_tournaments()).await
+    }
+
+    /// 大会の概要を取得 (`BoatRaceEngine::get_tournament_summary`)
+    pub async fn get_tournament_summary(&self, tournament_id: String) -> Result<TournamentSummary> {
+        self.call(move |engine| engine.get_tournament_summary(&tournament_id)).await
+    }
+
+    /// 大会とそのデータを削除 (`BoatRaceEngine::delete_tournament`)
+    pub async fn delete_tournament(&self, tournament_id: String) -> Result<DeleteReport> {
+        self.call(move |engine| engine.delete_tournament(&tournament_id)).await
+    }
+
+    /// 個別レースデータを保存 (`BoatRaceEngine::put_race_data`)
+    pub async fn put_race_data<T: Serialize + Send + 'static>(&self, tournament_id: String, timestamp: u64, data: T) -> Result<()> {
+        self.call(move |engine| engine.put_race_data(&tournament_id, timestamp, &data)).await
+    }
+
+    /// レースデータをまとめて保存 (`BoatRaceEngine::put_races_bulk`)
+    pub async fn put_races_bulk<T: Serialize + Send + 'static>(&self, tournament_id: String, races: Vec<(u64, T)>) -> Result<usize> {
+        self.call(move |engine| engine.put_races_bulk(&tournament_id, &races)).await
+    }
+
+    /// 個別レースデータを取得 (`BoatRaceEngine::get_race_data`)
+    pub async fn get_race_data<T: DeserializeOwned + Send + 'static>(&self, tournament_id: String, timestamp: u64) -> Result<T> {
+        self.call(move |engine| engine.get_race_data(&tournament_id, timestamp)).await
+    }
+
+    /// 大会の全レースデータを取得 (`BoatRaceEngine::get_tournament_races`)
+    pub async fn get_tournament_races<T: DeserializeOwned + Send + 'static>(&self, tournament_id: String) -> Result<Vec<T>> {
+        self.call(move |engine| engine.get_tournament_races(&tournament_id)).await
+    }
+
+    /// 大会のレース数を取得 (`BoatRaceEngine::count_races`)
+    pub async fn count_races(&self, tournament_id: String) -> Result<usize> {
+        self.call(move |engine| engine.count_races(&tournament_id)).await
+    }
+
+    /// 大会のレースのタイムスタンプと値のバイト数を取得 (`BoatRaceEngine::get_race_sizes`)
+    pub async fn get_race_sizes(&self, tournament_id: String) -> Result<Vec<(u64, usize)>> {
+        self.call(move |engine| engine.get_race_sizes(&tournament_id)).await
+    }
+
+    /// 件数の統計を取得 (`BoatRaceEngine::get_statistics`)
+    pub async fn get_statistics(&self) -> Result<(usize, usize, usize)> {
+        self.call(|engine| engine.get_statistics()).await
+    }
+
+    /// データベースの詳細な統計を取得 (`BoatRaceEngine::get_detailed_statistics`)
+    pub async fn get_detailed_statistics(&self) -> Result<DatabaseStatistics> {
+        self.call(|engine| engine.get_detailed_statistics()).await
+    }
+}
+
+/// 共有した値に対する同期処理を `spawn_blocking` で実行し、その完了を待つ Future を返す
+///
+/// ロックは await で待ち、得てから処理を送るため、待っている呼び出しはブロッキング用スレッドを使わない。
+/// 処理がパニックした場合は待機側でパニックを再開する (ロックはパニックで解放され、汚染されない)
+fn run_blocking<T, R, F>(target: &Arc<Mutex<T>>, f: F) -> impl Future<Output = R> + Send + 'static
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: FnOnce(&mut T) -> R + Send + 'static,
+{
+    let target = Arc::clone(target);
+    async move {
+        let mut guard = target.lock_owned().await;
+        match tokio::task::spawn_blocking(move || f(&mut guard)).await {
+            Ok(value) => value,
+            Err(error) => match error.try_into_panic() {
+                Ok(panic) => resume_unwind(panic),
+                // ランタイムの終了で取り消された場合
+                Err(error) => panic!("norimaki-db blocking task failed: {}", error),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grade, MemoryStore};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    include!("../testdata/sample.rs");
+
+    #[tokio::test]
+    async fn test_async_engine_put_get_schedule() {
+        let engine = AsyncBoatRaceEngine::new(BoatRaceEngine::new(MemoryStore::new()));
+        engine.put_monthly_schedule(sample_data()).await.unwrap();
+        let schedule = engine.get_monthly_schedule(202509).await.unwrap();
+        assert_eq!(schedule.events.len(), sample_data().events.len());
+
+        engine.put_race_data("tokyo_bay_cup".to_string(), 1, "race1".to_string()).await.unwrap();
+        let race: String = engine.get_race_data("tokyo_bay_cup".to_string(), 1).await.unwrap();
+        assert_eq!(race, "race1");
+        let count = engine.call(|engine| engine.count_races("tokyo_bay_cup")).await.unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_async_engine_concurrent_reads() {
+        let engine = AsyncBoatRaceEngine::new(BoatRaceEngine::new(MemoryStore::new()));
+        engine.put_monthly_schedule(sample_data()).await.unwrap();
+
+        // 複数タスクから同時に読んでも結果は正しく、エンジンの呼び出しは重ならない (直列化される)
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let engine = engine.clone();
+                let running = Arc::clone(&running);
+                let max_running = Arc::clone(&max_running);
+                tokio::spawn(async move {
+                    engine
+                        .call(move |engine| {
+                            max_running.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                            std::thread::sleep(Duration::from_millis(5));
+                            let schedule = engine.get_monthly_schedule(202509);
+                            running.fetch_sub(1, Ordering::SeqCst);
+                            schedule
+                        })
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+        for reader in readers {
+            assert_eq!(reader.await.unwrap().events.len(), sample_data().events.len());
+        }
+        assert_eq!(max_running.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_blocked_call_does_not_stall_executor() {
+        let engine = AsyncBoatRaceEngine::new(BoatRaceEngine::new(MemoryStore::new()));
+        let (release, released) = std::sync::mpsc::channel::<u32>();
+        // エンジンの呼び出しがブロックしている間も、単一スレッドの executor は他のタスクを進める
+        let blocked = tokio::spawn(engine.call(move |_| released.recv().unwrap()));
+        let other = tokio::spawn(async { 42 });
+        assert_eq!(other.await.unwrap(), 42);
+        release.send(7).unwrap();
+        assert_eq!(blocked.await.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_read_apis() {
+        let engine = AsyncBoatRaceEngine::new(BoatRaceEngine::new(MemoryStore::new()));
+        engine.put_monthly_schedule(sample_data()).await.unwrap();
+        engine.put_race_data("tokyo_bay_cup".to_string(), 1757640600000, "race1".to_string()).await.unwrap();
+
+        let sizes = engine.get_race_sizes("tokyo_bay_cup".to_string()).await.unwrap();
+        assert_eq!(sizes.len(), 1);
+        assert_eq!(sizes[0].0, 1757640600000);
+        assert_eq!(engine.count_races("tokyo_bay_cup".to_string()).await.unwrap(), 1);
+        let stats = engine.get_detailed_statistics().await.unwrap();
+        assert_eq!(stats.months, vec![202509]);
+        assert_eq!(engine.year_overview(2025).await.unwrap(), engine.call(|engine| engine.year_overview(2025)).await.unwrap());
+        assert_eq!(engine.get_schedules_between(202509, 202509).await.unwrap().len(), 1);
+
+        let (rows, csv) = engine.export_month_csv(202509, Vec::new()).await.unwrap();
+        assert_eq!(rows, sample_data().events.len());
+        assert!(!csv.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_runs_off_the_runtime_thread() {
+        let engine = AsyncBoatRaceEngine::new(BoatRaceEngine::new(MemoryStore::new()));
+        let caller = std::thread::current().id();
+        let worker = engine.call(|_| std::thread::current().id()).await;
+        assert_ne!(worker, caller);
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_survives_panicking_call() {
+        let engine = AsyncBoatRaceEngine::new(BoatRaceEngine::new(MemoryStore::new()));
+        let panicking = engine.clone();
+        let panicked = tokio::spawn(async move { panicking.call(|_| -> () { panic!("boom") }).await }).await;
+        assert!(panicked.unwrap_err().is_panic());
+        engine.put_monthly_schedule(sample_data()).await.unwrap();
+        assert_eq!(engine.get_monthly_schedule(202509).await.unwrap().events.len(), sample_data().events.len());
+    }
+
+    #[tokio::test]
+    async fn test_async_engine_store() {
+        let engine = AsyncBoatRaceEngine::new(BoatRaceEngine::new(MemoryStore::new()));
+        engine.put_race_data("tokyo_bay_cup".to_string(), 1, "race1".to_string()).await.unwrap();
+        let store = engine.store();
+        let key = crate::key::tournament_key("tokyo_bay_cup", 1);
+        assert!(store.get(key.clone()).await.unwrap().is_some());
+        store.put("Umemo".to_string(), "note".to_string()).await.unwrap();
+        assert!(store.keys().await.unwrap().contains(&"Umemo".to_string()));
+        store.delete(key).await.unwrap();
+        let races: Vec<String> = engine.get_tournament_races("tokyo_bay_cup".to_string()).await.unwrap();
+        assert!(races.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_as_async_store() {
+        let store = SyncAsAsync::new(MemoryStore::new());
+        store.put("key1".to_string(), "value1".to_string()).await.unwrap();
+        assert_eq!(store.get("key1".to_string()).await.unwrap(), Some("value1".to_string()));
+        assert_eq!(store.scan("a".to_string(), "z".to_string()).await.unwrap().len(), 1);
+        assert!(store.put(String::new(), "value".to_string()).await.is_err());
+        store.delete("key1".to_string()).await.unwrap();
+        assert!(store.keys().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+//! FileStore のデータベースを確認・操作するコマンドラインツール
+//!
+//! 使用方法: `cargo run --bin norimaki-cli -- <command> <db.json> [args...]`
+
+use std::fs::File;
+use std::path::Path;
+use std::process::ExitCode;
+
+use norimaki_db::{BoatRaceEngine, FileStore, StoreError};
+
+const USAGE: &str = "\
+usage: norimaki-cli <command> <db.json> [args...]
+
+commands:
+  stats <db>                            show database statistics
+  months <db>                           list months that have events
+  show <db> <year_month>                list the events of a month
+  year <db> <year>                      show events per month, grade and venue for a year
+  tournaments <db>                      list tournaments with their months and race counts
+  races <db> <tournament_id>            list the races stored for a tournament
+  delete-month <db> <year_month>        delete a month's schedule
+  export-csv <db> <year_month> <out>    write a month's schedule as CSV
+
+year_month is YYYYMM or YYYY-MM (e.g. 202509 or 2025-09)";
+
+/// コマンドラインのエラー (使い方の誤りは終了コード 2、データベースのエラーは 1)
+enum CliError {
+    Usage(String),
+    Store(StoreError),
+}
+
+impl From<StoreError> for CliError {
+    fn from(error: StoreError) -> Self {
+        CliError::Store(error)
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(error: std::io::Error) -> Self {
+        CliError::Store(error.into())
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(message)) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+        Err(CliError::Store(error)) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), CliError> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(CliError::Usage("missing command".to_string()));
+    };
+    let expected = match command.as_str() {
+        "stats" | "months" | "tournaments" => 1,
+        "show" | "year" | "races" | "delete-month" => 2,
+        "export-csv" => 3,
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        other => return Err(CliError::Usage(format!("unknown command {:?}", other))),
+    };
+    if rest.len() != expected {
+        return Err(CliError::Usage(format!(
+            "{} expects {} argument(s), got {}",
+            command,
+            expected,
+            rest.len()
+        )));
+    }
+
+    let mut engine = open(&rest[0])?;
+    match command.as_str() {
+        "stats" => stats(&engine),
+        "months" => months(&engine),
+        "show" => show(&mut engine, parse_year_month(&rest[1])?),
+        "year" => {
+            let year = rest[1]
+                .parse()
+                .ok()
+                .filter(|year| (1..=9999).contains(year))
+                .ok_or_else(|| CliError::Usage(format!("invalid year {:?}", rest[1])))?;
+            print!("{}", engine.year_overview(year)?);
+            Ok(())
+        }
+        "tournaments" => tournaments(&engine),
+        "races" => races(&mut engine, &rest[1]),
+        "delete-month" => {
+            let year_month = parse_year_month(&rest[1])?;
+            let deleted = engine.delete_monthly_schedule(year_month)?;
+            println!("deleted {} event(s) from {}", deleted, year_month);
+            Ok(())
+        }
+        "export-csv" => {
+            let year_month = parse_year_month(&rest[1])?;
+            let written = engine.export_month_csv(year_month, File::create(&rest[2])?)?;
+            println!("wrote {} event(s) to {}", written, rest[2]);
+            Ok(())
+        }
+        _ => unreachable!("command was validated above"),
+    }
+}
+
+/// 既存のデータベースを開く (FileStore は存在しないファイルを黙って作るため、先に確かめる)
+fn open(path: &str) -> Result<BoatRaceEngine<FileStore>, CliError> {
+    if !Path::new(path).is_file() {
+        let error = std::io::Error::new(std::io::ErrorKind::NotFound, format!("database file {:?} does not exist", path));
+        return Err(CliError::Store(error.into()));
+    }
+    Ok(BoatRaceEngine::new(FileStore::new(path)?))
+}
+
+/// "YYYYMM" または "YYYY-MM" 形式の年月を解析
+fn parse_year_month(value: &str) -> Result<u32, CliError> {
+    let digits = value.replace('-', "");
+    let invalid = || CliError::Usage(format!("invalid year_month {:?}", value));
+    if digits.len() != 6 || (value.contains('-') && value.len() != 7) {
+        return Err(invalid());
+    }
+    let year_month: u32 = digits.parse().map_err(|_| invalid())?;
+    if !(1..=12).contains(&(year_month % 100)) {
+        return Err(invalid());
+    }
+    Ok(year_month)
+}
+
+fn stats(engine: &BoatRaceEngine<FileStore>) -> Result<(), CliError> {
+    let stats = engine.get_detailed_statistics()?;
+    let months = stats.months.iter().map(u32::to_string).collect::<Vec<_>>().join(" ");
+    let rows = vec![
+        vec!["monthly entries".to_string(), stats.monthly_entries.to_string()],
+        vec!["tournaments".to_string(), stats.tournaments.to_string()],
+        vec!["races".to_string(), stats.races.to_string()],
+        vec!["racers".to_string(), stats.racers.to_string()],
+        vec!["months".to_string(), months],
+        vec!["total bytes".to_string(), stats.total_bytes.to_string()],
+        vec!["corrupted entries".to_string(), stats.corrupted_entries.to_string()],
+    ];
+    print_table(&["statistic", "value"], &rows);
+    Ok(())
+}
+
+fn months(engine: &BoatRaceEngine<FileStore>) -> Result<(), CliError> {
+    let stats = engine.get_detailed_statistics()?;
+    for year_month in stats.months {
+        println!("{}", year_month);
+    }
+    Ok(())
+}
+
+fn show(engine: &mut BoatRaceEngine<FileStore>, year_month: u32) -> Result<(), CliError> {
+    let schedule = engine.get_monthly_schedule(year_month)?;
+    let rows: Vec<Vec<String>> = schedule
+        .events
+        .iter()
+        .map(|event| {
+            vec![
+                event.start_date.clone(),
+                event.duration_days.to_string(),
+                event.venue_id.to_string(),
+                event.venue_name.clone(),
+                event.grade.to_string(),
+                event.event_name.clone(),
+            ]
+        })
+        .collect();
+    print_table(&["start_date", "days", "venue_id", "venue", "grade", "event"], &rows);
+    Ok(())
+}
+
+fn tournaments(engine: &BoatRaceEngine<FileStore>) -> Result<(), CliError> {
+    let rows: Vec<Vec<String>> = engine
+        .list_tournaments()?
+        .into_iter()
+        .map(|summary| {
+            vec![
+                summary.tournament_id,
+                summary.months.iter().map(u32::to_string).collect::<Vec<_>>().join(" "),
+                summary.race_count.to_string(),
+                summary.event.map(|event| event.event_name).unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(&["tournament_id", "months", "races", "event"], &rows);
+    Ok(())
+}
+
+fn races(engine: &mut BoatRaceEngine<FileStore>, tournament_id: &str) -> Result<(), CliError> {
+    let rows: Vec<Vec<String>> = engine
+        .get_race_sizes(tournament_id)?
+        .into_iter()
+        .map(|(timestamp, bytes)| {
+            let time = i64::try_from(timestamp)
+                .ok()
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|when| when.to_rfc3339())
+                .unwrap_or_default();
+            vec![timestamp.to_string(), time, bytes.to_string()]
+        })
+        .collect();
+    print_table(&["timestamp", "time (UTC)", "bytes"], &rows);
+    Ok(())
+}
+
+/// 左揃えの列を2つの空白で区切った表を出力
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - cell.chars().count())))
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(headers.to_vec());
+    let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    line(rule.iter().map(String::as_str).collect());
+    for row in rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
@@ -0,0 +1,423 @@
+//! 省メモリのインメモリストア
+//!
+//! 大量のレースを `MemoryStore` に載せると、同じトーナメント ID で始まるキーと
+//! 同じ内容の小さな値ごとに `String` が確保され、それが使用メモリの大半を占める。
+//! `CompactMemoryStore` はキーの接頭辞と値を共有し、重複した確保をなくす
+
+use crate::key::SEPARATOR;
+use crate::{KeyValueStore, Result, StoreError};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::mem::size_of;
+use std::ops::Bound;
+use std::sync::Arc;
+
+/// 接頭辞ごとのエントリ (接頭辞を除いたキーの残り → 値)
+type Group = BTreeMap<Box<str>, Arc<str>>;
+
+/// キーの接頭辞と値を共有するインメモリストア
+///
+/// キーは最後のセパレータまでを接頭辞として1つだけ保持し、エントリごとには残りの部分だけを持つ。
+/// 値は `Arc<str>` で保持し、同じ内容の値は重複排除テーブルを通して1つの確保を共有する。
+/// `KeyValueStore` としての振る舞いは `MemoryStore` と同じで、`keys` はキー順に返す
+///
+/// ```
+/// use norimaki_db::{CompactMemoryStore, KeyValueStore, MemoryStore};
+///
+/// let mut compact = CompactMemoryStore::new();
+/// let mut plain = MemoryStore::new();
+/// for i in 0..1000 {
+///     let key = format!("Ttokyo_cup\0{:08}", i);
+///     compact.put(key.clone(), "x".repeat(200))?;
+///     plain.put(key, "x".repeat(200))?;
+/// }
+/// assert_eq!(compact.distinct_values(), 1);
+/// assert!(compact.memory_usage() < plain.memory_usage() / 4);
+/// # Ok::<(), norimaki_db::StoreError>(())
+/// ```
+#[derive(Default)]
+pub struct CompactMemoryStore {
+    /// 接頭辞順のグループ (範囲の走査は重なり得る接頭辞だけを辿る)
+    groups: BTreeMap<Arc<str>, Group>,
+    /// 値の重複排除テーブル (いずれかのエントリが参照している値だけを保持する)
+    values: HashSet<Arc<str>>,
+    len: usize,
+}
+
+/// キーを接頭辞 (最後のセパレータまで、セパレータを含む) と残りに分ける
+fn split_key(key: &str) -> (&str, &str) {
+    match key.bytes().rposition(|byte| byte == SEPARATOR) {
+        Some(index) => key.split_at(index + 1),
+        None => ("", key),
+    }
+}
+
+/// `Arc<str>` 1つ分のヒープ使用量 (参照カウント2つと文字列本体)
+fn arc_str_bytes(value: &str) -> usize {
+    2 * size_of::<usize>() + value.len()
+}
+
+impl CompactMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// エントリ数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// エントリが1件もなければ true
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 共有している値の種類数
+    pub fn distinct_values(&self) -> usize {
+        self.values.len()
+    }
+
+    /// ヒープ使用量の概算 (バイト)
+    ///
+    /// 文字列本体と、エントリ・接頭辞・値ごとの管理領域を数える。
+    /// アロケータやハッシュテーブルの空き領域は含まないため、`MemoryStore::memory_usage` との比較に使う
+    ///
+    /// # Returns
+    /// 推定バイト数
+    pub fn memory_usage(&self) -> usize {
+        let groups: usize = self
+            .groups
+            .iter()
+            .map(|(prefix, group)| {
+                let entries: usize = group.keys().map(|suffix| suffix.len() + size_of::<(Box<str>, Arc<str>)>()).sum();
+                arc_str_bytes(prefix) + size_of::<(Arc<str>, Group)>() + entries
+            })
+            .sum();
+        let values: usize = self.values.iter().map(|value| arc_str_bytes(value) + size_of::<Arc<str>>()).sum();
+        groups + values
+    }
+
+    /// エントリを追加または上書きする (キーは検証済み)
+    fn insert(&mut self, key: &str, value: String) {
+        let value = self.intern_value(value);
+        let (prefix, suffix) = split_key(key);
+        let group = match self.groups.get_mut(prefix) {
+            Some(group) => group,
+            None => self.groups.entry(Arc::from(prefix)).or_default(),
+        };
+        match group.insert(Box::from(suffix), value) {
+            Some(previous) => self.release_value(previous),
+            None => self.len += 1,
+        }
+    }
+
+    /// 値を重複排除テーブルから取り出す (なければ登録する)
+    fn intern_value(&mut self, value: String) -> Arc<str> {
+        if let Some(shared) = self.values.get(value.as_str()) {
+            return Arc::clone(shared);
+        }
+        let shared: Arc<str> = Arc::from(value);
+        self.values.insert(Arc::clone(&shared));
+        shared
+    }
+
+    /// エントリから外した値を手放す (他に参照がなければテーブルからも消す)
+    fn release_value(&mut self, value: Arc<str>) {
+        // テーブルと外したエントリの2つだけが参照している
+        if Arc::strong_count(&value) == 2 {
+            self.values.remove(&*value);
+        }
+    }
+
+    /// `[start, end)` のキーを持ち得るグループ (`start < end` であること)
+    ///
+    /// 範囲内のキーの接頭辞は、`start` の接頭辞であるか、それ自身が範囲内にある。
+    /// 前者は `start` のセパレータの位置ごとに引き、後者は接頭辞の範囲で辿るため、無関係な接頭辞は見ない
+    fn overlapping_groups<'a>(&'a self, start: &'a str, end: &'a str) -> impl Iterator<Item = (&'a Arc<str>, &'a Group)> + 'a {
+        let separators = start.bytes().enumerate().filter(|(_, byte)| *byte == SEPARATOR).map(|(index, _)| index + 1);
+        let start_prefixes = std::iter::once(0)
+            .chain(separators)
+            .filter(move |len| *len < start.len())
+            .filter_map(move |len| self.groups.get_key_value(&start[..len]));
+        start_prefixes.chain(self.groups.range::<str, _>((Bound::Included(start), Bound::Excluded(end))))
+    }
+
+    /// キー順に並べたエントリ (範囲を指定した場合はその範囲のみ)
+    fn sorted_entries<'a>(&'a self, range: Option<(&'a str, &'a str)>) -> Vec<(String, &'a Arc<str>)> {
+        let join = |prefix: &str, suffix: &str| {
+            let mut key = String::with_capacity(prefix.len() + suffix.len());
+            key.push_str(prefix);
+            key.push_str(suffix);
+            key
+        };
+        let mut entries = Vec::new();
+        match range {
+            None => {
+                for (prefix, group) in &self.groups {
+                    entries.extend(group.iter().map(|(suffix, value)| (join(prefix, suffix), value)));
+                }
+            }
+            Some((start, end)) if start < end => {
+                for (prefix, group) in self.overlapping_groups(start, end) {
+                    // 範囲の端と同じ接頭辞を持つグループだけ、残りの部分で範囲を絞る
+                    let lower = start.strip_prefix(&**prefix).map_or(Bound::Unbounded, Bound::Included);
+                    let upper = end.strip_prefix(&**prefix).map_or(Bound::Unbounded, Bound::Excluded);
+                    entries.extend(group.range::<str, _>((lower, upper)).map(|(suffix, value)| (join(prefix, suffix), value)));
+                }
+            }
+            Some(_) => {}
+        }
+        // 接頭辞の長さが違うグループのキーは互いに入り組むため、最後に全体のキー順に並べる
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+}
+
+/// 値の共有は複製元と分けて作り直す (参照カウントで重複排除テーブルを管理しているため)
+impl Clone for CompactMemoryStore {
+    fn clone(&self) -> Self {
+        let mut store = Self::new();
+        for (key, value) in self.sorted_entries(None) {
+            store.insert(&key, value.to_string());
+        }
+        store
+    }
+}
+
+impl fmt::Debug for CompactMemoryStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompactMemoryStore")
+            .field("entries", &self.len)
+            .field("distinct_values", &self.values.len())
+            .field("memory_usage", &self.memory_usage())
+            .finish()
+    }
+}
+
+impl fmt::Display for CompactMemoryStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "compact in-memory store ({} entries, {} distinct values)", self.len, self.values.len())
+    }
+}
+
+impl KeyValueStore for CompactMemoryStore {
+    fn put(&mut self, key: String, value: String) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        self.insert(&key, value);
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        let (prefix, suffix) = split_key(key);
+        Ok(self
+            .groups
+            .get(prefix)
+            .and_then(|group| group.get(suffix))
+            .map(|value| value.to_string()))
+    }
+
+    fn contains_key(&self, key: &str) -> Result<bool> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        let (prefix, suffix) = split_key(key);
+        Ok(self.groups.get(prefix).is_some_and(|group| group.contains_key(suffix)))
+    }
+
+    fn with_value<R>(&self, key: &str, f: impl FnOnce(&str) -> R) -> Result<Option<R>> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        let (prefix, suffix) = split_key(key);
+        Ok(self.groups.get(prefix).and_then(|group| group.get(suffix)).map(|value| f(value)))
+    }
+
+    fn scan_with(&mut self, start: &str, end: &str, mut f: impl FnMut(&str, &str) -> Result<()>) -> Result<()> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        for (key, value) in self.sorted_entries(Some((start, end))) {
+            f(&key, value)?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        let (prefix, suffix) = split_key(key);
+        let Some(group) = self.groups.get_mut(prefix) else {
+            return Ok(());
+        };
+        let removed = group.remove(suffix);
+        if group.is_empty() {
+            self.groups.remove(prefix);
+        }
+        if let Some(removed) = removed {
+            self.len -= 1;
+            self.release_value(removed);
+        }
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        Ok(self.sorted_entries(None).into_iter().map(|(key, _)| key).collect())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.groups.clear();
+        self.values.clear();
+        self.len = 0;
+        Ok(())
+    }
+
+    fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(self
+            .sorted_entries(Some((start, end)))
+            .into_iter()
+            .map(|(key, value)| (key, value.to_string()))
+            .collect())
+    }
+
+    fn scan_keys(&mut self, start: &str, end: &str) -> Result<Vec<String>> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(self.sorted_entries(Some((start, end))).into_iter().map(|(key, _)| key).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoatRaceEngine, Grade, MemoryStore, MonthlySchedule, RaceEvent};
+
+    include!("../testdata/sample.rs");
+
+    #[test]
+    fn test_identical_values_share_storage() {
+        let value = "race".repeat(64);
+        let mut store = CompactMemoryStore::new();
+        for i in 0..1000 {
+            store.put(format!("Ttokyo_cup\0{:08}", i), value.clone()).unwrap();
+        }
+        let after_1k = store.memory_usage();
+        for i in 1000..10_000 {
+            store.put(format!("Ttokyo_cup\0{:08}", i), value.clone()).unwrap();
+        }
+        let after_10k = store.memory_usage();
+
+        assert_eq!(store.len(), 10_000);
+        assert_eq!(store.distinct_values(), 1);
+        // 9000 件追加しても値本体は増えず、増えるのはエントリごとの管理領域とキーの残りだけ
+        assert!(after_10k - after_1k < 9000 * value.len() / 4);
+
+        let mut plain = MemoryStore::new();
+        for i in 0..10_000 {
+            plain.put(format!("Ttokyo_cup\0{:08}", i), value.clone()).unwrap();
+        }
+        assert!(after_10k < plain.memory_usage() / 4);
+    }
+
+    #[test]
+    fn test_released_values_leave_dedup_table() {
+        let mut store = CompactMemoryStore::new();
+        store.put("Ua".to_string(), "shared".to_string()).unwrap();
+        store.put("Ub".to_string(), "shared".to_string()).unwrap();
+        store.put("Uc".to_string(), "other".to_string()).unwrap();
+        assert_eq!(store.distinct_values(), 2);
+
+        store.delete("Ua").unwrap();
+        assert_eq!(store.distinct_values(), 2);
+        store.put("Ub".to_string(), "other".to_string()).unwrap();
+        assert_eq!(store.distinct_values(), 1);
+        let copy = store.clone();
+        store.delete("Ub").unwrap();
+        store.delete("Uc").unwrap();
+        assert!(store.is_empty());
+        assert_eq!(store.distinct_values(), 0);
+        assert_eq!(store.memory_usage(), 0);
+        assert_eq!(copy.len(), 2);
+        assert_eq!(copy.distinct_values(), 1);
+    }
+
+    #[test]
+    fn test_keys_and_scan_match_memory_store() {
+        // 接頭辞の長さが違うキーも全体のキー順で並ぶ
+        let keys = ["a\0z", "a\0b\0c", "a\0b", "a", "b\0\0", "a\0", "ab"];
+        let mut compact = CompactMemoryStore::new();
+        let mut plain = MemoryStore::new();
+        for (i, key) in keys.iter().enumerate() {
+            compact.put(key.to_string(), i.to_string()).unwrap();
+            plain.put(key.to_string(), i.to_string()).unwrap();
+        }
+        assert_eq!(compact.keys().unwrap(), plain.keys().unwrap());
+        for key in keys {
+            assert_eq!(compact.get(key).unwrap(), plain.get(key).unwrap());
+        }
+        assert_eq!(compact.get("a\0q").unwrap(), None);
+
+        let mut expected = plain.scan("a\0", "a\0c").unwrap();
+        expected.sort();
+        assert_eq!(compact.scan("a\0", "a\0c").unwrap(), expected);
+        assert_eq!(compact.scan_keys("a\0", "a\0c").unwrap(), vec!["a\0".to_string(), "a\0b".to_string(), "a\0b\0c".to_string()]);
+        assert!(matches!(compact.put(String::new(), "x".to_string()), Err(StoreError::InvalidKey)));
+    }
+
+    #[test]
+    fn test_narrow_scan_skips_unrelated_prefixes() {
+        let mut store = CompactMemoryStore::new();
+        for key in ["Ua", "M202508\0a", "M202509\0a", "M202509\0b\0c", "M202510\0a", "Ttokyo_cup\0r1", "Ttokyo_cup2\0r1"] {
+            store.put(key.to_string(), "x".to_string()).unwrap();
+        }
+        let visited = |start: &str, end: &str| -> Vec<String> {
+            store.overlapping_groups(start, end).map(|(prefix, _)| prefix.to_string()).collect()
+        };
+        // 接頭辞のないキーのグループ ("Ua") はどの範囲でも引くが、グループ内は範囲で絞る
+        assert_eq!(visited("M202509\0", "M202509\x01"), vec!["", "M202509\0", "M202509\0b\0"]);
+        assert_eq!(visited("Ttokyo_cup\0", "Ttokyo_cup\x01"), vec!["", "Ttokyo_cup\0"]);
+        // start の途中までを接頭辞に持つグループも範囲を持ち得る
+        assert_eq!(visited("M202509\0a", "M202509\0b"), vec!["", "M202509\0"]);
+        assert_eq!(store.scan_keys("M202509\0", "M202509\x01").unwrap(), vec!["M202509\0a", "M202509\0b\0c"]);
+    }
+
+    #[test]
+    fn test_scan_ranges_match_memory_store() {
+        let keys = ["a", "a\0", "a\0b", "a\0b\0", "a\0b\0c", "a\0c", "a\x01", "ab", "ab\0z", "b", "b\0\0"];
+        let mut compact = CompactMemoryStore::new();
+        let mut plain = MemoryStore::new();
+        for (i, key) in keys.iter().enumerate() {
+            compact.put(key.to_string(), i.to_string()).unwrap();
+            plain.put(key.to_string(), i.to_string()).unwrap();
+        }
+        let bounds = ["a", "a\0", "a\0a", "a\0b", "a\0b\0", "a\0b\0d", "a\x01", "ab\0", "ab\0zz", "b", "c"];
+        for start in bounds {
+            for end in bounds {
+                let mut expected = plain.scan(start, end).unwrap();
+                expected.sort();
+                assert_eq!(compact.scan(start, end).unwrap(), expected, "[{:?}, {:?})", start, end);
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_over_compact_store() {
+        let mut compact = BoatRaceEngine::new(CompactMemoryStore::new());
+        let mut plain = BoatRaceEngine::new(MemoryStore::new());
+        let schedule = sample_data();
+        compact.put_monthly_schedule(&schedule).unwrap();
+        plain.put_monthly_schedule(&schedule).unwrap();
+
+        assert_eq!(compact.get_monthly_schedule(202509).unwrap(), plain.get_monthly_schedule(202509).unwrap());
+        assert_eq!(compact.store().keys().unwrap(), plain.store().keys().unwrap());
+    }
+}
@@ -0,0 +1,68 @@
+//! 圧縮処理モジュール
+//!
+//! 大きな値の圧縮に使う deflate (RFC 1951) の圧縮・展開を flate2 で提供する (`compression` フィーチャー)。
+//! 出力は raw deflate ストリームなので、zlib などの他言語のライブラリでも展開できる
+
+use crate::{Result, StoreError};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder};
+use std::io::{Read, Write};
+
+/// deflate の最大圧縮率 (展開後のバイト数の見積もりの上限に使う)
+const MAX_RATIO: usize = 1032;
+
+/// deflate で圧縮する
+pub(crate) fn compress(input: &[u8]) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(input).expect("writing to a Vec does not fail");
+    encoder.finish().expect("writing to a Vec does not fail")
+}
+
+/// deflate ストリームを展開する
+///
+/// # Arguments
+/// * `input` - 圧縮されたストリーム
+/// * `size` - 展開後のバイト数
+pub(crate) fn decompress(input: &[u8], size: usize) -> Result<Vec<u8>> {
+    let corrupt = || StoreError::serialization("corrupt compressed value");
+    // 壊れた値の巨大な size で過大に確保しないよう、deflate の最大圧縮率で上限を設ける
+    let mut output = Vec::with_capacity(size.min(input.len().saturating_mul(MAX_RATIO)));
+    // size より長く展開される値は壊れているので、1バイト超えた時点で読むのをやめる
+    DeflateDecoder::new(input)
+        .take(size as u64 + 1)
+        .read_to_end(&mut output)
+        .map_err(|_| corrupt())?;
+    if output.len() != size {
+        return Err(corrupt());
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let repetitive = "平和島 トーキョー・ベイ・カップ ".repeat(400).into_bytes();
+        let mut pseudo_random = Vec::new();
+        let mut state = 1u32;
+        for _ in 0..5000 {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            pseudo_random.push((state >> 16) as u8);
+        }
+        for input in [Vec::new(), b"short".to_vec(), vec![0u8; 70000], repetitive.clone(), pseudo_random] {
+            let compressed = compress(&input);
+            assert_eq!(decompress(&compressed, input.len()).unwrap(), input);
+        }
+        assert!(compress(&repetitive).len() < repetitive.len() / 10);
+    }
+
+    #[test]
+    fn test_corrupt_input() {
+        let compressed = compress(&vec![7u8; 1000]);
+        assert!(decompress(&compressed, 999).is_err());
+        assert!(decompress(&compressed, 1001).is_err());
+        assert!(decompress(&compressed[..compressed.len() - 1], 1000).is_err());
+        assert!(decompress(&[0xff, 0x01, 0x00], 100).is_err());
+    }
+}
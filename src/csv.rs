@@ -0,0 +1,145 @@
+//! CSV 処理モジュール
+//!
+//! スケジュールの CSV 入出力で使う最小限の RFC 4180 形式の書き込み・読み込み機能を提供
+
+use std::io::{Read, Write};
+
+use crate::{Result, StoreError};
+
+/// Excel 向けの UTF-8 BOM
+const BOM: &str = "\u{feff}";
+
+/// 1行分のフィールドを書き込む
+///
+/// カンマ・ダブルクォート・改行を含むフィールドはダブルクォートで囲み、内部のダブルクォートは二重にする
+///
+/// # Arguments
+/// * `writer` - 出力先
+/// * `fields` - フィールドの一覧
+pub(crate) fn write_record<W: Write, S: AsRef<str>>(writer: &mut W, fields: &[S]) -> Result<()> {
+    let line = fields
+        .iter()
+        .map(|field| quote(field.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    writer.write_all(line.as_bytes())?;
+    writer.write_all(b"\r\n")?;
+    Ok(())
+}
+
+/// 必要に応じて UTF-8 BOM を書き込む
+pub(crate) fn write_bom<W: Write>(writer: &mut W, bom: bool) -> Result<()> {
+    if bom {
+        writer.write_all(BOM.as_bytes())?;
+    }
+    Ok(())
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// CSV 全体を読み込み、行ごとのフィールドに分解する
+///
+/// 先頭の UTF-8 BOM と空行は無視する
+///
+/// # Arguments
+/// * `reader` - 入力元
+///
+/// # Returns
+/// (行番号, フィールド) のベクター。行番号は1始まりで、レコードが始まる物理行
+pub(crate) fn read_records<R: Read>(mut reader: R) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let text = text.strip_prefix(BOM).unwrap_or(&text);
+
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(StoreError::ValidationError {
+            field: format!("row {}", record_line),
+            message: "unterminated quoted field".to_string(),
+        });
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_quoting() {
+        let mut buffer = Vec::new();
+        write_bom(&mut buffer, true).unwrap();
+        write_record(&mut buffer, &["a", "b,c", "say \"hi\"", "line1\nline2"]).unwrap();
+        write_record(&mut buffer, &["", "x"]).unwrap();
+
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        assert!(text.starts_with('\u{feff}'));
+        assert!(text.contains("\"b,c\",\"say \"\"hi\"\"\""));
+
+        let records = read_records(buffer.as_slice()).unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (1, vec!["a".to_string(), "b,c".to_string(), "say \"hi\"".to_string(), "line1\nline2".to_string()]),
+                (3, vec![String::new(), "x".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_records_rejects_unterminated_quote() {
+        assert!(read_records("a,\"b\n".as_bytes()).is_err());
+        assert_eq!(read_records("a,b".as_bytes()).unwrap(), vec![(1, vec!["a".to_string(), "b".to_string()])]);
+    }
+}
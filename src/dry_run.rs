@@ -0,0 +1,42 @@
+//! ドライランモジュール
+//!
+//! `BoatRaceEngine::dry_run` で書き込まれるはずだった変更の一覧を提供する
+
+use crate::overlay::Change;
+use crate::{KeyValueStore, Result};
+
+/// ドライランで書き込まれるはずだった変更
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DryRunReport {
+    /// 書き込まれるキーと値のバイト数 (キー順)
+    pub written: Vec<(String, usize)>,
+    /// 削除されるキー (キー順、実際のストアに存在するもののみ)
+    pub deleted: Vec<String>,
+}
+
+impl DryRunReport {
+    /// `OverlayStore` に保持した変更から結果を作る
+    ///
+    /// # Arguments
+    /// * `base` - 実際のストア (削除対象のキーが存在するかの確認に使う)
+    /// * `changes` - `OverlayStore::into_changes` の結果
+    pub(crate) fn from_changes(base: &impl KeyValueStore, changes: Vec<Change>) -> Result<Self> {
+        let mut report = DryRunReport::default();
+        for change in changes {
+            match change {
+                Change::Put { key, value } => report.written.push((key, value.len())),
+                Change::Delete { key } => {
+                    if base.contains_key(&key)? {
+                        report.deleted.push(key);
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// 変更がないか
+    pub fn is_empty(&self) -> bool {
+        self.written.is_empty() && self.deleted.is_empty()
+    }
+}
@@ -1,23 +1,993 @@
-/// 競艇データエンジン
-/// 
-/// KeyValueStoreを基盤とした競艇データ専用の高級API
+//! 競艇データエンジン
+//! 
+//! KeyValueStoreを基盤とした競艇データ専用の高級API
 
 use crate::{
-    key::{monthly_key, tournament_key, monthly_scan_range, tournament_scan_range, generate_tournament_id},
-    value::{serialize_to_string, deserialize_from_string},
-    KeyValueStore, Result, MonthlySchedule, RaceEvent,
+    key::{Key, STATISTICS_META_KEY, empty_month_marker_key, last_modified_key, parse_last_modified_key, parse_written_tournament_id, try_venue_index_key, venue_index_scan_range, name_index_key, name_index_scan_range, venue_index_namespace_range, name_index_namespace_range, try_racer_index_key, racer_index_scan_range, try_participants_key, try_tournament_meta_key, tournament_meta_scan_range, tournament_key_matches, classify_key, user_key, Namespace, PREFIX_INDEX, PREFIX_MONTHLY, try_daily_key, try_monthly_key, daily_scan_range, daily_month_scan_range, namespace_scan_range, odds_tournament_scan_range, PREFIX_DAILY, PREFIX_TOURNAMENT, try_tournament_key, validate_tournament_id, monthly_scan_range, months_scan_range, next_year_month, prev_year_month, validate_year_month, tournament_scan_range, tournament_time_range, try_racer_key, racer_scan_range, try_result_key, result_scan_range, try_odds_key, odds_scan_range, try_payout_key, payout_scan_range, conditions_key, conditions_scan_range, venue_key, venue_scan_range, yearly_scan_range, generate_tournament_id},
+    value::{deserialize_from_string, deserialize_tagged, tag_value, Codec, ValueCodec},
+    Grade, KeyValueStore, Result, StoreError, MonthlySchedule, TournamentStatus, Payout, RaceDayConditions, RaceEvent, RaceEventUpdate, RaceResult, Racer, VenueInfo,
 };
-use serde::{Serialize, de::DeserializeOwned};
-use chrono::{NaiveDate, Datelike};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{Read, Write};
+use std::ops::{ControlFlow, RangeInclusive};
+use std::any::TypeId;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use crate::dry_run::DryRunReport;
+use crate::overlay::OverlayStore;
+use crate::metrics::{EngineMetrics, OperationCounters};
+
+/// 大会削除の結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteReport {
+    /// 削除した月別ビューの数
+    pub monthly_entries: usize,
+    /// 削除した日別ビューの数
+    pub daily_entries: usize,
+    /// 削除したレースデータの数
+    pub race_data: usize,
+    /// 削除したレース結果の数
+    pub results: usize,
+    /// 削除した払戻金の数
+    pub payouts: usize,
+    /// 削除したオッズスナップショットの数
+    pub odds_snapshots: usize,
+    /// 削除した大会メタデータの数
+    pub metadata: usize,
+}
+
+impl DeleteReport {
+    /// 削除したキーの総数
+    pub fn total(&self) -> usize {
+        self.monthly_entries
+            + self.daily_entries
+            + self.race_data
+            + self.results
+            + self.payouts
+            + self.odds_snapshots
+            + self.metadata
+    }
+}
+
+/// スケジュール取り込み時に既存エントリと内容が異なる場合の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// 新しい内容で上書きする
+    Overwrite,
+    /// 既存の内容を残す
+    Skip,
+    /// `StoreError::Conflict` を返し、何も書き込まない
+    Error,
+}
+
+/// `put_monthly_schedule_checked_with` のオプション
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// 同じ会場で開催期間が重なる大会 (`find_schedule_conflicts`) を新たに生じさせるスケジュールを拒否する
+    pub reject_venue_conflicts: bool,
+}
+
+/// 既存エントリと内容が異なったイベント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportConflict {
+    /// 大会ID
+    pub tournament_id: String,
+    /// 既存のシリアライズ済みの値
+    pub before: String,
+    /// 取り込もうとしたシリアライズ済みの値
+    pub after: String,
+}
+
+/// スケジュール取り込みの結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// 新規に追加した大会ID
+    pub added: Vec<String>,
+    /// 既存と同じ内容だった大会ID
+    pub unchanged: Vec<String>,
+    /// 上書きした大会ID
+    pub overwritten: Vec<String>,
+    /// 既存と内容が異なった大会（ポリシーに関わらず記録する）
+    pub conflicts: Vec<ImportConflict>,
+}
+
+/// 月別スケジュールの差分
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleDiff {
+    /// 取り込み予定にのみ存在するイベント
+    pub added: Vec<RaceEvent>,
+    /// 保存済みにのみ存在するイベント
+    pub removed: Vec<RaceEvent>,
+    /// 内容が異なるイベント (変更前, 変更後)
+    pub changed: Vec<(RaceEvent, RaceEvent)>,
+}
+
+impl ScheduleDiff {
+    /// 差分がないか
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// 重複エントリ削除の結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DedupeReport {
+    /// (残したキー, 削除したキー) の一覧
+    pub collapsed: Vec<(String, Vec<String>)>,
+}
+
+impl DedupeReport {
+    /// 削除したキーの総数
+    pub fn removed(&self) -> usize {
+        self.collapsed.iter().map(|(_, removed)| removed.len()).sum()
+    }
+}
+
+/// スケジュール同期のオプション
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncOptions {
+    /// 削除した大会のうち、どの月にも登録が残らない大会のレースデータ (T) も削除する
+    pub purge_orphaned_races: bool,
+}
+
+/// スケジュール同期の結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// 新規に追加した大会ID
+    pub added: Vec<String>,
+    /// 内容を更新した大会ID
+    pub updated: Vec<String>,
+    /// 既存と同じ内容だった大会ID
+    pub unchanged: Vec<String>,
+    /// スケジュールから消えたため削除した大会ID
+    pub removed: Vec<String>,
+    /// 削除したレースデータの数
+    pub purged_races: usize,
+}
+
+/// 大会の概要
+#[derive(Debug, Clone)]
+pub struct TournamentSummary {
+    /// 大会ID
+    pub tournament_id: String,
+    /// 月別ビューのイベント情報（月別エントリがない場合は None）
+    pub event: Option<RaceEvent>,
+    /// 月別ビューに登録されている年月 (YYYYMM形式、昇順)
+    pub months: Vec<u32>,
+    /// 大会データ (T) のレース数
+    pub race_count: usize,
+    /// 最も古いレースのタイムスタンプ（レースがない場合は None）
+    pub first_race: Option<u64>,
+    /// 最も新しいレースのタイムスタンプ（レースがない場合は None）
+    pub last_race: Option<u64>,
+    /// レースデータのキーと値の合計バイト数
+    pub race_bytes: usize,
+}
+
+/// 大会ごとに集めたキー（月別ビューの登録月とレースデータのキー）
+#[derive(Default)]
+struct TournamentKeys {
+    months: BTreeSet<u32>,
+    races: Vec<(u64, String)>,
+}
+
+/// エンジンの書き込みによる論理的な変更（フックに渡す）
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EngineEvent {
+    /// 月別ビューのイベントを書き込んだ
+    MonthlyEventPut { year_month: u32, tournament_id: String },
+    /// 月別ビューのイベントを削除した
+    MonthlyEventDeleted { year_month: u32, tournament_id: String },
+    /// レースデータを書き込んだ
+    RaceDataPut { tournament_id: String, timestamp: u64 },
+    /// レースデータを削除した
+    RaceDataDeleted { tournament_id: String, timestamp: u64 },
+    /// レース結果を書き込んだ
+    RaceResultPut { tournament_id: String, timestamp: u64 },
+    /// 大会を削除した（個々のエントリの削除イベントの後に通知する）
+    TournamentDeleted { tournament_id: String },
+}
+
+impl EngineEvent {
+    /// 書き込み・削除したキーに対応するイベント（フックの対象外のキーは None）
+    fn from_key(key: &str, deleted: bool) -> Option<Self> {
+        match (key.parse::<Key>().ok()?, deleted) {
+            (Key::Monthly { year_month, tournament_id }, false) => Some(EngineEvent::MonthlyEventPut { year_month, tournament_id }),
+            (Key::Monthly { year_month, tournament_id }, true) => Some(EngineEvent::MonthlyEventDeleted { year_month, tournament_id }),
+            (Key::Tournament { tournament_id, timestamp }, false) => Some(EngineEvent::RaceDataPut { tournament_id, timestamp }),
+            (Key::Tournament { tournament_id, timestamp }, true) => Some(EngineEvent::RaceDataDeleted { tournament_id, timestamp }),
+            (Key::Result { tournament_id, timestamp }, false) => Some(EngineEvent::RaceResultPut { tournament_id, timestamp }),
+            _ => None,
+        }
+    }
+}
+
+/// フック内で発生した panic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookPanic {
+    /// panic したときにフックへ渡したイベント
+    pub event: EngineEvent,
+    /// panic のメッセージ (文字列以外のペイロードは "unknown panic")
+    pub message: String,
+}
+
+impl std::fmt::Display for HookPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "engine hook panicked on {:?}: {}", self.event, self.message)
+    }
+}
+
+/// レースデータの書き込み方 (既存のレースの扱い)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RaceWrite {
+    /// 上書きまたは新規作成
+    Upsert,
+    /// 新規作成のみ (既存なら KeyExists)
+    Create,
+    /// 更新のみ (存在しなければ NotFound)
+    Update,
+}
+
+/// 書き込み後に呼び出すフック
+type Hook = Box<dyn Fn(&EngineEvent) + Send>;
+
+/// 現在時刻 (エポックミリ秒) を返す時計
+type Clock = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+/// システム時計の現在時刻 (エポックミリ秒)
+fn system_clock() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// 大会単位のレースデータ統計
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TournamentStats {
+    /// レース数
+    pub race_count: usize,
+    /// 最も古いレースのタイムスタンプ
+    pub earliest: Option<u64>,
+    /// 最も新しいレースのタイムスタンプ
+    pub latest: Option<u64>,
+    /// キーと値の合計バイト数
+    pub total_bytes: usize,
+}
+
+/// データベース全体の統計
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DatabaseStatistics {
+    /// 月別ビューのエントリ数
+    pub monthly_entries: usize,
+    /// 月別ビューに登録されている大会数
+    pub tournaments: usize,
+    /// 大会データ (T) のレース数
+    pub races: usize,
+    /// 選手マスタの登録数
+    pub racers: usize,
+    /// 月別ビューに登録されている年月 (YYYYMM形式、昇順)
+    pub months: Vec<u32>,
+    /// グレード別の大会数
+    pub events_per_grade: HashMap<Grade, usize>,
+    /// 会場別の大会数
+    pub events_per_venue: HashMap<u32, usize>,
+    /// 全キーと値の合計バイト数
+    pub total_bytes: u64,
+    /// デシリアライズできなかった月別エントリ数
+    pub corrupted_entries: usize,
+    /// どの名前空間のキー形式も満たさず、集計から除いたキーの数 (ユーザー領域のキーは含まない)
+    pub unclassified_keys: usize,
+}
+
+/// 年間概要の1か月分
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MonthOverview {
+    /// 年月 (YYYYMM形式)
+    pub year_month: u32,
+    /// イベント数 (同一内容の重複登録は1件と数える)
+    pub events: usize,
+    /// グレード別のイベント数 (イベントのないグレードは含まない)
+    pub grades: BTreeMap<Grade, usize>,
+    /// イベントのある会場ID (昇順)
+    pub venues: Vec<u32>,
+}
+
+/// 年間の月別概要 (`year_overview`)
+///
+/// `Display` は1か月1行の表を出力する
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct YearOverview {
+    /// 対象の年
+    pub year: u32,
+    /// 1月から12月までの12か月分
+    pub months: Vec<MonthOverview>,
+    /// デシリアライズできなかった月別エントリ数
+    pub corrupted_entries: usize,
+}
+
+impl std::fmt::Display for YearOverview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "month    events")?;
+        for grade in Grade::ALL.iter().rev() {
+            write!(f, "  {:>4}", grade.as_str())?;
+        }
+        writeln!(f, "  venues")?;
+        for month in &self.months {
+            write!(f, "{:<7}  {:>6}", format_year_month(month.year_month), month.events)?;
+            for grade in Grade::ALL.iter().rev() {
+                write!(f, "  {:>4}", month.grades.get(grade).copied().unwrap_or(0))?;
+            }
+            let venues: Vec<String> = month.venues.iter().map(u32::to_string).collect();
+            writeln!(f, "  {}", if venues.is_empty() { "-".to_string() } else { venues.join(",") })?;
+        }
+        if self.corrupted_entries > 0 {
+            writeln!(f, "corrupted entries: {}", self.corrupted_entries)?;
+        }
+        Ok(())
+    }
+}
+
+/// エンジンが書き込みのたびに更新する統計カウンタ
+///
+/// メタデータキーに永続化されるため、再オープン後も全件走査なしで参照できる
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatisticsCounters {
+    /// 月別ビューのエントリ数
+    pub monthly_entries: usize,
+    /// 大会データ (T) のレース数
+    pub races: usize,
+    /// 月別ビューに登録されている大会数
+    pub tournaments: usize,
+}
+
+/// セカンダリインデックスの設定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexOptions {
+    /// 会場IDによるインデックスを書き込み時に維持する
+    pub by_venue: bool,
+    /// イベント名・会場名の文字バイグラムによるインデックスを書き込み時に維持する
+    pub by_name: bool,
+}
+
+impl IndexOptions {
+    /// いずれかのインデックスが有効か
+    pub fn any(&self) -> bool {
+        self.by_venue || self.by_name
+    }
+}
+
+/// インデックスの整合性チェック結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexCheck {
+    /// 月別ビューに対応するエントリがあるべきなのに存在しないインデックスキー
+    pub missing: Vec<String>,
+    /// 対応する月別ビューがない、または会場が食い違っているインデックスキー
+    pub stale: Vec<String>,
+}
+
+impl IndexCheck {
+    /// インデックスが月別ビューと一致しているか
+    pub fn is_consistent(&self) -> bool {
+        self.missing.is_empty() && self.stale.is_empty()
+    }
+}
+
+/// 月別ビュー (M) と大会データ (T) の整合性チェック結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// レースデータはあるが、どの月にも登録されていない大会ID（昇順）
+    pub orphaned_tournaments: Vec<String>,
+    /// 孤立した大会のレースデータのキー
+    pub orphaned_races: Vec<String>,
+    /// 値を RaceEvent にデシリアライズできない月別ビューのキー
+    pub corrupt_entries: Vec<String>,
+    /// 予約済みの名前空間のプレフィックスで始まるが、その名前空間のキー形式を満たさないキー
+    pub malformed_keys: Vec<String>,
+    /// 予約済みの名前空間にもユーザー領域 (U) にも属さないキー（報告のみで、修復でも削除しない）
+    pub unclassified_keys: Vec<String>,
+}
+
+impl IntegrityReport {
+    /// 問題が見つからなかったか (`unclassified_keys` は問題として扱わない)
+    pub fn is_clean(&self) -> bool {
+        self.orphaned_races.is_empty() && self.corrupt_entries.is_empty() && self.malformed_keys.is_empty()
+    }
+}
+
+/// 名前空間単位の削除結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClearReport {
+    /// 名前空間ごとの削除したキーの数（キーがなかった名前空間は含まない）
+    pub deleted: BTreeMap<Namespace, usize>,
+}
+
+impl ClearReport {
+    /// 削除したキーの総数
+    pub fn total(&self) -> usize {
+        self.deleted.values().sum()
+    }
+}
+
+/// 月別データのアーカイブ結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveReport {
+    /// 年月 (YYYYMM形式) ごとのアーカイブした月別エントリ数
+    pub months: BTreeMap<u32, usize>,
+    /// アーカイブした大会ID（昇順）
+    pub tournaments: Vec<String>,
+    /// アーカイブしたレースデータの数
+    pub races: usize,
+    /// アーカイブしたレース結果・払戻金・オッズ・メタデータ・インデックスのエントリ数
+    pub other_entries: usize,
+    /// カットオフ以降の月にも登録があるためアーカイブしなかった大会ID（昇順）
+    pub skipped: Vec<String>,
+}
+
+/// JSON エクスポートの件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportStats {
+    /// 出力した年月の数
+    pub months: usize,
+    /// 出力した月別エントリ (イベント) の数
+    pub events: usize,
+    /// 出力したレースデータの数
+    pub races: usize,
+}
+
+/// JSON インポートの結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportStats {
+    /// 取り込んだ年月の数
+    pub months: usize,
+    /// 全月分をまとめたイベントの取り込み結果
+    pub events: ImportReport,
+    /// 書き込んだレースデータの数
+    pub races: usize,
+}
+
+/// `get_monthly_schedule_with` のオプション
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScheduleOptions {
+    /// 中止 (`TournamentStatus::Cancelled`) にした大会を除く
+    pub exclude_cancelled: bool,
+}
+
+/// 大会の状態を保存するメタデータのキー (`list_tournament_meta` にも現れる)
+const STATUS_META_KEY: &str = "status";
+
+/// CSV 出力のオプション
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// 先頭に UTF-8 BOM を付ける (Excel で文字化けさせないため)
+    pub bom: bool,
+}
+
+/// `dump` の出力対象
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DumpOptions {
+    /// このプレフィックスで始まるキーのみ出力する (例: "M" で月別ビュー全体)
+    pub prefix: Option<String>,
+    /// 出力する最大行数
+    pub limit: Option<usize>,
+}
+
+/// スケジュール CSV の列 (年間 CSV は先頭に year_month 列が付く)
+const CSV_COLUMNS: [&str; 7] = ["venue_id", "venue_name", "event_name", "grade", "start_date", "duration_days", "end_date"];
+
+/// 書き込めるキーの最大バイト数の既定値
+pub const DEFAULT_MAX_KEY_BYTES: usize = 1024;
+
+/// 書き込める値の最大バイト数の既定値 (4 MiB)
+pub const DEFAULT_MAX_VALUE_BYTES: usize = 4 * 1024 * 1024;
+
+/// `next_event_after` などが検索する月数の既定値
+pub const DEFAULT_SEARCH_HORIZON_MONTHS: u32 = 12;
+
+/// `put_race_data_unique` がタイムスタンプをずらす最大回数 (1 ms 単位なので 1 秒分)
+pub const MAX_TIMESTAMP_BUMPS: u64 = 1000;
+
+/// JSON エクスポートの文書形式
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonExport {
+    /// 年月 ("YYYY-MM") ごとのイベント
+    months: BTreeMap<String, Vec<RaceEvent>>,
+    /// レースデータ (型は呼び出し側が決めるため保存形式のまま)
+    #[serde(default)]
+    races: Vec<JsonRace>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonRace {
+    tournament_id: String,
+    timestamp: u64,
+    /// ストアに保存された値 (bincode + Base64 または JSON)
+    data: String,
+}
+
+/// `repair` で削除する対象
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepairOptions {
+    /// 孤立した大会のレースデータを削除する
+    pub delete_orphans: bool,
+    /// デシリアライズできない月別ビューを削除する
+    pub delete_corrupt: bool,
+    /// 解釈できないキーを削除する
+    pub delete_malformed: bool,
+}
 
 pub struct BoatRaceEngine<K: KeyValueStore> {
     store: K,
+    strict_venues: bool,
+    /// 書き込み時にイベントを検証するか
+    validate_events: bool,
+    /// 検証時に許容する venue_id の範囲
+    venue_ids: RangeInclusive<u32>,
+    indexes: IndexOptions,
+    /// 統計カウンタ（メタデータキーが存在しない場合は None）
+    counters: Option<StatisticsCounters>,
+    /// 大会IDごとの月別ビューのエントリ数 (統計カウンタの大会数を差分で更新するために使う)
+    ///
+    /// 統計カウンタと同じく、書き込みはすべて `commit` を通る前提で保持する。
+    /// 未作成の場合 (None) は最初に必要になった時点で月別ビューを1度だけ走査して作る
+    tournament_months: Option<HashMap<String, usize>>,
+    /// 書き込み時の値の形式
+    codec: Codec,
+    /// 大きな値の圧縮設定 (None は圧縮しない)
+    #[cfg(feature = "compression")]
+    compression: Option<crate::value::Compression>,
+    /// 書き込めるキーの最大バイト数
+    max_key_bytes: usize,
+    /// 書き込める値の最大バイト数 (エンコード・圧縮後)
+    max_value_bytes: usize,
+    /// 書き込み成功後に呼び出すフック
+    hook: Option<Hook>,
+    /// フック内で最後に発生した panic
+    last_hook_panic: Mutex<Option<HookPanic>>,
+    /// ストア操作のカウンタ
+    metrics: OperationCounters,
+    /// 最終更新時刻の記録に使う時計
+    clock: Clock,
+    /// `next_event_after` などが検索する月数
+    search_horizon_months: u32,
+    /// `with_type_tag` で登録した型ごとの型名
+    type_tags: HashMap<TypeId, &'static str>,
+}
+
+/// ストアの内容は出力せず、ストアの要約と設定だけを出力する
+impl<K: KeyValueStore + std::fmt::Debug> std::fmt::Debug for BoatRaceEngine<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("BoatRaceEngine");
+        debug.field("store", &self.store).field("codec", &self.codec);
+        #[cfg(feature = "compression")]
+        debug.field("compression", &self.compression);
+        debug
+            .field("indexes", &self.indexes)
+            .field("counters", &self.counters)
+            .field("hook", &self.hook.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl<K: KeyValueStore> BoatRaceEngine<K> {
     /// 新しいエンジンインスタンスを作成
     pub fn new(store: K) -> Self {
-        Self { store }
+        // 読み込めないカウンタは存在しないものとして扱い、必要になった時点で再集計する
+        let counters = store
+            .get(STATISTICS_META_KEY)
+            .ok()
+            .flatten()
+            .and_then(|value| deserialize_from_string(&value).ok());
+        Self {
+            store,
+            strict_venues: false,
+            validate_events: true,
+            venue_ids: crate::DEFAULT_VENUE_IDS,
+            indexes: IndexOptions::default(),
+            counters,
+            tournament_months: None,
+            codec: Codec::default(),
+            #[cfg(feature = "compression")]
+            compression: None,
+            max_key_bytes: DEFAULT_MAX_KEY_BYTES,
+            max_value_bytes: DEFAULT_MAX_VALUE_BYTES,
+            hook: None,
+            last_hook_panic: Mutex::new(None),
+            metrics: OperationCounters::default(),
+            clock: Arc::new(system_clock),
+            search_horizon_months: DEFAULT_SEARCH_HORIZON_MONTHS,
+            type_tags: HashMap::new(),
+        }
+    }
+
+    /// セカンダリインデックスを有効にしたエンジンインスタンスを作成
+    ///
+    /// 既存のデータベースで有効にする場合は `rebuild_indexes` で既存エントリのインデックスを作成すること
+    ///
+    /// # Arguments
+    /// * `store` - ストア
+    /// * `options` - 維持するインデックス
+    pub fn with_indexing(store: K, options: IndexOptions) -> Self {
+        Self {
+            indexes: options,
+            ..Self::new(store)
+        }
+    }
+
+    /// 会場マスタの厳格チェックを設定
+    ///
+    /// 有効にすると、スケジュール登録時に未登録の venue_id や
+    /// 会場マスタと異なる venue_name を持つイベントを `StoreError::InvalidVenue` で拒否する。
+    /// 会場マスタにない venue_id は全国の会場表 (`venues`) の正式名称と照合する
+    /// (別表記の会場名は `RaceEvent::normalize_venue` で正式名称にそろえてから登録する)
+    pub fn with_strict_venues(mut self, strict: bool) -> Self {
+        self.strict_venues = strict;
+        self
+    }
+
+    /// イベント検証の有無を設定
+    ///
+    /// 既定では有効。不正な開始日などを含む既存データを扱う場合に無効にする。
+    /// 無効時は開始日が不正なイベントも保存するが、日別ビューは登録しない。
+    pub fn with_validation(mut self, validate: bool) -> Self {
+        self.validate_events = validate;
+        self
+    }
+
+    /// 検証時に許容する venue_id の範囲を設定（既定は 1..=24）
+    pub fn with_venue_id_range(mut self, venue_ids: RangeInclusive<u32>) -> Self {
+        self.venue_ids = venue_ids;
+        self
+    }
+
+    /// 書き込む値の形式を設定（既定は `Codec::BincodeBase64`）
+    ///
+    /// 読み込み時は形式を自動判別するため、既存のデータベースで切り替えても既存の値はそのまま読める。
+    /// `Codec::Json` にすると FileStore のファイルを人が読める
+    pub fn with_codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// 大きな値の圧縮を設定（既定は圧縮しない。`compression` フィーチャー）
+    ///
+    /// シリアライズしたバイト数が `threshold` を超える値を、Base64 にする前に `algorithm` で圧縮して書き込む。
+    /// 読み込み時は圧縮の有無を自動判別する
+    #[cfg(feature = "compression")]
+    pub fn with_compression(mut self, compression: crate::value::Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// 書き込めるキーの最大バイト数を設定（既定は `DEFAULT_MAX_KEY_BYTES`）
+    pub fn with_max_key_bytes(mut self, limit: usize) -> Self {
+        self.max_key_bytes = limit;
+        self
+    }
+
+    /// 書き込める値の最大バイト数を設定（既定は `DEFAULT_MAX_VALUE_BYTES`）
+    ///
+    /// 上限はストアに格納する文字列 (エンコード・圧縮後) の大きさに対して適用する。
+    /// 上限を超える書き込みはストアに触れる前に `StoreError::ValueTooLarge` で拒否する
+    pub fn with_max_value_bytes(mut self, limit: usize) -> Self {
+        self.max_value_bytes = limit;
+        self
+    }
+
+    /// `next_event_after` / `next_event_at_venue` が検索する月数を設定
+    ///
+    /// 既定値は `DEFAULT_SEARCH_HORIZON_MONTHS`。0 を指定した場合は1か月とみなす
+    ///
+    /// # Arguments
+    /// * `months` - 基準日の月から数えて検索する月数
+    pub fn with_search_horizon(mut self, months: u32) -> Self {
+        self.search_horizon_months = months.max(1);
+        self
+    }
+
+    /// 最終更新時刻の記録に使う時計を設定
+    ///
+    /// 既定ではシステム時計を使う。テストで時刻を固定する場合に差し替える
+    ///
+    /// # Arguments
+    /// * `clock` - 現在時刻 (エポックミリ秒) を返す関数
+    pub fn with_clock(mut self, clock: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// レースデータの型に型名を登録する
+    ///
+    /// `tournament::<T>` のハンドルで書き込む値に型名を記録し、読み込み時に型名を確かめる。
+    /// 別の型名が記録された値を読むと `expected type 型名, found 記録された型名` の
+    /// `StoreError::SerializationError` になる。型名のない値と汎用のメソッドは従来どおり読める
+    ///
+    /// # Arguments
+    /// * `type_name` - 値に記録する型名 (空でなく ':' を含まないこと。不正な場合は書き込み時にエラー)
+    pub fn with_type_tag<T: 'static>(mut self, type_name: &'static str) -> Self {
+        self.type_tags.insert(TypeId::of::<T>(), type_name);
+        self
+    }
+
+    /// 型を固定した大会のレースデータ操作を取得
+    ///
+    /// 型を一度だけ指定すれば、以降の読み書きでターボフィッシュを書かずに済む
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    pub fn tournament<T>(&mut self, tournament_id: &str) -> TournamentHandle<'_, K, T> {
+        TournamentHandle {
+            engine: self,
+            tournament_id: tournament_id.to_string(),
+            _type: PhantomData,
+        }
+    }
+
+    /// `with_type_tag` で登録した型名
+    fn type_tag<T: 'static>(&self) -> Option<&'static str> {
+        self.type_tags.get(&TypeId::of::<T>()).copied()
+    }
+
+    /// 値を読み、型名が登録された型なら記録された型名も確かめてデシリアライズする
+    fn store_read_typed<T: DeserializeOwned + 'static>(&self, key: &str) -> Result<Option<T>> {
+        let Some(expected) = self.type_tag::<T>() else {
+            return self.store_read(key);
+        };
+        self.metrics.record_get();
+        self.store
+            .with_value(key, |value| {
+                deserialize_tagged(value, expected).map_err(|error| {
+                    self.metrics.record_serialization_failure();
+                    error.with_key(key)
+                })
+            })?
+            .transpose()
+    }
+
+    /// 書き込みを通知するフックを設定（既存のフックは置き換える）
+    ///
+    /// フックは月別ビュー・大会データ・レース結果への書き込みがストアに反映された後に、
+    /// 書き込んだ順 (書き込み → 削除) で `EngineEvent` ごとに呼び出される。
+    /// フックは書き込みを取り消せない。フック内の panic は捕捉して `metrics().hook_panics` に数え、
+    /// 最後の panic を `last_hook_panic` で取得できるように保持する
+    /// (`tracing` フィーチャーでは error イベントも出力する)。操作は成功として返す
+    ///
+    /// # Arguments
+    /// * `hook` - 変更ごとに呼び出す関数
+    pub fn set_hook(&mut self, hook: Box<dyn Fn(&EngineEvent) + Send>) {
+        self.hook = Some(hook);
+    }
+
+    /// フックを解除
+    pub fn clear_hook(&mut self) {
+        self.hook = None;
+    }
+
+    /// フック内で最後に発生した panic を取得
+    ///
+    /// # Returns
+    /// 最後の panic のイベントとメッセージ（panic が発生していない場合は None）
+    pub fn last_hook_panic(&self) -> Option<HookPanic> {
+        self.last_hook_panic.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    /// フックにイベントを通知
+    fn notify(&self, events: &[EngineEvent]) {
+        let Some(hook) = &self.hook else {
+            return;
+        };
+        for event in events {
+            if let Err(panic) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| hook(event))) {
+                self.metrics.record_hook_panic();
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("unknown panic");
+                #[cfg(feature = "tracing")]
+                tracing::error!(event = ?event, message, "engine hook panicked");
+                let panic = HookPanic { event: event.clone(), message: message.to_string() };
+                *self.last_hook_panic.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(panic);
+            }
+        }
+    }
+
+    /// メトリクスを取得
+    ///
+    /// ストア操作の回数はこのエンジンの生成時からの累計。エントリ数は統計カウンタから取得し、
+    /// カウンタが存在しない場合は `counters` が None になる (`rebuild_statistics` で作成できる)
+    pub fn metrics(&self) -> EngineMetrics {
+        self.metrics.snapshot(self.counters)
+    }
+
+    fn store_get(&self, key: &str) -> Result<Option<String>> {
+        self.metrics.record_get();
+        self.store.get(key)
+    }
+
+    /// キーの存在確認 (値は複製しない)
+    fn store_contains(&self, key: &str) -> Result<bool> {
+        self.metrics.record_get();
+        self.store.contains_key(key)
+    }
+
+    fn store_keys(&self) -> Result<Vec<String>> {
+        self.metrics.record_scan();
+        self.store.keys()
+    }
+
+    fn store_scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+        self.metrics.record_scan();
+        let mut results = self.store.scan(start, end)?;
+        results.retain(|(key, _)| self.is_well_formed(key));
+        Ok(results)
+    }
+
+    fn store_scan_keys(&mut self, start: &str, end: &str) -> Result<Vec<String>> {
+        self.metrics.record_scan();
+        let mut keys = self.store.scan_keys(start, end)?;
+        keys.retain(|key| self.is_well_formed(key));
+        Ok(keys)
+    }
+
+    /// キーの値を複製せずにデシリアライズする
+    fn store_read<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.metrics.record_get();
+        self.store.with_value(key, |value| decode_with(&self.metrics, key, value))?.transpose()
+    }
+
+    /// 範囲内のエントリを値を複製せずにデシリアライズする (形式の不正なキーは読み飛ばす。順不同)
+    ///
+    /// デシリアライズに失敗した値があればキーを付けたエラーを返す
+    fn store_scan_decoded<T: DeserializeOwned>(&mut self, start: &str, end: &str) -> Result<Vec<(String, T)>> {
+        self.store_scan_decoded_where(start, end, |_| true)
+    }
+
+    /// `store_scan_decoded` のうち、`keep` が true を返すキーの値だけをデシリアライズする
+    fn store_scan_decoded_where<T: DeserializeOwned>(
+        &mut self,
+        start: &str,
+        end: &str,
+        keep: impl Fn(&str) -> bool,
+    ) -> Result<Vec<(String, T)>> {
+        self.metrics.record_scan();
+        let metrics = &self.metrics;
+        let mut entries = Vec::new();
+        self.store.scan_with(start, end, |key, value| {
+            if well_formed(metrics, key) && keep(key) {
+                entries.push((key.to_string(), decode_with(metrics, key, value)?));
+            }
+            Ok(())
+        })?;
+        Ok(entries)
+    }
+
+    /// スキャン結果のキーの形式を検証し、不正なキーは読み飛ばしてメトリクスに記録する
+    fn is_well_formed(&self, key: &str) -> bool {
+        well_formed(&self.metrics, key)
+    }
+
+    fn store_put(&mut self, key: String, value: String) -> Result<()> {
+        let bytes = key.len() + value.len();
+        self.store.put(key, value)?;
+        self.metrics.record_puts(1, bytes);
+        Ok(())
+    }
+
+    /// 削除と書き込みをまとめて適用し、途中で失敗した場合は適用前の状態に戻す
+    ///
+    /// `apply_batch` が1件ずつ書き込むストアでは、失敗までの書き込みが残り得るため、
+    /// 変更するキーの現在の値を控えておき、失敗したら書き戻す (なかったキーは削除する)。
+    /// 書き戻しにも失敗した場合は元のエラーを返す (`check_integrity` / `repair` で修復する)
+    fn store_apply_batch(&mut self, puts: Vec<(String, String)>, deletes: &[String]) -> Result<()> {
+        let (count, bytes) = (puts.len(), puts.iter().map(|(key, value)| key.len() + value.len()).sum());
+        let mut previous: BTreeMap<String, Option<String>> = BTreeMap::new();
+        for key in deletes.iter().chain(puts.iter().map(|(key, _)| key)) {
+            if !previous.contains_key(key) {
+                previous.insert(key.clone(), self.store.get(key)?);
+            }
+        }
+        if let Err(error) = self.store.apply_batch(puts, deletes) {
+            self.rollback_batch(previous);
+            return Err(error);
+        }
+        self.metrics.record_puts(count, bytes);
+        self.metrics.record_deletes(deletes.len());
+        Ok(())
+    }
+
+    /// 失敗したバッチで変更された可能性のあるキーを控えておいた値に戻す
+    fn rollback_batch(&mut self, previous: BTreeMap<String, Option<String>>) {
+        let mut restores = Vec::new();
+        let mut removals = Vec::new();
+        for (key, value) in previous {
+            match value {
+                Some(value) => restores.push((key, value)),
+                None => removals.push(key),
+            }
+        }
+        let result = self.store.apply_batch(restores, &removals);
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            tracing::warn!(%error, "rolling back a failed batch failed");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = result;
+    }
+
+    /// ストアから読んだ値をデシリアライズし、失敗した場合はエラーにキーを付ける
+    fn decode_entry<T: DeserializeOwned>(&self, key: &str, value: &str) -> Result<T> {
+        decode_with(&self.metrics, key, value)
+    }
+
+    /// スキャン結果をキー順に並べてからデシリアライズする (`rayon` フィーチャーが有効なら並列に)
+    ///
+    /// 失敗した値が複数ある場合、どのキーのエラーを返すかは決まっていない
+    fn decode_sorted<T: DeserializeOwned + Send>(&self, mut results: Vec<(String, String)>) -> Result<Vec<(String, T)>> {
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        let metrics = &self.metrics;
+        let decode = |(key, value): (String, String)| {
+            let decoded = decode_with(metrics, &key, &value)?;
+            Ok((key, decoded))
+        };
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            results.into_par_iter().map(decode).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            results.into_iter().map(decode).collect()
+        }
+    }
+
+    /// 月別エントリを (venue_id, event_name, start_date) が等しいものごとにまとめる
+    ///
+    /// 各グループの先頭は残すべきエントリ（大会IDが現行の生成規則と一致するもの、なければキー順で最初のもの）
+    fn group_duplicates(&self, mut entries: Vec<(String, RaceEvent)>) -> Vec<Vec<(String, RaceEvent)>> {
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut groups: BTreeMap<(u32, String, String), Vec<(String, RaceEvent)>> = BTreeMap::new();
+        for (key, event) in entries {
+            let identity = (event.venue_id, event.event_name.clone(), event.start_date.clone());
+            groups.entry(identity).or_default().push((key, event));
+        }
+
+        groups
+            .into_values()
+            .map(|mut group| {
+                let canonical = group.iter().position(|(key, event)| {
+                    matches!(key.parse::<Key>(), Ok(Key::Monthly { tournament_id, .. })
+                        if tournament_id == event_tournament_id(event))
+                });
+                if let Some(index) = canonical {
+                    let entry = group.remove(index);
+                    group.insert(0, entry);
+                }
+                group
+            })
+            .collect()
+    }
+
+    /// 書き込むエントリのキーと値の大きさを検査
+    fn check_entry_size(&self, key: &str, value: &str) -> Result<()> {
+        if key.len() > self.max_key_bytes {
+            return Err(StoreError::KeyTooLarge { size: key.len(), limit: self.max_key_bytes });
+        }
+        if value.len() > self.max_value_bytes {
+            return Err(StoreError::ValueTooLarge { size: value.len(), limit: self.max_value_bytes });
+        }
+        Ok(())
+    }
+
+    /// 値を設定された形式でエンコードし、必要なら圧縮する
+    fn encode_value<T: Serialize>(&self, value: &T) -> Result<String> {
+        #[cfg(feature = "compression")]
+        if let Some(compression) = &self.compression {
+            return self.codec.encode_compressed(value, compression)
+                .inspect_err(|_| self.metrics.record_serialization_failure());
+        }
+        self.codec.encode(value).inspect_err(|_| self.metrics.record_serialization_failure())
     }
 
     /// ストアへの参照を取得
@@ -25,6 +995,91 @@ impl<K: KeyValueStore> BoatRaceEngine<K> {
         &self.store
     }
 
+    /// ストアへの可変参照を取得
+    ///
+    /// ストアへ直接書き込んだ内容は統計カウンタやインデックスに反映されないため、
+    /// 書き込んだ後は `rebuild_statistics` / `rebuild_indexes` を呼ぶこと
+    ///
+    /// # Example
+    /// ```rust
+    /// use norimaki_db::{BoatRaceEngine, KeyValueStore, MemoryStore};
+    ///
+    /// let mut engine = BoatRaceEngine::new(MemoryStore::new());
+    /// engine.put_race_data("tokyo_bay_cup", 1694524800000, &"R1")?;
+    ///
+    /// // セッションの途中でストアの内容を別のストアへ退避
+    /// let mut backup = MemoryStore::new();
+    /// for (key, value) in engine.store_mut().scan("\u{1}", "\u{10FFFF}")? {
+    ///     backup.put(key, value)?;
+    /// }
+    ///
+    /// // 退避後もエンジンをそのまま使い続けられる
+    /// engine.put_race_data("tokyo_bay_cup", 1694526600000, &"R2")?;
+    /// assert_eq!(engine.count_races("tokyo_bay_cup")?, 2);
+    ///
+    /// // 退避したストアからエンジンを作り直す
+    /// let mut restored = BoatRaceEngine::new(backup);
+    /// assert_eq!(restored.count_races("tokyo_bay_cup")?, 1);
+    /// # Ok::<(), norimaki_db::StoreError>(())
+    /// ```
+    pub fn store_mut(&mut self) -> &mut K {
+        &mut self.store
+    }
+
+    /// エンジンを破棄してストアを取り出す
+    ///
+    /// 取り出したストアは `BoatRaceEngine::new` に渡せば再びエンジンとして使える
+    pub fn into_store(self) -> K {
+        self.store
+    }
+
+    /// 同じ設定で別のストアを使うエンジンを作成
+    ///
+    /// 設定と統計カウンタを引き継ぐ。フック・最後のフックの panic・操作のメトリクスは引き継がない
+    fn with_store<S: KeyValueStore>(&self, store: S) -> BoatRaceEngine<S> {
+        BoatRaceEngine {
+            store,
+            strict_venues: self.strict_venues,
+            validate_events: self.validate_events,
+            venue_ids: self.venue_ids.clone(),
+            indexes: self.indexes,
+            counters: self.counters,
+            tournament_months: self.tournament_months.clone(),
+            codec: self.codec,
+            #[cfg(feature = "compression")]
+            compression: self.compression,
+            max_key_bytes: self.max_key_bytes,
+            max_value_bytes: self.max_value_bytes,
+            hook: None,
+            last_hook_panic: Mutex::new(None),
+            metrics: OperationCounters::default(),
+            clock: Arc::clone(&self.clock),
+            search_horizon_months: self.search_horizon_months,
+            type_tags: self.type_tags.clone(),
+        }
+    }
+
+    /// 書き込みを実際のストアに反映せずに処理を実行
+    ///
+    /// クロージャには同じ設定で `OverlayStore` を使うエンジンが渡される。読み込みは実際のストアに対して行い、
+    /// 書き込み・削除は捨てられる (クロージャ内の読み込みはそれまでの書き込みを反映する)。
+    /// フックは呼び出されず、統計カウンタもこのエンジンには反映されない
+    ///
+    /// # Arguments
+    /// * `f` - 実行する処理
+    ///
+    /// # Returns
+    /// クロージャの戻り値と、書き込まれるはずだった変更の一覧
+    pub fn dry_run<T, F>(&self, f: F) -> Result<(T, DryRunReport)>
+    where
+        F: FnOnce(&mut BoatRaceEngine<OverlayStore<'_, K>>) -> Result<T>,
+    {
+        let mut engine = self.with_store(OverlayStore::new(&self.store, crate::MemoryStore::new()));
+        let output = f(&mut engine)?;
+        let report = DryRunReport::from_changes(&self.store, engine.into_store().into_changes())?;
+        Ok((output, report))
+    }
+
     /// 月別スケジュールを保存
     /// 
     /// # Arguments
@@ -32,297 +1087,7772 @@ impl<K: KeyValueStore> BoatRaceEngine<K> {
     /// 
     /// # Returns
     /// 操作結果
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(year_month = %schedule.year_month, events = schedule.events.len())))]
     pub fn put_monthly_schedule(&mut self, schedule: &MonthlySchedule) -> Result<()> {
-        // 年月をu32に変換 (例: "2025-09" -> 202509)
-        let year_month = parse_year_month(&schedule.year_month)?;
-        
-        for event in &schedule.events {
-            let tournament_id = generate_tournament_id(&event.venue_name, &event.event_name);
-            let key = monthly_key(year_month, &tournament_id);
-            let value = serialize_to_string(event)?;
-            self.store.put(key, value)?;
-        }
-        
+        self.import_schedule(schedule, ConflictPolicy::Overwrite)?;
         Ok(())
     }
 
-    /// 月別スケジュールを取得
-    /// 
+    /// 公式サイト形式のスケジュール JSON を解析して保存
+    ///
+    /// 解析は `import::parse_official_schedule` を参照
+    ///
     /// # Arguments
-    /// * `year_month` - 取得対象の年月 (例: 202509)
-    /// 
+    /// * `json` - 公式サイト形式の JSON 文字列
+    ///
     /// # Returns
-    /// 月別スケジュール
-    pub fn get_monthly_schedule(&mut self, year_month: u32) -> Result<MonthlySchedule> {
-        let (start, end) = monthly_scan_range(year_month);
-        let results = self.store.scan(&start, &end)?;
-        
-        let mut events = Vec::new();
-        for (_, value) in results {
-            let event: RaceEvent = deserialize_from_string(&value)?;
-            events.push(event);
-        }
-        
-        // 開始日でソート
-        events.sort_by(|a, b| a.start_date.cmp(&b.start_date));
-        
-        Ok(MonthlySchedule {
-            year_month: format_year_month(year_month),
-            events,
-        })
+    /// 保存した月別スケジュール
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(bytes = json.len())))]
+    pub fn import_official_schedule(&mut self, json: &str) -> Result<MonthlySchedule> {
+        let schedule = crate::import::parse_official_schedule(json)?;
+        self.put_monthly_schedule(&schedule)?;
+        Ok(schedule)
+    }
+
+    /// 既存エントリとの食い違いを検出しながら月別スケジュールを保存
+    ///
+    /// 既存エントリとデシリアライズ結果が等しいイベントは書き込まない。
+    /// 内容が異なるイベントは `policy` に従って上書き・スキップ・エラーのいずれかで扱う。
+    /// 上書き時は、新しい開催日に含まれない旧開催日の日別ビューを削除する。
+    ///
+    /// # Arguments
+    /// * `schedule` - 保存する月別スケジュール
+    /// * `policy` - 内容が異なる場合の扱い
+    ///
+    /// # Returns
+    /// 取り込み結果。`ConflictPolicy::Error` で食い違いがあった場合は `StoreError::Conflict`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(year_month = %schedule.year_month, events = schedule.events.len())))]
+    pub fn put_monthly_schedule_checked(&mut self, schedule: &MonthlySchedule, policy: ConflictPolicy) -> Result<ImportReport> {
+        self.import_schedule(schedule, policy)
+    }
+
+    /// オプションを指定して、既存エントリとの食い違いを検出しながら月別スケジュールを保存
+    ///
+    /// `options.reject_venue_conflicts` が有効な場合、取り込むイベントが同じ会場の別の大会
+    /// (取り込むスケジュール内、またはイベントが開催される各月に登録済みのもの) と開催期間が重なると
+    /// `StoreError::ValidationError` を返し、何も書き込まない
+    ///
+    /// # Arguments
+    /// * `schedule` - 保存する月別スケジュール
+    /// * `policy` - 内容が異なる場合の扱い
+    /// * `options` - 取り込みオプション
+    ///
+    /// # Returns
+    /// 取り込み結果
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(year_month = %schedule.year_month, events = schedule.events.len())))]
+    pub fn put_monthly_schedule_checked_with(
+        &mut self,
+        schedule: &MonthlySchedule,
+        policy: ConflictPolicy,
+        options: ImportOptions,
+    ) -> Result<ImportReport> {
+        if options.reject_venue_conflicts {
+            self.check_venue_conflicts(&schedule.events)?;
+        }
+        self.import_schedule(schedule, policy)
+    }
+
+    /// 取り込むイベントが同じ会場の別の大会と開催期間が重ならないか確認
+    ///
+    /// 既存の大会は各イベントが開催される全月から集め、同じ大会ID (取り込みで置き換わるもの) は比較しない
+    fn check_venue_conflicts(&mut self, events: &[RaceEvent]) -> Result<()> {
+        let mut existing: BTreeMap<String, RaceEvent> = BTreeMap::new();
+        let mut months = BTreeSet::new();
+        for event in events {
+            // 日付が不正なイベントは取り込み時の検証に任せる
+            months.extend(event.months_covered().unwrap_or_default());
+        }
+        for year_month in months {
+            for event in self.read_month_events(year_month)? {
+                existing.entry(event_tournament_id(&event)).or_insert(event);
+            }
+        }
+        for event in events {
+            existing.remove(&event_tournament_id(event));
+        }
+
+        for (index, event) in events.iter().enumerate() {
+            let others = events[index + 1..].iter().chain(existing.values());
+            for other in others {
+                if other.venue_id == event.venue_id
+                    && event_tournament_id(other) != event_tournament_id(event)
+                    && event.overlaps(other).unwrap_or(false)
+                {
+                    return Err(crate::StoreError::ValidationError {
+                        field: "venue_id".to_string(),
+                        message: format!(
+                            "{:?} ({}, {} days) overlaps {:?} ({}, {} days) at venue {}",
+                            event.event_name,
+                            event.start_date,
+                            event.duration_days,
+                            other.event_name,
+                            other.start_date,
+                            other.duration_days,
+                            event.venue_id
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 月別スケジュールの保存 (put_monthly_schedule / put_monthly_schedule_checked の共通処理)
+    fn import_schedule(&mut self, schedule: &MonthlySchedule, policy: ConflictPolicy) -> Result<ImportReport> {
+        // 年月をu32に変換 (例: "2025-09" -> 202509)
+        let year_month = parse_year_month(&schedule.year_month)?;
+        let mut puts = Vec::new();
+        let mut deletes = Vec::new();
+        let report = self.plan_schedule_import(year_month, &schedule.events, policy, &mut puts, &mut deletes)?;
+        self.commit(puts, deletes)?;
+        Ok(report)
+    }
+
+    /// 月別スケジュールとストアの差分を取り、取り込みに必要な変更をバッチに追加
+    fn plan_schedule_import(
+        &self,
+        year_month: u32,
+        events: &[RaceEvent],
+        policy: ConflictPolicy,
+        puts: &mut Vec<(String, String)>,
+        deletes: &mut Vec<String>,
+    ) -> Result<ImportReport> {
+        // 書き込み前に全イベントを検証
+        for event in events {
+            self.validate_event(event, Some(year_month))?;
+            self.check_venue(event)?;
+        }
+        
+        // 空のスケジュールはマーカーを残し、「未取り込み」と区別できるようにする
+        let marker = empty_month_marker_key(year_month);
+        if events.is_empty() {
+            puts.push((marker, String::new()));
+        } else {
+            deletes.push(marker);
+        }
+
+        let mut report = ImportReport::default();
+        for event in events {
+            let tournament_id = event_tournament_id(event);
+            let key = try_monthly_key(year_month, &tournament_id)?;
+            let new_daily = if self.validate_events {
+                daily_keys(event, &tournament_id)?
+            } else {
+                daily_keys(event, &tournament_id).unwrap_or_default()
+            };
+
+            // 変更のないイベントはエンコードしない (値は書き込むイベントごとに一度だけ作る)
+            let value = match self.store_get(&key)? {
+                None => {
+                    report.added.push(tournament_id.clone());
+                    self.encode_value(event)?
+                }
+                Some(before) => {
+                    // デシリアライズできない既存エントリは食い違いとして扱う
+                    let existing = deserialize_from_string::<RaceEvent>(&before).ok();
+                    if existing.as_ref() == Some(event) {
+                        report.unchanged.push(tournament_id);
+                        continue;
+                    }
+                    let value = self.encode_value(event)?;
+                    report.conflicts.push(ImportConflict {
+                        tournament_id: tournament_id.clone(),
+                        before,
+                        after: value.clone(),
+                    });
+                    match policy {
+                        ConflictPolicy::Skip => continue,
+                        ConflictPolicy::Error => return Err(crate::StoreError::Conflict(key)),
+                        ConflictPolicy::Overwrite => {
+                            report.overwritten.push(tournament_id.clone());
+                            if let Some(existing) = existing {
+                                let mut stale = daily_keys(&existing, &tournament_id).unwrap_or_default();
+                                stale.retain(|key| !new_daily.contains(key));
+                                deletes.extend(stale);
+                            }
+                        }
+                    }
+                    value
+                }
+            };
+
+            puts.extend(new_daily.into_iter().map(|key| (key, value.clone())));
+            puts.push((key, value));
+        }
+        Ok(report)
+    }
+
+    /// 保存済みの月別スケジュールと取り込み予定のスケジュールを比較
+    ///
+    /// 大会IDで対応付け、同じ大会IDのイベントはフィールド単位で比較する。
+    /// イベント名や会場名が変わると大会IDも変わるため、削除と追加の組として報告される。
+    /// ストアへの書き込みは行わない。
+    ///
+    /// # Arguments
+    /// * `schedule` - 取り込み予定の月別スケジュール
+    ///
+    /// # Returns
+    /// 差分（各一覧は大会ID順）
+    pub fn diff_monthly_schedule(&mut self, schedule: &MonthlySchedule) -> Result<ScheduleDiff> {
+        let year_month = parse_year_month(&schedule.year_month)?;
+        let (start, end) = monthly_scan_range(year_month);
+
+        let mut stored = BTreeMap::new();
+        for (key, event) in self.store_scan_decoded::<RaceEvent>(&start, &end)? {
+            if let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() {
+                stored.insert(tournament_id, event);
+            }
+        }
+        let incoming: BTreeMap<String, &RaceEvent> = schedule
+            .events
+            .iter()
+            .map(|event| (event_tournament_id(event), event))
+            .collect();
+
+        let mut diff = ScheduleDiff::default();
+        for (tournament_id, &event) in &incoming {
+            match stored.remove(tournament_id) {
+                None => diff.added.push(event.clone()),
+                Some(old) if old != *event => diff.changed.push((old, event.clone())),
+                Some(_) => {}
+            }
+        }
+        diff.removed = stored.into_values().collect();
+        Ok(diff)
+    }
+
+    /// 月別スケジュールをストアと同期
+    ///
+    /// `sync_monthly_schedule_with` を既定のオプション（レースデータは残す）で呼び出す
+    ///
+    /// # Arguments
+    /// * `schedule` - 最新の月別スケジュール
+    ///
+    /// # Returns
+    /// 同期結果
+    pub fn sync_monthly_schedule(&mut self, schedule: &MonthlySchedule) -> Result<SyncReport> {
+        self.sync_monthly_schedule_with(schedule, SyncOptions::default())
+    }
+
+    /// オプションを指定して月別スケジュールをストアと同期
+    ///
+    /// 取り込むスケジュールに含まれないその月の既存エントリを（その月の日別ビューとともに）削除し、
+    /// 残りを上書き保存する。すべての変更は1回の一括操作で書き込む。
+    ///
+    /// # Arguments
+    /// * `schedule` - 最新の月別スケジュール
+    /// * `options` - 同期オプション
+    ///
+    /// # Returns
+    /// 同期結果
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(year_month = %schedule.year_month, events = schedule.events.len())))]
+    pub fn sync_monthly_schedule_with(&mut self, schedule: &MonthlySchedule, options: SyncOptions) -> Result<SyncReport> {
+        let year_month = parse_year_month(&schedule.year_month)?;
+        let mut puts = Vec::new();
+        let mut deletes = Vec::new();
+        let import = self.plan_schedule_import(year_month, &schedule.events, ConflictPolicy::Overwrite, &mut puts, &mut deletes)?;
+
+        let incoming: BTreeSet<String> = schedule
+            .events
+            .iter()
+            .map(event_tournament_id)
+            .collect();
+
+        let mut report = SyncReport {
+            added: import.added,
+            updated: import.overwritten,
+            unchanged: import.unchanged,
+            ..Default::default()
+        };
+
+        let (start, end) = monthly_scan_range(year_month);
+        for (key, value) in self.store_scan(&start, &end)? {
+            let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() else {
+                continue;
+            };
+            if incoming.contains(&tournament_id) {
+                continue;
+            }
+            // デシリアライズできないエントリは月別ビューのみ削除する
+            if let Ok(event) = deserialize_from_string::<RaceEvent>(&value) {
+                deletes.extend(month_daily_keys(&event, &tournament_id, year_month)?);
+            }
+            deletes.push(key);
+            report.removed.push(tournament_id);
+        }
+
+        if options.purge_orphaned_races {
+            for tournament_id in &report.removed {
+                // 他の月に登録が残っている大会は孤立していない
+                let other_months = self
+                    .keys_for_tournament_view(PREFIX_MONTHLY, tournament_id)?
+                    .into_iter()
+                    .any(|key| !deletes.contains(&key));
+                if !other_months {
+                    let races = self.scan_tournament_keys(tournament_scan_range(tournament_id), tournament_id)?;
+                    report.purged_races += races.len();
+                    deletes.extend(races);
+                }
+            }
+        }
+
+        self.commit(puts, deletes)?;
+        Ok(report)
+    }
+
+    /// 月別スケジュールを取得
+    /// 
+    /// # Arguments
+    /// * `year_month` - 取得対象の年月 (例: 202509)
+    /// 
+    /// # Returns
+    /// 月別スケジュール
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn get_monthly_schedule(&mut self, year_month: u32) -> Result<MonthlySchedule> {
+        self.get_monthly_schedule_with(year_month, ScheduleOptions::default())
+    }
+
+    /// オプションを指定して月別スケジュールを取得
+    ///
+    /// # Arguments
+    /// * `year_month` - 取得対象の年月 (例: 202509)
+    /// * `options` - 中止した大会を除くかどうか
+    ///
+    /// # Returns
+    /// 月別スケジュール
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn get_monthly_schedule_with(&mut self, year_month: u32, options: ScheduleOptions) -> Result<MonthlySchedule> {
+        let groups = self.duplicate_groups(year_month)?;
+        self.schedule_from_groups(year_month, groups, options)
+    }
+
+    /// 月別スケジュールを、値のデシリアライズを並列に行って取得
+    ///
+    /// 先にスキャンしてから値をデシリアライズする。`rayon` フィーチャーが有効な場合は全コアで並列に、
+    /// 無効な場合は順に行う。結果は `get_monthly_schedule` と同じ
+    ///
+    /// # Arguments
+    /// * `year_month` - 取得対象の年月 (例: 202509)
+    ///
+    /// # Returns
+    /// 月別スケジュール。デシリアライズできない値があればそのキーを付けたエラー
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn get_monthly_schedule_par(&mut self, year_month: u32) -> Result<MonthlySchedule> {
+        let (start, end) = monthly_scan_range(year_month);
+        let results = self.store_scan(&start, &end)?;
+        let groups = self.group_duplicates(self.decode_sorted(results)?);
+        self.schedule_from_groups(year_month, groups, ScheduleOptions::default())
+    }
+
+    /// 重複をまとめたグループから月別スケジュールを組み立てる (各グループの先頭を残し、開始日順)
+    fn schedule_from_groups(&self, year_month: u32, groups: Vec<Vec<(String, RaceEvent)>>, options: ScheduleOptions) -> Result<MonthlySchedule> {
+        // 同じイベントが別の大会IDで重複登録されている場合は1件にまとめる
+        let mut events = Vec::new();
+        for group in groups {
+            let Some((key, event)) = group.into_iter().next() else {
+                continue;
+            };
+            if options.exclude_cancelled {
+                if let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() {
+                    if self.status_override(&tournament_id)? == Some(TournamentStatus::Cancelled) {
+                        continue;
+                    }
+                }
+            }
+            events.push(event);
+        }
+        
+        // 開始日でソート
+        events.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+        
+        Ok(MonthlySchedule {
+            year_month: format_year_month(year_month),
+            events,
+        })
+    }
+
+    /// 月別エントリを (venue_id, event_name, start_date) が等しいものごとにまとめる
+    ///
+    /// 各グループの先頭は残すべきエントリ（`group_duplicates` を参照）
+    fn duplicate_groups(&mut self, year_month: u32) -> Result<Vec<Vec<(String, RaceEvent)>>> {
+        let (start, end) = monthly_scan_range(year_month);
+        let entries = self.store_scan_decoded(&start, &end)?;
+        Ok(self.group_duplicates(entries))
+    }
+
+    /// 月別スケジュールを読み取り専用で取得（`get_monthly_schedule` と同じく重複をまとめ、開始日順）
+    fn read_month_events(&mut self, year_month: u32) -> Result<Vec<RaceEvent>> {
+        let (start, end) = monthly_scan_range(year_month);
+        let entries = self.store_scan_decoded::<RaceEvent>(&start, &end)?;
+        let mut events: Vec<RaceEvent> = self.group_duplicates(entries)
+            .into_iter()
+            .filter_map(|group| group.into_iter().next().map(|(_, event)| event))
+            .collect();
+        events.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+        Ok(events)
+    }
+
+    /// 指定日以降の次のイベントを取得
+    ///
+    /// 指定日に開催中のイベントも対象とする。指定日の月から1か月ずつ、設定した月数
+    /// (`with_search_horizon`) まで月別ビューを検索する
+    ///
+    /// # Arguments
+    /// * `date` - 基準日 (YYYY-MM-DD形式)
+    /// * `grades` - 対象のグレード (None はすべて)
+    ///
+    /// # Returns
+    /// 最も早く始まるイベント（同日開始は会場ID順、見つからない場合は None）
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn next_event_after(&mut self, date: &str, grades: Option<&[Grade]>) -> Result<Option<RaceEvent>> {
+        self.find_next_event(date, |event| grades.is_none_or(|grades| grades.contains(&event.grade)))
+    }
+
+    /// 会場の指定日以降の次のイベントを取得
+    ///
+    /// 検索範囲と開催中のイベントの扱いは `next_event_after` と同じ
+    ///
+    /// # Arguments
+    /// * `venue_id` - 会場ID
+    /// * `date` - 基準日 (YYYY-MM-DD形式)
+    ///
+    /// # Returns
+    /// 最も早く始まるイベント（見つからない場合は None）
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn next_event_at_venue(&mut self, venue_id: u32, date: &str) -> Result<Option<RaceEvent>> {
+        self.find_next_event(date, |event| event.venue_id == venue_id)
+    }
+
+    /// 基準日に開催中またはそれ以降に始まるイベントのうち、条件に合う最も早いものを探す
+    ///
+    /// 開催期間は最長30日なので、開催中のイベントを拾うため前月から検索する。
+    /// 月跨ぎのイベントが開始月以外にだけ登録されている場合に備え、最初に見つかった月の翌月まで検索する
+    fn find_next_event(&mut self, date: &str, matches: impl Fn(&RaceEvent) -> bool) -> Result<Option<RaceEvent>> {
+        let date = parse_date(date)?;
+        let first_month = date.year() as u32 * 100 + date.month();
+        let mut year_month = if first_month / 100 > 0 { prev_year_month(first_month) } else { first_month };
+        let last_month = (1..self.search_horizon_months).fold(first_month, |month, _| next_year_month(month));
+
+        let mut best: Option<(NaiveDate, RaceEvent)> = None;
+        let mut found = false;
+        while year_month <= last_month {
+            for event in self.get_monthly_schedule(year_month)?.events {
+                // 日付が不正なイベントは対象外
+                let (Ok(start), Ok(end)) = (event.start(), event.end()) else {
+                    continue;
+                };
+                if end < date || !matches(&event) {
+                    continue;
+                }
+                let better = match &best {
+                    Some((best_start, best_event)) => (start, event.venue_id) < (*best_start, best_event.venue_id),
+                    None => true,
+                };
+                if better {
+                    best = Some((start, event));
+                }
+            }
+            // 見つかった月の翌月まで検索したら終わる
+            if found {
+                break;
+            }
+            found = best.is_some() && year_month >= first_month;
+            year_month = next_year_month(year_month);
+        }
+        Ok(best.map(|(_, event)| event))
+    }
+
+    /// 同じ会場で開催期間が重なる大会の組を検出
+    ///
+    /// 指定月に登録されているイベント (月跨ぎの大会を含む) を会場ごとにまとめ、実際の開催期間
+    /// (`RaceEvent::overlaps`) で重なりを調べる。同一内容の重複登録は1件にまとめてから比較し、
+    /// 日付が不正なイベントは比較しない
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    ///
+    /// # Returns
+    /// 重なる (先に始まるイベント, 後に始まるイベント) の組（会場ID・開始日順）
+    pub fn find_schedule_conflicts(&mut self, year_month: u32) -> Result<Vec<(RaceEvent, RaceEvent)>> {
+        validate_year_month(year_month)?;
+        let mut by_venue: BTreeMap<u32, Vec<RaceEvent>> = BTreeMap::new();
+        for event in self.read_month_events(year_month)? {
+            by_venue.entry(event.venue_id).or_default().push(event);
+        }
+
+        let mut conflicts = Vec::new();
+        for events in by_venue.values() {
+            // read_month_events は開始日順
+            for (index, event) in events.iter().enumerate() {
+                for other in &events[index + 1..] {
+                    if event.overlaps(other).unwrap_or(false) {
+                        conflicts.push((event.clone(), other.clone()));
+                    }
+                }
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// 重複登録された月別エントリを削除
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    ///
+    /// # Returns
+    /// 削除した月別エントリの数
+    pub fn dedupe_month(&mut self, year_month: u32) -> Result<usize> {
+        Ok(self.dedupe_month_report(year_month)?.removed())
+    }
+
+    /// 重複登録された月別エントリを削除し、まとめたキーを報告
+    ///
+    /// (venue_id, event_name, start_date) が等しいエントリのうち1件を残し、残りを
+    /// その月の日別ビューとともに削除する。すべての変更は1回の一括操作で書き込む。
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    ///
+    /// # Returns
+    /// 残したキーと削除したキーの一覧
+    pub fn dedupe_month_report(&mut self, year_month: u32) -> Result<DedupeReport> {
+        validate_year_month(year_month)?;
+        let mut report = DedupeReport::default();
+        let mut deletes = Vec::new();
+        for group in self.duplicate_groups(year_month)? {
+            let mut entries = group.into_iter();
+            let Some((kept, _)) = entries.next() else {
+                continue;
+            };
+            let mut removed = Vec::new();
+            for (key, event) in entries {
+                if let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() {
+                    deletes.extend(month_daily_keys(&event, &tournament_id, year_month)?);
+                }
+                deletes.push(key.clone());
+                removed.push(key);
+            }
+            if !removed.is_empty() {
+                report.collapsed.push((kept, removed));
+            }
+        }
+
+        if !deletes.is_empty() {
+            self.commit(Vec::new(), deletes)?;
+        }
+        Ok(report)
+    }
+
+    /// 月別スケジュールを取得し、取り込まれていない月は None を返す
+    ///
+    /// 月別エントリが1件もなく、空のスケジュールとして保存された記録もない月を未取り込みとみなす
+    ///
+    /// # Arguments
+    /// * `year_month` - 取得対象の年月 (例: 202509)
+    ///
+    /// # Returns
+    /// 月別スケジュール（未取り込みの場合は None）
+    pub fn try_get_monthly_schedule(&mut self, year_month: u32) -> Result<Option<MonthlySchedule>> {
+        validate_year_month(year_month)?;
+        let schedule = self.get_monthly_schedule(year_month)?;
+        if schedule.events.is_empty() && !self.store_contains(&empty_month_marker_key(year_month))? {
+            return Ok(None);
+        }
+        Ok(Some(schedule))
+    }
+
+    /// 月別スケジュールを削除
+    ///
+    /// 指定月の月別ビューと、その月に含まれる日別ビューを削除する。
+    /// 大会データ (レースデータ) には影響しない。
+    /// 削除はキー単位で行うため、デシリアライズできないエントリがあっても削除は中断しない
+    /// (そのエントリの日別ビューは、その月の日別ビューから大会IDで探して削除する)。
+    ///
+    /// # Arguments
+    /// * `year_month` - 削除対象の年月 (例: 202509)
+    ///
+    /// # Returns
+    /// 削除した月別エントリ数
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn delete_monthly_schedule(&mut self, year_month: u32) -> Result<usize> {
+        validate_year_month(year_month)?;
+        let keys = self.scan_keys(monthly_scan_range(year_month))?;
+
+        let mut deleted = 0;
+        let mut deletes = Vec::new();
+        let mut undecodable = HashSet::new();
+        for key in keys {
+            let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() else {
+                continue;
+            };
+            self.metrics.record_get();
+            let metrics = &self.metrics;
+            match self.store.with_value(&key, |value| decode_with::<RaceEvent>(metrics, &key, value).ok())? {
+                Some(Some(event)) => deletes.extend(month_daily_keys(&event, &tournament_id, year_month)?),
+                Some(None) => {
+                    undecodable.insert(tournament_id);
+                }
+                None => {}
+            }
+            deletes.push(key);
+            deleted += 1;
+        }
+        if !undecodable.is_empty() {
+            let daily = self.scan_keys(daily_month_scan_range(year_month))?;
+            deletes.extend(daily.into_iter().filter(|key| {
+                matches!(key.parse::<Key>(), Ok(Key::Daily { tournament_id, .. }) if undecodable.contains(&tournament_id))
+            }));
+        }
+        deletes.push(empty_month_marker_key(year_month));
+        self.commit(Vec::new(), deletes)?;
+        Ok(deleted)
+    }
+
+    /// 月別スケジュールから単一のイベントを削除
+    ///
+    /// エントリをデシリアライズできない場合も削除する
+    /// (日別ビューは、その月の日別ビューから大会IDで探して削除する)。
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// 削除した場合は true、存在しなかった場合は false
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn delete_race_event(&mut self, year_month: u32, tournament_id: &str) -> Result<bool> {
+        validate_year_month(year_month)?;
+        if tournament_id.is_empty() {
+            return Err(crate::StoreError::InvalidKey);
+        }
+        let key = try_monthly_key(year_month, tournament_id)?;
+        self.metrics.record_get();
+        let metrics = &self.metrics;
+        let mut deletes = match self.store.with_value(&key, |value| decode_with::<RaceEvent>(metrics, &key, value).ok())? {
+            Some(Some(event)) => month_daily_keys(&event, tournament_id, year_month)?,
+            Some(None) => {
+                let daily = self.scan_keys(daily_month_scan_range(year_month))?;
+                daily
+                    .into_iter()
+                    .filter(|key| matches!(key.parse::<Key>(), Ok(Key::Daily { tournament_id: id, .. }) if id == tournament_id))
+                    .collect()
+            }
+            None => return Ok(false),
+        };
+        deletes.push(key);
+        self.commit(Vec::new(), deletes)?;
+        Ok(true)
+    }
+
+    /// 大会を関連データごと削除
+    ///
+    /// 全月の月別ビュー・日別ビューと、大会に紐づくレースデータ・結果・払戻金・オッズ・メタデータを削除する。
+    /// 削除はストアへの一括操作で行う。
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// 名前空間ごとの削除件数（未知の大会IDの場合はすべて0）
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn delete_tournament(&mut self, tournament_id: &str) -> Result<DeleteReport> {
+        if tournament_id.is_empty() {
+            return Err(crate::StoreError::InvalidKey);
+        }
+        validate_tournament_id(tournament_id)?;
+
+        let monthly = self.keys_for_tournament_view(PREFIX_MONTHLY, tournament_id)?;
+        let daily = self.keys_for_tournament_view(PREFIX_DAILY, tournament_id)?;
+        let race_data = self.scan_tournament_keys(tournament_scan_range(tournament_id), tournament_id)?;
+        let results = self.scan_tournament_keys(result_scan_range(tournament_id), tournament_id)?;
+        let payouts = self.scan_tournament_keys(payout_scan_range(tournament_id), tournament_id)?;
+        let odds = self.scan_tournament_keys(odds_tournament_scan_range(tournament_id), tournament_id)?;
+        let metadata = self.scan_tournament_keys(tournament_meta_scan_range(tournament_id), tournament_id)?;
+
+        let report = DeleteReport {
+            monthly_entries: monthly.len(),
+            daily_entries: daily.len(),
+            race_data: race_data.len(),
+            results: results.len(),
+            payouts: payouts.len(),
+            odds_snapshots: odds.len(),
+            metadata: metadata.len(),
+        };
+
+        let keys: Vec<String> = [monthly, daily, race_data, results, payouts, odds, metadata].concat();
+        if !keys.is_empty() {
+            self.commit(Vec::new(), keys)?;
+            self.notify(&[EngineEvent::TournamentDeleted { tournament_id: tournament_id.to_string() }]);
+        }
+        Ok(report)
+    }
+
+    /// カットオフより前の月だけに登録されている大会をアーカイブ用ストアへ移動
+    ///
+    /// 大会の月別ビュー (M)、大会データ (レースデータ・結果・払戻金・オッズ・メタデータ) と
+    /// インデックスのエントリをアーカイブへコピーした後、それらと日別ビューを稼働中のストアから削除する。
+    /// カットオフをまたぐ大会は移動しない。
+    /// アーカイブに同じキーが異なる値で既にある場合は何も書き込まずに `StoreError::KeyExists` を返す
+    /// (同じ値なら上書きする。途中で失敗したアーカイブをやり直せる)
+    ///
+    /// # Arguments
+    /// * `cutoff_year_month` - この年月より前の月が対象 (例: 202601)
+    /// * `archive` - アーカイブ先のストア
+    ///
+    /// # Returns
+    /// 月ごとの件数などのアーカイブ結果
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, archive)))]
+    pub fn archive_months_before(&mut self, cutoff_year_month: u32, archive: &mut impl KeyValueStore) -> Result<ArchiveReport> {
+        validate_year_month(cutoff_year_month)?;
+
+        let mut registrations: BTreeMap<String, Vec<(u32, String, String)>> = BTreeMap::new();
+        let (start, end) = namespace_scan_range(PREFIX_MONTHLY);
+        for (key, value) in self.store_scan(&start, &end)? {
+            if let Ok(Key::Monthly { year_month, tournament_id }) = key.parse::<Key>() {
+                registrations.entry(tournament_id).or_default().push((year_month, key, value));
+            }
+        }
+
+        let mut report = ArchiveReport::default();
+        let mut copies = Vec::new();
+        let mut deletes = Vec::new();
+        for (tournament_id, entries) in registrations {
+            if entries.iter().all(|(year_month, ..)| *year_month >= cutoff_year_month) {
+                continue;
+            }
+            if entries.iter().any(|(year_month, ..)| *year_month >= cutoff_year_month) {
+                report.skipped.push(tournament_id);
+                continue;
+            }
+
+            for (year_month, key, value) in entries {
+                // デシリアライズできないエントリの日別ビューはその月の日別ビューから探す
+                match deserialize_from_string::<RaceEvent>(&value) {
+                    Ok(event) => deletes.extend(month_daily_keys(&event, &tournament_id, year_month)?),
+                    Err(_) => {
+                        let daily = self.scan_keys(daily_month_scan_range(year_month))?;
+                        deletes.extend(daily.into_iter().filter(|key| tournament_key_matches(key, &tournament_id)));
+                    }
+                }
+                *report.months.entry(year_month).or_default() += 1;
+                deletes.push(key.clone());
+                copies.push((key, value));
+            }
+            for range in tournament_data_ranges(&tournament_id) {
+                for (key, value) in self.scan_tournament(range, &tournament_id)? {
+                    match classify_key(&key) {
+                        Some(Namespace::Tournament) => report.races += 1,
+                        _ => report.other_entries += 1,
+                    }
+                    deletes.push(key.clone());
+                    copies.push((key, value));
+                }
+            }
+            report.tournaments.push(tournament_id);
+        }
+        if report.tournaments.is_empty() {
+            return Ok(report);
+        }
+
+        let archived: BTreeSet<&str> = report.tournaments.iter().map(String::as_str).collect();
+        let (start, end) = namespace_scan_range(PREFIX_INDEX);
+        for (key, value) in self.store_scan(&start, &end)? {
+            if key.parse::<Key>().is_ok_and(|parsed| parsed.tournament_id().is_some_and(|id| archived.contains(id))) {
+                report.other_entries += 1;
+                deletes.push(key.clone());
+                copies.push((key, value));
+            }
+        }
+
+        for (key, value) in &copies {
+            if archive.with_value(key, |existing| existing != value)?.unwrap_or(false) {
+                return Err(crate::StoreError::KeyExists { key: key.clone() });
+            }
+        }
+        // アーカイブへの書き込みが成功してから稼働中のストアを削除する
+        archive.apply_batch(copies, &[])?;
+        self.commit(Vec::new(), deletes)?;
+        Ok(report)
+    }
+
+    /// アーカイブ用ストアから月別スケジュールを復元
+    ///
+    /// 指定月の月別ビューと日別ビュー、およびその大会の大会データ (レースデータ・結果・払戻金・オッズ・メタデータ)
+    /// とインデックスを稼働中のストアに書き戻す。会場・名前のインデックスは指定月のものだけを戻す。
+    /// アーカイブ側のデータは削除しない
+    ///
+    /// # Arguments
+    /// * `year_month` - 復元対象の年月 (例: 202512)
+    /// * `archive` - アーカイブ元のストア
+    ///
+    /// # Returns
+    /// 復元した月別エントリ数
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, archive)))]
+    pub fn restore_month(&mut self, year_month: u32, archive: &mut impl KeyValueStore) -> Result<usize> {
+        validate_year_month(year_month)?;
+
+        let (start, end) = monthly_scan_range(year_month);
+        let monthly = archive.scan(&start, &end)?;
+        let mut tournaments = BTreeSet::new();
+        let mut puts = Vec::new();
+        for (key, value) in &monthly {
+            let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() else {
+                continue;
+            };
+            let event: RaceEvent = self.decode_entry(key, value)?;
+            for daily in month_daily_keys(&event, &tournament_id, year_month)? {
+                puts.push((daily, value.clone()));
+            }
+            for (start, end) in tournament_data_ranges(&tournament_id) {
+                let data = archive.scan(&start, &end)?;
+                puts.extend(data.into_iter().filter(|(key, _)| tournament_key_matches(key, &tournament_id)));
+            }
+            tournaments.insert(tournament_id);
+        }
+        if tournaments.is_empty() {
+            return Ok(0);
+        }
+
+        let (start, end) = namespace_scan_range(PREFIX_INDEX);
+        for (key, value) in archive.scan(&start, &end)? {
+            let restored = match key.parse::<Key>() {
+                Ok(Key::VenueIndex { year_month: ym, tournament_id, .. } | Key::NameIndex { year_month: ym, tournament_id, .. }) => {
+                    ym == year_month && tournaments.contains(&tournament_id)
+                }
+                Ok(parsed) => parsed.tournament_id().is_some_and(|id| tournaments.contains(id)),
+                Err(_) => false,
+            };
+            if restored {
+                puts.push((key, value));
+            }
+        }
+
+        let restored = tournaments.len();
+        puts.extend(monthly);
+        self.commit(puts, vec![empty_month_marker_key(year_month)])?;
+        Ok(restored)
+    }
+
+    /// 月別スケジュールとレースデータを可読な JSON として出力
+    ///
+    /// イベントはデコード済みの RaceEvent として出力する。レースデータは型が呼び出し側依存のため、
+    /// 大会IDとタイムスタンプごとに保存形式 (Base64) のまま出力する
+    ///
+    /// # Arguments
+    /// * `writer` - 出力先
+    ///
+    /// # Returns
+    /// 出力件数
+    pub fn export_json<W: Write>(&mut self, writer: W) -> Result<ExportStats> {
+        let mut document = JsonExport { months: BTreeMap::new(), races: Vec::new() };
+        let mut stats = ExportStats::default();
+
+        // 同じ内容なら同じ出力になるよう、ストアの走査順によらずキー順に出力する
+        let (start, end) = namespace_scan_range(PREFIX_MONTHLY);
+        let mut months = self.store_scan(&start, &end)?;
+        months.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        for (key, value) in months {
+            if let Ok(Key::Monthly { year_month, .. }) = key.parse::<Key>() {
+                let event: RaceEvent = self.decode_entry(&key, &value)?;
+                document.months.entry(format_year_month(year_month)).or_default().push(event);
+                stats.events += 1;
+            }
+        }
+
+        let (start, end) = namespace_scan_range(PREFIX_TOURNAMENT);
+        let mut races = self.store_scan(&start, &end)?;
+        races.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        for (key, value) in races {
+            if let Ok(Key::Tournament { tournament_id, timestamp }) = key.parse::<Key>() {
+                document.races.push(JsonRace { tournament_id, timestamp, data: value });
+                stats.races += 1;
+            }
+        }
+        stats.months = document.months.len();
+
+        serde_json::to_writer_pretty(writer, &document)?;
+        Ok(stats)
+    }
+
+    /// 月別スケジュールを CSV として出力
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    /// * `writer` - 出力先
+    ///
+    /// # Returns
+    /// 出力したイベント数
+    pub fn export_month_csv<W: Write>(&mut self, year_month: u32, writer: W) -> Result<usize> {
+        self.export_month_csv_with(year_month, writer, CsvOptions::default())
+    }
+
+    /// オプションを指定して月別スケジュールを CSV として出力
+    ///
+    /// 1行1イベントで、列は venue_id, venue_name, event_name, grade, start_date, duration_days, end_date。
+    /// end_date は最終開催日
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    /// * `writer` - 出力先
+    /// * `options` - 出力オプション
+    ///
+    /// # Returns
+    /// 出力したイベント数
+    pub fn export_month_csv_with<W: Write>(&mut self, year_month: u32, mut writer: W, options: CsvOptions) -> Result<usize> {
+        validate_year_month(year_month)?;
+        let events = self.read_month_events(year_month)?;
+        crate::csv::write_bom(&mut writer, options.bom)?;
+        crate::csv::write_record(&mut writer, &CSV_COLUMNS)?;
+        for event in &events {
+            crate::csv::write_record(&mut writer, &csv_fields(event))?;
+        }
+        Ok(events.len())
+    }
+
+    /// 年間スケジュールを CSV として出力
+    ///
+    /// # Arguments
+    /// * `year` - 対象の年 (例: 2025)
+    /// * `writer` - 出力先
+    ///
+    /// # Returns
+    /// 出力したイベント数
+    pub fn export_year_csv<W: Write>(&mut self, year: u32, writer: W) -> Result<usize> {
+        self.export_year_csv_with(year, writer, CsvOptions::default())
+    }
+
+    /// オプションを指定して年間スケジュールを CSV として出力
+    ///
+    /// 月別 CSV の列の前に year_month 列 ("YYYY-MM") が付く
+    ///
+    /// # Arguments
+    /// * `year` - 対象の年 (例: 2025)
+    /// * `writer` - 出力先
+    /// * `options` - 出力オプション
+    ///
+    /// # Returns
+    /// 出力したイベント数
+    pub fn export_year_csv_with<W: Write>(&mut self, year: u32, mut writer: W, options: CsvOptions) -> Result<usize> {
+        crate::csv::write_bom(&mut writer, options.bom)?;
+        let mut header = vec!["year_month"];
+        header.extend(CSV_COLUMNS);
+        crate::csv::write_record(&mut writer, &header)?;
+
+        let mut written = 0;
+        for month in 1..=12 {
+            let year_month = year * 100 + month;
+            validate_year_month(year_month)?;
+            for event in self.read_month_events(year_month)? {
+                let mut fields = vec![format_year_month(year_month)];
+                fields.extend(csv_fields(&event));
+                crate::csv::write_record(&mut writer, &fields)?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    /// `export_month_csv` の形式の CSV を月別スケジュールとして取り込む
+    ///
+    /// 列はヘッダー名で対応付ける。end_date と year_month 列は無視する。先頭の BOM は読み飛ばす。
+    /// 既存の同じ大会は上書きする
+    ///
+    /// # Arguments
+    /// * `year_month` - 取り込み先の年月 (例: 202509)
+    /// * `reader` - 入力元
+    ///
+    /// # Returns
+    /// 取り込み結果。値が不正な場合は行番号付きの `StoreError::ValidationError`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, reader)))]
+    pub fn import_month_csv<R: Read>(&mut self, year_month: u32, reader: R) -> Result<ImportReport> {
+        validate_year_month(year_month)?;
+        let mut records = crate::csv::read_records(reader)?.into_iter();
+        let Some((_, header)) = records.next() else {
+            return Err(crate::StoreError::ValidationError {
+                field: "header".to_string(),
+                message: "CSV is empty".to_string(),
+            });
+        };
+        let column = |name: &str| {
+            header.iter().position(|column| column.trim() == name).ok_or_else(|| crate::StoreError::ValidationError {
+                field: name.to_string(),
+                message: "column is missing from the header".to_string(),
+            })
+        };
+        let venue_id = column("venue_id")?;
+        let venue_name = column("venue_name")?;
+        let event_name = column("event_name")?;
+        let grade = column("grade")?;
+        let start_date = column("start_date")?;
+        let duration_days = column("duration_days")?;
+
+        let mut events = Vec::new();
+        for (row, fields) in records {
+            let field = |index: usize, name: &str| {
+                fields.get(index).map(|value| value.trim()).ok_or_else(|| crate::StoreError::ValidationError {
+                    field: name.to_string(),
+                    message: format!("row {}: value is missing", row),
+                })
+            };
+            let number = |index: usize, name: &str| -> Result<u32> {
+                let value = field(index, name)?;
+                value.parse().map_err(|_| crate::StoreError::ValidationError {
+                    field: name.to_string(),
+                    message: format!("row {}: {:?} is not a number", row, value),
+                })
+            };
+            let date = field(start_date, "start_date")?;
+            if let Err(crate::StoreError::InvalidDate { input, reason }) = parse_date(date) {
+                return Err(crate::StoreError::ValidationError {
+                    field: "start_date".to_string(),
+                    message: format!("row {}: {:?} is not a valid YYYY-MM-DD date ({})", row, input, reason),
+                });
+            }
+            events.push(RaceEvent {
+                venue_id: number(venue_id, "venue_id")?,
+                venue_name: field(venue_name, "venue_name")?.to_string(),
+                event_name: field(event_name, "event_name")?.to_string(),
+                grade: field(grade, "grade")?.parse().map_err(|error| match error {
+                    crate::StoreError::ValidationError { field, message } => crate::StoreError::ValidationError {
+                        field,
+                        message: format!("row {}: {}", row, message),
+                    },
+                    error => error,
+                })?,
+                start_date: date.to_string(),
+                duration_days: number(duration_days, "duration_days")?,
+                notes: None,
+            });
+        }
+
+        let schedule = MonthlySchedule { year_month: format_year_month(year_month), events };
+        self.put_monthly_schedule_checked(&schedule, ConflictPolicy::Overwrite)
+    }
+
+    /// 月別スケジュールを iCalendar (.ics) として出力
+    ///
+    /// イベントごとに終日の VEVENT を1つ出力する (DTEND は最終開催日の翌日)。
+    /// UID は大会IDと年月から決まるため、再出力したファイルを取り込んでも予定は重複しない。
+    /// 開始日が不正なイベントは出力しない
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    /// * `writer` - 出力先
+    ///
+    /// # Returns
+    /// 出力したイベント数
+    pub fn export_ics<W: Write>(&mut self, year_month: u32, mut writer: W) -> Result<usize> {
+        validate_year_month(year_month)?;
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let line = |writer: &mut W, line: &str| crate::ics::write_line(writer, line);
+
+        line(&mut writer, "BEGIN:VCALENDAR")?;
+        line(&mut writer, "VERSION:2.0")?;
+        line(&mut writer, "PRODID:-//norimaki-db//Boat Race Schedule//JA")?;
+        line(&mut writer, "CALSCALE:GREGORIAN")?;
+
+        let mut written = 0;
+        for event in self.read_month_events(year_month)? {
+            let Ok(start) = parse_date(&event.start_date) else {
+                continue;
+            };
+            let end = start + chrono::Duration::days(event.duration_days as i64);
+            let uid = format!("{}-{:06}@norimaki-db", event_tournament_id(&event), year_month);
+
+            line(&mut writer, "BEGIN:VEVENT")?;
+            line(&mut writer, &format!("UID:{}", crate::ics::escape_text(&uid)))?;
+            line(&mut writer, &format!("DTSTAMP:{}", stamp))?;
+            line(&mut writer, &format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")))?;
+            line(&mut writer, &format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")))?;
+            let summary = format!("{} {}", event.grade, event.event_name);
+            line(&mut writer, &format!("SUMMARY:{}", crate::ics::escape_text(&summary)))?;
+            line(&mut writer, &format!("LOCATION:{}", crate::ics::escape_text(&event.venue_name)))?;
+            line(&mut writer, "END:VEVENT")?;
+            written += 1;
+        }
+
+        line(&mut writer, "END:VCALENDAR")?;
+        Ok(written)
+    }
+
+    /// キー空間を人が読める形で出力
+    ///
+    /// 1行1キーで、キーを論理的な要素 (名前空間・年月・大会ID・RFC 3339 形式の日時など) に分解し、
+    /// 値のバイト数を添える。月別・日別ビューの値は RaceEvent の1行要約も出力する。
+    /// キー順に並べるため、実行ごとの出力を diff で比較できる
+    ///
+    /// # Arguments
+    /// * `writer` - 出力先
+    /// * `options` - 絞り込み条件
+    ///
+    /// # Returns
+    /// 操作結果
+    pub fn dump<W: Write>(&self, mut writer: W, options: DumpOptions) -> Result<()> {
+        let mut keys = self.store_keys()?;
+        if let Some(prefix) = &options.prefix {
+            keys.retain(|key| key.starts_with(prefix.as_str()));
+        }
+        keys.truncate(options.limit.unwrap_or(usize::MAX));
+
+        for key in keys {
+            let Some(value) = self.store_get(&key)? else {
+                continue;
+            };
+            let parsed = key.parse::<Key>();
+            let mut line = format!("{} [{} bytes]", describe_key(&key, parsed.as_ref().ok()), value.len());
+            if let Ok(Key::Monthly { .. } | Key::Daily { .. }) = parsed {
+                match deserialize_from_string::<RaceEvent>(&value) {
+                    Ok(event) => line.push_str(&format!(
+                        " {} {} {} {} ({} days)",
+                        event.grade,
+                        event.venue_name,
+                        event.event_name,
+                        event.start_date,
+                        event.duration_days
+                    )),
+                    Err(_) => line.push_str(" <undecodable>"),
+                }
+            }
+            writeln!(writer, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// `export_json` で出力した JSON を取り込む
+    ///
+    /// 全体を一度のバッチで書き込む。既存と内容が異なるイベント・レースデータは `policy` に従う
+    ///
+    /// # Arguments
+    /// * `reader` - 入力元
+    /// * `policy` - 既存データと食い違う場合の扱い
+    ///
+    /// # Returns
+    /// 取り込み結果
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, reader)))]
+    pub fn import_json<R: Read>(&mut self, reader: R, policy: ConflictPolicy) -> Result<ImportStats> {
+        let document: JsonExport = serde_json::from_reader(reader)?;
+        let mut stats = ImportStats::default();
+        let mut puts = Vec::new();
+        let mut deletes = Vec::new();
+
+        for (year_month, events) in &document.months {
+            let year_month = parse_year_month(year_month)?;
+            let report = self.plan_schedule_import(year_month, events, policy, &mut puts, &mut deletes)?;
+            stats.events.added.extend(report.added);
+            stats.events.unchanged.extend(report.unchanged);
+            stats.events.overwritten.extend(report.overwritten);
+            stats.events.conflicts.extend(report.conflicts);
+            stats.months += 1;
+        }
+
+        for race in document.races {
+            let key = try_tournament_key(&race.tournament_id, race.timestamp)?;
+            match self.store_get(&key)? {
+                Some(existing) if existing == race.data => continue,
+                Some(_) if policy == ConflictPolicy::Skip => continue,
+                Some(_) if policy == ConflictPolicy::Error => return Err(crate::StoreError::Conflict(key)),
+                _ => {}
+            }
+            puts.push((key, race.data));
+            stats.races += 1;
+        }
+
+        self.commit(puts, deletes)?;
+        Ok(stats)
+    }
+
+    /// 書き込みと削除を一括で適用し、統計カウンタを更新する
+    ///
+    /// カウンタが有効な場合は更新後のカウンタも同じバッチで書き込む
+    fn commit(&mut self, mut puts: Vec<(String, String)>, mut deletes: Vec<String>) -> Result<()> {
+        if self.indexes.any() {
+            self.add_index_changes(&mut puts, &mut deletes)?;
+        }
+
+        // レースデータまたは結果を削除したレースの出走者インデックスを消す
+        let removed_races: BTreeSet<(String, u64)> = deletes
+            .iter()
+            .filter_map(|key| match key.parse::<Key>() {
+                Ok(Key::Tournament { tournament_id, timestamp }) | Ok(Key::Result { tournament_id, timestamp }) => {
+                    Some((tournament_id, timestamp))
+                }
+                _ => None,
+            })
+            .collect();
+        for (tournament_id, timestamp) in removed_races {
+            self.add_participant_changes(&tournament_id, timestamp, &[], &mut puts, &mut deletes)?;
+        }
+
+        for (key, value) in &puts {
+            self.check_entry_size(key, value)?;
+        }
+        // 最終更新時刻はエンジン内部のキーなのでサイズ制限の対象外
+        self.add_last_modified_changes(&mut puts, &deletes)?;
+
+        let events: Vec<EngineEvent> = match self.hook {
+            Some(_) => puts
+                .iter()
+                .filter_map(|(key, _)| EngineEvent::from_key(key, false))
+                .chain(deletes.iter().filter_map(|key| EngineEvent::from_key(key, true)))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        if let Some(counters) = self.counters {
+            let (updated, month_counts) = self.counters_after(counters, &puts, &deletes)?;
+            if updated != counters {
+                puts.push((STATISTICS_META_KEY.to_string(), self.encode_value(&updated)?));
+            }
+            trace_batch(&puts, &deletes);
+            self.store_apply_batch(puts, &deletes)?;
+            self.counters = Some(updated);
+            if let Some(tournament_months) = &mut self.tournament_months {
+                for (tournament_id, count) in month_counts {
+                    if count == 0 {
+                        tournament_months.remove(&tournament_id);
+                    } else {
+                        tournament_months.insert(tournament_id, count);
+                    }
+                }
+            }
+        } else {
+            trace_batch(&puts, &deletes);
+            self.store_apply_batch(puts, &deletes)?;
+        }
+        self.notify(&events);
+        Ok(())
+    }
+
+    /// 月別エントリ・レースデータを書き込み・削除した大会の最終更新時刻を書き込みに加える
+    ///
+    /// 最終更新時刻は大会を削除しても残す (差分同期で削除を検出できるように)
+    fn add_last_modified_changes(&self, puts: &mut Vec<(String, String)>, deletes: &[String]) -> Result<()> {
+        // 大会IDはキーから借用し、書き込むキーを作るときだけ確保する
+        let touched: Vec<String> = puts
+            .iter()
+            .map(|(key, _)| key)
+            .chain(deletes)
+            .filter_map(|key| parse_written_tournament_id(key))
+            .collect::<BTreeSet<&str>>()
+            .into_iter()
+            .map(last_modified_key)
+            .collect();
+        if touched.is_empty() {
+            return Ok(());
+        }
+
+        let now = self.encode_value(&(self.clock)())?;
+        puts.extend(touched.into_iter().map(|key| (key, now.clone())));
+        Ok(())
+    }
+
+    /// 月別エントリに対して有効なインデックスキーを生成
+    ///
+    /// 会場IDが会場インデックスの3桁に収まらない場合は `StoreError::InvalidKey`
+    fn index_keys(&self, year_month: u32, tournament_id: &str, event: &RaceEvent) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        if self.indexes.by_venue {
+            keys.push(try_venue_index_key(event.venue_id, year_month, tournament_id)?);
+        }
+        if self.indexes.by_name {
+            let mut tokens = name_tokens(&event.event_name);
+            tokens.extend(name_tokens(&event.venue_name));
+            keys.extend(tokens.iter().map(|token| name_index_key(token, year_month, tournament_id)));
+        }
+        Ok(keys)
+    }
+
+    /// 月別ビューの書き込み・削除に伴うインデックスの変更をバッチに追加
+    fn add_index_changes(&self, puts: &mut Vec<(String, String)>, deletes: &mut Vec<String>) -> Result<()> {
+        let monthly = |key: &str| match key.parse::<Key>() {
+            Ok(Key::Monthly { year_month, tournament_id }) => Some((year_month, tournament_id)),
+            _ => None,
+        };
+
+        // 上書きで会場や名前が変わる場合に備え、既存エントリのインデックスは常に削除対象とする
+        let mut index_deletes = Vec::new();
+        for key in deletes.iter().chain(puts.iter().map(|(key, _)| key)) {
+            let Some((year_month, tournament_id)) = monthly(key) else {
+                continue;
+            };
+            if let Some(value) = self.store_get(key)? {
+                if let Ok(event) = deserialize_from_string::<RaceEvent>(&value) {
+                    // 書き込めなかったはずのインデックスは消す必要もない
+                    index_deletes.extend(self.index_keys(year_month, &tournament_id, &event).unwrap_or_default());
+                }
+            }
+        }
+
+        let mut index_puts = Vec::new();
+        for (key, value) in puts.iter() {
+            if let Some((year_month, tournament_id)) = monthly(key) {
+                let event: RaceEvent = self.decode_entry(key, value)?;
+                let keys = self.index_keys(year_month, &tournament_id, &event)?;
+                index_puts.extend(keys.into_iter().map(|key| (key, String::new())));
+            }
+        }
+        puts.extend(index_puts);
+        deletes.extend(index_deletes);
+        Ok(())
+    }
+
+    /// レースの出走者インデックスを置き換える変更をバッチに追加
+    ///
+    /// 既存の出走者一覧に含まれる選手のインデックスを削除し、`racer_ids` のインデックスを書き込む
+    fn add_participant_changes(
+        &self,
+        tournament_id: &str,
+        timestamp: u64,
+        racer_ids: &[u32],
+        puts: &mut Vec<(String, String)>,
+        deletes: &mut Vec<String>,
+    ) -> Result<()> {
+        let list_key = try_participants_key(tournament_id, timestamp)?;
+        if let Some(value) = self.store_get(&list_key)? {
+            let previous: Vec<u32> = deserialize_from_string(&value).unwrap_or_default();
+            deletes.extend(previous.into_iter().filter_map(|racer_id| try_racer_index_key(racer_id, tournament_id, timestamp).ok()));
+            deletes.push(list_key.clone());
+        }
+
+        let mut racer_ids = racer_ids.to_vec();
+        racer_ids.sort_unstable();
+        racer_ids.dedup();
+        if racer_ids.is_empty() {
+            return Ok(());
+        }
+        for &racer_id in &racer_ids {
+            puts.push((try_racer_index_key(racer_id, tournament_id, timestamp)?, String::new()));
+        }
+        puts.push((list_key, self.encode_value(&racer_ids)?));
+        Ok(())
+    }
+
+    /// 月別ビューから期待されるインデックスキーを生成
+    fn expected_index_keys(&mut self) -> Result<BTreeSet<String>> {
+        let (start, end) = namespace_scan_range(PREFIX_MONTHLY);
+        let mut expected = BTreeSet::new();
+        for (key, value) in self.store_scan(&start, &end)? {
+            // デシリアライズできないエントリはインデックス化できない
+            if let (Ok(Key::Monthly { year_month, tournament_id }), Ok(event)) =
+                (key.parse::<Key>(), deserialize_from_string::<RaceEvent>(&value))
+            {
+                expected.extend(self.index_keys(year_month, &tournament_id, &event)?);
+            }
+        }
+        Ok(expected)
+    }
+
+    /// 月別ビューから派生するインデックス（会場・名前）の既存キーを取得
+    fn derived_index_keys(&mut self) -> Result<Vec<String>> {
+        let mut keys = self.scan_keys(venue_index_namespace_range())?;
+        keys.extend(self.scan_keys(name_index_namespace_range())?);
+        Ok(keys)
+    }
+
+    /// セカンダリインデックスを月別ビューから作り直す
+    ///
+    /// 会場・名前インデックスの既存キーをすべて削除し、有効なインデックスのみ再作成する。
+    /// 明示的に登録する出走者インデックスは対象外。
+    ///
+    /// # Returns
+    /// 作成したインデックスキーの数
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn rebuild_indexes(&mut self) -> Result<usize> {
+        let stale = self.derived_index_keys()?;
+        let expected = self.expected_index_keys()?;
+
+        let created = expected.len();
+        let puts = expected.into_iter().map(|key| (key, String::new())).collect();
+        self.store_apply_batch(puts, &stale)?;
+        Ok(created)
+    }
+
+    /// セカンダリインデックスが月別ビューと一致しているか検証
+    ///
+    /// インデックスが無効な場合は、残っているインデックスキーをすべて stale として報告する
+    ///
+    /// # Returns
+    /// 不足・余剰のインデックスキー
+    pub fn check_indexes(&mut self) -> Result<IndexCheck> {
+        let actual: BTreeSet<String> = self.derived_index_keys()?.into_iter().collect();
+        let expected = self.expected_index_keys()?;
+
+        Ok(IndexCheck {
+            missing: expected.difference(&actual).cloned().collect(),
+            stale: actual.difference(&expected).cloned().collect(),
+        })
+    }
+
+    /// バッチ適用後の統計カウンタと、エントリ数が変わる大会IDごとの適用後の月別エントリ数を計算
+    fn counters_after(
+        &mut self,
+        mut counters: StatisticsCounters,
+        puts: &[(String, String)],
+        deletes: &[String],
+    ) -> Result<(StatisticsCounters, Vec<(String, usize)>)> {
+        let put_keys: BTreeSet<&str> = puts.iter().map(|(key, _)| key.as_str()).collect();
+        let delete_keys: BTreeSet<&str> = deletes.iter().map(String::as_str).collect();
+        // 大会IDごとの月別エントリ数の増減
+        let mut month_deltas: BTreeMap<String, isize> = BTreeMap::new();
+
+        for &key in put_keys.union(&delete_keys) {
+            // rebuild_statistics と同じく、形式を満たすキーだけを数える
+            let parsed = match key.parse::<Key>() {
+                Ok(parsed @ (Key::Monthly { .. } | Key::Tournament { .. })) => parsed,
+                _ => continue,
+            };
+            // apply_batch は削除 → 書き込みの順なので、書き込み対象のキーは必ず残る
+            let before = self.store_contains(key)?;
+            let after = put_keys.contains(key) || (before && !delete_keys.contains(key));
+            let delta = match (before, after) {
+                (false, true) => 1,
+                (true, false) => -1,
+                _ => continue,
+            };
+            match parsed {
+                Key::Monthly { tournament_id, .. } => {
+                    counters.monthly_entries = counters.monthly_entries.saturating_add_signed(delta);
+                    *month_deltas.entry(tournament_id).or_default() += delta;
+                }
+                _ => counters.races = counters.races.saturating_add_signed(delta),
+            }
+        }
+
+        // 大会数は月別ビューに1件以上のエントリを持つ大会IDの数
+        let mut month_counts = Vec::new();
+        if month_deltas.values().any(|delta| *delta != 0) {
+            let tournament_months = self.tournament_months()?;
+            for (tournament_id, delta) in month_deltas {
+                let before = tournament_months.get(&tournament_id).copied().unwrap_or(0);
+                let after = before.saturating_add_signed(delta);
+                match (before, after) {
+                    (0, 1..) => counters.tournaments += 1,
+                    (1.., 0) => counters.tournaments = counters.tournaments.saturating_sub(1),
+                    _ => {}
+                }
+                month_counts.push((tournament_id, after));
+            }
+        }
+        Ok((counters, month_counts))
+    }
+
+    /// 大会IDごとの月別エントリ数 (未作成なら月別ビューを走査して作る)
+    fn tournament_months(&mut self) -> Result<&HashMap<String, usize>> {
+        if self.tournament_months.is_none() {
+            let mut tournament_months: HashMap<String, usize> = HashMap::new();
+            for key in self.scan_keys(namespace_scan_range(PREFIX_MONTHLY))? {
+                if let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() {
+                    *tournament_months.entry(tournament_id).or_default() += 1;
+                }
+            }
+            self.tournament_months = Some(tournament_months);
+        }
+        Ok(self.tournament_months.get_or_insert_with(HashMap::new))
+    }
+
+    /// 月別・日別ビューから指定大会のキーを収集
+    fn keys_for_tournament_view(&mut self, prefix: u8, tournament_id: &str) -> Result<Vec<String>> {
+        let keys = self.scan_keys(namespace_scan_range(prefix))?;
+        Ok(keys
+            .into_iter()
+            .filter(|key| tournament_key_matches(key, tournament_id))
+            .collect())
+    }
+
+    /// 大会IDで保存された大会データ (レースデータ・結果・払戻・オッズ・メタデータ) があるか
+    fn has_tournament_data(&mut self, tournament_id: &str) -> Result<bool> {
+        for range in tournament_data_ranges(tournament_id) {
+            if !self.scan_tournament_keys(range, tournament_id)?.is_empty() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// 大会のスキャン範囲内のキーを取得し、その大会のキーだけに絞り込む
+    fn scan_tournament_keys(&mut self, range: (String, String), tournament_id: &str) -> Result<Vec<String>> {
+        let mut keys = self.scan_keys(range)?;
+        keys.retain(|key| tournament_key_matches(key, tournament_id));
+        Ok(keys)
+    }
+
+    /// 大会のスキャン範囲内のキーと値を取得し、その大会のキーだけに絞り込む
+    fn scan_tournament(&mut self, (start, end): (String, String), tournament_id: &str) -> Result<Vec<(String, String)>> {
+        let mut results = self.store_scan(&start, &end)?;
+        results.retain(|(key, _)| tournament_key_matches(key, tournament_id));
+        Ok(results)
+    }
+
+    /// `scan_tournament` と同じ範囲の値を、複製せずにデシリアライズして取得
+    fn scan_tournament_decoded<T: DeserializeOwned>(&mut self, (start, end): (String, String), tournament_id: &str) -> Result<Vec<(String, T)>> {
+        self.store_scan_decoded_where(&start, &end, |key| tournament_key_matches(key, tournament_id))
+    }
+
+    /// スキャン範囲内のキーのみを取得
+    fn scan_keys(&mut self, (start, end): (String, String)) -> Result<Vec<String>> {
+        self.store_scan_keys(&start, &end)
+    }
+
+    /// 月別スケジュールの単一イベントを取得
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// イベント（存在しない場合は None）
+    pub fn get_race_event(&self, year_month: u32, tournament_id: &str) -> Result<Option<RaceEvent>> {
+        let key = try_monthly_key(year_month, tournament_id)?;
+        self.store_read(&key)
+    }
+
+    /// 月を指定せずに大会のイベントを検索
+    ///
+    /// 月跨ぎで複数月に登録されている場合は最も古い月のエントリを返す
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// (年月, イベント)（存在しない場合は None）
+    pub fn find_race_event(&mut self, tournament_id: &str) -> Result<Option<(u32, RaceEvent)>> {
+        validate_tournament_id(tournament_id)?;
+        let first_month = self
+            .scan_keys(namespace_scan_range(PREFIX_MONTHLY))?
+            .iter()
+            .filter_map(|key| match key.parse::<Key>() {
+                Ok(Key::Monthly { year_month, tournament_id: id }) if id == tournament_id => Some(year_month),
+                _ => None,
+            })
+            .min();
+
+        match first_month {
+            Some(year_month) => Ok(self.get_race_event(year_month, tournament_id)?.map(|event| (year_month, event))),
+            None => Ok(None),
+        }
+    }
+
+    /// 月別スケジュールの単一イベントを更新
+    ///
+    /// 会場名またはイベント名の変更で大会IDが変わる場合は、新しいキーへの書き込みと
+    /// 古いキーの削除を一括で行う。日別ビューも更新後の開催日に合わせて書き換える。
+    /// 大会データ (レースデータ・結果・払戻・オッズ・メタデータ) は大会IDで保存されているため、
+    /// それらを持つ大会の大会IDは変更できない (先に `delete_tournament` で削除すること)。
+    ///
+    /// # Arguments
+    /// * `year_month` - 対象の年月 (例: 202509)
+    /// * `tournament_id` - 大会ID
+    /// * `update` - 変更内容
+    ///
+    /// # Returns
+    /// 更新後のイベント。対象が存在しない場合は `StoreError::NotFound`、
+    /// 変更後の大会IDのエントリが同じ月に既にある場合は `StoreError::KeyExists` (何も書き込まない)、
+    /// 大会データを持つ大会の大会IDを変更しようとした場合は `StoreError::ValidationError`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, update)))]
+    pub fn update_race_event(&mut self, year_month: u32, tournament_id: &str, update: RaceEventUpdate) -> Result<RaceEvent> {
+        validate_year_month(year_month)?;
+        let old_key = try_monthly_key(year_month, tournament_id)?;
+        let value = self.store_get(&old_key)?
+            .ok_or_else(|| crate::StoreError::not_found(old_key.clone()))?;
+        let old_event: RaceEvent = self.decode_entry(&old_key, &value)?;
+
+        let new_event = update.apply(&old_event);
+        self.validate_event(&new_event, Some(year_month))?;
+        self.check_venue(&new_event)?;
+        let new_id = event_tournament_id(&new_event);
+        let new_key = try_monthly_key(year_month, &new_id)?;
+        let new_value = self.encode_value(&new_event)?;
+
+        if new_key != old_key {
+            if self.store_get(&new_key)?.is_some() {
+                return Err(StoreError::KeyExists { key: new_key });
+            }
+            if self.has_tournament_data(tournament_id)? {
+                return Err(crate::StoreError::ValidationError {
+                    field: "tournament_id".to_string(),
+                    message: format!(
+                        "renaming {:?} would change its tournament id to {:?}, but race data is stored under the current id",
+                        tournament_id, new_id
+                    ),
+                });
+            }
+        }
+
+        let new_daily = daily_keys(&new_event, &new_id)?;
+        let mut deletes = daily_keys(&old_event, tournament_id).unwrap_or_default();
+        deletes.retain(|key| !new_daily.contains(key));
+        if new_key != old_key {
+            deletes.push(old_key);
+        }
+
+        let mut puts: Vec<(String, String)> = new_daily.into_iter().map(|key| (key, new_value.clone())).collect();
+        puts.push((new_key, new_value));
+
+        self.commit(puts, deletes)?;
+        Ok(new_event)
+    }
+
+    /// 複数月の月別スケジュールをまとめて取得
+    ///
+    /// # Arguments
+    /// * `from` - 開始年月 (例: 202511)
+    /// * `to` - 終了年月 (例: 202602、この月を含む)
+    ///
+    /// # Returns
+    /// 各月の月別スケジュール（古い月から順、イベントのない月も含む）
+    pub fn get_schedules_between(&mut self, from: u32, to: u32) -> Result<Vec<MonthlySchedule>> {
+        let (start, end) = months_scan_range(from, to)?;
+        self.collect_schedules(&start, &end, from, to)
+    }
+
+    /// 年間スケジュールを取得
+    ///
+    /// # Arguments
+    /// * `year` - 取得対象の年 (例: 2025)
+    ///
+    /// # Returns
+    /// 1月から12月までの12件の月別スケジュール（イベントのない月も含む）
+    pub fn get_yearly_schedule(&mut self, year: u32) -> Result<Vec<MonthlySchedule>> {
+        let (start, end) = yearly_scan_range(year);
+        self.collect_schedules(&start, &end, year * 100 + 1, year * 100 + 12)
+    }
+
+    /// グレードで絞り込んだ年間スケジュールを取得
+    ///
+    /// # Arguments
+    /// * `year` - 取得対象の年 (例: 2025)
+    /// * `grade` - グレード (例: `Grade::G1`)
+    ///
+    /// # Returns
+    /// 1月から12月までの12件の月別スケジュール（該当グレードのイベントのみ）
+    pub fn get_yearly_schedule_by_grade(&mut self, year: u32, grade: Grade) -> Result<Vec<MonthlySchedule>> {
+        let mut schedules = self.get_yearly_schedule(year)?;
+        for schedule in &mut schedules {
+            schedule.events.retain(|event| event.grade == grade);
+        }
+        Ok(schedules)
+    }
+
+    /// グレードで年間のイベントを検索
+    ///
+    /// 保存済みの表記ゆれ ("Ｇ１", "GⅠ" など) は読み込み時に正規化されるため、正規の表記と同じグレードとして扱う
+    ///
+    /// # Arguments
+    /// * `year` - 対象の年 (例: 2025)
+    /// * `grades` - 対象のグレード (例: `&[Grade::SG, Grade::G1]`)
+    ///
+    /// # Returns
+    /// (年月, イベント) のベクター（開始日順）
+    pub fn get_events_by_grade(&mut self, year: u32, grades: &[Grade]) -> Result<Vec<(u32, RaceEvent)>> {
+        let (start, end) = yearly_scan_range(year);
+        let results = self.store_scan_decoded::<RaceEvent>(&start, &end)?;
+
+        let mut events = Vec::new();
+        for (key, event) in results {
+            if let Ok(Key::Monthly { year_month, .. }) = key.parse::<Key>() {
+                if grades.contains(&event.grade) {
+                    events.push((year_month, event));
+                }
+            }
+        }
+
+        events.sort_by(|a, b| a.1.start_date.cmp(&b.1.start_date).then(a.0.cmp(&b.0)));
+        Ok(events)
+    }
+
+    /// 年間の月別概要を取得
+    ///
+    /// その年の月別ビューを1回だけ走査し、月ごとのイベント数・グレード別の数・会場を集計する。
+    /// デシリアライズできないエントリは数えて飛ばす
+    ///
+    /// # Arguments
+    /// * `year` - 対象の年 (例: 2025)
+    ///
+    /// # Returns
+    /// 1月から12月までの概要
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn year_overview(&mut self, year: u32) -> Result<YearOverview> {
+        validate_year_month(year * 100 + 1)?;
+        let mut overview = YearOverview {
+            year,
+            months: (1..=12).map(|month| MonthOverview { year_month: year * 100 + month, ..Default::default() }).collect(),
+            corrupted_entries: 0,
+        };
+        let mut seen: BTreeSet<(u32, u32, String, String)> = BTreeSet::new();
+        let mut venues: Vec<BTreeSet<u32>> = vec![BTreeSet::new(); 12];
+
+        let (start, end) = yearly_scan_range(year);
+        for (key, value) in self.store_scan(&start, &end)? {
+            let Ok(Key::Monthly { year_month, .. }) = key.parse::<Key>() else {
+                continue;
+            };
+            let Some(index) = (year_month % 100).checked_sub(1).filter(|index| *index < 12) else {
+                continue;
+            };
+            let Ok(event) = self.decode_entry::<RaceEvent>(&key, &value) else {
+                overview.corrupted_entries += 1;
+                continue;
+            };
+            // 同一内容の重複登録は1件と数える (get_monthly_schedule と同じ)
+            if !seen.insert((year_month, event.venue_id, event.event_name.clone(), event.start_date.clone())) {
+                continue;
+            }
+            let month = &mut overview.months[index as usize];
+            month.events += 1;
+            *month.grades.entry(event.grade).or_default() += 1;
+            venues[index as usize].insert(event.venue_id);
+        }
+        for (month, venues) in overview.months.iter_mut().zip(venues) {
+            month.venues = venues.into_iter().collect();
+        }
+        Ok(overview)
+    }
+
+    /// 会場で全期間のイベントを検索
+    ///
+    /// 会場インデックスが有効な場合はインデックスを走査し、該当する月別エントリのみを読み込む。
+    /// 無効な場合は月別ビュー全体を走査する。
+    ///
+    /// # Arguments
+    /// * `venue_id` - 会場ID
+    ///
+    /// # Returns
+    /// (年月, イベント) のベクター（開始日順）
+    pub fn get_events_by_venue(&mut self, venue_id: u32) -> Result<Vec<(u32, RaceEvent)>> {
+        let mut events = Vec::new();
+        if self.indexes.by_venue {
+            for key in self.scan_keys(venue_index_scan_range(venue_id))? {
+                if let Ok(Key::VenueIndex { year_month, tournament_id, .. }) = key.parse::<Key>() {
+                    if let Some(event) = self.get_race_event(year_month, &tournament_id)? {
+                        events.push((year_month, event));
+                    }
+                }
+            }
+        } else {
+            let (start, end) = namespace_scan_range(PREFIX_MONTHLY);
+            for (key, event) in self.store_scan_decoded::<RaceEvent>(&start, &end)? {
+                if let Ok(Key::Monthly { year_month, .. }) = key.parse::<Key>() {
+                    if event.venue_id == venue_id {
+                        events.push((year_month, event));
+                    }
+                }
+            }
+        }
+
+        events.sort_by(|a, b| a.1.start_date.cmp(&b.1.start_date).then(a.0.cmp(&b.0)));
+        Ok(events)
+    }
+
+    /// イベント名・会場名の部分一致でイベントを検索
+    ///
+    /// ASCII文字は大文字小文字を区別せず、それ以外の文字は完全一致で比較する。
+    /// 名前インデックスが有効で、クエリが2文字以上の場合はインデックスから候補を絞り込み、
+    /// 該当する月別エントリのみを読み込む。それ以外は月別ビューを走査する。
+    ///
+    /// # Arguments
+    /// * `query` - 検索文字列 (例: "高松宮")
+    /// * `year_month` - 指定した場合はその月のみを検索 (例: `Some(202509)`)
+    ///
+    /// # Returns
+    /// (年月, イベント) のベクター（開始日順）
+    pub fn search_events(&mut self, query: &str, year_month: Option<u32>) -> Result<Vec<(u32, RaceEvent)>> {
+        if let Some(year_month) = year_month {
+            validate_year_month(year_month)?;
+        }
+        let query = query.to_ascii_lowercase();
+        let matches = |event: &RaceEvent| {
+            event.event_name.to_ascii_lowercase().contains(&query)
+                || event.venue_name.to_ascii_lowercase().contains(&query)
+        };
+
+        let tokens = name_tokens(&query);
+        let mut events = Vec::new();
+        if self.indexes.by_name && !tokens.is_empty() {
+            // 全トークンを含む (年月, 大会ID) が候補。バイグラムの一致は部分一致を保証しないので再検証する
+            let mut candidates: Option<BTreeSet<(u32, String)>> = None;
+            for token in &tokens {
+                let found: BTreeSet<(u32, String)> = self
+                    .scan_keys(name_index_scan_range(token, year_month))?
+                    .into_iter()
+                    .filter_map(|key| match key.parse::<Key>() {
+                        Ok(Key::NameIndex { year_month, tournament_id, .. }) => Some((year_month, tournament_id)),
+                        _ => None,
+                    })
+                    .collect();
+                let narrowed = match candidates {
+                    Some(candidates) => candidates.intersection(&found).cloned().collect(),
+                    None => found,
+                };
+                let done = narrowed.is_empty();
+                candidates = Some(narrowed);
+                if done {
+                    break;
+                }
+            }
+            for (year_month, tournament_id) in candidates.unwrap_or_default() {
+                if let Some(event) = self.get_race_event(year_month, &tournament_id)? {
+                    if matches(&event) {
+                        events.push((year_month, event));
+                    }
+                }
+            }
+        } else {
+            let (start, end) = match year_month {
+                Some(year_month) => monthly_scan_range(year_month),
+                None => namespace_scan_range(PREFIX_MONTHLY),
+            };
+            for (key, event) in self.store_scan_decoded::<RaceEvent>(&start, &end)? {
+                if let Ok(Key::Monthly { year_month, .. }) = key.parse::<Key>() {
+                    if matches(&event) {
+                        events.push((year_month, event));
+                    }
+                }
+            }
+        }
+
+        events.sort_by(|a, b| a.1.start_date.cmp(&b.1.start_date).then(a.0.cmp(&b.0)));
+        Ok(events)
+    }
+
+    /// スキャン結果を月ごとにまとめ、`from` から `to` までの月別スケジュールを組み立てる
+    fn collect_schedules(&mut self, start: &str, end: &str, from: u32, to: u32) -> Result<Vec<MonthlySchedule>> {
+        let results = self.store_scan_decoded::<RaceEvent>(start, end)?;
+
+        let mut by_month: HashMap<u32, Vec<(String, RaceEvent)>> = HashMap::new();
+        for (key, event) in results {
+            if let Ok(Key::Monthly { year_month, .. }) = key.parse::<Key>() {
+                by_month.entry(year_month).or_default().push((key, event));
+            }
+        }
+
+        // 月ごとに get_monthly_schedule と同じ重複の除去と並べ替えを通す
+        let mut schedules = Vec::new();
+        let mut year_month = from;
+        while year_month <= to {
+            let groups = self.group_duplicates(by_month.remove(&year_month).unwrap_or_default());
+            schedules.push(self.schedule_from_groups(year_month, groups, ScheduleOptions::default())?);
+            year_month = next_year_month(year_month);
+        }
+
+        Ok(schedules)
+    }
+
+    /// 個別レースデータを保存
+    ///
+    /// 同じ大会・タイムスタンプのレースが既にある場合は上書きする。
+    /// 上書きしたくない場合は `put_race_data_unique` を使う
+    /// 
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    /// * `data` - レースデータ
+    /// 
+    /// # Returns
+    /// 操作結果
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, data)))]
+    pub fn put_race_data<T: Serialize>(&mut self, tournament_id: &str, timestamp: u64, data: &T) -> Result<()> {
+        let (key, value) = self.encode_race(tournament_id, timestamp, data)?;
+        self.write_race(key, value, RaceWrite::Upsert)
+    }
+
+    /// 新しいレースデータを保存 (既存のレースは上書きしない)
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    /// * `data` - レースデータ
+    ///
+    /// # Returns
+    /// 操作結果。同じタイムスタンプのレースが既にある場合は `StoreError::KeyExists` (何も書き込まない)
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, data)))]
+    pub fn create_race_data<T: Serialize>(&mut self, tournament_id: &str, timestamp: u64, data: &T) -> Result<()> {
+        let (key, value) = self.encode_race(tournament_id, timestamp, data)?;
+        self.write_race(key, value, RaceWrite::Create)
+    }
+
+    /// 既存のレースデータを更新
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    /// * `data` - 新しいレースデータ
+    ///
+    /// # Returns
+    /// 操作結果。レースが存在しない場合は `StoreError::NotFound` (何も書き込まない)
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, data)))]
+    pub fn update_race_data<T: Serialize>(&mut self, tournament_id: &str, timestamp: u64, data: &T) -> Result<()> {
+        let (key, value) = self.encode_race(tournament_id, timestamp, data)?;
+        self.write_race(key, value, RaceWrite::Update)
+    }
+
+    /// レースデータのキーと値を作る
+    fn encode_race<T: Serialize>(&self, tournament_id: &str, timestamp: u64, data: &T) -> Result<(String, String)> {
+        let key = try_tournament_key(tournament_id, timestamp)?;
+        let value = self.encode_value(data)?;
+        Ok((key, value))
+    }
+
+    /// 既存のレースの有無を確認してからレースデータを書き込む
+    fn write_race(&mut self, key: String, value: String, mode: RaceWrite) -> Result<()> {
+        if mode != RaceWrite::Upsert {
+            let exists = self.store_contains(&key)?;
+            if mode == RaceWrite::Create && exists {
+                return Err(StoreError::KeyExists { key });
+            }
+            if mode == RaceWrite::Update && !exists {
+                return Err(StoreError::not_found(key));
+            }
+        }
+        self.commit(vec![(key, value)], Vec::new())
+    }
+
+    /// 既存のレースを上書きせずに個別レースデータを保存
+    ///
+    /// 同じタイムスタンプのレースが既にある場合、`auto_bump` が false なら `StoreError::KeyExists` を返す。
+    /// true なら空いているタイムスタンプが見つかるまで 1 ms ずつ後ろにずらす
+    /// (秒単位に丸められたタイムスタンプの衝突を想定し、最大 `MAX_TIMESTAMP_BUMPS` 回)
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    /// * `data` - レースデータ
+    /// * `auto_bump` - 衝突時にタイムスタンプをずらすか
+    ///
+    /// # Returns
+    /// 実際に保存したタイムスタンプ。空きが見つからない場合は元のキーの `StoreError::KeyExists`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, data)))]
+    pub fn put_race_data_unique<T: Serialize>(&mut self, tournament_id: &str, timestamp: u64, data: &T, auto_bump: bool) -> Result<u64> {
+        let (key, value) = self.encode_race(tournament_id, timestamp, data)?;
+        if !auto_bump {
+            self.write_race(key, value, RaceWrite::Create)?;
+            return Ok(timestamp);
+        }
+        if !self.store_contains(&key)? {
+            self.write_race(key, value, RaceWrite::Upsert)?;
+            return Ok(timestamp);
+        }
+
+        // 候補の範囲を一度だけスキャンして、使われているタイムスタンプを集める
+        let last = timestamp.saturating_add(MAX_TIMESTAMP_BUMPS);
+        let (start, end) = match last.checked_add(1) {
+            Some(end) => tournament_time_range(tournament_id, timestamp, end),
+            // 終了キーは含まないため、u64::MAX まで含めるには大会全体の終端を使う
+            None => (key.clone(), tournament_scan_range(tournament_id).1),
+        };
+        let taken: BTreeSet<u64> = self
+            .scan_tournament_keys((start, end), tournament_id)?
+            .iter()
+            .filter_map(|key| match key.parse::<Key>() {
+                Ok(Key::Tournament { timestamp, .. }) => Some(timestamp),
+                _ => None,
+            })
+            .collect();
+        let Some(free) = (1..=MAX_TIMESTAMP_BUMPS)
+            .map_while(|bump| timestamp.checked_add(bump))
+            .find(|candidate| !taken.contains(candidate))
+        else {
+            return Err(StoreError::KeyExists { key });
+        };
+        self.write_race(try_tournament_key(tournament_id, free)?, value, RaceWrite::Upsert)?;
+        Ok(free)
+    }
+
+    /// 大会のレースデータをまとめて保存
+    ///
+    /// すべてを先にシリアライズしてから一度のバッチで書き込むため、
+    /// 途中でエラーになった場合は何も書き込まれない。FileStore の書き出しも一度だけになる
+    /// (`SyncPolicy::OnFlush` の FileStore なら、複数回呼んだ後の `flush` で一度だけ書き出せる)
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `races` - (タイムスタンプ, レースデータ) のスライス
+    ///
+    /// # Returns
+    /// 書き込んだレース数
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, races), fields(races = races.len())))]
+    pub fn put_races_bulk<T: Serialize>(&mut self, tournament_id: &str, races: &[(u64, T)]) -> Result<usize> {
+        let mut puts = Vec::with_capacity(races.len());
+        for (timestamp, data) in races {
+            puts.push((try_tournament_key(tournament_id, *timestamp)?, self.encode_value(data)?));
+        }
+        self.commit_races_bulk(puts)
+    }
+
+    /// 複数大会のレースデータをまとめて保存
+    ///
+    /// # Arguments
+    /// * `races` - (大会ID, タイムスタンプ, レースデータ) のスライス
+    ///
+    /// # Returns
+    /// 書き込んだレース数
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(races = races.len())))]
+    pub fn put_races_bulk_multi<T: Serialize>(&mut self, races: &[(String, u64, T)]) -> Result<usize> {
+        let mut puts = Vec::with_capacity(races.len());
+        for (tournament_id, timestamp, data) in races {
+            puts.push((try_tournament_key(tournament_id, *timestamp)?, self.encode_value(data)?));
+        }
+        self.commit_races_bulk(puts)
+    }
+
+    fn commit_races_bulk(&mut self, puts: Vec<(String, String)>) -> Result<usize> {
+        let written = puts.len();
+        if written > 0 {
+            self.commit(puts, Vec::new())?;
+        }
+        Ok(written)
+    }
+
+    /// 個別レースデータを削除
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    ///
+    /// # Returns
+    /// 削除した場合は true、存在しなかった場合は false
+    pub fn delete_race_data(&mut self, tournament_id: &str, timestamp: u64) -> Result<bool> {
+        let key = try_tournament_key(tournament_id, timestamp)?;
+        if !self.store_contains(&key)? {
+            return Ok(false);
+        }
+        self.commit(Vec::new(), vec![key])?;
+        Ok(true)
+    }
+
+    /// 指定時刻より前のレースデータを削除
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `cutoff` - この時刻より前 (cutoff を含まない) のレースを削除
+    ///
+    /// # Returns
+    /// 削除したレースデータの数
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn delete_races_before(&mut self, tournament_id: &str, cutoff: u64) -> Result<usize> {
+        validate_tournament_id(tournament_id)?;
+        let keys = self.scan_tournament_keys(tournament_time_range(tournament_id, 0, cutoff), tournament_id)?;
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        let deleted = keys.len();
+        self.commit(Vec::new(), keys)?;
+        Ok(deleted)
+    }
+
+    /// 大会の全レースデータを、値のデシリアライズを並列に行って取得
+    ///
+    /// 先にスキャンしてから値をデシリアライズする。`rayon` フィーチャーが有効な場合は全コアで並列に、
+    /// 無効な場合は順に行う。結果は `get_tournament_races` と同じ
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// レースデータのベクター（タイムスタンプ順）。形式の不正なキーはエラーにせず読み飛ばし、`metrics().malformed_keys` に数える。
+    /// デシリアライズできない値があれば、そのキーを含む `StoreError::SerializationError`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn get_tournament_races_par<T: DeserializeOwned + Send>(&mut self, tournament_id: &str) -> Result<Vec<T>> {
+        validate_tournament_id(tournament_id)?;
+        let results = self.scan_tournament(tournament_scan_range(tournament_id), tournament_id)?;
+        Ok(self.decode_sorted(results)?.into_iter().map(|(_, race)| race).collect())
+    }
+
+    /// 大会の全レースデータを取得
+    /// 
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// 
+    /// # Returns
+    /// レースデータのベクター（タイムスタンプ順）。形式の不正なキーはエラーにせず読み飛ばし、`metrics().malformed_keys` に数える。
+    /// デシリアライズできない値があれば、そのキーを含む `StoreError::SerializationError`
+    pub fn get_tournament_races<T: DeserializeOwned>(&mut self, tournament_id: &str) -> Result<Vec<T>> {
+        validate_tournament_id(tournament_id)?;
+        let mut results = self.scan_tournament_decoded::<T>(tournament_scan_range(tournament_id), tournament_id)?;
+        // タイムスタンプは固定桁の16進数なのでキー順がタイムスタンプ順になる
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        
+        Ok(results.into_iter().map(|(_, race)| race).collect())
+    }
+
+    /// 大会の全レースデータをタイムスタンプ付きで取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// (タイムスタンプ, レースデータ) のベクター（タイムスタンプ順）。形式の不正なキーはエラーにせず読み飛ばし、`metrics().malformed_keys` に数える。
+    /// デシリアライズできない値があれば、そのキーを含む `StoreError::SerializationError`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn get_tournament_races_with_keys<T: DeserializeOwned>(&mut self, tournament_id: &str) -> Result<Vec<(u64, T)>> {
+        validate_tournament_id(tournament_id)?;
+        self.scan_races_with_keys(tournament_scan_range(tournament_id), tournament_id)
+    }
+
+    /// 大会のレースデータを1件ずつデシリアライズしてクロージャに渡す
+    ///
+    /// 先に読むのはキーだけで、値はクロージャに渡す直前に1件ずつデシリアライズするため、
+    /// 大会全体を `Vec` に読み込まずに集計できる。クロージャが `ControlFlow::Break` を返すと残りは読まない
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `f` - (タイムスタンプ, レースデータ) をタイムスタンプ順に受け取るクロージャ
+    ///
+    /// # Returns
+    /// 成功時は()。形式の不正なキーはエラーにせず読み飛ばし、`metrics().malformed_keys` に数える。
+    /// デシリアライズできない値があれば、そのキーを含む `StoreError::SerializationError`
+    pub fn for_each_race<T: DeserializeOwned>(&mut self, tournament_id: &str, mut f: impl FnMut(u64, T) -> ControlFlow<()>) -> Result<()> {
+        self.try_for_each_race(tournament_id, |timestamp, race| Ok::<_, StoreError>(f(timestamp, race)))
+    }
+
+    /// `for_each_race` のクロージャが失敗しうる版
+    ///
+    /// クロージャがエラーを返した時点で打ち切り、そのエラーをそのまま返す。
+    /// ストアやデシリアライズのエラーは `E: From<StoreError>` で変換して返す
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `f` - (タイムスタンプ, レースデータ) をタイムスタンプ順に受け取るクロージャ
+    ///
+    /// # Returns
+    /// 成功時は()。クロージャのエラー、またはストアのエラーを変換したもの
+    pub fn try_for_each_race<T, E>(
+        &mut self,
+        tournament_id: &str,
+        mut f: impl FnMut(u64, T) -> std::result::Result<ControlFlow<()>, E>,
+    ) -> std::result::Result<(), E>
+    where
+        T: DeserializeOwned,
+        E: From<StoreError>,
+    {
+        validate_tournament_id(tournament_id)?;
+        // タイムスタンプは固定桁の16進数なのでキー順がタイムスタンプ順になる
+        let mut keys = self.scan_keys(tournament_scan_range(tournament_id))?;
+        keys.sort();
+        for key in keys {
+            let Ok(Key::Tournament { timestamp, tournament_id: id }) = key.parse::<Key>() else {
+                continue;
+            };
+            if id != tournament_id {
+                continue;
+            }
+            // キーを読んだ後に削除されたエントリは飛ばす
+            let Some(race) = self.store_read::<T>(&key)? else {
+                continue;
+            };
+            if f(timestamp, race)?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// 大会内の指定時間帯のレースデータを取得
+    ///
+    /// 範囲は [from_ts, to_ts) で、ちょうど `to_ts` のレースは含まない
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `from_ts` - 開始タイムスタンプ（含む）
+    /// * `to_ts` - 終了タイムスタンプ（含まない）
+    ///
+    /// # Returns
+    /// (タイムスタンプ, レースデータ) のベクター（タイムスタンプ順）。形式の不正なキーはエラーにせず読み飛ばし、`metrics().malformed_keys` に数える。
+    /// デシリアライズできない値があれば、そのキーを含む `StoreError::SerializationError`
+    pub fn get_races_between<T: DeserializeOwned>(&mut self, tournament_id: &str, from_ts: u64, to_ts: u64) -> Result<Vec<(u64, T)>> {
+        validate_tournament_id(tournament_id)?;
+        if from_ts >= to_ts {
+            return Ok(Vec::new());
+        }
+        self.scan_races_with_keys(tournament_time_range(tournament_id, from_ts, to_ts), tournament_id)
+    }
+
+    /// 大会内の指定時間帯のレース数を取得（値はデシリアライズしない）
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `from_ts` - 開始タイムスタンプ（含む）
+    /// * `to_ts` - 終了タイムスタンプ（含まない）
+    ///
+    /// # Returns
+    /// 範囲内のレース数
+    pub fn count_races_between(&mut self, tournament_id: &str, from_ts: u64, to_ts: u64) -> Result<usize> {
+        validate_tournament_id(tournament_id)?;
+        if from_ts >= to_ts {
+            return Ok(0);
+        }
+        Ok(self.scan_tournament_keys(tournament_time_range(tournament_id, from_ts, to_ts), tournament_id)?.len())
+    }
+
+    /// 大会のレース数を取得（キーのみを走査）
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// レース数
+    pub fn count_races(&mut self, tournament_id: &str) -> Result<usize> {
+        validate_tournament_id(tournament_id)?;
+        Ok(self.scan_tournament_keys(tournament_scan_range(tournament_id), tournament_id)?.len())
+    }
+
+    /// 大会の各レースの値の大きさを取得
+    ///
+    /// 値のデシリアライズは行わないため、型が分からない場合や壊れた値が含まれていても使える
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// (タイムスタンプ, 値のバイト数) のベクター（タイムスタンプ順）
+    pub fn get_race_sizes(&mut self, tournament_id: &str) -> Result<Vec<(u64, usize)>> {
+        validate_tournament_id(tournament_id)?;
+        let results = self.scan_tournament(tournament_scan_range(tournament_id), tournament_id)?;
+        let mut sizes: Vec<(u64, usize)> = results
+            .iter()
+            .filter_map(|(key, value)| match key.parse::<Key>() {
+                Ok(Key::Tournament { timestamp, .. }) => Some((timestamp, value.len())),
+                _ => None,
+            })
+            .collect();
+        sizes.sort_unstable();
+        Ok(sizes)
+    }
+
+    /// 大会のレースデータ統計を取得
+    ///
+    /// 値のデシリアライズは行わないため、壊れた値が含まれていても集計できる
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// 大会統計
+    pub fn tournament_statistics(&mut self, tournament_id: &str) -> Result<TournamentStats> {
+        validate_tournament_id(tournament_id)?;
+        let results = self.scan_tournament(tournament_scan_range(tournament_id), tournament_id)?;
+
+        let mut stats = TournamentStats::default();
+        for (key, value) in &results {
+            stats.race_count += 1;
+            stats.total_bytes += key.len() + value.len();
+            if let Ok(Key::Tournament { timestamp, .. }) = key.parse::<Key>() {
+                stats.earliest = Some(stats.earliest.map_or(timestamp, |t| t.min(timestamp)));
+                stats.latest = Some(stats.latest.map_or(timestamp, |t| t.max(timestamp)));
+            }
+        }
+        Ok(stats)
+    }
+
+    /// スキャン範囲内のレースデータをタイムスタンプ付きで取得
+    fn scan_races_with_keys<T: DeserializeOwned>(&mut self, range: (String, String), tournament_id: &str) -> Result<Vec<(u64, T)>> {
+        let results = self.scan_tournament_decoded::<T>(range, tournament_id)?;
+
+        let mut races = Vec::new();
+        for (key, race) in results {
+            let Ok(Key::Tournament { timestamp, .. }) = key.parse::<Key>() else {
+                continue;
+            };
+            races.push((timestamp, race));
+        }
+
+        races.sort_by_key(|(timestamp, _)| *timestamp);
+        Ok(races)
+    }
+
+    /// 特定のレースデータを取得
+    /// 
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    /// 
+    /// # Returns
+    /// レースデータ
+    pub fn get_race_data<T: DeserializeOwned>(&self, tournament_id: &str, timestamp: u64) -> Result<T> {
+        let key = try_tournament_key(tournament_id, timestamp)?;
+        self.store_read(&key)?
+            .ok_or_else(|| crate::StoreError::not_found(key))
+    }
+
+    /// 日時を指定して個別レースデータを保存
+    ///
+    /// 日時は UTC として扱い、エポックミリ秒に変換して保存する。
+    /// キーは `put_race_data` と同じ形式なので、既存データや生のタイムスタンプ API と互換性がある。
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `when` - レースの日時 (UTC)
+    /// * `data` - レースデータ
+    ///
+    /// # Returns
+    /// 操作結果。1970年より前の日時は `StoreError::InvalidDate`
+    pub fn put_race_data_at<T: Serialize>(&mut self, tournament_id: &str, when: NaiveDateTime, data: &T) -> Result<()> {
+        self.put_race_data(tournament_id, datetime_to_millis(when)?, data)
+    }
+
+    /// タイムゾーン付きの日時を指定して個別レースデータを保存
+    ///
+    /// JST (+09:00) などのオフセット付き日時をそのまま渡せる。保存形式は `put_race_data_at` と同じ
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `when` - レースの日時
+    /// * `data` - レースデータ
+    ///
+    /// # Returns
+    /// 操作結果。1970年より前の日時は `StoreError::InvalidDate`
+    pub fn put_race_data_at_offset<T: Serialize>(&mut self, tournament_id: &str, when: DateTime<FixedOffset>, data: &T) -> Result<()> {
+        self.put_race_data_at(tournament_id, when.naive_utc(), data)
+    }
+
+    /// 日時を指定して個別レースデータを取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `when` - レースの日時 (UTC。オフセット付き日時は `naive_utc()` で変換する)
+    ///
+    /// # Returns
+    /// レースデータ
+    pub fn get_race_data_at<T: DeserializeOwned>(&self, tournament_id: &str, when: NaiveDateTime) -> Result<T> {
+        self.get_race_data(tournament_id, datetime_to_millis(when)?)
+    }
+
+    /// 大会内の指定期間のレースデータを日時付きで取得
+    ///
+    /// 範囲は [from, to) で、`get_races_between` と同じ規則に従う
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `from` - 開始日時 (UTC、含む)
+    /// * `to` - 終了日時 (UTC、含まない)
+    ///
+    /// # Returns
+    /// (日時, レースデータ) のベクター（日時順）
+    pub fn get_races_between_dates<T: DeserializeOwned>(&mut self, tournament_id: &str, from: NaiveDateTime, to: NaiveDateTime) -> Result<Vec<(NaiveDateTime, T)>> {
+        let from_ts = datetime_to_millis(from)?;
+        let to_ts = datetime_to_millis(to)?;
+        Ok(self
+            .get_races_between(tournament_id, from_ts, to_ts)?
+            .into_iter()
+            .filter_map(|(timestamp, race)| millis_to_datetime(timestamp).map(|when| (when, race)))
+            .collect())
+    }
+
+    /// レース番号を指定して個別レースデータを保存
+    ///
+    /// 日付とレース番号から「その日の JST 0:00 + レース番号分」のタイムスタンプを決定的に作り、
+    /// `put_race_data` と同じキー形式で保存する。実際の発走時刻 (JST 0:13 以降) を
+    /// タイムスタンプとして保存したレースとは衝突しない
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `date` - 開催日 ("YYYY-MM-DD")
+    /// * `race_number` - レース番号 (1〜12)
+    /// * `data` - レースデータ
+    ///
+    /// # Returns
+    /// 操作結果。レース番号が範囲外の場合は `StoreError::ValidationError`
+    pub fn put_numbered_race<T: Serialize>(&mut self, tournament_id: &str, date: &str, race_number: u32, data: &T) -> Result<()> {
+        let timestamp = numbered_race_timestamp(date, race_number)?;
+        self.put_race_data(tournament_id, timestamp, data)
+    }
+
+    /// レース番号を指定して個別レースデータを取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `date` - 開催日 ("YYYY-MM-DD")
+    /// * `race_number` - レース番号 (1〜12)
+    ///
+    /// # Returns
+    /// レースデータ
+    pub fn get_numbered_race<T: DeserializeOwned>(&self, tournament_id: &str, date: &str, race_number: u32) -> Result<T> {
+        let timestamp = numbered_race_timestamp(date, race_number)?;
+        self.get_race_data(tournament_id, timestamp)
+    }
+
+    /// 開催日のレース番号付きレースをすべて取得
+    ///
+    /// タイムスタンプで保存したレースは含まない
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `date` - 開催日 ("YYYY-MM-DD")
+    ///
+    /// # Returns
+    /// (レース番号, レースデータ) のベクター（レース番号順）
+    pub fn get_day_races<T: DeserializeOwned>(&mut self, tournament_id: &str, date: &str) -> Result<Vec<(u32, T)>> {
+        let first = numbered_race_timestamp(date, 1)?;
+        let end = numbered_race_timestamp(date, MAX_RACE_NUMBER)? + NUMBERED_RACE_STEP_MS;
+        Ok(self
+            .get_races_between(tournament_id, first, end)?
+            .into_iter()
+            .map(|(timestamp, race)| ((((timestamp - first) / NUMBERED_RACE_STEP_MS) + 1) as u32, race))
+            .collect())
+    }
+
+    /// レース結果を保存
+    ///
+    /// 任意のレースデータとは別の名前空間に保存されるため、
+    /// 同じ大会ID・タイムスタンプの `put_race_data` と衝突しない
+    ///
+    /// # Arguments
+    /// * `result` - 保存するレース結果
+    ///
+    /// # Returns
+    /// 操作結果
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(tournament_id = %result.tournament_id, timestamp = result.timestamp)))]
+    pub fn put_race_result(&mut self, result: &RaceResult) -> Result<()> {
+        let key = try_result_key(&result.tournament_id, result.timestamp)?;
+        let value = self.encode_value(result)?;
+
+        // 着順の選手を出走者インデックスに登録（登録番号 0 は未割り当てなので除く）
+        let racer_ids: Vec<u32> = result
+            .finishing_order
+            .iter()
+            .map(|&(_, racer_id)| racer_id)
+            .filter(|&racer_id| racer_id != 0)
+            .collect();
+        let mut puts = vec![(key, value)];
+        let mut deletes = Vec::new();
+        self.add_participant_changes(&result.tournament_id, result.timestamp, &racer_ids, &mut puts, &mut deletes)?;
+        self.commit(puts, deletes)
+    }
+
+    /// レースの出走者を登録
+    ///
+    /// 既に登録されている出走者は置き換える。空のスライスを渡すと登録を解除する。
+    /// レースデータまたはレース結果を削除すると、そのレースの出走者登録も削除される。
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    /// * `racer_ids` - 出走した選手の登録番号
+    ///
+    /// # Returns
+    /// 操作結果。登録番号 0 を含む場合は `StoreError::InvalidKey`
+    pub fn index_race_participants(&mut self, tournament_id: &str, timestamp: u64, racer_ids: &[u32]) -> Result<()> {
+        let mut puts = Vec::new();
+        let mut deletes = Vec::new();
+        self.add_participant_changes(tournament_id, timestamp, racer_ids, &mut puts, &mut deletes)?;
+        self.commit(puts, deletes)
+    }
+
+    /// 選手が出走したレースを取得
+    ///
+    /// # Arguments
+    /// * `racer_id` - 選手の登録番号
+    ///
+    /// # Returns
+    /// (大会ID, タイムスタンプ) のベクター（タイムスタンプ順）
+    pub fn get_races_for_racer(&mut self, racer_id: u32) -> Result<Vec<(String, u64)>> {
+        let mut races: Vec<(String, u64)> = self
+            .scan_keys(racer_index_scan_range(racer_id))?
+            .into_iter()
+            .filter_map(|key| match key.parse::<Key>() {
+                Ok(Key::RacerIndex { tournament_id, timestamp, .. }) => Some((tournament_id, timestamp)),
+                _ => None,
+            })
+            .collect();
+        races.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(races)
+    }
+
+    /// 特定のレース結果を取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    ///
+    /// # Returns
+    /// レース結果
+    pub fn get_race_result(&self, tournament_id: &str, timestamp: u64) -> Result<RaceResult> {
+        let key = try_result_key(tournament_id, timestamp)?;
+        self.store_read(&key)?
+            .ok_or_else(|| crate::StoreError::not_found(key))
+    }
+
+    /// 大会の全レース結果を取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// レース結果のベクター（タイムスタンプ順）。形式の不正なキーはエラーにせず読み飛ばし、`metrics().malformed_keys` に数える。
+    /// デシリアライズできない値があれば、そのキーを含む `StoreError::SerializationError`
+    pub fn get_tournament_results(&mut self, tournament_id: &str) -> Result<Vec<RaceResult>> {
+        validate_tournament_id(tournament_id)?;
+        let mut race_results: Vec<RaceResult> = self
+            .scan_tournament_decoded(result_scan_range(tournament_id), tournament_id)?
+            .into_iter()
+            .map(|(_, result)| result)
+            .collect();
+
+        race_results.sort_by_key(|r| r.timestamp);
+        Ok(race_results)
+    }
+
+    /// 大会の確定済みレース結果のみを取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// 確定済みのレース結果（タイムスタンプ順）
+    pub fn get_decided_results(&mut self, tournament_id: &str) -> Result<Vec<RaceResult>> {
+        let mut results = self.get_tournament_results(tournament_id)?;
+        results.retain(RaceResult::is_decided);
+        Ok(results)
+    }
+
+    /// 会場の気象条件を保存
+    ///
+    /// # Arguments
+    /// * `venue_id` - 会場ID
+    /// * `date` - 対象日 (例: "2025-09-13")
+    /// * `conditions` - 気象条件
+    ///
+    /// # Returns
+    /// 操作結果。日付が YYYY-MM-DD 形式でない場合は `StoreError::InvalidDate`
+    pub fn put_conditions(&mut self, venue_id: u32, date: &str, conditions: &RaceDayConditions) -> Result<()> {
+        let key = conditions_key(venue_id, date_to_u32(parse_date(date)?));
+        let value = self.encode_value(conditions)?;
+        self.check_entry_size(&key, &value)?;
+        self.store_put(key, value)
+    }
+
+    /// 会場の気象条件を取得
+    ///
+    /// # Arguments
+    /// * `venue_id` - 会場ID
+    /// * `date` - 対象日 (例: "2025-09-13")
+    ///
+    /// # Returns
+    /// 気象条件（未登録の場合は None）
+    pub fn get_conditions(&self, venue_id: u32, date: &str) -> Result<Option<RaceDayConditions>> {
+        let key = conditions_key(venue_id, date_to_u32(parse_date(date)?));
+        self.store_read(&key)
+    }
+
+    /// 会場の期間内の気象条件を取得
+    ///
+    /// # Arguments
+    /// * `venue_id` - 会場ID
+    /// * `from_date` - 開始日 (例: "2025-09-10")
+    /// * `to_date` - 終了日 (例: "2025-09-16"、この日を含む)
+    ///
+    /// # Returns
+    /// 気象条件のベクター（日付順）
+    pub fn get_conditions_range(&mut self, venue_id: u32, from_date: &str, to_date: &str) -> Result<Vec<RaceDayConditions>> {
+        let from = date_to_u32(parse_date(from_date)?);
+        let to = date_to_u32(parse_date(to_date)?);
+        if from > to {
+            return Err(crate::StoreError::invalid_value(format!("from_date {} is after to_date {}", from_date, to_date)));
+        }
+
+        let (start, end) = conditions_scan_range(venue_id, from, to);
+        let mut results = self.store_scan_decoded(&start, &end)?;
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(results.into_iter().map(|(_, conditions)| conditions).collect())
+    }
+
+    /// レースの払戻金を保存
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    /// * `payout` - 払戻金
+    ///
+    /// # Returns
+    /// 操作結果
+    pub fn put_payouts(&mut self, tournament_id: &str, timestamp: u64, payout: &Payout) -> Result<()> {
+        let key = try_payout_key(tournament_id, timestamp)?;
+        let value = self.encode_value(payout)?;
+        self.check_entry_size(&key, &value)?;
+        self.store_put(key, value)
+    }
+
+    /// レースの払戻金を取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `timestamp` - レースのタイムスタンプ
+    ///
+    /// # Returns
+    /// 払戻金（未登録の場合は None）
+    pub fn get_payouts(&self, tournament_id: &str, timestamp: u64) -> Result<Option<Payout>> {
+        let key = try_payout_key(tournament_id, timestamp)?;
+        self.store_read(&key)
+    }
+
+    /// 大会全体の3連単払戻金の合計を取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// 3連単払戻金の合計（円）
+    pub fn get_tournament_payout_total(&mut self, tournament_id: &str) -> Result<u64> {
+        validate_tournament_id(tournament_id)?;
+        let results = self.scan_tournament_decoded::<Payout>(payout_scan_range(tournament_id), tournament_id)?;
+
+        let mut total = 0;
+        for (_, payout) in results {
+            total += payout.trifecta.iter().map(|entry| entry.amount).sum::<u64>();
+        }
+        Ok(total)
+    }
+
+    /// オッズのスナップショットを保存
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `race_timestamp` - レースのタイムスタンプ
+    /// * `snapshot_time` - スナップショット取得時刻（エポックミリ秒）
+    /// * `odds` - オッズデータ
+    ///
+    /// # Returns
+    /// 操作結果
+    pub fn put_odds_snapshot<T: Serialize>(
+        &mut self,
+        tournament_id: &str,
+        race_timestamp: u64,
+        snapshot_time: u64,
+        odds: &T,
+    ) -> Result<()> {
+        let key = try_odds_key(tournament_id, race_timestamp, snapshot_time)?;
+        let value = self.encode_value(odds)?;
+        self.check_entry_size(&key, &value)?;
+        self.store_put(key, value)
+    }
+
+    /// レースのオッズ履歴を取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `race_timestamp` - レースのタイムスタンプ
+    ///
+    /// # Returns
+    /// (スナップショット取得時刻, オッズ) のベクター（古い順）。形式の不正なキーはエラーにせず読み飛ばし、`metrics().malformed_keys` に数える。
+    /// デシリアライズできない値があれば、そのキーを含む `StoreError::SerializationError`
+    pub fn get_odds_history<T: DeserializeOwned>(&mut self, tournament_id: &str, race_timestamp: u64) -> Result<Vec<(u64, T)>> {
+        validate_tournament_id(tournament_id)?;
+        let (start, end) = odds_scan_range(tournament_id, race_timestamp);
+        let results = self.store_scan_decoded::<T>(&start, &end)?;
+
+        let mut history = Vec::new();
+        for (key, odds) in results {
+            if let Ok(Key::Odds { snapshot_time, .. }) = key.parse::<Key>() {
+                history.push((snapshot_time, odds));
+            }
+        }
+
+        history.sort_by_key(|(snapshot_time, _)| *snapshot_time);
+        Ok(history)
+    }
+
+    /// レースの最新オッズを取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `race_timestamp` - レースのタイムスタンプ
+    ///
+    /// # Returns
+    /// 最新の (スナップショット取得時刻, オッズ)
+    pub fn get_latest_odds<T: DeserializeOwned>(&mut self, tournament_id: &str, race_timestamp: u64) -> Result<(u64, T)> {
+        validate_tournament_id(tournament_id)?;
+        let (start, end) = odds_scan_range(tournament_id, race_timestamp);
+        let results = self.store_scan(&start, &end)?;
+
+        let (snapshot_time, key, value) = results
+            .into_iter()
+            .filter_map(|(key, value)| match key.parse::<Key>() {
+                Ok(Key::Odds { snapshot_time, .. }) => Some((snapshot_time, key, value)),
+                _ => None,
+            })
+            .max_by_key(|(snapshot_time, _, _)| *snapshot_time)
+            .ok_or_else(|| crate::StoreError::not_found(start.clone()))?;
+        Ok((snapshot_time, self.decode_entry(&key, &value)?))
+    }
+
+    /// 大会を開催期間に含まれるすべての月に登録（月跨ぎ大会対応）
+    ///
+    /// 各月への書き込みは `put_monthly_schedule` と同じ処理で行い、日別ビューも登録する
+    ///
+    /// # Arguments
+    /// * `event` - 登録する大会情報
+    ///
+    /// # Returns
+    /// 登録に使用した大会ID（`put_race_data` などにそのまま渡せる）
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(venue = %event.venue_name, event = %event.event_name)))]
+    pub fn register_tournament(&mut self, event: &RaceEvent) -> Result<String> {
+        self.validate_event(event, None)?;
+
+        let mut puts = Vec::new();
+        let mut deletes = Vec::new();
+        // 開始月から終了月まで、各月に登録
+        for year_month in event.months_covered()? {
+            self.plan_schedule_import(year_month, std::slice::from_ref(event), ConflictPolicy::Overwrite, &mut puts, &mut deletes)?;
+        }
+
+        self.commit(puts, deletes)?;
+        Ok(event_tournament_id(event))
+    }
+
+    /// 大会を複数の月に登録（月跨ぎ大会対応）
+    /// 
+    /// # Arguments
+    /// * `tournament` - 登録する大会情報
+    /// 
+    /// # Returns
+    /// 登録に使用した大会ID
+    pub fn register_tournament_to_months(&mut self, tournament: &RaceEvent) -> Result<String> {
+        self.register_tournament(tournament)
+    }
+
+    /// 期間内に開催されるイベントを取得
+    ///
+    /// 各イベントの開催期間 (start_date から duration_days 日間) が [from, to] と重なるものを返す。
+    /// 前月から続くイベントを拾うため、`from` の前月から `to` の月までの月別ビューを走査する。
+    /// 複数月に登録されたイベントは大会IDで重複を除く。
+    ///
+    /// # Arguments
+    /// * `from` - 開始日 (例: "2025-09-28")
+    /// * `to` - 終了日 (例: "2025-10-03"、この日を含む)
+    ///
+    /// # Returns
+    /// 期間内に開催されるイベント（開始日順）
+    pub fn get_events_in_date_range(&mut self, from: &str, to: &str) -> Result<Vec<RaceEvent>> {
+        let from_date = parse_date(from)?;
+        let to_date = parse_date(to)?;
+        if from_date > to_date {
+            return Err(crate::StoreError::invalid_date(from, format_args!("range start is after range end {:?}", to)));
+        }
+
+        let first_month = prev_year_month(from_date.year() as u32 * 100 + from_date.month());
+        let last_month = to_date.year() as u32 * 100 + to_date.month();
+        let (start, end) = months_scan_range(first_month, last_month)?;
+        let results = self.store_scan(&start, &end)?;
+
+        let mut seen = BTreeSet::new();
+        let mut events = Vec::new();
+        for (key, value) in results {
+            let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() else {
+                continue;
+            };
+            if seen.contains(&tournament_id) {
+                continue;
+            }
+            let event: RaceEvent = self.decode_entry(&key, &value)?;
+            // 開始日が不正なイベントや期間0日のイベントは対象外
+            let (Ok(event_start), Ok(event_end)) = (event.start(), event.end()) else {
+                continue;
+            };
+            if event_start <= to_date && event_end >= from_date {
+                seen.insert(tournament_id);
+                events.push(event);
+            }
+        }
+
+        events.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+        Ok(events)
+    }
+
+    /// 指定日に開催中の大会を取得
+    ///
+    /// # Arguments
+    /// * `date` - 対象日 (例: "2025-09-13")
+    ///
+    /// # Returns
+    /// 開催期間が対象日を含むイベント（開始日順、大会IDで重複除去）
+    pub fn get_ongoing_events(&mut self, date: &str) -> Result<Vec<RaceEvent>> {
+        self.get_events_in_date_range(date, date)
+    }
+
+    /// 近日開催予定の大会を取得
+    ///
+    /// # Arguments
+    /// * `date` - 基準日 (例: "2025-09-13")
+    /// * `within_days` - 基準日から何日先までを対象とするか
+    ///
+    /// # Returns
+    /// 開始日が基準日の翌日から `within_days` 日後までのイベント（開始日順、大会IDで重複除去）
+    pub fn get_upcoming_events(&mut self, date: &str, within_days: u32) -> Result<Vec<RaceEvent>> {
+        let from_date = parse_date(date)?;
+        let to_date = from_date + chrono::Duration::days(within_days as i64);
+
+        // 開始日の月に登録されるため、期間が触れる月だけを走査する
+        let first_month = from_date.year() as u32 * 100 + from_date.month();
+        let last_month = to_date.year() as u32 * 100 + to_date.month();
+        let (start, end) = months_scan_range(first_month, last_month)?;
+        let results = self.store_scan(&start, &end)?;
+
+        let mut seen = BTreeSet::new();
+        let mut events = Vec::new();
+        for (key, value) in results {
+            let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() else {
+                continue;
+            };
+            if seen.contains(&tournament_id) {
+                continue;
+            }
+            let event: RaceEvent = self.decode_entry(&key, &value)?;
+            let Ok(event_start) = event.start() else {
+                continue;
+            };
+            if event_start > from_date && event_start <= to_date {
+                seen.insert(tournament_id);
+                events.push(event);
+            }
+        }
+
+        events.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+        Ok(events)
+    }
+
+    /// 指定日に開催中のイベントを取得
+    ///
+    /// # Arguments
+    /// * `date` - 対象日 (例: "2025-09-13")
+    ///
+    /// # Returns
+    /// その日に開催されているイベント（開始日順）
+    pub fn get_events_on_date(&mut self, date: &str) -> Result<Vec<RaceEvent>> {
+        let (start, end) = daily_scan_range(date_to_u32(parse_date(date)?));
+        let mut events: Vec<RaceEvent> = self
+            .store_scan_decoded(&start, &end)?
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect();
+
+        events.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+        Ok(events)
+    }
+
+    /// 会場マスタを保存
+    ///
+    /// # Arguments
+    /// * `venue` - 保存する会場情報
+    ///
+    /// # Returns
+    /// 操作結果
+    pub fn put_venue(&mut self, venue: &VenueInfo) -> Result<()> {
+        let key = venue_key(venue.venue_id);
+        let value = self.encode_value(venue)?;
+        self.check_entry_size(&key, &value)?;
+        self.store_put(key, value)
+    }
+
+    /// 会場マスタを取得
+    ///
+    /// # Arguments
+    /// * `venue_id` - 会場ID
+    ///
+    /// # Returns
+    /// 会場情報（未登録の場合は None）
+    pub fn get_venue(&self, venue_id: u32) -> Result<Option<VenueInfo>> {
+        let key = venue_key(venue_id);
+        self.store_read(&key)
+    }
+
+    /// 登録済みの全会場を取得
+    ///
+    /// # Returns
+    /// 会場情報のベクター（venue_id順）
+    pub fn list_venues(&mut self) -> Result<Vec<VenueInfo>> {
+        let (start, end) = venue_scan_range();
+        let mut venues: Vec<VenueInfo> = self
+            .store_scan_decoded(&start, &end)?
+            .into_iter()
+            .map(|(_, venue)| venue)
+            .collect();
+
+        venues.sort_by_key(|v| v.venue_id);
+        Ok(venues)
+    }
+
+    /// 選手マスタを保存（既存の選手は上書き）
+    ///
+    /// # Arguments
+    /// * `racer` - 保存する選手情報
+    ///
+    /// # Returns
+    /// 操作結果。登録番号が 0 の場合は `StoreError::InvalidKey`
+    pub fn put_racer(&mut self, racer: &Racer) -> Result<()> {
+        let key = try_racer_key(racer.racer_id)?;
+        let value = self.encode_value(racer)?;
+        self.check_entry_size(&key, &value)?;
+        self.store_put(key, value)
+    }
+
+    /// 選手マスタを取得
+    ///
+    /// # Arguments
+    /// * `racer_id` - 選手の登録番号
+    ///
+    /// # Returns
+    /// 選手情報（未登録の場合は None）
+    pub fn get_racer(&self, racer_id: u32) -> Result<Option<Racer>> {
+        let key = try_racer_key(racer_id)?;
+        self.store_read(&key)
+    }
+
+    /// 登録済みの全選手を取得
+    ///
+    /// # Returns
+    /// 選手情報のベクター（登録番号順）
+    pub fn list_racers(&mut self) -> Result<Vec<Racer>> {
+        let (start, end) = racer_scan_range();
+        let mut racers: Vec<Racer> = self
+            .store_scan_decoded(&start, &end)?
+            .into_iter()
+            .map(|(_, racer)| racer)
+            .collect();
+
+        racers.sort_by_key(|r| r.racer_id);
+        Ok(racers)
+    }
+
+    /// 名前の部分一致で選手を検索
+    ///
+    /// # Arguments
+    /// * `substr` - 検索する文字列
+    ///
+    /// # Returns
+    /// 名前に `substr` を含む選手（登録番号順）
+    pub fn search_racers_by_name(&mut self, substr: &str) -> Result<Vec<Racer>> {
+        let mut racers = self.list_racers()?;
+        racers.retain(|r| r.name.contains(substr));
+        Ok(racers)
+    }
+
+    /// イベントの内容を検証
+    ///
+    /// 大会名・会場名が空でないか、開始日が実在する日付か、開催日数が 1..=30 か、venue_id が許容範囲内か、
+    /// `year_month` を指定した場合は開催期間がその月と重なるかを確認する
+    fn validate_event(&self, event: &RaceEvent, year_month: Option<u32>) -> Result<()> {
+        if !self.validate_events {
+            return Ok(());
+        }
+        event.validate(&self.venue_ids, year_month)
+    }
+
+    /// 厳格モード時にイベントの会場が会場マスタ (なければ全国の会場表) と一致するか検証
+    fn check_venue(&self, event: &RaceEvent) -> Result<()> {
+        if !self.strict_venues {
+            return Ok(());
+        }
+
+        // 会場マスタに登録がなければ全国の会場表の正式名称と照合する
+        let name = match self.get_venue(event.venue_id)? {
+            Some(venue) => venue.name,
+            None => crate::venues::venue_name(event.venue_id)
+                .ok_or_else(|| crate::StoreError::InvalidVenue {
+                    field: "venue_id".to_string(),
+                    value: event.venue_id.to_string(),
+                })?
+                .to_string(),
+        };
+        if name != event.venue_name {
+            return Err(crate::StoreError::InvalidVenue {
+                field: "venue_name".to_string(),
+                value: event.venue_name.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// 全大会IDを取得
+    ///
+    /// # Returns
+    /// 月別ビューまたは大会データを持つ大会IDの一覧（重複なし、昇順）
+    pub fn list_tournament_ids(&self) -> Result<Vec<String>> {
+        Ok(self.collect_tournaments()?.into_keys().collect())
+    }
+
+    /// 全大会の概要を取得
+    ///
+    /// # Returns
+    /// 大会概要の一覧（大会ID順）
+    pub fn list_tournaments(&self) -> Result<Vec<TournamentSummary>> {
+        self.collect_tournaments()?
+            .into_iter()
+            .map(|(tournament_id, keys)| self.summarize_tournament(tournament_id, keys))
+            .collect()
+    }
+
+    /// 大会メタデータを設定
+    ///
+    /// 大会に任意のキーと値の組 (スポンサー名・放送局など) を付ける。同じキーの値は上書きする。
+    /// 大会が登録済みかどうかは確認しない
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `key` - メタデータのキー (空文字列・セパレータ 0x00 を含むキーは `StoreError::InvalidKey`)
+    /// * `value` - メタデータの値
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, value)))]
+    pub fn set_tournament_meta(&mut self, tournament_id: &str, key: &str, value: &str) -> Result<()> {
+        if tournament_id.is_empty() {
+            return Err(crate::StoreError::InvalidKey);
+        }
+        let key = try_tournament_meta_key(tournament_id, key)?;
+        let value = self.encode_value(&value)?;
+        self.check_entry_size(&key, &value)?;
+        self.store_put(key, value)
+    }
+
+    /// 大会メタデータを取得
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `key` - メタデータのキー
+    ///
+    /// # Returns
+    /// メタデータの値（未設定の場合は None）
+    pub fn get_tournament_meta(&self, tournament_id: &str, key: &str) -> Result<Option<String>> {
+        let key = try_tournament_meta_key(tournament_id, key)?;
+        self.store_read(&key)
+    }
+
+    /// 大会メタデータを一覧
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// (キー, 値) のベクター（キー順）
+    pub fn list_tournament_meta(&mut self, tournament_id: &str) -> Result<Vec<(String, String)>> {
+        validate_tournament_id(tournament_id)?;
+        let mut entries = Vec::new();
+        for (key, value) in self.scan_tournament(tournament_meta_scan_range(tournament_id), tournament_id)? {
+            if let Ok(Key::TournamentMeta { meta_key, .. }) = key.parse::<Key>() {
+                entries.push((meta_key, self.decode_entry(&key, &value)?));
+            }
+        }
+        entries.sort();
+        Ok(entries)
+    }
+
+    /// 大会メタデータを削除
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `key` - メタデータのキー
+    ///
+    /// # Returns
+    /// 削除した場合は true、未設定の場合は false
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn delete_tournament_meta(&mut self, tournament_id: &str, key: &str) -> Result<bool> {
+        let key = try_tournament_meta_key(tournament_id, key)?;
+        if !self.store_contains(&key)? {
+            return Ok(false);
+        }
+        self.commit(Vec::new(), vec![key])?;
+        Ok(true)
+    }
+
+    /// ユーザー領域 (U) に値をそのまま書き込む
+    ///
+    /// ユーザー領域のキーはスキャン・統計・整合性チェック・修復・フックのいずれの対象にもならず、
+    /// エンジンが書き換えたり削除したりすることはない。ストアへ直接 "M" や "T" で始まるキーを
+    /// 書き込む代わりに使う。値はコーデック・圧縮を通さずに保存する
+    ///
+    /// # Arguments
+    /// * `name` - キー名 (ストアには先頭に "U" を付けたキーで保存する)
+    /// * `value` - 値
+    ///
+    /// # Returns
+    /// 操作結果（キー・値がサイズ上限を超える場合は `KeyTooLarge` / `ValueTooLarge`）
+    pub fn put_raw_user_key(&mut self, name: &str, value: &str) -> Result<()> {
+        let key = user_key(name);
+        self.check_entry_size(&key, value)?;
+        self.store_put(key, value.to_string())
+    }
+
+    /// ユーザー領域 (U) の値を取得
+    ///
+    /// # Arguments
+    /// * `name` - `put_raw_user_key` に渡したキー名
+    ///
+    /// # Returns
+    /// 値（存在しない場合は None）
+    pub fn get_raw_user_key(&self, name: &str) -> Result<Option<String>> {
+        self.store_get(&user_key(name))
+    }
+
+    /// ユーザー領域 (U) の値を削除
+    ///
+    /// # Arguments
+    /// * `name` - `put_raw_user_key` に渡したキー名
+    ///
+    /// # Returns
+    /// 削除した場合は true、存在しない場合は false
+    pub fn delete_raw_user_key(&mut self, name: &str) -> Result<bool> {
+        let key = user_key(name);
+        if !self.store_contains(&key)? {
+            return Ok(false);
+        }
+        self.store_apply_batch(Vec::new(), &[key])?;
+        Ok(true)
+    }
+
+    /// 大会の状態を設定
+    ///
+    /// `Cancelled` と `Finished` は大会メタデータ (キー "status") に保存し、日程より優先する。
+    /// `Scheduled` と `Ongoing` は日程から決まるため、保存済みの状態を取り消す
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `status` - 設定する状態
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn set_tournament_status(&mut self, tournament_id: &str, status: TournamentStatus) -> Result<()> {
+        if status.is_final() {
+            self.set_tournament_meta(tournament_id, STATUS_META_KEY, status.as_str())
+        } else {
+            self.delete_tournament_meta(tournament_id, STATUS_META_KEY).map(|_| ())
+        }
+    }
+
+    /// 大会の状態を取得
+    ///
+    /// 保存済みの状態 (`Cancelled` / `Finished`) があればそれを、なければ開催期間と `today` から求めた状態を返す
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    /// * `today` - 基準日
+    ///
+    /// # Returns
+    /// 大会の状態（状態が保存されておらず月別エントリもない場合は NotFound エラー）
+    pub fn get_tournament_status(&mut self, tournament_id: &str, today: NaiveDate) -> Result<TournamentStatus> {
+        if let Some(status) = self.status_override(tournament_id)? {
+            return Ok(status);
+        }
+        match self.find_race_event(tournament_id)? {
+            Some((_, event)) => event.status_on(today),
+            None => Err(crate::StoreError::not_found(tournament_id)),
+        }
+    }
+
+    /// 指定月に登録されている大会のうち、指定した状態のものを取得
+    ///
+    /// # Arguments
+    /// * `status` - 絞り込む状態
+    /// * `year_month` - 対象の年月 (例: 202509)
+    /// * `today` - 日程から状態を求める基準日
+    ///
+    /// # Returns
+    /// (大会ID, イベント) のベクター（開始日順、同日は大会ID順）
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn list_tournaments_by_status(
+        &mut self,
+        status: TournamentStatus,
+        year_month: u32,
+        today: NaiveDate,
+    ) -> Result<Vec<(String, RaceEvent)>> {
+        validate_year_month(year_month)?;
+        let (start, end) = monthly_scan_range(year_month);
+        let mut tournaments = Vec::new();
+        for (key, event) in self.store_scan_decoded::<RaceEvent>(&start, &end)? {
+            let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() else {
+                continue;
+            };
+            let current = match self.status_override(&tournament_id)? {
+                Some(stored) => stored,
+                None => event.status_on(today)?,
+            };
+            if current == status {
+                tournaments.push((tournament_id, event));
+            }
+        }
+        tournaments.sort_by(|(a_id, a), (b_id, b)| a.start_date.cmp(&b.start_date).then_with(|| a_id.cmp(b_id)));
+        Ok(tournaments)
+    }
+
+    /// 大会の最終更新時刻を取得
+    ///
+    /// エンジン経由で月別エントリまたはレースデータを書き込み・削除した時刻を返す。
+    /// ストアに直接書き込んだ変更は記録されない。`delete_tournament` で削除した大会も削除時刻が残る
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// 最終更新時刻（エポックミリ秒、記録がない場合は None）
+    pub fn last_modified(&self, tournament_id: &str) -> Result<Option<u64>> {
+        validate_tournament_id(tournament_id)?;
+        let key = last_modified_key(tournament_id);
+        self.store_read(&key)
+    }
+
+    /// 指定時刻以降に更新された大会を取得
+    ///
+    /// 差分同期向け。`last_modified` と同じく、ストアに直接書き込んだ変更は対象外
+    ///
+    /// # Arguments
+    /// * `since_epoch_ms` - この時刻 (エポックミリ秒、この時刻を含む) 以降の更新が対象
+    ///
+    /// # Returns
+    /// 大会IDのベクター（最終更新時刻順、同時刻は大会ID順）
+    pub fn modified_since(&self, since_epoch_ms: u64) -> Result<Vec<String>> {
+        let mut modified = Vec::new();
+        for key in self.store_keys()? {
+            let Some(tournament_id) = parse_last_modified_key(&key) else {
+                continue;
+            };
+            // keys() と get() の間に削除されたキーは数えない
+            if let Some(value) = self.store_get(&key)? {
+                let modified_at: u64 = self.decode_entry(&key, &value)?;
+                if modified_at >= since_epoch_ms {
+                    modified.push((modified_at, tournament_id.to_string()));
+                }
+            }
+        }
+        modified.sort();
+        Ok(modified.into_iter().map(|(_, tournament_id)| tournament_id).collect())
+    }
+
+    /// 保存済みの大会の状態 (`set_tournament_status` で設定したもの)
+    fn status_override(&self, tournament_id: &str) -> Result<Option<TournamentStatus>> {
+        match self.get_tournament_meta(tournament_id, STATUS_META_KEY)? {
+            Some(value) => Ok(Some(value.parse()?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 大会の概要を取得
+    ///
+    /// 月別ビューのイベント情報・登録月と、大会データ (T) のレース数・期間・容量をまとめて返す。
+    /// 月別エントリのみでレースがない大会はレース数 0、タイムスタンプ None となる
+    ///
+    /// # Arguments
+    /// * `tournament_id` - 大会ID
+    ///
+    /// # Returns
+    /// 大会の概要（月別エントリもレースデータもない場合は NotFound エラー）
+    pub fn get_tournament_summary(&self, tournament_id: &str) -> Result<TournamentSummary> {
+        validate_tournament_id(tournament_id)?;
+        let mut keys = TournamentKeys::default();
+        for key in self.store_keys()? {
+            match key.parse::<Key>() {
+                Ok(Key::Monthly { year_month, tournament_id: id }) if id == tournament_id => {
+                    keys.months.insert(year_month);
+                }
+                Ok(Key::Tournament { tournament_id: id, timestamp }) if id == tournament_id => {
+                    keys.races.push((timestamp, key));
+                }
+                _ => {}
+            }
+        }
+        if keys.months.is_empty() && keys.races.is_empty() {
+            return Err(crate::StoreError::not_found(tournament_id));
+        }
+        self.summarize_tournament(tournament_id.to_string(), keys)
+    }
+
+    /// 集めたキーから大会の概要を組み立てる
+    fn summarize_tournament(&self, tournament_id: String, keys: TournamentKeys) -> Result<TournamentSummary> {
+        let event = match keys.months.iter().next() {
+            Some(&year_month) => self.get_race_event(year_month, &tournament_id)?,
+            None => None,
+        };
+        let mut race_bytes = 0;
+        for (_, key) in &keys.races {
+            // keys() と get() の間に削除されたキーは数えない
+            if let Some(value) = self.store_get(key)? {
+                race_bytes += key.len() + value.len();
+            }
+        }
+        Ok(TournamentSummary {
+            event,
+            months: keys.months.into_iter().collect(),
+            race_count: keys.races.len(),
+            first_race: keys.races.iter().map(|(timestamp, _)| *timestamp).min(),
+            last_race: keys.races.iter().map(|(timestamp, _)| *timestamp).max(),
+            race_bytes,
+            tournament_id,
+        })
+    }
+
+    /// 大会IDごとに登録月とレースデータのキーを集計
+    fn collect_tournaments(&self) -> Result<BTreeMap<String, TournamentKeys>> {
+        let mut tournaments: BTreeMap<String, TournamentKeys> = BTreeMap::new();
+        for key in self.store_keys()? {
+            match key.parse::<Key>() {
+                Ok(Key::Monthly { year_month, tournament_id }) => {
+                    tournaments.entry(tournament_id).or_default().months.insert(year_month);
+                }
+                Ok(Key::Tournament { tournament_id, timestamp }) => {
+                    tournaments.entry(tournament_id).or_default().races.push((timestamp, key));
+                }
+                _ => {}
+            }
+        }
+        Ok(tournaments)
+    }
+
+    /// データ統計を取得
+    /// 
+    /// # Returns
+    /// (月数, 大会数, レース数) のタプル。選手数などは `get_detailed_statistics` を使う
+    pub fn get_statistics(&mut self) -> Result<(usize, usize, usize)> {
+        let stats = self.get_detailed_statistics()?;
+        Ok((stats.monthly_entries, stats.tournaments, stats.races))
+    }
+
+    /// 統計カウンタを取得
+    ///
+    /// エンジンが管理するカウンタを返すため、通常はキーの走査を行わない。
+    /// カウンタが存在しない場合（初回利用時や旧バージョンで作成したストア）は
+    /// `rebuild_statistics` で再集計してから返す。
+    ///
+    /// # Returns
+    /// 統計カウンタ
+    pub fn get_statistics_fast(&mut self) -> Result<StatisticsCounters> {
+        match self.counters {
+            Some(counters) => Ok(counters),
+            None => self.rebuild_statistics(),
+        }
+    }
+
+    /// 全キーを走査して統計カウンタを再集計し、メタデータキーに保存
+    ///
+    /// ストアへ直接書き込んだ場合など、カウンタが実データと食い違っている恐れがあるときに使う
+    ///
+    /// # Returns
+    /// 再集計後の統計カウンタ
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn rebuild_statistics(&mut self) -> Result<StatisticsCounters> {
+        let mut counters = StatisticsCounters::default();
+        let mut tournament_months: HashMap<String, usize> = HashMap::new();
+        for key in self.store_keys()? {
+            // 予約済みのプレフィックスで始まるだけのキーは数えない
+            match key.parse::<Key>() {
+                Ok(Key::Monthly { tournament_id, .. }) => {
+                    counters.monthly_entries += 1;
+                    *tournament_months.entry(tournament_id).or_default() += 1;
+                }
+                Ok(Key::Tournament { .. }) => counters.races += 1,
+                _ => {}
+            }
+        }
+        counters.tournaments = tournament_months.len();
+
+        self.store_put(STATISTICS_META_KEY.to_string(), self.encode_value(&counters)?)?;
+        self.counters = Some(counters);
+        self.tournament_months = Some(tournament_months);
+        Ok(counters)
+    }
+
+    /// 詳細なデータ統計を取得
+    ///
+    /// 月別ビューのイベントは大会ごとに一度だけデシリアライズし、グレード別・会場別に集計する。
+    /// デシリアライズに失敗したエントリは `corrupted_entries` に計上して処理を続ける。
+    ///
+    /// # Returns
+    /// データベース統計
+    pub fn get_detailed_statistics(&self) -> Result<DatabaseStatistics> {
+        let mut stats = DatabaseStatistics::default();
+        let mut months = BTreeSet::new();
+        let mut tournaments = BTreeSet::new();
+
+        for key in self.store_keys()? {
+            let Some(value) = self.store_get(&key)? else {
+                continue;
+            };
+            stats.total_bytes += (key.len() + value.len()) as u64;
+
+            // 日別ビュー (D) は月別ビューの派生なので集計対象外
+            let Some(namespace) = classify_key(&key) else {
+                stats.unclassified_keys += 1;
+                continue;
+            };
+            match namespace {
+                Namespace::Monthly => {
+                    stats.monthly_entries += 1;
+                    let Ok(Key::Monthly { year_month, tournament_id }) = key.parse::<Key>() else {
+                        continue;
+                    };
+                    months.insert(year_month);
+                    if !tournaments.insert(tournament_id) {
+                        continue;
+                    }
+                    match deserialize_from_string::<RaceEvent>(&value) {
+                        Ok(event) => {
+                            *stats.events_per_grade.entry(event.grade).or_default() += 1;
+                            *stats.events_per_venue.entry(event.venue_id).or_default() += 1;
+                        }
+                        Err(_) => stats.corrupted_entries += 1,
+                    }
+                }
+                Namespace::Tournament => stats.races += 1,
+                Namespace::Racer => stats.racers += 1,
+                _ => {}
+            }
+        }
+
+        stats.tournaments = tournaments.len();
+        stats.months = months.into_iter().collect();
+        Ok(stats)
+    }
+
+    /// 月別ビューと大会データの整合性をチェック
+    ///
+    /// 読み取りのみで、何も削除しない
+    ///
+    /// # Returns
+    /// 孤立した大会・壊れたエントリ・不正なキーの一覧
+    pub fn check_integrity(&self) -> Result<IntegrityReport> {
+        let mut report = IntegrityReport::default();
+        let mut registered = BTreeSet::new();
+        let mut races: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for key in self.store_keys()? {
+            let Some(namespace) = classify_key(&key) else {
+                // 予約済みの名前空間で形式を満たさないキーは不正、それ以外は関知しないキー
+                match key.as_bytes().first().copied().and_then(Namespace::from_prefix) {
+                    Some(_) => report.malformed_keys.push(key),
+                    None => report.unclassified_keys.push(key),
+                }
+                continue;
+            };
+            match namespace {
+                Namespace::Monthly => {
+                    let Ok(Key::Monthly { tournament_id, .. }) = key.parse::<Key>() else {
+                        continue;
+                    };
+                    let valid = match self.store_get(&key)? {
+                        Some(value) => deserialize_from_string::<RaceEvent>(&value).is_ok(),
+                        None => continue,
+                    };
+                    registered.insert(tournament_id);
+                    if !valid {
+                        report.corrupt_entries.push(key);
+                    }
+                }
+                Namespace::Tournament => {
+                    if let Ok(Key::Tournament { tournament_id, .. }) = key.parse::<Key>() {
+                        races.entry(tournament_id).or_default().push(key);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (tournament_id, keys) in races {
+            if !registered.contains(&tournament_id) {
+                report.orphaned_tournaments.push(tournament_id);
+                report.orphaned_races.extend(keys);
+            }
+        }
+        Ok(report)
+    }
+
+    /// 整合性チェックで見つかった問題をオプションに従って削除
+    ///
+    /// # Arguments
+    /// * `options` - 削除する対象
+    ///
+    /// # Returns
+    /// 削除前のチェック結果
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn repair(&mut self, options: RepairOptions) -> Result<IntegrityReport> {
+        let report = self.check_integrity()?;
+        let mut deletes = Vec::new();
+        if options.delete_orphans {
+            deletes.extend(report.orphaned_races.iter().cloned());
+        }
+        if options.delete_corrupt {
+            deletes.extend(report.corrupt_entries.iter().cloned());
+        }
+        if options.delete_malformed {
+            deletes.extend(report.malformed_keys.iter().cloned());
+        }
+        if !deletes.is_empty() {
+            self.commit(Vec::new(), deletes)?;
+        }
+        Ok(report)
+    }
+
+    /// エンジンが管理するデータだけを削除
+    ///
+    /// 予約済みの名前空間 (`Namespace::is_reserved`) のキー形式を満たすキーを1回のバッチで削除する。
+    /// ユーザー領域 (U) のキーと、どの名前空間にも分類できないキーは残す。
+    /// フックは呼ばず、統計カウンタはストアから消えるため未作成の状態に戻る
+    ///
+    /// # Returns
+    /// 名前空間ごとの削除件数
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn clear_managed_data(&mut self) -> Result<ClearReport> {
+        self.clear_namespaces(Namespace::is_reserved)
+    }
+
+    /// 1つの名前空間のキーだけを削除
+    ///
+    /// 再構築の前にインデックス (`Namespace::Index`) をまとめて消す場合などに使う。
+    /// 他の名前空間との整合性は保たないため、月別ビューだけを消すと日別ビューやインデックスは残る。
+    /// フックは呼ばない。統計カウンタが有効なまま月別ビューか大会データを消した場合は再集計する
+    ///
+    /// # Arguments
+    /// * `namespace` - 削除する名前空間
+    ///
+    /// # Returns
+    /// 名前空間ごとの削除件数。ユーザー領域を指定した場合は `ValidationError`
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn clear_namespace(&mut self, namespace: Namespace) -> Result<ClearReport> {
+        if !namespace.is_reserved() {
+            return Err(StoreError::ValidationError {
+                field: "namespace".to_string(),
+                message: "the user namespace is never modified by the engine".to_string(),
+            });
+        }
+        self.clear_namespaces(|candidate| candidate == namespace)
+    }
+
+    /// 条件に合う名前空間のキーを1回のバッチで削除し、統計カウンタを合わせる
+    fn clear_namespaces(&mut self, matches: impl Fn(Namespace) -> bool) -> Result<ClearReport> {
+        let mut report = ClearReport::default();
+        let mut deletes = Vec::new();
+        for key in self.store_keys()? {
+            if let Some(namespace) = classify_key(&key).filter(|namespace| matches(*namespace)) {
+                *report.deleted.entry(namespace).or_default() += 1;
+                deletes.push(key);
+            }
+        }
+        if deletes.is_empty() {
+            return Ok(report);
+        }
+
+        trace_batch(&[], &deletes);
+        self.store_apply_batch(Vec::new(), &deletes)?;
+        if report.deleted.contains_key(&Namespace::Meta) {
+            self.counters = None;
+            self.tournament_months = None;
+        } else if self.counters.is_some()
+            && (report.deleted.contains_key(&Namespace::Monthly) || report.deleted.contains_key(&Namespace::Tournament))
+        {
+            self.rebuild_statistics()?;
+        }
+        Ok(report)
+    }
+}
+
+/// 型を固定した大会のレースデータ操作 (`BoatRaceEngine::tournament` で取得)
+///
+/// 型を `with_type_tag` で登録している場合は、書き込む値に型名を記録し、読み込み時に型名を確かめる
+pub struct TournamentHandle<'a, K: KeyValueStore, T> {
+    engine: &'a mut BoatRaceEngine<K>,
+    tournament_id: String,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<K: KeyValueStore, T: Serialize + DeserializeOwned + 'static> TournamentHandle<'_, K, T> {
+    /// 大会ID
+    pub fn tournament_id(&self) -> &str {
+        &self.tournament_id
+    }
+
+    /// レースデータを保存 (`put_race_data` と同じく同じタイムスタンプのレースは上書き)
+    ///
+    /// # Arguments
+    /// * `timestamp` - レースのタイムスタンプ
+    /// * `data` - レースデータ
+    pub fn put(&mut self, timestamp: u64, data: &T) -> Result<()> {
+        let (key, value) = self.engine.encode_race(&self.tournament_id, timestamp, data)?;
+        let value = match self.engine.type_tag::<T>() {
+            Some(type_name) => tag_value(type_name, &value)?,
+            None => value,
+        };
+        self.engine.write_race(key, value, RaceWrite::Upsert)
+    }
+
+    /// レースデータを取得
+    ///
+    /// # Arguments
+    /// * `timestamp` - レースのタイムスタンプ
+    ///
+    /// # Returns
+    /// レースデータ。存在しない場合は `StoreError::NotFound`、別の型名が記録されている場合は `StoreError::SerializationError`
+    pub fn get(&self, timestamp: u64) -> Result<T> {
+        let key = try_tournament_key(&self.tournament_id, timestamp)?;
+        self.engine.store_read_typed(&key)?
+            .ok_or_else(|| StoreError::not_found(key))
+    }
+
+    /// 大会の全レースデータを取得
+    ///
+    /// # Returns
+    /// (タイムスタンプ, レースデータ) のベクター（タイムスタンプ順）
+    pub fn all(&mut self) -> Result<Vec<(u64, T)>> {
+        let timestamps = self.timestamps()?;
+        self.read_all(timestamps)
+    }
+
+    /// 最新のレースデータを最大 `n` 件取得
+    ///
+    /// # Returns
+    /// (タイムスタンプ, レースデータ) のベクター（新しい順）
+    pub fn latest(&mut self, n: usize) -> Result<Vec<(u64, T)>> {
+        let timestamps = self.timestamps()?;
+        self.read_all(timestamps.into_iter().rev().take(n).collect())
+    }
+
+    /// 大会のレースのタイムスタンプ（昇順）
+    fn timestamps(&mut self) -> Result<Vec<u64>> {
+        validate_tournament_id(&self.tournament_id)?;
+        let mut timestamps: Vec<u64> = self
+            .engine
+            .scan_tournament_keys(tournament_scan_range(&self.tournament_id), &self.tournament_id)?
+            .iter()
+            .filter_map(|key| match key.parse::<Key>() {
+                Ok(Key::Tournament { timestamp, .. }) => Some(timestamp),
+                _ => None,
+            })
+            .collect();
+        timestamps.sort_unstable();
+        Ok(timestamps)
+    }
+
+    /// 指定したタイムスタンプのレースデータを順に読む (読む間に削除されたレースは飛ばす)
+    fn read_all(&self, timestamps: Vec<u64>) -> Result<Vec<(u64, T)>> {
+        let mut races = Vec::with_capacity(timestamps.len());
+        for timestamp in timestamps {
+            let key = try_tournament_key(&self.tournament_id, timestamp)?;
+            if let Some(race) = self.engine.store_read_typed(&key)? {
+                races.push((timestamp, race));
+            }
+        }
+        Ok(races)
+    }
+}
+
+/// バッチで書き込む・削除するキーを debug イベントとして出力 (`tracing` フィーチャー)
+#[cfg(feature = "tracing")]
+fn trace_batch(puts: &[(String, String)], deletes: &[String]) {
+    for (key, value) in puts {
+        tracing::debug!(key = ?key, bytes = value.len(), "put");
+    }
+    for key in deletes {
+        tracing::debug!(key = ?key, "delete");
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+fn trace_batch(_puts: &[(String, String)], _deletes: &[String]) {}
+
+/// 年月文字列をu32に変換 (例: "2025-09" -> 202509)
+fn parse_year_month(year_month: &str) -> Result<u32> {
+    let invalid = || crate::StoreError::invalid_value(format!("year_month {:?} is not YYYY-MM", year_month));
+    let parts: Vec<&str> = year_month.split('-').collect();
+    if parts.len() != 2 {
+        return Err(invalid());
+    }
+    
+    let year: u32 = parts[0].parse()
+        .map_err(|_| invalid())?;
+    let month: u32 = parts[1].parse()
+        .map_err(|_| invalid())?;
+    
+    if !(1..=12).contains(&month) {
+        return Err(invalid());
+    }
+    
+    Ok(year * 100 + month)
+}
+
+/// キーの形式を検証し、不正なキーはメトリクスに記録する (`BoatRaceEngine::is_well_formed`)
+///
+/// ストアを借用したまま呼べるよう、エンジンではなくメトリクスだけを受け取る
+fn well_formed(metrics: &OperationCounters, key: &str) -> bool {
+    if classify_key(key).is_some() {
+        return true;
+    }
+    metrics.record_malformed_key();
+    #[cfg(feature = "tracing")]
+    tracing::warn!(key = ?key, "skipping malformed key");
+    false
+}
+
+/// ストアから読んだ値をデシリアライズし、失敗した場合はエラーにキーを付ける (`BoatRaceEngine::decode_entry`)
+fn decode_with<T: DeserializeOwned>(metrics: &OperationCounters, key: &str, value: &str) -> Result<T> {
+    deserialize_from_string(value).map_err(|error| {
+        metrics.record_serialization_failure();
+        error.with_key(key)
+    })
+}
+
+/// イベントの開催日ごとの日別ビューキーを生成
+fn daily_keys(event: &RaceEvent, tournament_id: &str) -> Result<Vec<String>> {
+    let start_date = event.start()?;
+    (0..event.duration_days)
+        .map(|offset| {
+            let date = start_date + chrono::Duration::days(offset as i64);
+            try_daily_key(date_to_u32(date), tournament_id)
+        })
+        .collect()
+}
+
+/// イベントの大会IDを生成
+///
+/// 月別ビュー・日別ビュー・インデックスのキーはすべてこの関数で得た大会IDを使う
+fn event_tournament_id(event: &RaceEvent) -> String {
+    generate_tournament_id(&event.venue_name, &event.event_name)
+}
+
+/// 名前インデックス用に文字バイグラムを切り出す
+///
+/// ASCII文字は小文字に揃える。セパレータを含むバイグラムはキーに埋め込めないため除く
+fn name_tokens(name: &str) -> BTreeSet<String> {
+    let chars: Vec<char> = name.to_ascii_lowercase().chars().collect();
+    chars
+        .windows(2)
+        .filter(|pair| !pair.contains(&'\0'))
+        .map(|pair| pair.iter().collect())
+        .collect()
+}
+
+/// `dump` 用にキーを論理的な要素に分解した文字列を作る
+fn describe_key(raw: &str, key: Option<&Key>) -> String {
+    let time = |timestamp: u64| match millis_to_datetime(timestamp) {
+        Some(when) => when.and_utc().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        None => timestamp.to_string(),
+    };
+    match key {
+        Some(Key::Monthly { year_month, tournament_id }) => {
+            format!("monthly year_month={} tournament_id={}", format_year_month(*year_month), tournament_id)
+        }
+        Some(Key::Tournament { tournament_id, timestamp }) => {
+            format!("race tournament_id={} time={}", tournament_id, time(*timestamp))
+        }
+        Some(Key::Daily { date, tournament_id }) => format!("daily date={} tournament_id={}", date, tournament_id),
+        Some(Key::Venue { venue_id }) => format!("venue venue_id={}", venue_id),
+        Some(Key::Racer { racer_id }) => format!("racer racer_id={}", racer_id),
+        Some(Key::Result { tournament_id, timestamp }) => {
+            format!("result tournament_id={} time={}", tournament_id, time(*timestamp))
+        }
+        Some(Key::Conditions { venue_id, date }) => format!("conditions venue_id={} date={}", venue_id, date),
+        Some(Key::Payout { tournament_id, timestamp }) => {
+            format!("payout tournament_id={} time={}", tournament_id, time(*timestamp))
+        }
+        Some(Key::Odds { tournament_id, race_timestamp, snapshot_time }) => format!(
+            "odds tournament_id={} race_time={} snapshot_time={}",
+            tournament_id,
+            time(*race_timestamp),
+            time(*snapshot_time)
+        ),
+        Some(Key::VenueIndex { venue_id, year_month, tournament_id }) => format!(
+            "index.venue venue_id={} year_month={} tournament_id={}",
+            venue_id,
+            format_year_month(*year_month),
+            tournament_id
+        ),
+        Some(Key::NameIndex { token, year_month, tournament_id }) => format!(
+            "index.name token={:?} year_month={} tournament_id={}",
+            token,
+            format_year_month(*year_month),
+            tournament_id
+        ),
+        Some(Key::RacerIndex { racer_id, tournament_id, timestamp }) => format!(
+            "index.racer racer_id={} tournament_id={} time={}",
+            racer_id,
+            tournament_id,
+            time(*timestamp)
+        ),
+        Some(Key::Participants { tournament_id, timestamp }) => {
+            format!("index.participants tournament_id={} time={}", tournament_id, time(*timestamp))
+        }
+        Some(Key::TournamentMeta { tournament_id, meta_key }) => {
+            format!("tournament_meta tournament_id={} key={:?}", tournament_id, meta_key)
+        }
+        None if classify_key(raw) == Some(Namespace::Meta) => format!("meta key={:?}", raw),
+        None if classify_key(raw) == Some(Namespace::User) => format!("user key={:?}", raw),
+        None => format!("unknown key={:?}", raw),
+    }
+}
+
+/// CSV の1行分のフィールド (`CSV_COLUMNS` の順)
+fn csv_fields(event: &RaceEvent) -> Vec<String> {
+    // 最終開催日 (開始日が不正な場合は空欄)
+    let end_date = event.end().map(|end| end.to_string()).unwrap_or_default();
+    vec![
+        event.venue_id.to_string(),
+        event.venue_name.clone(),
+        event.event_name.clone(),
+        event.grade.to_string(),
+        event.start_date.clone(),
+        event.duration_days.to_string(),
+        end_date,
+    ]
+}
+
+/// 指定月に含まれるイベント開催日の日別ビューキーを生成
+fn month_daily_keys(event: &RaceEvent, tournament_id: &str, year_month: u32) -> Result<Vec<String>> {
+    // 開始日が不正なイベントは日別ビューを持たない
+    let Ok(keys) = daily_keys(event, tournament_id) else {
+        return Ok(Vec::new());
+    };
+    Ok(keys
+        .into_iter()
+        .filter(|key| matches!(key.parse::<Key>(), Ok(Key::Daily { date, .. }) if date / 100 == year_month))
+        .collect())
+}
+
+/// 大会IDで保存される大会データ (レースデータ・結果・払戻金・オッズ・メタデータ) のスキャン範囲
+///
+/// 範囲には大会IDが前方一致する別の大会のキーも含まれるため、`tournament_key_matches` で絞り込むこと
+fn tournament_data_ranges(tournament_id: &str) -> [(String, String); 5] {
+    [
+        tournament_scan_range(tournament_id),
+        result_scan_range(tournament_id),
+        payout_scan_range(tournament_id),
+        odds_tournament_scan_range(tournament_id),
+        tournament_meta_scan_range(tournament_id),
+    ]
+}
+
+/// 日付文字列を解析 (例: "2025-09-13")
+fn parse_date(date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| crate::StoreError::from(e).with_input(date))
+}
+
+/// UTC の日時をエポックミリ秒に変換
+fn datetime_to_millis(when: NaiveDateTime) -> Result<u64> {
+    u64::try_from(when.and_utc().timestamp_millis())
+        .map_err(|_| crate::StoreError::invalid_date(when.to_string(), "before the Unix epoch"))
+}
+
+/// エポックミリ秒を UTC の日時に変換
+fn millis_to_datetime(timestamp: u64) -> Option<NaiveDateTime> {
+    let millis = i64::try_from(timestamp).ok()?;
+    DateTime::from_timestamp_millis(millis).map(|when| when.naive_utc())
+}
+
+/// 1日あたりの最大レース番号
+const MAX_RACE_NUMBER: u32 = 12;
+
+/// レース番号1つ分のタイムスタンプ間隔 (1分)
+const NUMBERED_RACE_STEP_MS: u64 = 60_000;
+
+/// JST の UTC からのオフセット (秒)
+const JST_OFFSET_SECS: i64 = 9 * 3600;
+
+/// 開催日とレース番号からタイムスタンプを作る (JST 0:00 + レース番号分)
+fn numbered_race_timestamp(date: &str, race_number: u32) -> Result<u64> {
+    if !(1..=MAX_RACE_NUMBER).contains(&race_number) {
+        return Err(crate::StoreError::ValidationError {
+            field: "race_number".to_string(),
+            message: format!("{} is outside 1..={}", race_number, MAX_RACE_NUMBER),
+        });
+    }
+    let jst_midnight = parse_date(date)?.and_time(chrono::NaiveTime::MIN) - chrono::Duration::seconds(JST_OFFSET_SECS);
+    Ok(datetime_to_millis(jst_midnight)? + race_number as u64 * NUMBERED_RACE_STEP_MS)
+}
+
+/// 日付をu32に変換 (例: 2025-09-13 -> 20250913)
+fn date_to_u32(date: NaiveDate) -> u32 {
+    date.year() as u32 * 10000 + date.month() * 100 + date.day()
+}
+
+/// u32を年月文字列に変換 (例: 202509 -> "2025-09")
+fn format_year_month(year_month: u32) -> String {
+    let year = year_month / 100;
+    let month = year_month % 100;
+    format!("{:04}-{:02}", year, month)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryStore;
+    use crate::value::serialize_to_string;
+
+    // テストデータをinclude!で読み込み
+    include!("../testdata/sample.rs");
+
+    #[test]
+    fn test_parse_year_month() {
+        assert_eq!(parse_year_month("2025-09").unwrap(), 202509);
+        assert_eq!(parse_year_month("2024-12").unwrap(), 202412);
+        assert!(parse_year_month("invalid").is_err());
+        assert!(parse_year_month("2025-13").is_err());
+    }
+
+    #[test]
+    fn test_format_year_month() {
+        assert_eq!(format_year_month(202509), "2025-09");
+        assert_eq!(format_year_month(202412), "2024-12");
+    }
+
+    #[test]
+    fn test_put_get_monthly_schedule() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let schedule = MonthlySchedule {
+            year_month: "2025-09".to_string(),
+            events: vec![
+                RaceEvent {
+                    venue_id: 4,
+                    venue_name: "平和島".to_string(),
+                    event_name: "トーキョー・ベイ・カップ".to_string(),
+                    grade: Grade::G1,
+                    start_date: "2025-09-10".to_string(),
+                    duration_days: 7,
+                    notes: None,
+                },
+            ],
+        };
+
+        // スケジュールを保存
+        engine.put_monthly_schedule(&schedule).unwrap();
+
+        // スケジュールを取得
+        let retrieved = engine.get_monthly_schedule(202509).unwrap();
+        assert_eq!(retrieved.year_month, "2025-09");
+        assert_eq!(retrieved.events.len(), 1);
+        assert_eq!(retrieved.events[0].venue_name, "平和島");
+    }
+
+    #[test]
+    fn test_race_data_operations() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        #[derive(Debug, Clone, PartialEq, Serialize, serde::Deserialize)]
+        struct RaceData {
+            race_number: u32,
+            participants: Vec<String>,
+        }
+
+        let race_data = RaceData {
+            race_number: 1,
+            participants: vec!["選手A".to_string(), "選手B".to_string()],
+        };
+
+        let tournament_id = "tokyo_bay_cup";
+        let timestamp = 1694524800000;
+
+        // レースデータを保存
+        engine.put_race_data(tournament_id, timestamp, &race_data).unwrap();
+
+        // レースデータを取得
+        let retrieved: RaceData = engine.get_race_data(tournament_id, timestamp).unwrap();
+        assert_eq!(retrieved.race_number, 1);
+        assert_eq!(retrieved.participants.len(), 2);
+
+        // 大会の全レースを取得
+        let all_races: Vec<RaceData> = engine.get_tournament_races(tournament_id).unwrap();
+        assert_eq!(all_races.len(), 1);
+        assert_eq!(all_races[0], race_data);
+    }
+
+    #[test]
+    fn test_register_tournament_to_months() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10, // 2026-01-06まで
+            notes: None,
+        };
+
+        // 月跨ぎ大会を登録
+        engine.register_tournament_to_months(&tournament).unwrap();
+
+        // 12月のスケジュールを確認
+        let dec_schedule = engine.get_monthly_schedule(202512).unwrap();
+        assert_eq!(dec_schedule.events.len(), 1);
+        assert_eq!(dec_schedule.events[0].event_name, "年末年始杯");
+
+        // 1月のスケジュールを確認
+        let jan_schedule = engine.get_monthly_schedule(202601).unwrap();
+        assert_eq!(jan_schedule.events.len(), 1);
+        assert_eq!(jan_schedule.events[0].event_name, "年末年始杯");
+    }
+
+    #[test]
+    fn test_get_schedules_between() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&tournament).unwrap();
+
+        let autumn = MonthlySchedule {
+            year_month: "2025-11".to_string(),
+            events: vec![RaceEvent {
+                venue_id: 1,
+                venue_name: "桐生".to_string(),
+                event_name: "Autumn Cup".to_string(),
+                grade: Grade::Ippan,
+                start_date: "2025-11-03".to_string(),
+                duration_days: 5,
+                notes: None,
+            }],
+        };
+        engine.put_monthly_schedule(&autumn).unwrap();
+
+        let schedules = engine.get_schedules_between(202511, 202602).unwrap();
+        let months: Vec<&str> = schedules.iter().map(|s| s.year_month.as_str()).collect();
+        assert_eq!(months, vec!["2025-11", "2025-12", "2026-01", "2026-02"]);
+
+        assert_eq!(schedules[0].events.len(), 1);
+        assert_eq!(schedules[0].events[0].event_name, "Autumn Cup");
+        // 月跨ぎ大会は各月に現れる
+        assert_eq!(schedules[1].events[0].event_name, "年末年始杯");
+        assert_eq!(schedules[2].events[0].event_name, "年末年始杯");
+        assert!(schedules[3].events.is_empty());
+
+        assert!(engine.get_schedules_between(202602, 202511).is_err());
+    }
+
+    #[test]
+    fn test_get_yearly_schedule() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&tournament).unwrap();
+
+        let schedule = MonthlySchedule {
+            year_month: "2025-09".to_string(),
+            events: vec![
+                RaceEvent {
+                    venue_id: 1,
+                    venue_name: "桐生".to_string(),
+                    event_name: "Kiryu Cup".to_string(),
+                    grade: Grade::Ippan,
+                    start_date: "2025-09-11".to_string(),
+                    duration_days: 6,
+                    notes: None,
+                },
+                RaceEvent {
+                    venue_id: 12,
+                    venue_name: "住之江".to_string(),
+                    event_name: "Takamatsunomiya Kinen".to_string(),
+                    grade: Grade::G1,
+                    start_date: "2025-09-13".to_string(),
+                    duration_days: 6,
+                    notes: None,
+                },
+            ],
+        };
+        engine.put_monthly_schedule(&schedule).unwrap();
+
+        let schedules = engine.get_yearly_schedule(2025).unwrap();
+        assert_eq!(schedules.len(), 12);
+        assert_eq!(schedules[0].year_month, "2025-01");
+        assert_eq!(schedules[11].year_month, "2025-12");
+        assert_eq!(schedules[8].events.len(), 2);
+        assert_eq!(schedules[11].events.len(), 1);
+        assert!(schedules[0].events.is_empty());
+
+        // 年跨ぎ大会は翌年の1月にのみ現れる
+        let next_year = engine.get_yearly_schedule(2026).unwrap();
+        assert_eq!(next_year.len(), 12);
+        assert_eq!(next_year[0].events.len(), 1);
+        assert!(next_year[1..].iter().all(|s| s.events.is_empty()));
+
+        let g1 = engine.get_yearly_schedule_by_grade(2025, Grade::G1).unwrap();
+        assert_eq!(g1.len(), 12);
+        assert_eq!(g1[8].events.len(), 1);
+        assert_eq!(g1[8].events[0].venue_name, "住之江");
+        assert_eq!(g1[11].events.len(), 1);
+    }
+
+    #[test]
+    fn test_get_events_on_date() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let schedule = MonthlySchedule {
+            year_month: "2025-09".to_string(),
+            events: vec![RaceEvent {
+                venue_id: 4,
+                venue_name: "平和島".to_string(),
+                event_name: "トーキョー・ベイ・カップ".to_string(),
+                grade: Grade::G1,
+                start_date: "2025-09-10".to_string(),
+                duration_days: 7,
+                notes: None,
+            }],
+        };
+        engine.put_monthly_schedule(&schedule).unwrap();
+
+        // 7日間の開催日すべてに現れる
+        for day in 10..=16 {
+            let events = engine.get_events_on_date(&format!("2025-09-{:02}", day)).unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].venue_name, "平和島");
+        }
+        // 開催前日と終了翌日には現れない
+        assert!(engine.get_events_on_date("2025-09-09").unwrap().is_empty());
+        assert!(engine.get_events_on_date("2025-09-17").unwrap().is_empty());
+
+        assert!(engine.get_events_on_date("2025/09/13").is_err());
+    }
+
+    #[test]
+    fn test_get_events_on_date_across_months() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&tournament).unwrap();
+
+        assert_eq!(engine.get_events_on_date("2025-12-31").unwrap().len(), 1);
+        assert_eq!(engine.get_events_on_date("2026-01-06").unwrap().len(), 1);
+        assert!(engine.get_events_on_date("2026-01-07").unwrap().is_empty());
+
+        // 日別ビューは統計に影響しない
+        let (monthly_count, tournament_count, race_count) = engine.get_statistics().unwrap();
+        assert_eq!(monthly_count, 2);
+        assert_eq!(tournament_count, 1);
+        assert_eq!(race_count, 0);
+    }
+
+    #[test]
+    fn test_venue_operations() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        assert_eq!(engine.get_venue(4).unwrap(), None);
+
+        for venue in VenueInfo::standard_venues() {
+            engine.put_venue(&venue).unwrap();
+        }
+
+        let heiwajima = engine.get_venue(4).unwrap().unwrap();
+        assert_eq!(heiwajima.name, "平和島");
+        assert_eq!(heiwajima.prefecture, "東京都");
+
+        let venues = engine.list_venues().unwrap();
+        assert_eq!(venues.len(), 24);
+        assert_eq!(venues[0].venue_id, 1);
+        assert_eq!(venues[23].venue_id, 24);
+
+        // 会場マスタは統計に影響しない
+        assert_eq!(engine.get_statistics().unwrap(), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_strict_venues() {
+        let store = MemoryStore::new();
+        // 会場マスタの検証に到達するよう venue_id の範囲検証は広げておく
+        let mut engine = BoatRaceEngine::new(store).with_strict_venues(true).with_venue_id_range(1..=99);
+        for venue in VenueInfo::standard_venues() {
+            engine.put_venue(&venue).unwrap();
+        }
+
+        let event = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "トーキョー・ベイ・カップ".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-09-10".to_string(),
+            duration_days: 7,
+            notes: None,
+        };
+        let schedule = MonthlySchedule {
+            year_month: "2025-09".to_string(),
+            events: vec![event.clone()],
+        };
+        engine.put_monthly_schedule(&schedule).unwrap();
+
+        // 末尾に空白のある会場名は拒否
+        let mut typo = event.clone();
+        typo.venue_name = "平和島 ".to_string();
+        let schedule = MonthlySchedule {
+            year_month: "2025-09".to_string(),
+            events: vec![typo],
+        };
+        match engine.put_monthly_schedule(&schedule) {
+            Err(crate::StoreError::InvalidVenue { field, value }) => {
+                assert_eq!(field, "venue_name");
+                assert_eq!(value, "平和島 ");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // 未登録の会場IDは拒否
+        let mut unknown = event.clone();
+        unknown.venue_id = 99;
+        match engine.register_tournament_to_months(&unknown) {
+            Err(crate::StoreError::InvalidVenue { field, value }) => {
+                assert_eq!(field, "venue_id");
+                assert_eq!(value, "99");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // 非厳格モードではチェックしない
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_venue_id_range(1..=99);
+        engine.register_tournament_to_months(&unknown).unwrap();
+    }
+
+    #[test]
+    fn test_strict_venues_without_master() {
+        // 会場マスタが空でも全国の会場表で照合する
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_strict_venues(true);
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+
+        let mut mismatched = sample_data();
+        mismatched.events[1].venue_id = 5;
+        assert!(matches!(
+            engine.put_monthly_schedule(&mismatched),
+            Err(crate::StoreError::InvalidVenue { ref field, .. }) if field == "venue_name"
+        ));
+
+        // 別表記は正規化すれば登録できる
+        let mut alias = RaceEvent { venue_id: 11, venue_name: "琵琶湖".to_string(), ..sample_data().events[1].clone() };
+        assert!(engine.register_tournament(&alias).is_err());
+        alias.normalize_venue().unwrap();
+        engine.register_tournament(&alias).unwrap();
+        assert_eq!(engine.get_events_by_venue(11).unwrap()[0].1.venue_name, "びわこ");
+    }
+
+    #[test]
+    fn test_register_tournament_invalid_date() {
+        let mut event = sample_data().events[1].clone();
+        event.start_date = "2025-09-31".to_string();
+
+        // 検証を無効にしても日付の解析エラーは入力と原因を持つ
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_validation(false);
+        let error = engine.register_tournament_to_months(&event).unwrap_err();
+        match &error {
+            crate::StoreError::InvalidDate { input, reason } => {
+                assert_eq!(input, "2025-09-31");
+                assert_eq!(reason, "input is out of range");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(error.to_string().contains("2025-09-31"));
+
+        let error = engine.get_events_on_date("2025-9-x").unwrap_err();
+        assert!(error.to_string().contains("2025-9-x"));
+
+        // 期間0日の大会は登録する月を決められない
+        let mut event = sample_data().events[1].clone();
+        event.duration_days = 0;
+        let error = engine.register_tournament(&event).unwrap_err();
+        assert!(matches!(error, crate::StoreError::ValidationError { ref field, .. } if field == "duration_days"));
+        assert!(engine.store().keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_racer_operations() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let racer = Racer {
+            racer_id: 4444,
+            name: "山田　太郎".to_string(),
+            branch: "東京".to_string(),
+            class: "Ａ１".to_string(),
+        };
+        engine.put_racer(&racer).unwrap();
+        engine.put_racer(&Racer {
+            racer_id: 3333,
+            name: "佐藤花子".to_string(),
+            branch: "福岡".to_string(),
+            class: "B1".to_string(),
+        }).unwrap();
+
+        // 全角文字を含む名前もそのまま復元される
+        assert_eq!(engine.get_racer(4444).unwrap(), Some(racer.clone()));
+        assert_eq!(engine.get_racer(1234).unwrap(), None);
+
+        // 既存の選手は上書きされる
+        let mut promoted = racer.clone();
+        promoted.class = "A1".to_string();
+        engine.put_racer(&promoted).unwrap();
+        assert_eq!(engine.get_racer(4444).unwrap().unwrap().class, "A1");
+
+        let racers = engine.list_racers().unwrap();
+        assert_eq!(racers.len(), 2);
+        assert_eq!(racers[0].racer_id, 3333);
+
+        let found = engine.search_racers_by_name("太郎").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].racer_id, 4444);
+        assert!(engine.search_racers_by_name("鈴木").unwrap().is_empty());
+
+        assert_eq!(engine.get_detailed_statistics().unwrap().racers, 2);
+    }
+
+    #[test]
+    fn test_racer_id_zero_rejected() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let racer = Racer {
+            racer_id: 0,
+            name: "名無し".to_string(),
+            branch: "東京".to_string(),
+            class: "B2".to_string(),
+        };
+        assert!(matches!(engine.put_racer(&racer), Err(crate::StoreError::InvalidKey)));
+        assert!(matches!(engine.get_racer(0), Err(crate::StoreError::InvalidKey)));
+        assert!(engine.list_racers().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_race_result_operations() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament_id = "tokyo_bay_cup";
+        let first = RaceResult {
+            tournament_id: tournament_id.to_string(),
+            timestamp: 1694524800000,
+            race_number: 1,
+            finishing_order: vec![(1, 4444), (2, 3333), (3, 5555)],
+            winning_technique: "逃げ".to_string(),
+            decided: true,
+        };
+        let second = RaceResult {
+            tournament_id: tournament_id.to_string(),
+            timestamp: 1694526600000,
+            race_number: 2,
+            finishing_order: vec![(4, 1111)],
+            winning_technique: "まくり".to_string(),
+            decided: false,
+        };
+        engine.put_race_result(&second).unwrap();
+        engine.put_race_result(&first).unwrap();
+
+        // 同じ大会ID・タイムスタンプの任意データと共存できる
+        engine.put_race_data(tournament_id, 1694524800000, &"raw race data").unwrap();
+
+        assert_eq!(engine.get_race_result(tournament_id, 1694524800000).unwrap(), first);
+        let raw: String = engine.get_race_data(tournament_id, 1694524800000).unwrap();
+        assert_eq!(raw, "raw race data");
+        assert!(matches!(
+            engine.get_race_result(tournament_id, 1),
+            Err(crate::StoreError::NotFound { .. })
+        ));
+
+        let results = engine.get_tournament_results(tournament_id).unwrap();
+        assert_eq!(results, vec![first.clone(), second]);
+
+        let decided = engine.get_decided_results(tournament_id).unwrap();
+        assert_eq!(decided, vec![first]);
+
+        // 任意のレースデータの取得に結果は混ざらない
+        let races: Vec<String> = engine.get_tournament_races(tournament_id).unwrap();
+        assert_eq!(races.len(), 1);
+    }
+
+    #[test]
+    fn test_odds_history() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament_id = "tokyo_bay_cup";
+        let race = 1694524800000;
+
+        engine.put_odds_snapshot(tournament_id, race, race - 60_000, &vec![1.5, 3.2]).unwrap();
+        engine.put_odds_snapshot(tournament_id, race, race - 180_000, &vec![1.8, 2.9]).unwrap();
+        engine.put_odds_snapshot(tournament_id, race, race - 120_000, &vec![1.6, 3.0]).unwrap();
+
+        // タイムスタンプが1だけ異なる隣接レース
+        engine.put_odds_snapshot(tournament_id, race + 1, race - 90_000, &vec![9.9]).unwrap();
+        engine.put_odds_snapshot(tournament_id, race - 1, race, &vec![8.8]).unwrap();
+
+        let history: Vec<(u64, Vec<f64>)> = engine.get_odds_history(tournament_id, race).unwrap();
+        let times: Vec<u64> = history.iter().map(|(t, _)| *t).collect();
+        assert_eq!(times, vec![race - 180_000, race - 120_000, race - 60_000]);
+        assert_eq!(history[0].1, vec![1.8, 2.9]);
+
+        let (latest_time, latest): (u64, Vec<f64>) = engine.get_latest_odds(tournament_id, race).unwrap();
+        assert_eq!(latest_time, race - 60_000);
+        assert_eq!(latest, vec![1.5, 3.2]);
+
+        let neighbor: Vec<(u64, Vec<f64>)> = engine.get_odds_history(tournament_id, race + 1).unwrap();
+        assert_eq!(neighbor.len(), 1);
+        assert_eq!(neighbor[0].1, vec![9.9]);
+
+        let missing: Result<(u64, Vec<f64>)> = engine.get_latest_odds(tournament_id, race + 2);
+        assert!(matches!(missing, Err(crate::StoreError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_payouts() {
+        use crate::PayoutEntry;
+
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament_id = "tokyo_bay_cup";
+        let first = Payout {
+            win: vec![PayoutEntry { combination: "1".to_string(), amount: 150 }],
+            place: vec![
+                PayoutEntry { combination: "1".to_string(), amount: 110 },
+                PayoutEntry { combination: "3".to_string(), amount: 240 },
+            ],
+            trifecta: vec![PayoutEntry { combination: "1-3-2".to_string(), amount: 2450 }],
+            ..Default::default()
+        };
+        let second = Payout {
+            trifecta: vec![PayoutEntry { combination: "4-1-2".to_string(), amount: 18760 }],
+            ..Default::default()
+        };
+        engine.put_payouts(tournament_id, 1694524800000, &first).unwrap();
+        engine.put_payouts(tournament_id, 1694526600000, &second).unwrap();
+
+        assert_eq!(engine.get_payouts(tournament_id, 1694524800000).unwrap(), Some(first));
+        assert_eq!(engine.get_tournament_payout_total(tournament_id).unwrap(), 2450 + 18760);
+        assert_eq!(engine.get_tournament_payout_total("other_cup").unwrap(), 0);
+
+        // 結果はあるが払戻金が未登録のレースは None
+        engine.put_race_result(&RaceResult {
+            tournament_id: tournament_id.to_string(),
+            timestamp: 1694528400000,
+            race_number: 3,
+            finishing_order: vec![(1, 4444)],
+            winning_technique: "逃げ".to_string(),
+            decided: true,
+        }).unwrap();
+        assert_eq!(engine.get_payouts(tournament_id, 1694528400000).unwrap(), None);
+    }
+
+    #[test]
+    fn test_conditions() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let conditions_on = |date: &str, venue_id: u32, wave_height_cm: u32| RaceDayConditions {
+            date: date.to_string(),
+            venue_id,
+            weather: "晴".to_string(),
+            wind_speed_mps: 3.0,
+            wind_direction: "北西".to_string(),
+            wave_height_cm,
+            temperature: 27.5,
+        };
+
+        for (day, wave) in [(12, 3), (10, 5), (11, 2), (17, 8)] {
+            let date = format!("2025-09-{:02}", day);
+            engine.put_conditions(4, &date, &conditions_on(&date, 4, wave)).unwrap();
+        }
+        engine.put_conditions(5, "2025-09-11", &conditions_on("2025-09-11", 5, 10)).unwrap();
+
+        let found = engine.get_conditions(4, "2025-09-10").unwrap().unwrap();
+        assert_eq!(found.wave_height_cm, 5);
+        assert_eq!(engine.get_conditions(4, "2025-09-13").unwrap(), None);
+
+        let range = engine.get_conditions_range(4, "2025-09-10", "2025-09-16").unwrap();
+        let dates: Vec<&str> = range.iter().map(|c| c.date.as_str()).collect();
+        assert_eq!(dates, vec!["2025-09-10", "2025-09-11", "2025-09-12"]);
+
+        // 不正な日付はエラー
+        let bad = conditions_on("2025-09-31", 4, 0);
+        assert!(matches!(engine.put_conditions(4, "2025-09-31", &bad), Err(crate::StoreError::InvalidDate { .. })));
+        assert!(engine.put_conditions(4, "20250913", &bad).is_err());
+        assert!(engine.get_conditions(4, "2025/09/13").is_err());
+        assert!(engine.get_conditions_range(4, "2025-09-16", "2025-09-10").is_err());
+    }
+
+    #[test]
+    fn test_delete_monthly_schedule() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let schedule = |year_month: &str, start_date: &str| MonthlySchedule {
+            year_month: year_month.to_string(),
+            events: vec![
+                RaceEvent {
+                    venue_id: 1,
+                    venue_name: "桐生".to_string(),
+                    event_name: "Kiryu Cup".to_string(),
+                    grade: Grade::Ippan,
+                    start_date: start_date.to_string(),
+                    duration_days: 3,
+                    notes: None,
+                },
+                RaceEvent {
+                    venue_id: 4,
+                    venue_name: "平和島".to_string(),
+                    event_name: "Tokyo Bay Cup".to_string(),
+                    grade: Grade::G1,
+                    start_date: start_date.to_string(),
+                    duration_days: 3,
+                    notes: None,
+                },
+            ],
+        };
+        engine.put_monthly_schedule(&schedule("2025-09", "2025-09-10")).unwrap();
+        engine.put_monthly_schedule(&schedule("2025-10", "2025-10-10")).unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1694524800000, &"race1").unwrap();
+
+        assert_eq!(engine.delete_monthly_schedule(202509).unwrap(), 2);
+        assert!(engine.get_monthly_schedule(202509).unwrap().events.is_empty());
+        assert!(engine.get_events_on_date("2025-09-11").unwrap().is_empty());
+
+        // 他の月と大会データは残る
+        assert_eq!(engine.get_monthly_schedule(202510).unwrap().events.len(), 2);
+        assert_eq!(engine.get_events_on_date("2025-10-11").unwrap().len(), 2);
+        let race: String = engine.get_race_data("tokyo_bay_cup", 1694524800000).unwrap();
+        assert_eq!(race, "race1");
+
+        assert_eq!(engine.delete_monthly_schedule(202509).unwrap(), 0);
+        assert!(engine.delete_monthly_schedule(202500).is_err());
+        assert!(engine.delete_monthly_schedule(202513).is_err());
+    }
+
+    #[test]
+    fn test_delete_monthly_schedule_with_undecodable_entry() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let event = |venue_id: u32, venue_name: &str, event_name: &str| RaceEvent {
+            venue_id,
+            venue_name: venue_name.to_string(),
+            event_name: event_name.to_string(),
+            grade: Grade::Ippan,
+            start_date: "2025-09-29".to_string(),
+            duration_days: 4,
+            notes: None,
+        };
+        engine
+            .put_monthly_schedule(&MonthlySchedule {
+                year_month: "2025-09".to_string(),
+                events: vec![event(1, "桐生", "Kiryu Cup"), event(4, "平和島", "Tokyo Bay Cup")],
+            })
+            .unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1694524800000, &"race1").unwrap();
+        let broken = crate::key::monthly_key(202509, &generate_tournament_id("平和島", "Tokyo Bay Cup"));
+        engine.store_mut().put(broken.clone(), "{not json".to_string()).unwrap();
+
+        assert_eq!(engine.delete_monthly_schedule(202509).unwrap(), 2);
+        assert!(engine.store().get(&broken).unwrap().is_none());
+        let (start, end) = daily_month_scan_range(202509);
+        assert!(engine.store_mut().scan_keys(&start, &end).unwrap().is_empty());
+        // 翌月にはみ出した日別ビューは残る (従来どおり)
+        assert_eq!(engine.get_events_on_date("2025-10-01").unwrap().len(), 2);
+        assert!(engine.get_monthly_schedule(202509).unwrap().events.is_empty());
+        let race: String = engine.get_race_data("tokyo_bay_cup", 1694524800000).unwrap();
+        assert_eq!(race, "race1");
+    }
+
+    #[test]
+    fn test_delete_race_event() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "Year End Cup".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&tournament).unwrap();
+        let tournament_id = generate_tournament_id(&tournament.venue_name, &tournament.event_name);
+
+        assert!(engine.delete_race_event(202512, &tournament_id).unwrap());
+        assert!(!engine.delete_race_event(202512, &tournament_id).unwrap());
+
+        // 12月分のみ削除され、1月分は残る
+        assert!(engine.get_monthly_schedule(202512).unwrap().events.is_empty());
+        assert!(engine.get_events_on_date("2025-12-30").unwrap().is_empty());
+        assert_eq!(engine.get_monthly_schedule(202601).unwrap().events.len(), 1);
+        assert_eq!(engine.get_events_on_date("2026-01-02").unwrap().len(), 1);
+
+        assert!(matches!(engine.delete_race_event(202601, ""), Err(crate::StoreError::InvalidKey)));
+        assert!(engine.delete_race_event(202600, &tournament_id).is_err());
+    }
+
+    #[test]
+    fn test_delete_undecodable_race_event() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let broken = event_tournament_id(&sample_data().events[0]);
+        let key = crate::key::monthly_key(202509, &broken);
+        engine.store_mut().put(key.clone(), "{not json".to_string()).unwrap();
+        assert!(engine.get_race_event(202509, &broken).is_err());
+
+        // 壊れたエントリも削除でき、その大会の日別ビューだけが消える
+        assert!(engine.delete_race_event(202509, &broken).unwrap());
+        assert!(engine.store().get(&key).unwrap().is_none());
+        let (start, end) = daily_month_scan_range(202509);
+        let daily = engine.store_mut().scan_keys(&start, &end).unwrap();
+        assert!(!daily.is_empty());
+        assert!(daily.iter().all(|key| matches!(key.parse::<Key>(), Ok(Key::Daily { tournament_id, .. }) if tournament_id != broken)));
+        assert!(!engine.delete_race_event(202509, &broken).unwrap());
+    }
+
+    #[test]
+    fn test_delete_tournament() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let cancelled = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "Year End Cup".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-30".to_string(),
+            duration_days: 3,
+            notes: None,
+        };
+        let other = RaceEvent {
+            venue_id: 1,
+            venue_name: "桐生".to_string(),
+            event_name: "Kiryu Cup".to_string(),
+            grade: Grade::Ippan,
+            start_date: "2025-12-30".to_string(),
+            duration_days: 3,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&cancelled).unwrap();
+        engine.register_tournament_to_months(&other).unwrap();
+
+        let tournament_id = generate_tournament_id(&cancelled.venue_name, &cancelled.event_name);
+        let other_id = generate_tournament_id(&other.venue_name, &other.event_name);
+        engine.put_race_data(&tournament_id, 1, &"race1").unwrap();
+        engine.put_race_data(&tournament_id, 2, &"race2").unwrap();
+        engine.put_race_data(&other_id, 1, &"other race").unwrap();
+        engine.put_payouts(&tournament_id, 1, &Payout::default()).unwrap();
+        engine.put_odds_snapshot(&tournament_id, 1, 0, &vec![1.5]).unwrap();
+        engine.set_tournament_meta(&tournament_id, "sponsor", "ACME").unwrap();
+        engine.set_tournament_meta(&other_id, "sponsor", "Kiryu Motors").unwrap();
+
+        let report = engine.delete_tournament(&tournament_id).unwrap();
+        assert_eq!(report, DeleteReport {
+            monthly_entries: 2,
+            daily_entries: 3,
+            race_data: 2,
+            results: 0,
+            payouts: 1,
+            odds_snapshots: 1,
+            metadata: 1,
+        });
+        assert_eq!(report.total(), 10);
+        assert!(engine.list_tournament_meta(&tournament_id).unwrap().is_empty());
+        assert_eq!(engine.get_tournament_meta(&other_id, "sponsor").unwrap(), Some("Kiryu Motors".to_string()));
+
+        // 他の大会は残る
+        let dec = engine.get_monthly_schedule(202512).unwrap();
+        assert_eq!(dec.events.len(), 1);
+        assert_eq!(dec.events[0].event_name, "Kiryu Cup");
+        assert_eq!(engine.get_events_on_date("2026-01-01").unwrap().len(), 1);
+        let races: Vec<String> = engine.get_tournament_races(&other_id).unwrap();
+        assert_eq!(races.len(), 1);
+
+        // 未知の大会IDは件数0
+        assert_eq!(engine.delete_tournament("unknown_cup").unwrap(), DeleteReport::default());
+    }
+
+    #[test]
+    fn test_tournament_meta() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        engine.put_race_data("tokyo", 100, &"tokyo race").unwrap();
+        engine.set_tournament_meta("tokyo", "sponsor", "ACME").unwrap();
+        engine.set_tournament_meta("tokyo", "broadcaster", "BOAT RACE TV").unwrap();
+        engine.set_tournament_meta("tokyo", "sponsor", "ACME Holdings").unwrap();
+        engine.set_tournament_meta("tokyo_bay", "sponsor", "Bay Corp").unwrap();
+
+        assert_eq!(engine.get_tournament_meta("tokyo", "sponsor").unwrap(), Some("ACME Holdings".to_string()));
+        assert_eq!(engine.get_tournament_meta("tokyo", "missing").unwrap(), None);
+        assert_eq!(
+            engine.list_tournament_meta("tokyo").unwrap(),
+            vec![
+                ("broadcaster".to_string(), "BOAT RACE TV".to_string()),
+                ("sponsor".to_string(), "ACME Holdings".to_string()),
+            ]
+        );
+
+        // メタデータはレースデータのスキャンに現れない
+        let races: Vec<String> = engine.get_tournament_races("tokyo").unwrap();
+        assert_eq!(races, vec!["tokyo race".to_string()]);
+
+        assert!(engine.delete_tournament_meta("tokyo", "sponsor").unwrap());
+        assert!(!engine.delete_tournament_meta("tokyo", "sponsor").unwrap());
+        assert_eq!(engine.list_tournament_meta("tokyo").unwrap().len(), 1);
+        assert_eq!(engine.list_tournament_meta("tokyo_bay").unwrap().len(), 1);
+
+        assert!(matches!(engine.set_tournament_meta("tokyo", "a\x00b", "x"), Err(crate::StoreError::InvalidKey)));
+        assert!(matches!(engine.set_tournament_meta("tokyo", "", "x"), Err(crate::StoreError::InvalidKey)));
+        assert!(matches!(engine.set_tournament_meta("", "sponsor", "x"), Err(crate::StoreError::InvalidKey)));
+        assert!(engine.get_tournament_meta("tokyo\x00", "sponsor").is_err());
+    }
+
+    #[test]
+    fn test_tournament_status() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let heiwajima = generate_tournament_id("平和島", "開設７１周年記念トーキョー・ベイ・カップ");
+        let kiryu = generate_tournament_id("桐生", "バスケで群馬を熱くする群馬クレインサンダーズカップ");
+        let suminoe = generate_tournament_id("住之江", "第５３回高松宮記念特別競走");
+
+        // 平和島は 2025-09-10 から 2025-09-16 まで
+        assert_eq!(engine.get_tournament_status(&heiwajima, date("2025-09-09")).unwrap(), TournamentStatus::Scheduled);
+        assert_eq!(engine.get_tournament_status(&heiwajima, date("2025-09-10")).unwrap(), TournamentStatus::Ongoing);
+        assert_eq!(engine.get_tournament_status(&heiwajima, date("2025-09-16")).unwrap(), TournamentStatus::Ongoing);
+        assert_eq!(engine.get_tournament_status(&heiwajima, date("2025-09-17")).unwrap(), TournamentStatus::Finished);
+
+        let ids = |list: Vec<(String, RaceEvent)>| list.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        let today = date("2025-09-12");
+        assert_eq!(ids(engine.list_tournaments_by_status(TournamentStatus::Ongoing, 202509, today).unwrap()), vec![heiwajima.clone(), kiryu.clone()]);
+        assert_eq!(ids(engine.list_tournaments_by_status(TournamentStatus::Scheduled, 202509, today).unwrap()), vec![suminoe.clone()]);
+
+        // 明示した状態は日程より優先する
+        engine.set_tournament_status(&suminoe, TournamentStatus::Cancelled).unwrap();
+        engine.set_tournament_status(&heiwajima, TournamentStatus::Finished).unwrap();
+        assert_eq!(engine.get_tournament_status(&suminoe, date("2025-09-15")).unwrap(), TournamentStatus::Cancelled);
+        assert_eq!(engine.get_tournament_status(&heiwajima, today).unwrap(), TournamentStatus::Finished);
+        assert!(engine.list_tournaments_by_status(TournamentStatus::Scheduled, 202509, today).unwrap().is_empty());
+        assert_eq!(ids(engine.list_tournaments_by_status(TournamentStatus::Cancelled, 202509, today).unwrap()), vec![suminoe.clone()]);
+        assert_eq!(ids(engine.list_tournaments_by_status(TournamentStatus::Ongoing, 202509, today).unwrap()), vec![kiryu.clone()]);
+
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 3);
+        let schedule = engine.get_monthly_schedule_with(202509, ScheduleOptions { exclude_cancelled: true }).unwrap();
+        assert_eq!(schedule.events.len(), 2);
+        assert!(schedule.events.iter().all(|event| event.venue_id != 12));
+
+        // Scheduled / Ongoing の設定は保存済みの状態を取り消す
+        engine.set_tournament_status(&heiwajima, TournamentStatus::Ongoing).unwrap();
+        assert_eq!(engine.get_tournament_status(&heiwajima, date("2025-09-09")).unwrap(), TournamentStatus::Scheduled);
+        assert!(engine.get_tournament_meta(&heiwajima, "status").unwrap().is_none());
+
+        let error = engine.get_tournament_status("unknown_cup", today).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_modified_since() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let now = Arc::new(AtomicU64::new(1000));
+        let clock = Arc::clone(&now);
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_clock(move || clock.load(Ordering::Relaxed));
+        let heiwajima = generate_tournament_id("平和島", "開設７１周年記念トーキョー・ベイ・カップ");
+        let kiryu = generate_tournament_id("桐生", "バスケで群馬を熱くする群馬クレインサンダーズカップ");
+        let suminoe = generate_tournament_id("住之江", "第５３回高松宮記念特別競走");
+
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert_eq!(engine.modified_since(0).unwrap().len(), 3);
+        assert_eq!(engine.last_modified(&kiryu).unwrap(), Some(1000));
+
+        now.store(2000, Ordering::Relaxed);
+        engine.put_race_data(&heiwajima, 1, &"race1").unwrap();
+        assert_eq!(engine.modified_since(1001).unwrap(), vec![heiwajima.clone()]);
+
+        // メタデータ・選手マスタの書き込みは大会の更新として記録しない
+        now.store(3000, Ordering::Relaxed);
+        engine.set_tournament_meta(&kiryu, "sponsor", "ACME").unwrap();
+        engine.set_tournament_status(&suminoe, TournamentStatus::Cancelled).unwrap();
+        engine.put_racer(&Racer { racer_id: 4444, name: "Test".to_string(), branch: "東京".to_string(), class: "A1".to_string() }).unwrap();
+        assert!(engine.modified_since(2001).unwrap().is_empty());
+
+        // 削除した大会も削除時刻が残る
+        now.store(4000, Ordering::Relaxed);
+        engine.delete_tournament(&suminoe).unwrap();
+        now.store(5000, Ordering::Relaxed);
+        engine.delete_race_data(&heiwajima, 1).unwrap();
+
+        // ストアへの直接の書き込みは記録されない
+        engine.store_mut().put(crate::tournament_key("raw_cup", 1), "raw".to_string()).unwrap();
+
+        assert_eq!(engine.modified_since(0).unwrap(), vec![kiryu.clone(), suminoe.clone(), heiwajima.clone()]);
+        assert_eq!(engine.modified_since(4000).unwrap(), vec![suminoe.clone(), heiwajima.clone()]);
+        assert!(engine.modified_since(5001).unwrap().is_empty());
+        assert_eq!(engine.last_modified(&suminoe).unwrap(), Some(4000));
+        assert_eq!(engine.last_modified(&heiwajima).unwrap(), Some(5000));
+        assert_eq!(engine.last_modified("raw_cup").unwrap(), None);
+        assert!(engine.last_modified("raw\x00cup").is_err());
+    }
+
+    #[test]
+    fn test_find_schedule_conflicts() {
+        let event = |venue_id: u32, event_name: &str, start_date: &str, duration_days: u32| RaceEvent {
+            venue_id,
+            venue_name: format!("venue{}", venue_id),
+            event_name: event_name.to_string(),
+            grade: Grade::Ippan,
+            start_date: start_date.to_string(),
+            duration_days,
+            notes: None,
+        };
+        let autumn = event(4, "Autumn Cup", "2025-10-01", 5); // 10-05まで
+        let typo = event(4, "Typo Cup", "2025-10-04", 1);
+        let harvest = event(4, "Harvest Cup", "2025-10-06", 4); // autumn の翌日から
+        let crossing = event(12, "Crossing Cup", "2025-09-28", 7); // 10-04まで
+        let october = event(12, "October Cup", "2025-10-04", 3);
+
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert!(engine.find_schedule_conflicts(202509).unwrap().is_empty());
+
+        engine.register_tournament_to_months(&crossing).unwrap();
+        let schedule = MonthlySchedule {
+            year_month: "2025-10".to_string(),
+            events: vec![harvest.clone(), typo.clone(), autumn.clone(), october.clone()],
+        };
+        engine.put_monthly_schedule(&schedule).unwrap();
+        assert_eq!(
+            engine.find_schedule_conflicts(202510).unwrap(),
+            vec![(autumn.clone(), typo.clone()), (crossing.clone(), october.clone())]
+        );
+        // 9月には October Cup が登録されていない
+        assert!(engine.find_schedule_conflicts(202509).unwrap().is_empty());
+        assert!(engine.find_schedule_conflicts(202513).is_err());
+
+        // 取り込み時の拒否
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.register_tournament_to_months(&crossing).unwrap();
+        let reject = ImportOptions { reject_venue_conflicts: true };
+        let month = |events: Vec<RaceEvent>| MonthlySchedule { year_month: "2025-10".to_string(), events };
+        engine.put_monthly_schedule_checked_with(&month(vec![autumn.clone()]), ConflictPolicy::Overwrite, reject).unwrap();
+        // 連続する (重ならない) 大会は受け入れる
+        engine.put_monthly_schedule_checked_with(&month(vec![harvest.clone()]), ConflictPolicy::Overwrite, reject).unwrap();
+
+        let error = engine
+            .put_monthly_schedule_checked_with(&month(vec![typo.clone()]), ConflictPolicy::Overwrite, reject)
+            .unwrap_err();
+        assert!(matches!(&error, crate::StoreError::ValidationError { field, .. } if field == "venue_id"));
+        assert!(engine.find_race_event(&event_tournament_id(&typo)).unwrap().is_none());
+        // 前月から続く大会とも比較する
+        assert!(engine.put_monthly_schedule_checked_with(&month(vec![october.clone()]), ConflictPolicy::Overwrite, reject).is_err());
+        // 取り込むスケジュール内の重なりも拒否する
+        let pair = vec![event(1, "First", "2025-10-10", 3), event(1, "Second", "2025-10-12", 3)];
+        assert!(engine.put_monthly_schedule_checked_with(&month(pair), ConflictPolicy::Overwrite, reject).is_err());
+        // 同じ大会の更新は自分自身とは比較しない
+        let mut shorter = autumn.clone();
+        shorter.duration_days = 4;
+        engine.put_monthly_schedule_checked_with(&month(vec![shorter]), ConflictPolicy::Overwrite, reject).unwrap();
+
+        assert!(engine.find_schedule_conflicts(202510).unwrap().is_empty());
+        // オプションなしでは従来どおり取り込む
+        engine.put_monthly_schedule_checked(&month(vec![typo.clone()]), ConflictPolicy::Overwrite).unwrap();
+        assert_eq!(engine.find_schedule_conflicts(202510).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_next_event() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let grand_prix = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "グランプリ".to_string(),
+            grade: Grade::SG,
+            start_date: "2025-12-16".to_string(),
+            duration_days: 6,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&grand_prix).unwrap();
+        // 開始月の月別ビューにだけ登録された月跨ぎの大会
+        let crossing = RaceEvent {
+            venue_id: 1,
+            venue_name: "桐生".to_string(),
+            event_name: "Crossing Cup".to_string(),
+            grade: Grade::Ippan,
+            start_date: "2025-10-28".to_string(),
+            duration_days: 7,
+            notes: None,
+        };
+        engine.put_monthly_schedule(&MonthlySchedule { year_month: "2025-10".to_string(), events: vec![crossing.clone()] }).unwrap();
+
+        let name = |event: Option<RaceEvent>| event.map(|event| event.event_name);
+        // 開催中のイベント (平和島は 09-10 から) も「次」に含める
+        assert_eq!(name(engine.next_event_after("2025-09-12", None).unwrap()).as_deref(), Some("開設７１周年記念トーキョー・ベイ・カップ"));
+        assert_eq!(name(engine.next_event_after("2025-09-16", None).unwrap()).as_deref(), Some("開設７１周年記念トーキョー・ベイ・カップ"));
+        // 住之江は 09-18 まで
+        assert_eq!(name(engine.next_event_after("2025-09-18", Some(&[Grade::SG, Grade::G1])).unwrap()).as_deref(), Some("第５３回高松宮記念特別競走"));
+        assert_eq!(engine.next_event_after("2025-09-19", Some(&[Grade::SG, Grade::G1])).unwrap(), Some(grand_prix.clone()));
+        assert_eq!(engine.next_event_after("2025-09-19", None).unwrap(), Some(crossing.clone()));
+
+        assert_eq!(name(engine.next_event_at_venue(12, "2025-09-01").unwrap()).as_deref(), Some("第５３回高松宮記念特別競走"));
+        assert_eq!(engine.next_event_at_venue(4, "2025-09-17").unwrap(), Some(grand_prix.clone()));
+        assert_eq!(engine.next_event_at_venue(1, "2025-11-03").unwrap(), Some(crossing.clone()));
+        assert_eq!(engine.next_event_at_venue(1, "2025-11-04").unwrap(), None);
+        assert_eq!(engine.next_event_at_venue(24, "2025-09-01").unwrap(), None);
+
+        // 検索する月数を超える先のイベントは見つからない
+        let mut engine = engine.with_search_horizon(3);
+        assert_eq!(engine.next_event_after("2025-09-19", Some(&[Grade::SG])).unwrap(), None);
+        assert_eq!(engine.next_event_after("2025-10-01", Some(&[Grade::SG])).unwrap(), Some(grand_prix));
+        assert!(engine.next_event_after("2025-13-01", None).is_err());
+    }
+
+    #[test]
+    fn test_year_overview() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let october = |venue_id: u32, event_name: &str, grade: Grade| RaceEvent {
+            venue_id,
+            venue_name: format!("venue{}", venue_id),
+            event_name: event_name.to_string(),
+            grade,
+            start_date: "2025-10-20".to_string(),
+            duration_days: 5,
+            notes: None,
+        };
+        engine
+            .put_monthly_schedule(&MonthlySchedule {
+                year_month: "2025-10".to_string(),
+                events: vec![october(24, "Omura Cup", Grade::G3), october(4, "Autumn SG", Grade::SG)],
+            })
+            .unwrap();
+        engine.store_mut().put(crate::monthly_key(202511, "broken"), "not base64!".to_string()).unwrap();
+        // 他の年のエントリは数えない
+        engine.store_mut().put(crate::monthly_key(202601, "next_year"), "not base64!".to_string()).unwrap();
+
+        let overview = engine.year_overview(2025).unwrap();
+        assert_eq!(overview.year, 2025);
+        assert_eq!(overview.months.len(), 12);
+        assert_eq!(overview.corrupted_entries, 1);
+        let september = &overview.months[8];
+        assert_eq!(september.year_month, 202509);
+        assert_eq!(september.events, 3);
+        assert_eq!(september.grades, BTreeMap::from([(Grade::Ippan, 1), (Grade::G1, 2)]));
+        assert_eq!(september.venues, vec![1, 4, 12]);
+        assert_eq!(overview.months[9].events, 2);
+        assert_eq!(overview.months[9].venues, vec![4, 24]);
+        assert_eq!(overview.months[10], MonthOverview { year_month: 202511, ..Default::default() });
+
+        let table = overview.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 1 + 12 + 1);
+        assert_eq!(lines[0], "month    events    SG    G1    G2    G3    一般  venues");
+        assert_eq!(lines[9], "2025-09       3     0     2     0     0     1  1,4,12");
+        assert_eq!(lines[1], "2025-01       0     0     0     0     0     0  -");
+        assert_eq!(lines[13], "corrupted entries: 1");
+        assert!(engine.year_overview(10000).is_err());
+    }
+
+    #[test]
+    fn test_delete_race_data() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        // "tokyo" は "tokyo_bay" の接頭辞
+        engine.put_race_data("tokyo", 100, &"tokyo race").unwrap();
+        engine.put_race_data("tokyo_bay", 100, &"tokyo bay race").unwrap();
+
+        assert!(engine.delete_race_data("tokyo", 100).unwrap());
+        assert!(!engine.delete_race_data("tokyo", 100).unwrap());
+
+        let remaining: String = engine.get_race_data("tokyo_bay", 100).unwrap();
+        assert_eq!(remaining, "tokyo bay race");
+        assert!(engine.delete_race_data("tokyo\x00", 100).is_err());
+    }
+
+    #[test]
+    fn test_delete_races_before() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        for timestamp in [100, 200, 300, 400] {
+            engine.put_race_data("tokyo", timestamp, &timestamp).unwrap();
+            engine.put_race_data("tokyo_bay", timestamp, &timestamp).unwrap();
+        }
+        engine.put_race_result(&RaceResult {
+            tournament_id: "tokyo".to_string(),
+            timestamp: 100,
+            race_number: 1,
+            finishing_order: vec![],
+            winning_technique: "逃げ".to_string(),
+            decided: true,
+        }).unwrap();
+
+        // cutoff ちょうどのレースは残る
+        assert_eq!(engine.delete_races_before("tokyo", 300).unwrap(), 2);
+        let mut races: Vec<u64> = engine.get_tournament_races("tokyo").unwrap();
+        races.sort();
+        assert_eq!(races, vec![300, 400]);
+
+        // 接頭辞を共有する大会と他の名前空間には影響しない
+        let other: Vec<u64> = engine.get_tournament_races("tokyo_bay").unwrap();
+        assert_eq!(other.len(), 4);
+        assert!(engine.get_race_result("tokyo", 100).is_ok());
+
+        assert_eq!(engine.delete_races_before("tokyo", 0).unwrap(), 0);
+        assert_eq!(engine.delete_races_before("tokyo", u64::MAX).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_update_race_event() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let schedule = MonthlySchedule {
+            year_month: "2025-09".to_string(),
+            events: vec![RaceEvent {
+                venue_id: 4,
+                venue_name: "Heiwajima".to_string(),
+                event_name: "Bay Cup".to_string(),
+                grade: Grade::G2,
+                start_date: "2025-09-10".to_string(),
+                duration_days: 3,
+                notes: None,
+            }],
+        };
+        engine.put_monthly_schedule(&schedule).unwrap();
+
+        // グレードと日程の変更
+        let updated = engine.update_race_event(202509, "heiwajima_bay_cup", RaceEventUpdate {
+            grade: Some(Grade::G1),
+            start_date: Some("2025-09-20".to_string()),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(updated.grade, Grade::G1);
+        assert_eq!(updated.event_name, "Bay Cup");
+
+        let month = engine.get_monthly_schedule(202509).unwrap();
+        assert_eq!(month.events.len(), 1);
+        assert_eq!(month.events[0].grade, Grade::G1);
+        assert!(engine.get_events_on_date("2025-09-10").unwrap().is_empty());
+        assert_eq!(engine.get_events_on_date("2025-09-21").unwrap()[0].grade, Grade::G1);
+
+        // イベント名の変更で大会IDが変わる
+        let renamed = engine.update_race_event(202509, "heiwajima_bay_cup", RaceEventUpdate {
+            event_name: Some("Tokyo Bay Cup".to_string()),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(renamed.event_name, "Tokyo Bay Cup");
+
+        let month = engine.get_monthly_schedule(202509).unwrap();
+        assert_eq!(month.events.len(), 1);
+        assert_eq!(month.events[0].event_name, "Tokyo Bay Cup");
+        assert!(engine.store().get(&crate::monthly_key(202509, "heiwajima_bay_cup")).unwrap().is_none());
+        assert!(engine.store().get(&crate::monthly_key(202509, "heiwajima_tokyo_bay_cup")).unwrap().is_some());
+        assert_eq!(engine.get_events_on_date("2025-09-21").unwrap().len(), 1);
+
+        // 変更後の大会IDのエントリが既にある場合は上書きしない
+        let mut other = schedule.events[0].clone();
+        other.event_name = "Harbor Cup".to_string();
+        engine.register_tournament_to_months(&other).unwrap();
+        let conflict = engine.update_race_event(202509, "heiwajima_tokyo_bay_cup", RaceEventUpdate {
+            event_name: Some("Harbor Cup".to_string()),
+            ..Default::default()
+        });
+        match conflict {
+            Err(crate::StoreError::KeyExists { key }) => assert_eq!(key, crate::monthly_key(202509, "heiwajima_harbor_cup")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 2);
+        assert_eq!(engine.get_race_event(202509, "heiwajima_harbor_cup").unwrap().unwrap().start_date, "2025-09-10");
+
+        // 大会データを持つ大会は大会IDを変更できない (名前以外の変更はできる)
+        engine.put_race_data("heiwajima_tokyo_bay_cup", 1758412800000, &"race1").unwrap();
+        let rename = engine.update_race_event(202509, "heiwajima_tokyo_bay_cup", RaceEventUpdate {
+            event_name: Some("Bay Final".to_string()),
+            ..Default::default()
+        });
+        assert!(matches!(rename, Err(crate::StoreError::ValidationError { ref field, .. }) if field == "tournament_id"));
+        assert!(engine.get_race_event(202509, "heiwajima_bay_final").unwrap().is_none());
+        engine.update_race_event(202509, "heiwajima_tokyo_bay_cup", RaceEventUpdate {
+            grade: Some(Grade::SG),
+            ..Default::default()
+        }).unwrap();
+        let race: String = engine.get_race_data("heiwajima_tokyo_bay_cup", 1758412800000).unwrap();
+        assert_eq!(race, "race1");
+
+        // 存在しないエントリはキー付きの NotFound
+        match engine.update_race_event(202509, "heiwajima_bay_cup", RaceEventUpdate::default()) {
+            Err(crate::StoreError::NotFound { key }) => {
+                assert_eq!(key, crate::monthly_key(202509, "heiwajima_bay_cup"));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_and_find_race_event() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament = RaceEvent {
+            venue_id: 4,
+            venue_name: "Heiwajima".to_string(),
+            event_name: "Year End Cup".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&tournament).unwrap();
+        let tournament_id = generate_tournament_id(&tournament.venue_name, &tournament.event_name);
+
+        // 両方の月から直接取得できる
+        let dec = engine.get_race_event(202512, &tournament_id).unwrap().unwrap();
+        assert_eq!(dec.event_name, "Year End Cup");
+        assert!(engine.get_race_event(202601, &tournament_id).unwrap().is_some());
+        assert!(engine.get_race_event(202602, &tournament_id).unwrap().is_none());
+        assert!(engine.get_race_event(202512, "unknown").unwrap().is_none());
+
+        // 月を知らなくても最も古い月のエントリが見つかる (月別ビューのスキャン1回)
+        let scans = engine.metrics().scans;
+        let (year_month, event) = engine.find_race_event(&tournament_id).unwrap().unwrap();
+        assert_eq!(year_month, 202512);
+        assert_eq!(event.start_date, "2025-12-28");
+        assert_eq!(engine.metrics().scans, scans + 1);
+        assert!(engine.find_race_event("unknown").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_list_tournaments() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let tournament = RaceEvent {
+            venue_id: 4,
+            venue_name: "Heiwajima".to_string(),
+            event_name: "Year End Cup".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&tournament).unwrap();
+        engine.put_race_data("heiwajima_year_end_cup", 1, &"race1").unwrap();
+        engine.put_race_data("heiwajima_year_end_cup", 2, &"race2").unwrap();
+        // 月別エントリのない大会
+        engine.put_race_data("orphan_cup", 1, &"race1").unwrap();
+
+        let ids = engine.list_tournament_ids().unwrap();
+        assert_eq!(ids, vec!["heiwajima_year_end_cup".to_string(), "orphan_cup".to_string()]);
+
+        let summaries = engine.list_tournaments().unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].tournament_id, "heiwajima_year_end_cup");
+        assert_eq!(summaries[0].event.as_ref().unwrap().event_name, "Year End Cup");
+        assert_eq!(summaries[0].months, vec![202512, 202601]);
+        assert_eq!(summaries[0].race_count, 2);
+        assert_eq!((summaries[0].first_race, summaries[0].last_race), (Some(1), Some(2)));
+
+        assert_eq!(summaries[1].tournament_id, "orphan_cup");
+        assert!(summaries[1].event.is_none());
+        assert!(summaries[1].months.is_empty());
+        assert_eq!(summaries[1].race_count, 1);
+    }
+
+    /// 通知されたイベントを記録するフックを設定
+    fn record_events(engine: &mut BoatRaceEngine<MemoryStore>) -> std::sync::Arc<std::sync::Mutex<Vec<EngineEvent>>> {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        engine.set_hook(Box::new(move |event| sink.lock().unwrap().push(event.clone())));
+        events
+    }
+
+    #[test]
+    fn test_hook_events() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_max_value_bytes(1024);
+        let events = record_events(&mut engine);
+
+        let schedule = sample_data();
+        engine.put_monthly_schedule(&schedule).unwrap();
+        let ids: Vec<String> = schedule.events.iter().map(|event| generate_tournament_id(&event.venue_name, &event.event_name)).collect();
+        assert_eq!(
+            *events.lock().unwrap(),
+            ids.iter()
+                .map(|id| EngineEvent::MonthlyEventPut { year_month: 202509, tournament_id: id.clone() })
+                .collect::<Vec<_>>()
+        );
+
+        events.lock().unwrap().clear();
+        engine.put_race_data(&ids[1], 100, &"race").unwrap();
+        engine.delete_tournament(&ids[1]).unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                EngineEvent::RaceDataPut { tournament_id: ids[1].clone(), timestamp: 100 },
+                EngineEvent::MonthlyEventDeleted { year_month: 202509, tournament_id: ids[1].clone() },
+                EngineEvent::RaceDataDeleted { tournament_id: ids[1].clone(), timestamp: 100 },
+                EngineEvent::TournamentDeleted { tournament_id: ids[1].clone() },
+            ]
+        );
+
+        // 失敗した書き込みは通知しない
+        events.lock().unwrap().clear();
+        assert!(engine.put_race_data(&ids[0], 1, &"x".repeat(2048)).is_err());
+        engine.clear_hook();
+        engine.put_race_data(&ids[0], 1, &"race").unwrap();
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_hook_panic_does_not_abort_write() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.set_hook(Box::new(|_| panic!("cache unavailable")));
+        engine.put_race_data("tokyo_bay_cup", 1, &"race1").unwrap();
+        engine.put_race_data("tokyo_bay_cup", 2, &"race2").unwrap();
+        let races: Vec<String> = engine.get_tournament_races("tokyo_bay_cup").unwrap();
+        assert_eq!(races, vec!["race1", "race2"]);
+        // レースごとに RaceDataPut の1件ずつ
+        assert_eq!(engine.metrics().hook_panics, 2);
+        let panic = engine.last_hook_panic().unwrap();
+        assert_eq!(panic.event, EngineEvent::RaceDataPut { tournament_id: "tokyo_bay_cup".to_string(), timestamp: 2 });
+        assert_eq!(panic.message, "cache unavailable");
+        assert!(panic.to_string().starts_with("engine hook panicked on RaceDataPut"));
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_clock(|| 1_700_000_000_000);
+        engine.put_race_data("tokyo_bay_cup", 1, &"race1").unwrap();
+        engine.put_race_data("tokyo_bay_cup", 2, &"race2").unwrap();
+        engine.put_race_data("tokyo_bay_cup", 3, &"race3").unwrap();
+        let entry_bytes = |key: &String| key.len() + engine.store().get(key).unwrap().unwrap().len();
+        let race_bytes: usize = engine.store().keys().unwrap().iter().filter(|key| !key.starts_with('\x01')).map(entry_bytes).sum();
+        let stamp = entry_bytes(&last_modified_key("tokyo_bay_cup"));
+        let races: Vec<String> = engine.get_tournament_races("tokyo_bay_cup").unwrap();
+        assert_eq!(races.len(), 3);
+        assert!(engine.delete_race_data("tokyo_bay_cup", 2).unwrap());
+        engine.store_mut().put(crate::tournament_key("tokyo_bay_cup", 9), "not base64!".to_string()).unwrap();
+        assert!(engine.get_race_data::<String>("tokyo_bay_cup", 9).is_err());
+        // delete_race_data は存在確認と出走者一覧の読み込みで 2 回 get する。
+        // レースの書き込み・削除のたびに最終更新時刻も書き込む
+        assert_eq!(
+            engine.metrics(),
+            EngineMetrics {
+                puts: 7,
+                gets: 3,
+                scans: 1,
+                deletes: 1,
+                bytes_written: (race_bytes + 4 * stamp) as u64,
+                serialization_failures: 1,
+                malformed_keys: 0,
+                hook_panics: 0,
+                counters: None,
+            }
+        );
+
+        engine.rebuild_statistics().unwrap();
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let metrics = engine.metrics();
+        assert_eq!(metrics.counters, Some(StatisticsCounters { monthly_entries: 3, races: 3, tournaments: 3 }));
+
+        let text = crate::render_prometheus(&metrics);
+        for line in text.lines() {
+            if let Some(comment) = line.strip_prefix("# ") {
+                let mut parts = comment.splitn(3, ' ');
+                let (kind, name, rest) = (parts.next().unwrap(), parts.next().unwrap(), parts.next().unwrap());
+                assert!(kind == "HELP" || (kind == "TYPE" && (rest == "counter" || rest == "gauge")), "{}", line);
+                assert!(name.starts_with("norimaki_"), "{}", line);
+                continue;
+            }
+            let (series, value) = line.rsplit_once(' ').unwrap();
+            assert!(value.parse::<u64>().is_ok(), "{}", line);
+            let name = series.split('{').next().unwrap();
+            assert!(name.starts_with("norimaki_") && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'), "{}", line);
+            if let Some(labels) = series.strip_prefix(name).filter(|labels| !labels.is_empty()) {
+                assert!(labels.starts_with("{namespace=\"") && labels.ends_with("\"}"), "{}", line);
+            }
+        }
+        assert!(text.contains(&format!("\nnorimaki_puts_total {}\n", metrics.puts)));
+        assert!(text.contains("\nnorimaki_entries{namespace=\"race\"} 3\n"));
+    }
+
+    #[test]
+    fn test_dry_run_schedule_import() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_race_data("tokyo_bay_cup", 1, &"race").unwrap();
+        let before = engine.store().clone();
+
+        let schedule = sample_data();
+        let (count, report) = engine
+            .dry_run(|tx| {
+                tx.put_monthly_schedule(&schedule)?;
+                // クロージャ内では書き込んだ内容が読める
+                Ok(tx.get_monthly_schedule(202509)?.events.len())
+            })
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let mut expected: Vec<String> = schedule.events.iter().map(|event| crate::monthly_key(202509, &event_tournament_id(event))).collect();
+        expected.sort();
+        let monthly: Vec<&String> = report.written.iter().map(|(key, _)| key).filter(|key| matches!(key.parse::<Key>(), Ok(Key::Monthly { .. }))).collect();
+        assert_eq!(monthly, expected.iter().collect::<Vec<_>>());
+        assert!(report.written.iter().all(|(_, bytes)| *bytes > 0));
+        assert!(report.deleted.is_empty());
+
+        // 実際のストアは変更されない
+        let sorted_keys = |store: &MemoryStore| {
+            let mut keys = store.keys().unwrap();
+            keys.sort();
+            keys
+        };
+        assert_eq!(sorted_keys(engine.store()), sorted_keys(&before));
+        assert!(engine.get_monthly_schedule(202509).unwrap().events.is_empty());
+
+        // 削除も実際のストアに存在するキーのみ報告する
+        engine.put_monthly_schedule(&schedule).unwrap();
+        let tournament_id = event_tournament_id(&schedule.events[0]);
+        let ((), report) = engine.dry_run(|tx| tx.delete_tournament(&tournament_id).map(|_| ())).unwrap();
+        assert!(report.deleted.contains(&crate::monthly_key(202509, &tournament_id)));
+        assert!(engine.find_race_event(&tournament_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_put_race_data_unique() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let timestamp = 1757484000000;
+        assert_eq!(engine.put_race_data_unique("tokyo_bay_cup", timestamp, &"race1", false).unwrap(), timestamp);
+
+        let error = engine.put_race_data_unique("tokyo_bay_cup", timestamp, &"race2", false).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::KeyExists);
+        assert_eq!(error.key(), Some(crate::tournament_key("tokyo_bay_cup", timestamp).as_str()));
+        assert_eq!(engine.get_race_data::<String>("tokyo_bay_cup", timestamp).unwrap(), "race1");
+
+        // 衝突したら 1 ms ずつずらす (timestamp + 1 も使用済み)
+        engine.put_race_data("tokyo_bay_cup", timestamp + 1, &"race2").unwrap();
+        assert_eq!(engine.put_race_data_unique("tokyo_bay_cup", timestamp, &"race3", true).unwrap(), timestamp + 2);
+        let races: Vec<(u64, String)> = engine.get_tournament_races_with_keys("tokyo_bay_cup").unwrap();
+        assert_eq!(
+            races,
+            vec![(timestamp, "race1".to_string()), (timestamp + 1, "race2".to_string()), (timestamp + 2, "race3".to_string())]
+        );
+
+        // 既定の put_race_data は上書きする
+        engine.put_race_data("tokyo_bay_cup", timestamp, &"replaced").unwrap();
+        assert_eq!(engine.get_race_data::<String>("tokyo_bay_cup", timestamp).unwrap(), "replaced");
+    }
+
+    #[test]
+    fn test_put_race_data_unique_bump_limit() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let races: Vec<(u64, &str)> = (0..=MAX_TIMESTAMP_BUMPS).map(|offset| (offset, "race")).collect();
+        engine.put_races_bulk("tokyo_bay_cup", &races).unwrap();
+        let error = engine.put_race_data_unique("tokyo_bay_cup", 0, &"late", true).unwrap_err();
+        assert_eq!(error.key(), Some(crate::tournament_key("tokyo_bay_cup", 0).as_str()));
+        assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), races.len());
+
+        // 上限付近でもあふれない
+        engine.put_race_data("tokyo_bay_cup", u64::MAX - 1, &"race").unwrap();
+        assert_eq!(engine.put_race_data_unique("tokyo_bay_cup", u64::MAX - 1, &"race", true).unwrap(), u64::MAX);
+        assert!(engine.put_race_data_unique("tokyo_bay_cup", u64::MAX, &"race", true).is_err());
+    }
+
+    #[test]
+    fn test_get_tournament_summary() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let tournament = RaceEvent {
+            venue_id: 4,
+            venue_name: "Heiwajima".to_string(),
+            event_name: "Year End Cup".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        let tournament_id = engine.register_tournament_to_months(&tournament).unwrap();
+
+        // 月別エントリのみでレースがない
+        let summary = engine.get_tournament_summary(&tournament_id).unwrap();
+        assert_eq!(summary.event.as_ref().unwrap().event_name, "Year End Cup");
+        assert_eq!(summary.months, vec![202512, 202601]);
+        assert_eq!(summary.race_count, 0);
+        assert_eq!((summary.first_race, summary.last_race), (None, None));
+        assert_eq!(summary.race_bytes, 0);
+
+        engine.put_race_data(&tournament_id, 1735603200, &"final").unwrap();
+        engine.put_race_data(&tournament_id, 1735344000, &"opening").unwrap();
+        let summary = engine.get_tournament_summary(&tournament_id).unwrap();
+        assert_eq!(summary.race_count, 2);
+        assert_eq!(summary.first_race, Some(1735344000));
+        assert_eq!(summary.last_race, Some(1735603200));
+        assert_eq!(summary.race_bytes, engine.tournament_statistics(&tournament_id).unwrap().total_bytes);
+        assert!(summary.race_bytes > 0);
+
+        // 月別エントリのない大会
+        engine.put_race_data("orphan_cup", 5, &"race").unwrap();
+        let orphan = engine.get_tournament_summary("orphan_cup").unwrap();
+        assert!(orphan.event.is_none());
+        assert!(orphan.months.is_empty());
+        assert_eq!((orphan.race_count, orphan.first_race, orphan.last_race), (1, Some(5), Some(5)));
+
+        let error = engine.get_tournament_summary("unknown_cup").unwrap_err();
+        assert!(error.is_not_found());
+        assert!(error.to_string().contains("unknown_cup"));
+        assert!(engine.get_tournament_summary("").is_err());
+    }
+
+    #[test]
+    fn test_get_events_by_grade() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+
+        let g1 = engine.get_events_by_grade(2025, &[Grade::G1]).unwrap();
+        assert_eq!(g1.len(), 2);
+        assert!(g1.iter().all(|(year_month, _)| *year_month == 202509));
+        // 開始日順
+        assert_eq!(g1[0].1.venue_name, "平和島");
+        assert_eq!(g1[1].1.venue_name, "住之江");
+
+        assert_eq!(engine.get_events_by_grade(2025, &[Grade::G1, Grade::Ippan]).unwrap().len(), 3);
+        assert!(engine.get_events_by_grade(2025, &[Grade::SG]).unwrap().is_empty());
+        assert!(engine.get_events_by_grade(2024, &[Grade::G1]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_events_by_grade_reads_messy_stored_grades() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        // 型付きのグレード導入前に書き込まれた表記ゆれのある値
+        for (index, grade) in ["G1 ", "Ｇ１", "g1", "GⅠ"].iter().enumerate() {
+            let value = serde_json::json!({
+                "venue_id": 4,
+                "venue_name": "平和島",
+                "event_name": format!("トーキョー・ベイ・カップ{}", index),
+                "grade": grade,
+                "start_date": "2025-09-10",
+                "duration_days": 7,
+            });
+            let key = crate::monthly_key(202509, &format!("heiwajima_{}", index));
+            engine.store_mut().put(key, value.to_string()).unwrap();
+        }
+
+        let events = engine.get_events_by_grade(2025, &[Grade::G1]).unwrap();
+        assert_eq!(events.len(), 4);
+        assert!(events.iter().all(|(_, event)| event.grade == Grade::G1));
+        assert!(engine.get_events_by_grade(2025, &[Grade::SG]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_events_in_date_range() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        // 月跨ぎの年末年始大会
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&year_end).unwrap();
+
+        // 12月のみに登録された短期大会
+        let schedule = MonthlySchedule {
+            year_month: "2025-12".to_string(),
+            events: vec![RaceEvent {
+                venue_id: 1,
+                venue_name: "桐生".to_string(),
+                event_name: "Kiryu Cup".to_string(),
+                grade: Grade::Ippan,
+                start_date: "2025-12-20".to_string(),
+                duration_days: 3,
+                notes: None,
+            }],
+        };
+        engine.put_monthly_schedule(&schedule).unwrap();
+
+        // 1月だけを指定しても前月登録分の年末年始杯が1件だけ返る
+        let events = engine.get_events_in_date_range("2026-01-03", "2026-01-10").unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_name, "年末年始杯");
+
+        // 両月にまたがる範囲でも重複しない
+        let events = engine.get_events_in_date_range("2025-12-01", "2026-01-31").unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_name, "Kiryu Cup");
+        assert_eq!(events[1].event_name, "年末年始杯");
+
+        // 終了翌日からは含まれない
+        assert!(engine.get_events_in_date_range("2026-01-07", "2026-01-31").unwrap().is_empty());
+        // 境界日を含む
+        assert_eq!(engine.get_events_in_date_range("2025-12-22", "2025-12-22").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_get_events_in_date_range_invalid() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        match engine.get_events_in_date_range("2025-13-01", "2025-12-31") {
+            Err(error @ crate::StoreError::InvalidDate { .. }) => assert!(error.to_string().contains("2025-13-01")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(matches!(
+            engine.get_events_in_date_range("2025-12-31", "2025-12-01"),
+            Err(crate::StoreError::InvalidDate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ongoing_and_upcoming_events() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&year_end).unwrap();
+        let new_year = RaceEvent {
+            venue_id: 1,
+            venue_name: "桐生".to_string(),
+            event_name: "New Year Cup".to_string(),
+            grade: Grade::Ippan,
+            start_date: "2026-01-05".to_string(),
+            duration_days: 4,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&new_year).unwrap();
+
+        let ongoing = engine.get_ongoing_events("2026-01-02").unwrap();
+        assert_eq!(ongoing.len(), 1);
+        assert_eq!(ongoing[0].event_name, "年末年始杯");
+
+        let ongoing = engine.get_ongoing_events("2026-01-06").unwrap();
+        assert_eq!(ongoing.len(), 2);
+        assert!(engine.get_ongoing_events("2026-01-09").unwrap().is_empty());
+
+        let upcoming = engine.get_upcoming_events("2025-12-25", 5).unwrap();
+        assert_eq!(upcoming.len(), 1);
+        assert_eq!(upcoming[0].event_name, "年末年始杯");
+
+        let upcoming = engine.get_upcoming_events("2025-12-25", 20).unwrap();
+        assert_eq!(upcoming.len(), 2);
+        assert_eq!(upcoming[1].event_name, "New Year Cup");
+
+        // 開催中の大会は近日開催に含まれない
+        assert_eq!(engine.get_upcoming_events("2025-12-28", 3).unwrap().len(), 0);
+        assert!(engine.get_upcoming_events("2025-12-32", 3).is_err());
+    }
+
+    #[test]
+    fn test_is_event_ongoing() {
+        let event = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        assert!(!event.is_event_ongoing("2025-12-27"));
+        assert!(event.is_event_ongoing("2025-12-28"));
+        assert!(event.is_event_ongoing("2026-01-06"));
+        assert!(!event.is_event_ongoing("2026-01-07"));
+        assert!(!event.is_event_ongoing("invalid"));
+    }
+
+    #[test]
+    fn test_get_tournament_races_with_keys() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        for (timestamp, race) in [(300, "race3"), (100, "race1"), (u64::MAX, "last"), (200, "race2")] {
+            engine.put_race_data("tokyo_bay_cup", timestamp, &race).unwrap();
+        }
+
+        let races: Vec<(u64, String)> = engine.get_tournament_races_with_keys("tokyo_bay_cup").unwrap();
+        let timestamps: Vec<u64> = races.iter().map(|(t, _)| *t).collect();
+        assert_eq!(timestamps, vec![100, 200, 300, u64::MAX]);
+        assert_eq!(races[0].1, "race1");
+
+        // 既存メソッドもタイムスタンプ順
+        let plain: Vec<String> = engine.get_tournament_races("tokyo_bay_cup").unwrap();
+        assert_eq!(plain, vec!["race1", "race2", "race3", "last"]);
+    }
+
+    /// 一方の大会IDが他方の接頭辞になっている場合も、件数・スキャン・削除が混ざらないこと
+    #[test]
+    fn test_tournament_prefix_ids_do_not_bleed() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let result = |tournament_id: &str, timestamp: u64| RaceResult {
+            tournament_id: tournament_id.to_string(),
+            timestamp,
+            race_number: 1,
+            finishing_order: vec![(1, 4444)],
+            winning_technique: "逃げ".to_string(),
+            decided: true,
+        };
+        for (tournament_id, count) in [("tokyo", 2u64), ("tokyo_bay_cup", 3)] {
+            for timestamp in 1..=count {
+                engine.put_race_data(tournament_id, timestamp, &format!("{} {}", tournament_id, timestamp)).unwrap();
+                engine.put_race_result(&result(tournament_id, timestamp)).unwrap();
+                engine.put_payouts(tournament_id, timestamp, &Payout::default()).unwrap();
+                engine.put_odds_snapshot(tournament_id, timestamp, 0, &vec![1.5]).unwrap();
+            }
+            engine.set_tournament_meta(tournament_id, "sponsor", tournament_id).unwrap();
+        }
+        // "tokyo" の範囲内に入るが別の大会IDとして解析されるキー
+        let crafted = format!("Ttokyo\x00x\x00{:016x}", 9);
+        assert!(!tournament_key_matches(&crafted, "tokyo"));
+        engine.store_mut().put(crafted.clone(), serialize_to_string(&"crafted").unwrap()).unwrap();
+
+        assert_eq!(engine.count_races("tokyo").unwrap(), 2);
+        assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), 3);
+        assert_eq!(engine.count_races_between("tokyo", 0, u64::MAX).unwrap(), 2);
+        let races: Vec<String> = engine.get_tournament_races("tokyo").unwrap();
+        assert_eq!(races, vec!["tokyo 1", "tokyo 2"]);
+        assert_eq!(engine.tournament_statistics("tokyo").unwrap().race_count, 2);
+        assert_eq!(engine.get_tournament_results("tokyo").unwrap().len(), 2);
+        assert_eq!(engine.list_tournament_meta("tokyo").unwrap().len(), 1);
+
+        assert_eq!(engine.delete_races_before("tokyo", 2).unwrap(), 1);
+        assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), 3);
+
+        let report = engine.delete_tournament("tokyo").unwrap();
+        assert_eq!((report.race_data, report.results, report.payouts, report.odds_snapshots, report.metadata), (1, 2, 2, 2, 1));
+        assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), 3);
+        assert_eq!(engine.get_tournament_results("tokyo_bay_cup").unwrap().len(), 3);
+        assert_eq!(engine.list_tournament_meta("tokyo_bay_cup").unwrap().len(), 1);
+        assert!(engine.store().get(&crafted).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_get_tournament_races_with_keys_malformed() {
+        let mut store = MemoryStore::new();
+        let malformed = "Ttokyo_bay_cup\x00zz".to_string();
+        store.put(malformed.clone(), serialize_to_string(&"bad").unwrap()).unwrap();
+        let mut engine = BoatRaceEngine::new(store);
+
+        // 不正なキーは読み飛ばしてメトリクスに記録する
+        let races = engine.get_tournament_races_with_keys::<String>("tokyo_bay_cup").unwrap();
+        assert!(races.is_empty());
+        assert_eq!(engine.metrics().malformed_keys, 1);
+        assert!(engine.store().get(&malformed).unwrap().is_some());
+    }
+
+    /// 範囲の境界・範囲内に置いた不正なキーがスキャン結果に混ざらないこと
+    #[test]
+    fn test_scan_skips_adversarial_keys() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1, &"race1").unwrap();
+
+        let (_, value) = engine.store_mut().scan("M202509\x00", "M202509\x01").unwrap().remove(0);
+        // 月・日の範囲の境界の直前・直後に置いたキー
+        for key in ["M202509", "M2025091\x00x", "M202509\x01x", "D20250913", "D202509131\x00x"] {
+            engine.store_mut().put(key.to_string(), value.clone()).unwrap();
+        }
+        // 月の間・大会の範囲の内側に入る不正なキー
+        for key in ["M2025081", "M2025090\x00x", "Ttokyo_bay_cup\x00zz"] {
+            engine.store_mut().put(key.to_string(), value.clone()).unwrap();
+        }
+
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 3);
+        assert_eq!(engine.get_events_on_date("2025-09-13").unwrap().len(), 3);
+        assert_eq!(engine.metrics().malformed_keys, 0);
+
+        let yearly = engine.get_yearly_schedule(2025).unwrap();
+        assert_eq!(yearly.iter().map(|schedule| schedule.events.len()).sum::<usize>(), 3);
+        // 年間の範囲には月の間のキーがすべて含まれる
+        assert_eq!(engine.metrics().malformed_keys, 5);
+
+        assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), 1);
+        let races: Vec<String> = engine.get_tournament_races("tokyo_bay_cup").unwrap();
+        assert_eq!(races, vec!["race1"]);
+        assert_eq!(engine.metrics().malformed_keys, 7);
+    }
+
+    #[test]
+    fn test_get_races_between() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        for timestamp in [100, 150, 200, 250] {
+            engine.put_race_data("tokyo", timestamp, &timestamp).unwrap();
+        }
+        engine.put_race_data("tokyo_bay", 150, &150u64).unwrap();
+
+        // to_ts ちょうどのレース (200) は含まない
+        let races: Vec<(u64, u64)> = engine.get_races_between("tokyo", 100, 200).unwrap();
+        assert_eq!(races, vec![(100, 100), (150, 150)]);
+        assert_eq!(engine.count_races_between("tokyo", 100, 200).unwrap(), 2);
+
+        let races: Vec<(u64, u64)> = engine.get_races_between("tokyo", 101, 201).unwrap();
+        assert_eq!(races, vec![(150, 150), (200, 200)]);
+
+        assert_eq!(engine.count_races_between("tokyo", 0, u64::MAX).unwrap(), 4);
+        assert_eq!(engine.count_races_between("tokyo", 200, 200).unwrap(), 0);
+        assert_eq!(engine.count_races_between("tokyo", 300, 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_tournament_statistics() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        let base = 1694524800000;
+        for i in 0..100u64 {
+            engine.put_race_data("tokyo", base + i * 1_800_000, &i).unwrap();
+        }
+        engine.put_race_data("tokyo_bay", 1, &"other").unwrap();
+
+        assert_eq!(engine.count_races("tokyo").unwrap(), 100);
+        assert_eq!(engine.count_races("unknown").unwrap(), 0);
+
+        let stats = engine.tournament_statistics("tokyo").unwrap();
+        assert_eq!(stats.race_count, 100);
+        assert_eq!(stats.earliest, Some(base));
+        assert_eq!(stats.latest, Some(base + 99 * 1_800_000));
+        assert!(stats.total_bytes > 0);
+
+        assert_eq!(engine.tournament_statistics("unknown").unwrap(), TournamentStats::default());
+    }
+
+    #[test]
+    fn test_tournament_statistics_ignores_payloads() {
+        let mut store = MemoryStore::new();
+        store.put(crate::tournament_key("tokyo", 10), "not base64!".to_string()).unwrap();
+        store.put(crate::tournament_key("tokyo", 20), "".to_string()).unwrap();
+        let mut engine = BoatRaceEngine::new(store);
+
+        assert_eq!(engine.count_races("tokyo").unwrap(), 2);
+        let stats = engine.tournament_statistics("tokyo").unwrap();
+        assert_eq!(stats.race_count, 2);
+        assert_eq!(stats.earliest, Some(10));
+        assert_eq!(stats.latest, Some(20));
+        assert_eq!(engine.get_race_sizes("tokyo").unwrap(), vec![(10, 11), (20, 0)]);
+        assert!(engine.get_race_sizes("tok").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_detailed_statistics() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::SG,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&year_end).unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1, &"race1").unwrap();
+
+        let stats = engine.get_detailed_statistics().unwrap();
+        assert_eq!(stats.monthly_entries, 5);
+        assert_eq!(stats.tournaments, 4);
+        assert_eq!(stats.races, 1);
+        assert_eq!(stats.months, vec![202509, 202512, 202601]);
+        assert_eq!(stats.events_per_grade.get(&Grade::G1), Some(&2));
+        assert_eq!(stats.events_per_grade.get(&Grade::Ippan), Some(&1));
+        // 月跨ぎ大会は1大会として数える
+        assert_eq!(stats.events_per_grade.get(&Grade::SG), Some(&1));
+        assert_eq!(stats.events_per_venue.get(&4), Some(&2));
+        assert_eq!(stats.corrupted_entries, 0);
+        assert!(stats.total_bytes > 0);
+
+        assert_eq!(engine.get_statistics().unwrap(), (5, 4, 1));
+    }
+
+    #[test]
+    fn test_archive_months_before_skips_straddling_tournaments() {
+        let mut engine = BoatRaceEngine::with_indexing(MemoryStore::new(), IndexOptions { by_venue: true, by_name: true });
+        let mut archive = MemoryStore::new();
+
+        // 月跨ぎの年末年始大会
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        let year_end_id = engine.register_tournament(&year_end).unwrap();
+        engine.put_race_data(&year_end_id, 1, &"year_end_race").unwrap();
+
+        let kiryu = RaceEvent {
+            venue_id: 1,
+            venue_name: "桐生".to_string(),
+            event_name: "Kiryu Cup".to_string(),
+            grade: Grade::Ippan,
+            start_date: "2025-12-20".to_string(),
+            duration_days: 3,
+            notes: None,
+        };
+        let schedule = MonthlySchedule { year_month: "2025-12".to_string(), events: vec![kiryu.clone()] };
+        engine.put_monthly_schedule(&schedule).unwrap();
+        let kiryu_id = event_tournament_id(&kiryu);
+        engine.put_race_data(&kiryu_id, 1, &"kiryu_race1").unwrap();
+        engine.put_race_data(&kiryu_id, 2, &"kiryu_race2").unwrap();
+        engine.put_race_result(&RaceResult {
+            tournament_id: kiryu_id.clone(),
+            timestamp: 1,
+            race_number: 1,
+            finishing_order: vec![(1, 4444)],
+            winning_technique: "逃げ".to_string(),
+            decided: true,
+        }).unwrap();
+        engine.put_payouts(&kiryu_id, 1, &Payout::default()).unwrap();
+        engine.set_tournament_meta(&kiryu_id, "sponsor", "桐生市").unwrap();
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+
+        let report = engine.archive_months_before(202601, &mut archive).unwrap();
+        let mut expected_months = BTreeMap::new();
+        expected_months.insert(202509, sample_data().events.len());
+        expected_months.insert(202512, 1);
+        assert_eq!(report.months, expected_months);
+        assert_eq!(report.races, 2);
+        // 結果・払戻金・メタデータ、出走者一覧と出走者インデックス、全大会の会場・名前インデックス
+        assert!(report.other_entries > 5, "{:?}", report);
+        assert_eq!(report.skipped, vec![year_end_id.clone()]);
+        assert!(report.tournaments.contains(&kiryu_id));
+
+        // 年末年始杯は両月とも稼働中のストアに残る
+        let december = engine.get_monthly_schedule(202512).unwrap();
+        assert_eq!(december.events, vec![year_end.clone()]);
+        assert_eq!(engine.get_monthly_schedule(202601).unwrap().events, vec![year_end]);
+        assert_eq!(engine.count_races(&year_end_id).unwrap(), 1);
+        assert_eq!(engine.count_races(&kiryu_id).unwrap(), 0);
+        assert!(engine.get_monthly_schedule(202509).unwrap().events.is_empty());
+        assert!(engine.get_events_on_date("2025-12-21").unwrap().is_empty());
+        // 大会データとインデックスも稼働中のストアに残らない
+        assert!(engine.get_race_result(&kiryu_id, 1).is_err());
+        assert!(engine.get_payouts(&kiryu_id, 1).unwrap().is_none());
+        assert!(engine.get_tournament_meta(&kiryu_id, "sponsor").unwrap().is_none());
+        assert!(engine.get_races_for_racer(4444).unwrap().is_empty());
+        assert!(engine.get_events_by_venue(1).unwrap().is_empty());
+        let (start, end) = namespace_scan_range(crate::key::PREFIX_INDEX);
+        let live_index = engine.store_mut().scan_keys(&start, &end).unwrap();
+        assert!(live_index.iter().all(|key| key.parse::<Key>().unwrap().tournament_id() == Some(year_end_id.as_str())));
+        assert!(engine.check_indexes().unwrap().is_consistent());
+
+        // 12月を復元すると桐生の大会と大会データ・インデックスが戻る
+        assert_eq!(engine.restore_month(202512, &mut archive).unwrap(), 1);
+        assert_eq!(engine.get_monthly_schedule(202512).unwrap().events.len(), 2);
+        assert_eq!(engine.count_races(&kiryu_id).unwrap(), 2);
+        assert_eq!(engine.get_events_on_date("2025-12-21").unwrap(), vec![kiryu.clone()]);
+        assert!(engine.get_race_result(&kiryu_id, 1).is_ok());
+        assert!(engine.get_payouts(&kiryu_id, 1).unwrap().is_some());
+        assert_eq!(engine.get_tournament_meta(&kiryu_id, "sponsor").unwrap().as_deref(), Some("桐生市"));
+        assert_eq!(engine.get_races_for_racer(4444).unwrap(), vec![(kiryu_id.clone(), 1)]);
+        assert_eq!(engine.get_events_by_venue(1).unwrap(), vec![(202512, kiryu)]);
+        assert!(engine.get_monthly_schedule(202509).unwrap().events.is_empty());
+        assert!(engine.check_indexes().unwrap().is_consistent());
+    }
+
+    #[test]
+    fn test_archive_months_before_rejects_conflicting_archive() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let tournament_id = event_tournament_id(&sample_data().events[0]);
+        engine.put_race_data(&tournament_id, 1, &"race1").unwrap();
+
+        // 別の値を持つアーカイブには書き込まず、稼働中のストアもそのまま
+        let mut archive = MemoryStore::new();
+        archive.put(crate::key::tournament_key(&tournament_id, 1), "other".to_string()).unwrap();
+        let error = engine.archive_months_before(202601, &mut archive).unwrap_err();
+        assert!(matches!(error, crate::StoreError::KeyExists { .. }));
+        assert_eq!(archive.keys().unwrap().len(), 1);
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), sample_data().events.len());
+        assert_eq!(engine.count_races(&tournament_id).unwrap(), 1);
+
+        // 同じ値が既にあるアーカイブ (やり直し) には書き込める
+        let mut archive = MemoryStore::new();
+        engine.store_mut().scan("\u{1}", "\u{10FFFF}").unwrap().into_iter().for_each(|(key, value)| archive.put(key, value).unwrap());
+        let report = engine.archive_months_before(202601, &mut archive).unwrap();
+        assert_eq!(report.tournaments.len(), sample_data().events.len());
+        assert!(engine.get_monthly_schedule(202509).unwrap().events.is_empty());
+    }
+
+    #[test]
+    fn test_export_import_json_round_trip() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        let year_end_id = engine.register_tournament(&year_end).unwrap();
+        engine.put_race_data(&year_end_id, 1, &"race1").unwrap();
+        engine.put_race_data(&year_end_id, 2, &vec![1u32, 2, 3]).unwrap();
+
+        let mut buffer = Vec::new();
+        let before = engine.metrics();
+        let stats = engine.export_json(&mut buffer).unwrap();
+        assert_eq!(stats, ExportStats { months: 3, events: sample_data().events.len() + 2, races: 2 });
+        // 月別ビューと大会データの2回の範囲スキャンだけで読む
+        let after = engine.metrics();
+        assert_eq!((after.scans - before.scans, after.gets - before.gets), (2, 0));
+        // イベントは人が読める形で出力される
+        let json = String::from_utf8(buffer.clone()).unwrap();
+        assert!(json.contains("開設７１周年記念トーキョー・ベイ・カップ"));
+
+        let mut restored = BoatRaceEngine::new(MemoryStore::new());
+        let imported = restored.import_json(buffer.as_slice(), ConflictPolicy::Error).unwrap();
+        assert_eq!(imported.months, 3);
+        assert_eq!(imported.events.added.len(), stats.events);
+        assert_eq!(imported.races, 2);
+        for year_month in [202509, 202512, 202601] {
+            assert_eq!(
+                restored.get_monthly_schedule(year_month).unwrap(),
+                engine.get_monthly_schedule(year_month).unwrap()
+            );
+        }
+        let race: Vec<u32> = restored.get_race_data(&year_end_id, 2).unwrap();
+        assert_eq!(race, vec![1, 2, 3]);
+        assert_eq!(
+            restored.get_events_on_date("2026-01-03").unwrap(),
+            engine.get_events_on_date("2026-01-03").unwrap()
+        );
+
+        // 同じ内容の再取り込みは変更なし
+        let again = restored.import_json(buffer.as_slice(), ConflictPolicy::Error).unwrap();
+        assert!(again.events.added.is_empty());
+        assert_eq!(again.races, 0);
+    }
+
+    #[test]
+    fn test_month_csv_round_trip() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let mut schedule = sample_data();
+        schedule.events[0].event_name = "Cup, \"Special\" Edition".to_string();
+        engine.put_monthly_schedule(&schedule).unwrap();
+
+        let mut buffer = Vec::new();
+        let options = CsvOptions { bom: true };
+        let written = engine.export_month_csv_with(202509, &mut buffer, options).unwrap();
+        assert_eq!(written, schedule.events.len());
+        let csv = String::from_utf8(buffer.clone()).unwrap();
+        assert!(csv.starts_with("\u{feff}venue_id,venue_name,event_name,grade,start_date,duration_days,end_date\r\n"));
+        assert!(csv.contains("\"Cup, \"\"Special\"\" Edition\""));
+        // 2025-09-10 から7日間の最終日
+        assert!(csv.contains(",2025-09-10,7,2025-09-16\r\n"));
+
+        let mut restored = BoatRaceEngine::new(MemoryStore::new());
+        let report = restored.import_month_csv(202509, buffer.as_slice()).unwrap();
+        assert_eq!(report.added.len(), schedule.events.len());
+        assert_eq!(
+            restored.get_monthly_schedule(202509).unwrap(),
+            engine.get_monthly_schedule(202509).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_year_csv_and_import_errors() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let mut buffer = Vec::new();
+        assert_eq!(engine.export_year_csv(2025, &mut buffer).unwrap(), sample_data().events.len());
+        let csv = String::from_utf8(buffer).unwrap();
+        assert!(csv.starts_with("year_month,venue_id,"));
+        assert!(csv.lines().skip(1).all(|line| line.starts_with("2025-09,")));
+
+        let header = "venue_id,venue_name,event_name,grade,start_date,duration_days\n";
+        let bad_number = format!("{}4,平和島,Cup,G1,2025-09-10,7\nx,桐生,Cup,G1,2025-09-11,3\n", header);
+        match engine.import_month_csv(202509, bad_number.as_bytes()) {
+            Err(crate::StoreError::ValidationError { field, message }) => {
+                assert_eq!(field, "venue_id");
+                assert!(message.starts_with("row 3:"), "{}", message);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let bad_date = format!("{}4,平和島,Cup,G1,2025-09-31,7\n", header);
+        match engine.import_month_csv(202509, bad_date.as_bytes()) {
+            Err(crate::StoreError::ValidationError { field, message }) => {
+                assert_eq!(field, "start_date");
+                assert!(message.starts_with("row 2:"), "{}", message);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let bad_grade = format!("{}4,平和島,Cup,G9,2025-09-10,7\n", header);
+        match engine.import_month_csv(202509, bad_grade.as_bytes()) {
+            Err(crate::StoreError::ValidationError { field, message }) => {
+                assert_eq!(field, "grade");
+                assert!(message.starts_with("row 2:"), "{}", message);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(engine.import_month_csv(202509, "venue_id,grade\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_export_ics() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let mut schedule = sample_data();
+        schedule.events[0].event_name = format!("{}; 準優勝戦, 優勝戦", schedule.events[0].event_name.repeat(3));
+        engine.put_monthly_schedule(&schedule).unwrap();
+
+        let mut buffer = Vec::new();
+        assert_eq!(engine.export_ics(202509, &mut buffer).unwrap(), schedule.events.len());
+        let ics = String::from_utf8(buffer).unwrap();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), schedule.events.len());
+
+        // 75 オクテットで折り返され、継続行は空白で始まる
+        let lines: Vec<&str> = ics.split("\r\n").collect();
+        assert!(lines.iter().all(|line| line.len() <= 75));
+        assert!(lines.iter().any(|line| line.starts_with(' ')));
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains("SUMMARY:G1 開設７１周年記念トーキョー・ベイ・カップ\r\n"));
+        assert!(unfolded.contains("\\; 準優勝戦\\, 優勝戦\r\n"));
+        assert!(unfolded.contains("LOCATION:平和島\r\n"));
+        assert!(unfolded.contains("DTSTART;VALUE=DATE:20250910\r\nDTEND;VALUE=DATE:20250917\r\n"));
+
+        // UID は再出力しても変わらない
+        let uids = |ics: &str| -> Vec<String> {
+            ics.replace("\r\n ", "").lines().filter(|line| line.starts_with("UID:")).map(str::to_string).collect()
+        };
+        let mut again = Vec::new();
+        engine.export_ics(202509, &mut again).unwrap();
+        assert_eq!(uids(&ics), uids(&String::from_utf8(again).unwrap()));
+        assert!(uids(&ics).iter().all(|uid| uid.ends_with("-202509@norimaki-db")));
+    }
+
+    #[test]
+    fn test_import_official_schedule() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let schedule = engine.import_official_schedule(include_str!("../testdata/official_schedule.json")).unwrap();
+        assert_eq!(schedule, sample_data());
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 3);
+
+        let malformed = include_str!("../testdata/official_schedule_malformed.json");
+        assert!(engine.import_official_schedule(malformed).is_err());
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let tournament_id = event_tournament_id(&sample_data().events[1]);
+        engine.put_numbered_race(&tournament_id, "2025-09-12", 7, &"race7").unwrap();
+
+        let key_count = engine.store_mut().keys().unwrap().len();
+        let render = |options: DumpOptions| {
+            let mut buffer = Vec::new();
+            engine.dump(&mut buffer, options).unwrap();
+            String::from_utf8(buffer).unwrap()
+        };
+
+        let all = render(DumpOptions::default());
+        assert_eq!(all, render(DumpOptions::default()));
+        let lines: Vec<&str> = all.lines().collect();
+        assert_eq!(lines.len(), key_count);
+        assert!(!all.contains('\0'));
+        assert!(all.contains(&format!(
+            "monthly year_month=2025-09 tournament_id={} [",
+            tournament_id
+        )));
+        assert!(all.contains("G1 平和島 開設７１周年記念トーキョー・ベイ・カップ 2025-09-10 (7 days)"));
+        assert!(all.contains(&format!("race tournament_id={} time=2025-09-11T15:07:00.000Z [", tournament_id)));
+
+        let monthly = render(DumpOptions { prefix: Some("M".to_string()), limit: Some(2) });
+        assert_eq!(monthly.lines().count(), 2);
+        assert!(monthly.lines().all(|line| line.starts_with("monthly ")));
+        assert!(lines.iter().any(|line| line.starts_with("daily date=20250910 ")));
+    }
+
+    #[test]
+    fn test_check_integrity_and_repair() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let registered = event_tournament_id(&sample_data().events[0]);
+        engine.put_race_data(&registered, 1, &"race1").unwrap();
+        engine.put_race_data("orphan_cup", 1, &"race1").unwrap();
+        engine.put_race_data("orphan_cup", 2, &"race2").unwrap();
+        let corrupt = crate::monthly_key(202510, "broken_cup");
+        engine.store_mut().put(corrupt.clone(), "not bincode".to_string()).unwrap();
+        engine.store_mut().put("Mbad".to_string(), "x".to_string()).unwrap();
+
+        let report = engine.check_integrity().unwrap();
+        assert!(!report.is_clean());
+        assert_eq!(report.orphaned_tournaments, vec!["orphan_cup".to_string()]);
+        assert_eq!(report.orphaned_races.len(), 2);
+        assert_eq!(report.corrupt_entries, vec![corrupt.clone()]);
+        assert_eq!(report.malformed_keys, vec!["Mbad".to_string()]);
+        // チェックだけでは何も消えない
+        assert_eq!(engine.count_races("orphan_cup").unwrap(), 2);
+
+        let options = RepairOptions { delete_orphans: true, ..Default::default() };
+        assert_eq!(engine.repair(options).unwrap(), report);
+        assert_eq!(engine.count_races("orphan_cup").unwrap(), 0);
+        assert_eq!(engine.count_races(&registered).unwrap(), 1);
+        assert!(engine.store_mut().get(&corrupt).unwrap().is_some());
+
+        let options = RepairOptions { delete_corrupt: true, delete_malformed: true, ..Default::default() };
+        engine.repair(options).unwrap();
+        assert!(engine.check_integrity().unwrap().is_clean());
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), sample_data().events.len());
+    }
+
+    /// 予約済みのプレフィックスで始まるだけのキーを集計に含めず、別に報告すること
+    #[test]
+    fn test_reserved_namespaces_are_classified_strictly() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let registered = event_tournament_id(&sample_data().events[0]);
+        engine.put_race_data(&registered, 1, &"race1").unwrap();
+        engine.store_mut().put("Manual_note".to_string(), "x".to_string()).unwrap();
+        engine.store_mut().put("Tokyo memo".to_string(), "x".to_string()).unwrap();
+        engine.store_mut().put("zzz".to_string(), "x".to_string()).unwrap();
+        engine.put_raw_user_key("Manual_note", "kept").unwrap();
+
+        let stats = engine.get_detailed_statistics().unwrap();
+        assert_eq!((stats.monthly_entries, stats.tournaments, stats.races), (3, 3, 1));
+        assert_eq!(stats.unclassified_keys, 3);
+        assert_eq!(stats.corrupted_entries, 0);
+        assert_eq!(engine.rebuild_statistics().unwrap(), StatisticsCounters { monthly_entries: 3, races: 1, tournaments: 3 });
+
+        let report = engine.check_integrity().unwrap();
+        assert_eq!(report.malformed_keys, vec!["Manual_note".to_string(), "Tokyo memo".to_string()]);
+        assert_eq!(report.unclassified_keys, vec!["zzz".to_string()]);
+
+        let options = RepairOptions { delete_malformed: true, ..Default::default() };
+        engine.repair(options).unwrap();
+        let report = engine.check_integrity().unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.unclassified_keys, vec!["zzz".to_string()]);
+        assert_eq!(engine.get_raw_user_key("Manual_note").unwrap(), Some("kept".to_string()));
+        assert_eq!(engine.store().get("UManual_note").unwrap(), Some("kept".to_string()));
+
+        // ユーザー領域のキーは統計カウンタを更新しない
+        assert_eq!(engine.get_statistics_fast().unwrap().monthly_entries, 3);
+        assert!(engine.delete_raw_user_key("Manual_note").unwrap());
+        assert!(!engine.delete_raw_user_key("Manual_note").unwrap());
+        assert_eq!(engine.get_raw_user_key("Manual_note").unwrap(), None);
+    }
+
+    #[test]
+    fn test_clear_namespace_and_managed_data() {
+        let options = IndexOptions { by_venue: true, by_name: true };
+        let mut engine = BoatRaceEngine::with_indexing(MemoryStore::new(), options);
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let registered = event_tournament_id(&sample_data().events[0]);
+        engine.put_race_data(&registered, 1, &"race1").unwrap();
+        engine.set_tournament_meta(&registered, "sponsor", "ACME").unwrap();
+        engine.rebuild_statistics().unwrap();
+        engine.put_raw_user_key("note", "kept").unwrap();
+        for foreign in ["Manual_note", "zzz", "\x01other"] {
+            engine.store_mut().put(foreign.to_string(), "x".to_string()).unwrap();
+        }
+        let keys = |engine: &BoatRaceEngine<MemoryStore>| engine.store().keys().unwrap().into_iter().collect::<BTreeSet<_>>();
+        let before = keys(&engine);
+
+        let report = engine.clear_namespace(Namespace::Index).unwrap();
+        let indexes: BTreeSet<String> = before.iter().filter(|key| key.starts_with('I')).cloned().collect();
+        assert!(!indexes.is_empty());
+        assert_eq!(report.deleted.into_iter().collect::<Vec<_>>(), vec![(Namespace::Index, indexes.len())]);
+        assert_eq!(keys(&engine), before.difference(&indexes).cloned().collect());
+        assert_eq!(engine.clear_namespace(Namespace::Index).unwrap().total(), 0);
+
+        // 月別ビューを消すと統計カウンタを再集計する
+        engine.clear_namespace(Namespace::Monthly).unwrap();
+        assert_eq!(engine.get_statistics_fast().unwrap(), StatisticsCounters { monthly_entries: 0, races: 1, tournaments: 0 });
+
+        let report = engine.clear_managed_data().unwrap();
+        assert_eq!(report.deleted.get(&Namespace::Tournament), Some(&1));
+        assert_eq!(report.deleted.get(&Namespace::TournamentMeta), Some(&1));
+        assert_eq!(report.deleted.get(&Namespace::Daily), Some(&sample_data().events.iter().map(|event| event.duration_days as usize).sum()));
+        assert!(report.deleted.contains_key(&Namespace::Meta));
+        assert!(!report.deleted.contains_key(&Namespace::User));
+        let expected: BTreeSet<String> = ["Unote", "Manual_note", "zzz", "\x01other"].into_iter().map(str::to_string).collect();
+        assert_eq!(keys(&engine), expected);
+        assert_eq!(engine.get_raw_user_key("note").unwrap(), Some("kept".to_string()));
+        assert_eq!(engine.get_statistics_fast().unwrap(), StatisticsCounters::default());
+
+        assert!(matches!(
+            engine.clear_namespace(Namespace::User),
+            Err(StoreError::ValidationError { field, .. }) if field == "namespace"
+        ));
+    }
+
+    #[test]
+    fn test_get_detailed_statistics_counts_corrupted_entries() {
+        let mut store = MemoryStore::new();
+        store.put(crate::monthly_key(202509, "broken"), "not base64!".to_string()).unwrap();
+        let mut engine = BoatRaceEngine::new(store);
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+
+        let stats = engine.get_detailed_statistics().unwrap();
+        assert_eq!(stats.monthly_entries, 4);
+        assert_eq!(stats.tournaments, 4);
+        assert_eq!(stats.corrupted_entries, 1);
+        assert_eq!(stats.events_per_grade.values().sum::<usize>(), 3);
+    }
+
+    /// カウンタと全件走査による統計が一致するか検証
+    fn assert_counters_match<K: KeyValueStore>(engine: &mut BoatRaceEngine<K>) {
+        let counters = engine.get_statistics_fast().unwrap();
+        let (monthly_entries, tournaments, races) = engine.get_statistics().unwrap();
+        assert_eq!(counters, StatisticsCounters { monthly_entries, races, tournaments });
+    }
+
+    #[test]
+    fn test_statistics_counters_track_mutations() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+        assert_eq!(engine.get_statistics_fast().unwrap(), StatisticsCounters::default());
+
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert_counters_match(&mut engine);
+
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::SG,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&year_end).unwrap();
+        // 同じ内容の再登録ではカウンタは変わらない
+        engine.register_tournament_to_months(&year_end).unwrap();
+        assert_counters_match(&mut engine);
+
+        for timestamp in 1..=5 {
+            engine.put_race_data("tokyo_bay_cup", timestamp, &"race").unwrap();
+        }
+        engine.put_race_data("tokyo_bay_cup", 3, &"overwrite").unwrap();
+        engine.put_race_data("other_cup", 1, &"race").unwrap();
+        assert_counters_match(&mut engine);
+
+        engine.delete_race_data("tokyo_bay_cup", 1).unwrap();
+        engine.delete_races_before("tokyo_bay_cup", 3).unwrap();
+        assert_counters_match(&mut engine);
+
+        let tournament_id = generate_tournament_id("平和島", "年末年始杯");
+        engine.delete_race_event(202512, &tournament_id).unwrap();
+        assert_counters_match(&mut engine);
+
+        let event = &sample_data().events[0];
+        let renamed_from = generate_tournament_id(&event.venue_name, &event.event_name);
+        let update = RaceEventUpdate {
+            event_name: Some("改名杯".to_string()),
+            ..Default::default()
+        };
+        engine.update_race_event(202509, &renamed_from, update).unwrap();
+        assert_counters_match(&mut engine);
+
+        engine.delete_monthly_schedule(202509).unwrap();
+        engine.delete_tournament(&tournament_id).unwrap();
+        engine.delete_tournament("other_cup").unwrap();
+        assert_counters_match(&mut engine);
+        assert_eq!(engine.get_statistics_fast().unwrap(), StatisticsCounters { monthly_entries: 0, races: 3, tournaments: 0 });
+    }
+
+    #[test]
+    fn test_statistics_counters_do_not_scan_monthly_view_per_write() {
+        fn schedule(month: u32) -> MonthlySchedule {
+            MonthlySchedule {
+                year_month: format!("2025-{:02}", month),
+                events: (1..=3)
+                    .map(|venue_id| RaceEvent {
+                        venue_id,
+                        venue_name: format!("Venue {}", venue_id),
+                        event_name: format!("Cup {}", month),
+                        grade: Grade::Ippan,
+                        start_date: format!("2025-{:02}-10", month),
+                        duration_days: 3,
+                        notes: None,
+                    })
+                    .collect(),
+            }
+        }
+        fn scans_for(engine: &mut BoatRaceEngine<MemoryStore>, month: u32) -> u64 {
+            let before = engine.metrics().scans;
+            engine.put_monthly_schedule(&schedule(month)).unwrap();
+            engine.metrics().scans - before
+        }
+
+        // カウンタを維持しても、書き込みごとのスキャン回数はカウンタなしの場合と変わらない
+        let mut counted = BoatRaceEngine::new(MemoryStore::new());
+        counted.rebuild_statistics().unwrap();
+        let mut plain = BoatRaceEngine::new(MemoryStore::new());
+        for month in 1..=6 {
+            assert_eq!(scans_for(&mut counted, month), scans_for(&mut plain, month), "month {}", month);
+        }
+        assert_counters_match(&mut counted);
+
+        // 再オープン後は最初の書き込みで1度だけ月別ビューを走査する
+        let mut reopened = BoatRaceEngine::new(counted.store().clone());
+        let mut plain = BoatRaceEngine::new(plain.store().clone());
+        assert_eq!(scans_for(&mut reopened, 7), scans_for(&mut plain, 7) + 1);
+        for month in 8..=10 {
+            assert_eq!(scans_for(&mut reopened, month), scans_for(&mut plain, month), "month {}", month);
+        }
+        assert_counters_match(&mut reopened);
+    }
+
+    #[test]
+    fn test_statistics_counters_persist_and_rebuild() {
+        let store = MemoryStore::new();
+        let mut engine = BoatRaceEngine::new(store);
+        engine.rebuild_statistics().unwrap();
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1, &"race").unwrap();
+        let counters = engine.get_statistics_fast().unwrap();
+
+        // 再オープン後もカウンタを引き継ぐ
+        let mut store = engine.store().clone();
+        let mut reopened = BoatRaceEngine::new(store.clone());
+        assert_eq!(reopened.get_statistics_fast().unwrap(), counters);
+
+        // ストアへの直接書き込みはカウンタに反映されないので再集計する
+        store.put(crate::tournament_key("tokyo_bay_cup", 2), serialize_to_string(&"race").unwrap()).unwrap();
+        let mut reopened = BoatRaceEngine::new(store);
+        assert_eq!(reopened.get_statistics_fast().unwrap().races, 1);
+        assert_eq!(reopened.rebuild_statistics().unwrap().races, 2);
+        assert_counters_match(&mut reopened);
+    }
+
+    /// 検索結果を比較可能な形に変換
+    fn event_ids(events: &[(u32, RaceEvent)]) -> Vec<(u32, String)> {
+        events
+            .iter()
+            .map(|(year_month, event)| (*year_month, generate_tournament_id(&event.venue_name, &event.event_name)))
+            .collect()
+    }
+
+    #[test]
+    fn test_venue_index_matches_full_scan() {
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::SG,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        let mut plain = BoatRaceEngine::new(MemoryStore::new());
+        let mut indexed = BoatRaceEngine::with_indexing(MemoryStore::new(), IndexOptions { by_venue: true, ..Default::default() });
+        for engine in [&mut plain, &mut indexed] {
+            engine.put_monthly_schedule(&sample_data()).unwrap();
+            engine.register_tournament_to_months(&year_end).unwrap();
+        }
+
+        for venue_id in [1, 4, 12, 24] {
+            let expected = plain.get_events_by_venue(venue_id).unwrap();
+            let actual = indexed.get_events_by_venue(venue_id).unwrap();
+            assert_eq!(event_ids(&actual), event_ids(&expected));
+        }
+        assert_eq!(indexed.get_events_by_venue(4).unwrap().len(), 3);
+        assert!(indexed.check_indexes().unwrap().is_consistent());
+
+        // 会場変更と削除でインデックスが追従する
+        let tournament_id = generate_tournament_id("平和島", "年末年始杯");
+        let update = RaceEventUpdate {
+            venue_id: Some(24),
+            ..Default::default()
+        };
+        indexed.update_race_event(202512, &tournament_id, update).unwrap();
+        indexed.delete_race_event(202601, &tournament_id).unwrap();
+        assert_eq!(indexed.get_events_by_venue(4).unwrap().len(), 1);
+        assert_eq!(event_ids(&indexed.get_events_by_venue(24).unwrap()), vec![(202512, tournament_id.clone())]);
+        assert!(indexed.check_indexes().unwrap().is_consistent());
+
+        indexed.delete_tournament(&tournament_id).unwrap();
+        indexed.delete_monthly_schedule(202509).unwrap();
+        assert!(indexed.get_events_by_venue(24).unwrap().is_empty());
+        assert!(indexed.check_indexes().unwrap().is_consistent());
+        assert!(indexed.store().keys().unwrap().iter().all(|key| !key.starts_with('I')));
+    }
+
+    #[test]
+    fn test_rebuild_indexes() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+
+        // インデックスなしで作成した既存データベースを開き直す
+        let store = engine.store().clone();
+        let mut indexed = BoatRaceEngine::with_indexing(store, IndexOptions { by_venue: true, ..Default::default() });
+        let check = indexed.check_indexes().unwrap();
+        assert_eq!(check.missing.len(), 3);
+        assert!(indexed.get_events_by_venue(4).unwrap().is_empty());
+
+        assert_eq!(indexed.rebuild_indexes().unwrap(), 3);
+        assert!(indexed.check_indexes().unwrap().is_consistent());
+        assert_eq!(
+            event_ids(&indexed.get_events_by_venue(4).unwrap()),
+            event_ids(&engine.get_events_by_venue(4).unwrap())
+        );
+
+        // インデックスを無効にして開くと残ったキーは stale
+        let mut plain = BoatRaceEngine::new(indexed.store().clone());
+        assert_eq!(plain.check_indexes().unwrap().stale.len(), 3);
+        assert_eq!(plain.rebuild_indexes().unwrap(), 0);
+        assert!(plain.check_indexes().unwrap().is_consistent());
+    }
+
+    #[test]
+    fn test_search_events_index_matches_full_scan() {
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "Year End Cup".to_string(),
+            grade: Grade::SG,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        let mut plain = BoatRaceEngine::new(MemoryStore::new());
+        let mut indexed = BoatRaceEngine::with_indexing(MemoryStore::new(), IndexOptions { by_name: true, ..Default::default() });
+        for engine in [&mut plain, &mut indexed] {
+            engine.put_monthly_schedule(&sample_data()).unwrap();
+            engine.register_tournament_to_months(&year_end).unwrap();
+        }
+
+        let queries = ["高松宮", "平和島", "カップ", "year end", "END", "Cup", "d c", "存在しない大会", "宮", ""];
+        for query in queries {
+            for year_month in [None, Some(202509), Some(202601)] {
+                let expected = plain.search_events(query, year_month).unwrap();
+                let actual = indexed.search_events(query, year_month).unwrap();
+                assert_eq!(event_ids(&actual), event_ids(&expected), "query {:?} in {:?}", query, year_month);
+            }
+        }
+
+        assert_eq!(indexed.search_events("高松宮", None).unwrap().len(), 1);
+        // 大文字小文字を区別しない ASCII と月跨ぎ大会
+        assert_eq!(indexed.search_events("YEAR END", None).unwrap().len(), 2);
+        assert_eq!(indexed.search_events("year end", Some(202601)).unwrap().len(), 1);
+        assert!(indexed.search_events("x", Some(202513)).is_err());
+        assert!(indexed.check_indexes().unwrap().is_consistent());
+
+        // 改名すると旧名では見つからなくなる
+        let tournament_id = generate_tournament_id("平和島", "Year End Cup");
+        let update = RaceEventUpdate {
+            event_name: Some("New Year Cup".to_string()),
+            ..Default::default()
+        };
+        indexed.update_race_event(202512, &tournament_id, update).unwrap();
+        assert_eq!(indexed.search_events("year end", None).unwrap().len(), 1);
+        assert_eq!(indexed.search_events("new year", None).unwrap().len(), 1);
+        assert!(indexed.check_indexes().unwrap().is_consistent());
+    }
+
+    #[test]
+    fn test_name_tokens() {
+        let tokens: Vec<String> = name_tokens("AbC杯").into_iter().collect();
+        assert_eq!(tokens, vec!["ab", "bc", "c杯"]);
+        assert!(name_tokens("宮").is_empty());
+    }
+
+    #[test]
+    fn test_participant_index() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_race_data("tokyo_bay_cup", 2000, &"race2").unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1000, &"race1").unwrap();
+        engine.put_race_data("kiryu_cup", 1500, &"race").unwrap();
+        engine.index_race_participants("tokyo_bay_cup", 2000, &[4444, 3333]).unwrap();
+        engine.index_race_participants("tokyo_bay_cup", 1000, &[4444]).unwrap();
+        engine.index_race_participants("kiryu_cup", 1500, &[4444, 4444]).unwrap();
+
+        assert_eq!(
+            engine.get_races_for_racer(4444).unwrap(),
+            vec![
+                ("tokyo_bay_cup".to_string(), 1000),
+                ("kiryu_cup".to_string(), 1500),
+                ("tokyo_bay_cup".to_string(), 2000),
+            ]
+        );
+        assert_eq!(engine.get_races_for_racer(3333).unwrap(), vec![("tokyo_bay_cup".to_string(), 2000)]);
+        assert!(engine.get_races_for_racer(1).unwrap().is_empty());
+        assert!(engine.index_race_participants("tokyo_bay_cup", 1000, &[0]).is_err());
+
+        // 再登録で出走者を置き換える
+        engine.index_race_participants("tokyo_bay_cup", 2000, &[3333]).unwrap();
+        assert_eq!(engine.get_races_for_racer(4444).unwrap().len(), 2);
+
+        // レースを削除するとインデックスも消える
+        engine.delete_race_data("tokyo_bay_cup", 1000).unwrap();
+        assert_eq!(engine.get_races_for_racer(4444).unwrap(), vec![("kiryu_cup".to_string(), 1500)]);
+        engine.delete_races_before("tokyo_bay_cup", 3000).unwrap();
+        assert!(engine.get_races_for_racer(3333).unwrap().is_empty());
+        engine.delete_tournament("kiryu_cup").unwrap();
+        assert!(engine.get_races_for_racer(4444).unwrap().is_empty());
+        // 残るのは最終更新時刻のみ
+        assert!(engine.store().keys().unwrap().iter().all(|key| key.starts_with('\x01')));
+    }
+
+    #[test]
+    fn test_put_race_result_indexes_participants() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let mut result = RaceResult {
+            tournament_id: "tokyo_bay_cup".to_string(),
+            timestamp: 1694524800000,
+            race_number: 1,
+            finishing_order: vec![(1, 4444), (2, 3333), (3, 0)],
+            winning_technique: "逃げ".to_string(),
+            decided: false,
+        };
+        engine.put_race_result(&result).unwrap();
+        assert_eq!(engine.get_races_for_racer(3333).unwrap(), vec![("tokyo_bay_cup".to_string(), 1694524800000)]);
+
+        // 結果の訂正で出走者も更新される
+        result.finishing_order = vec![(1, 4444), (2, 5555)];
+        result.decided = true;
+        engine.put_race_result(&result).unwrap();
+        assert!(engine.get_races_for_racer(3333).unwrap().is_empty());
+        assert_eq!(engine.get_races_for_racer(5555).unwrap().len(), 1);
+
+        // 派生インデックスの再構築では消えない
+        engine.rebuild_indexes().unwrap();
+        assert_eq!(engine.get_races_for_racer(4444).unwrap().len(), 1);
+
+        engine.delete_tournament("tokyo_bay_cup").unwrap();
+        assert!(engine.get_races_for_racer(4444).unwrap().is_empty());
+        assert!(engine.store().keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_put_monthly_schedule_checked() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let report = engine.put_monthly_schedule_checked(&sample_data(), ConflictPolicy::Error).unwrap();
+        assert_eq!(report.added.len(), 3);
+        assert!(report.conflicts.is_empty());
+
+        // 同じ内容の再取り込みは何も変更しない
+        let report = engine.put_monthly_schedule_checked(&sample_data(), ConflictPolicy::Error).unwrap();
+        assert_eq!(report.unchanged.len(), 3);
+        assert!(report.added.is_empty() && report.overwritten.is_empty() && report.conflicts.is_empty());
+
+        let mut changed = sample_data();
+        changed.events[1].start_date = "2025-09-20".to_string();
+        let tournament_id = generate_tournament_id(&changed.events[1].venue_name, &changed.events[1].event_name);
+
+        // Error: 何も書き込まずにエラー
+        match engine.put_monthly_schedule_checked(&changed, ConflictPolicy::Error) {
+            Err(crate::StoreError::Conflict(key)) => assert_eq!(key, crate::monthly_key(202509, &tournament_id)),
+            other => panic!("Expected Conflict, got {:?}", other),
+        }
+        assert_eq!(engine.get_race_event(202509, &tournament_id).unwrap().unwrap().start_date, "2025-09-10");
+
+        // Skip: 食い違いを報告して既存を残す
+        let report = engine.put_monthly_schedule_checked(&changed, ConflictPolicy::Skip).unwrap();
+        assert_eq!(report.unchanged.len(), 2);
+        assert!(report.overwritten.is_empty());
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.tournament_id, tournament_id);
+        let before: RaceEvent = deserialize_from_string(&conflict.before).unwrap();
+        let after: RaceEvent = deserialize_from_string(&conflict.after).unwrap();
+        assert_eq!(before.start_date, "2025-09-10");
+        assert_eq!(after, changed.events[1]);
+        assert_eq!(engine.get_race_event(202509, &tournament_id).unwrap().unwrap().start_date, "2025-09-10");
+
+        // Overwrite: 上書きし、旧開催日の日別ビューを消す
+        let report = engine.put_monthly_schedule_checked(&changed, ConflictPolicy::Overwrite).unwrap();
+        assert_eq!(report.overwritten, vec![tournament_id.clone()]);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(engine.get_race_event(202509, &tournament_id).unwrap().unwrap(), changed.events[1]);
+        assert!(engine.get_events_on_date("2025-09-10").unwrap().iter().all(|e| e.venue_id != 4));
+        assert!(engine.get_events_on_date("2025-09-26").unwrap().iter().any(|e| e.venue_id == 4));
+    }
+
+    #[test]
+    fn test_sync_monthly_schedule() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let cancelled = &sample_data().events[0];
+        let cancelled_id = generate_tournament_id(&cancelled.venue_name, &cancelled.event_name);
+        engine.put_race_data(&cancelled_id, 1, &"race").unwrap();
+
+        let mut latest = sample_data();
+        latest.events.remove(0);
+        latest.events[0].grade = Grade::SG;
+        latest.events.push(RaceEvent {
+            venue_id: 24,
+            venue_name: "大村".to_string(),
+            event_name: "新設杯".to_string(),
+            grade: Grade::Ippan,
+            start_date: "2025-09-20".to_string(),
+            duration_days: 3,
+            notes: None,
+        });
+
+        let report = engine.sync_monthly_schedule(&latest).unwrap();
+        assert_eq!(report.added, vec![generate_tournament_id("大村", "新設杯")]);
+        assert_eq!(report.updated.len(), 1);
+        assert_eq!(report.unchanged.len(), 1);
+        assert_eq!(report.removed, vec![cancelled_id.clone()]);
+        assert_eq!(report.purged_races, 0);
+
+        let schedule = engine.get_monthly_schedule(202509).unwrap();
+        assert_eq!(schedule.events.len(), 3);
+        assert!(schedule.events.iter().all(|e| e.venue_id != 1));
+        assert!(engine.get_events_on_date("2025-09-11").unwrap().iter().all(|e| e.venue_id != 1));
+        // 既定ではレースデータは残す
+        assert_eq!(engine.count_races(&cancelled_id).unwrap(), 1);
+
+        // 再同期は差分なし
+        let report = engine.sync_monthly_schedule(&latest).unwrap();
+        assert_eq!(report.unchanged.len(), 3);
+        assert!(report.added.is_empty() && report.updated.is_empty() && report.removed.is_empty());
+    }
+
+    #[test]
+    fn test_sync_monthly_schedule_purges_orphaned_races() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let year_end = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "年末年始杯".to_string(),
+            grade: Grade::SG,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 10,
+            notes: None,
+        };
+        engine.register_tournament_to_months(&year_end).unwrap();
+        let tournament_id = generate_tournament_id("平和島", "年末年始杯");
+        engine.put_race_data(&tournament_id, 1, &"race").unwrap();
+
+        let options = SyncOptions { purge_orphaned_races: true };
+        let empty = |year_month: &str| MonthlySchedule {
+            year_month: year_month.to_string(),
+            events: Vec::new(),
+        };
+
+        // 1月に登録が残るのでレースデータは消さない
+        let report = engine.sync_monthly_schedule_with(&empty("2025-12"), options).unwrap();
+        assert_eq!(report.removed, vec![tournament_id.clone()]);
+        assert_eq!(report.purged_races, 0);
+        assert_eq!(engine.count_races(&tournament_id).unwrap(), 1);
+
+        let report = engine.sync_monthly_schedule_with(&empty("2026-01"), options).unwrap();
+        assert_eq!(report.purged_races, 1);
+        assert_eq!(engine.count_races(&tournament_id).unwrap(), 0);
+        // 残るのは空の月として取り込んだことを示すマーカーのみ
+        assert_eq!(engine.try_get_monthly_schedule(202601).unwrap(), Some(empty("2026-01")));
+        assert!(engine.store().keys().unwrap().iter().all(|key| key.starts_with('\x01')));
+    }
+
+    #[test]
+    fn test_diff_monthly_schedule() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert!(engine.diff_monthly_schedule(&sample_data()).unwrap().is_empty());
+
+        let original = sample_data();
+        let mut incoming = sample_data();
+        incoming.events.remove(0);
+        incoming.events[0].duration_days = 8;
+        incoming.events[1].event_name = "高松宮記念".to_string();
+
+        let keys_before = engine.store().keys().unwrap().len();
+        let diff = engine.diff_monthly_schedule(&incoming).unwrap();
+        assert_eq!(diff.changed, vec![(original.events[1].clone(), incoming.events[0].clone())]);
+        // 改名は削除と追加の組になる
+        assert_eq!(diff.added, vec![incoming.events[1].clone()]);
+        assert_eq!(diff.removed.len(), 2);
+        assert!(diff.removed.contains(&original.events[0]));
+        assert!(diff.removed.contains(&original.events[2]));
+
+        // 読み取りのみで何も書き込まない
+        assert_eq!(engine.store().keys().unwrap().len(), keys_before);
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 3);
+
+        let empty_month = MonthlySchedule {
+            year_month: "2025-10".to_string(),
+            events: sample_data().events,
+        };
+        assert_eq!(engine.diff_monthly_schedule(&empty_month).unwrap().added.len(), 3);
+    }
+
+    /// 検証エラーのフィールド名を取り出す
+    fn validation_field(result: Result<()>) -> String {
+        match result {
+            Err(crate::StoreError::ValidationError { field, .. }) => field,
+            other => panic!("Expected ValidationError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_event_validation() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let schedule_with = |change: &dyn Fn(&mut RaceEvent)| {
+            let mut schedule = sample_data();
+            change(&mut schedule.events[1]);
+            schedule
+        };
+
+        let bad_date = schedule_with(&|e| e.start_date = "2025-13-45".to_string());
+        assert_eq!(validation_field(engine.put_monthly_schedule(&bad_date)), "start_date");
+
+        let zero_days = schedule_with(&|e| e.duration_days = 0);
+        assert_eq!(validation_field(engine.put_monthly_schedule(&zero_days)), "duration_days");
+        let long = schedule_with(&|e| e.duration_days = 31);
+        assert_eq!(validation_field(engine.put_monthly_schedule(&long)), "duration_days");
+
+        let bad_venue = schedule_with(&|e| e.venue_id = 25);
+        assert_eq!(validation_field(engine.put_monthly_schedule(&bad_venue)), "venue_id");
+        let blank_name = schedule_with(&|e| e.event_name = " ".to_string());
+        assert_eq!(validation_field(engine.put_monthly_schedule(&blank_name)), "event_name");
+        let blank_venue = schedule_with(&|e| e.venue_name = String::new());
+        assert_eq!(validation_field(engine.put_monthly_schedule(&blank_venue)), "venue_name");
+
+        let other_month = schedule_with(&|e| e.start_date = "2025-11-10".to_string());
+        match engine.put_monthly_schedule(&other_month) {
+            Err(crate::StoreError::ValidationError { field, message }) => {
+                assert_eq!(field, "start_date");
+                assert!(message.contains("トーキョー・ベイ・カップ") && message.contains("2025-09"));
+            }
+            other => panic!("Expected ValidationError, got {:?}", other),
+        }
+
+        // 前月から続くイベントは受け付ける
+        let carried_over = schedule_with(&|e| e.start_date = "2025-08-29".to_string());
+        engine.put_monthly_schedule(&carried_over).unwrap();
+        // 失敗した取り込みは何も書き込んでいない
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 3);
+
+        let zero_year_end = RaceEvent { duration_days: 0, ..sample_data().events[0].clone() };
+        assert_eq!(validation_field(engine.register_tournament_to_months(&zero_year_end).map(|_| ())), "duration_days");
+
+        let tournament_id = generate_tournament_id(&sample_data().events[0].venue_name, &sample_data().events[0].event_name);
+        let update = RaceEventUpdate { venue_id: Some(0), ..Default::default() };
+        assert!(matches!(
+            engine.update_race_event(202509, &tournament_id, update),
+            Err(crate::StoreError::ValidationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_event_validation_opt_out() {
+        let legacy = MonthlySchedule {
+            year_month: "2025-09".to_string(),
+            events: vec![RaceEvent {
+                venue_id: 99,
+                venue_name: "旧会場".to_string(),
+                event_name: "旧大会".to_string(),
+                grade: Grade::Ippan,
+                start_date: "2025-13-45".to_string(),
+                duration_days: 0,
+                notes: None,
+            }],
+        };
+
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_validation(false);
+        engine.put_monthly_schedule(&legacy).unwrap();
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events, legacy.events);
+
+        // venue_id の許容範囲は変更できる
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_venue_id_range(1..=99);
+        let mut schedule = sample_data();
+        schedule.events[0].venue_id = 99;
+        engine.put_monthly_schedule(&schedule).unwrap();
+    }
+
+    #[test]
+    fn test_into_store_round_trip() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        engine.rebuild_statistics().unwrap();
+
+        let mut store = engine.into_store();
+        assert!(store.get(&crate::monthly_key(202509, &generate_tournament_id("平和島", "開設７１周年記念トーキョー・ベイ・カップ"))).unwrap().is_some());
+
+        // ストアを再度渡せばカウンタも引き継がれる
+        store.put(crate::tournament_key("tokyo_bay_cup", 1), serialize_to_string(&"race").unwrap()).unwrap();
+        let mut engine = BoatRaceEngine::new(store);
+        assert_eq!(engine.get_statistics_fast().unwrap().monthly_entries, 3);
+        assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), 1);
+
+        engine.store_mut().clear().unwrap();
+        assert_eq!(engine.rebuild_statistics().unwrap(), StatisticsCounters::default());
+    }
+
+    #[test]
+    fn test_try_get_monthly_schedule() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+
+        // 未取り込みの月
+        assert!(engine.try_get_monthly_schedule(209912).unwrap().is_none());
+        assert!(engine.get_monthly_schedule(209912).unwrap().events.is_empty());
+        assert!(engine.try_get_monthly_schedule(209913).is_err());
+
+        // 空のスケジュールとして取り込んだ月
+        let empty = MonthlySchedule {
+            year_month: "2025-10".to_string(),
+            events: Vec::new(),
+        };
+        engine.put_monthly_schedule(&empty).unwrap();
+        assert_eq!(engine.try_get_monthly_schedule(202510).unwrap(), Some(empty.clone()));
+        // マーカーは月別ビューの集計に影響しない
+        assert_eq!(engine.get_statistics().unwrap(), (0, 0, 0));
+        assert!(engine.get_yearly_schedule(2025).unwrap().iter().all(|s| s.events.is_empty()));
+
+        // イベントのある月
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert_eq!(engine.try_get_monthly_schedule(202509).unwrap().unwrap().events.len(), 3);
+
+        // 空の月にイベントを取り込むとマーカーは消え、月を削除すると未取り込みに戻る
+        let october = MonthlySchedule {
+            year_month: "2025-10".to_string(),
+            events: vec![RaceEvent { start_date: "2025-10-01".to_string(), ..sample_data().events[0].clone() }],
+        };
+        engine.put_monthly_schedule(&october).unwrap();
+        engine.delete_monthly_schedule(202510).unwrap();
+        assert!(engine.try_get_monthly_schedule(202510).unwrap().is_none());
+
+        engine.put_monthly_schedule(&empty).unwrap();
+        engine.delete_monthly_schedule(202510).unwrap();
+        assert!(engine.try_get_monthly_schedule(202510).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dedupe_month() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+
+        // 旧方式の大会IDで同じイベントが登録されている状態を再現
+        let event = sample_data().events[1].clone();
+        let canonical = crate::monthly_key(202509, &generate_tournament_id(&event.venue_name, &event.event_name));
+        let legacy_ids = ["venue_4_legacy", "平和島_legacy"];
+        for legacy_id in legacy_ids {
+            engine.store_mut().put(crate::monthly_key(202509, legacy_id), serialize_to_string(&event).unwrap()).unwrap();
+        }
+        let legacy_daily = crate::daily_key(20250910, legacy_ids[0]);
+        engine.store_mut().put(legacy_daily.clone(), serialize_to_string(&event).unwrap()).unwrap();
+
+        // 取得時は1件にまとめる
+        let schedule = engine.get_monthly_schedule(202509).unwrap();
+        assert_eq!(schedule.events.len(), 3);
+        assert_eq!(schedule.events.iter().filter(|e| **e == event).count(), 1);
+
+        let report = engine.dedupe_month_report(202509).unwrap();
+        assert_eq!(report.removed(), 2);
+        assert_eq!(report.collapsed.len(), 1);
+        let (kept, removed) = &report.collapsed[0];
+        assert_eq!(kept, &canonical);
+        assert_eq!(removed.len(), 2);
+
+        let keys = engine.store().keys().unwrap();
+        assert!(keys.contains(&canonical));
+        assert!(!keys.contains(&legacy_daily));
+        assert!(legacy_ids.iter().all(|id| !keys.contains(&crate::monthly_key(202509, id))));
+        assert_eq!(engine.dedupe_month(202509).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_schedules_between_matches_monthly_schedule() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+
+        // 旧方式の大会IDで重複したイベントと、同じ開始日の別の場のイベント
+        let event = sample_data().events[1].clone();
+        engine.store_mut().put(crate::monthly_key(202509, "venue_4_legacy"), serialize_to_string(&event).unwrap()).unwrap();
+        let same_day = RaceEvent { venue_id: 1, venue_name: "桐生".to_string(), event_name: "ゆ～の庄杯".to_string(), ..event.clone() };
+        engine.store_mut().put(crate::monthly_key(202509, "zz_kiryu"), serialize_to_string(&same_day).unwrap()).unwrap();
+
+        let monthly = engine.get_monthly_schedule(202509).unwrap();
+        assert_eq!(monthly.events.iter().filter(|e| **e == event).count(), 1);
+        let ties: Vec<u32> = monthly.events.iter().filter(|e| e.start_date == event.start_date).map(|e| e.venue_id).collect();
+        assert_eq!(ties, vec![1, event.venue_id]);
+
+        assert_eq!(engine.get_schedules_between(202509, 202509).unwrap()[0], monthly);
+        assert_eq!(engine.get_yearly_schedule(2025).unwrap()[8], monthly);
+    }
+
+    #[test]
+    fn test_register_tournament_returns_id() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let year_end = RaceEvent {
+            venue_id: 24,
+            venue_name: "大村".to_string(),
+            event_name: "年末年始特別競走".to_string(),
+            grade: Grade::SG,
+            start_date: "2025-12-28".to_string(),
+            duration_days: 8,
+            notes: None,
+        };
+
+        let tournament_id = engine.register_tournament(&year_end).unwrap();
+        assert_eq!(tournament_id, generate_tournament_id("大村", "年末年始特別競走"));
+        assert_eq!(engine.register_tournament_to_months(&year_end).unwrap(), tournament_id);
+
+        // 返された大会IDでレースデータを保存・取得できる
+        engine.put_race_data(&tournament_id, 1, &"race1").unwrap();
+        engine.put_race_data(&tournament_id, 2, &"race2").unwrap();
+        let races: Vec<String> = engine.get_tournament_races(&tournament_id).unwrap();
+        assert_eq!(races, vec!["race1", "race2"]);
+
+        // 月別ビューとレースデータが同じ大会として扱われる
+        let summaries = engine.list_tournaments().unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].tournament_id, tournament_id);
+        assert_eq!(summaries[0].months, vec![202512, 202601]);
+        assert_eq!(summaries[0].race_count, 2);
+
+        // put_monthly_schedule と同じキーに書き込む
+        let december = MonthlySchedule {
+            year_month: "2025-12".to_string(),
+            events: vec![year_end.clone()],
+        };
+        let report = engine.put_monthly_schedule_checked(&december, ConflictPolicy::Error).unwrap();
+        assert_eq!(report.unchanged, vec![tournament_id]);
+    }
+
+    #[test]
+    fn test_race_data_at_datetime() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let when = NaiveDate::from_ymd_opt(2023, 9, 12).unwrap().and_hms_opt(13, 20, 0).unwrap();
+        engine.put_race_data_at("tokyo_bay_cup", when, &"race1").unwrap();
+
+        // 生のタイムスタンプ API からエポックミリ秒で取得できる
+        let race: String = engine.get_race_data("tokyo_bay_cup", 1694524800000).unwrap();
+        assert_eq!(race, "race1");
+        let race: String = engine.get_race_data_at("tokyo_bay_cup", when).unwrap();
+        assert_eq!(race, "race1");
+
+        // JST の 22:50 は UTC の 13:50
+        let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+        let later = NaiveDate::from_ymd_opt(2023, 9, 12).unwrap().and_hms_opt(22, 50, 0).unwrap()
+            .and_local_timezone(jst)
+            .unwrap();
+        engine.put_race_data_at_offset("tokyo_bay_cup", later, &"race2").unwrap();
+        let race: String = engine.get_race_data("tokyo_bay_cup", 1694526600000).unwrap();
+        assert_eq!(race, "race2");
+
+        let from = NaiveDate::from_ymd_opt(2023, 9, 12).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        let to = NaiveDate::from_ymd_opt(2023, 9, 12).unwrap().and_hms_opt(13, 50, 0).unwrap();
+        let races: Vec<(NaiveDateTime, String)> = engine.get_races_between_dates("tokyo_bay_cup", from, to).unwrap();
+        assert_eq!(races, vec![(when, "race1".to_string())]);
+
+        let before_epoch = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        assert!(matches!(
+            engine.put_race_data_at("tokyo_bay_cup", before_epoch, &"old"),
+            Err(crate::StoreError::InvalidDate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_numbered_races() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_numbered_race("tokyo_bay_cup", "2025-09-12", 7, &"race7").unwrap();
+        engine.put_numbered_race("tokyo_bay_cup", "2025-09-12", 1, &"race1").unwrap();
+        engine.put_numbered_race("tokyo_bay_cup", "2025-09-12", 12, &"race12").unwrap();
+        engine.put_numbered_race("tokyo_bay_cup", "2025-09-13", 1, &"next_day").unwrap();
+
+        let race: String = engine.get_numbered_race("tokyo_bay_cup", "2025-09-12", 7).unwrap();
+        assert_eq!(race, "race7");
+        // 2025-09-12 0:07 JST = 2025-09-11 15:07 UTC
+        let race: String = engine.get_race_data("tokyo_bay_cup", 1757603220000).unwrap();
+        assert_eq!(race, "race7");
+
+        // 実際の発走時刻で保存したレース (JST 10:30, 前日の JST 23:59) とは混ざらない
+        engine.put_race_data("tokyo_bay_cup", 1757640600000, &"timed").unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1757602740000, &"late_night").unwrap();
+        let day: Vec<(u32, String)> = engine.get_day_races("tokyo_bay_cup", "2025-09-12").unwrap();
+        assert_eq!(
+            day,
+            vec![(1, "race1".to_string()), (7, "race7".to_string()), (12, "race12".to_string())]
+        );
+        let timed: String = engine.get_race_data("tokyo_bay_cup", 1757640600000).unwrap();
+        assert_eq!(timed, "timed");
+
+        for bad in [0, 13] {
+            assert!(matches!(
+                engine.put_numbered_race("tokyo_bay_cup", "2025-09-12", bad, &"bad"),
+                Err(crate::StoreError::ValidationError { .. })
+            ));
+        }
+        assert!(matches!(
+            engine.get_numbered_race::<String>("tokyo_bay_cup", "2025-09-31", 1),
+            Err(crate::StoreError::InvalidDate { .. })
+        ));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_put_races_bulk_saves_once() {
+        let test_file = "test_engine_races_bulk.json";
+        std::fs::remove_file(test_file).ok();
+        {
+            let mut engine = BoatRaceEngine::new(crate::FileStore::new(test_file).unwrap());
+            let races: Vec<(u64, String)> = (1..=12).map(|n| (1000 + n, format!("race{}", n))).collect();
+            assert_eq!(engine.put_races_bulk("tokyo_bay_cup", &races).unwrap(), 12);
+            assert_eq!(engine.store_mut().save_count(), 1);
+
+            let multi = vec![
+                ("cup_a".to_string(), 1u64, "a1".to_string()),
+                ("cup_b".to_string(), 2u64, "b2".to_string()),
+            ];
+            assert_eq!(engine.put_races_bulk_multi(&multi).unwrap(), 2);
+            assert_eq!(engine.store_mut().save_count(), 2);
+
+            // 不正なIDが含まれる場合は何も書き込まない
+            let bad = vec![
+                ("cup_c".to_string(), 1u64, "c1".to_string()),
+                ("bad\0id".to_string(), 2u64, "bad".to_string()),
+            ];
+            assert!(engine.put_races_bulk_multi(&bad).is_err());
+            assert_eq!(engine.store_mut().save_count(), 2);
+            assert_eq!(engine.count_races("cup_c").unwrap(), 0);
+        }
+        {
+            let mut engine = BoatRaceEngine::new(crate::FileStore::new(test_file).unwrap());
+            assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), 12);
+            let race: String = engine.get_race_data("cup_b", 2).unwrap();
+            assert_eq!(race, "b2");
+        }
+        std::fs::remove_file(test_file).ok();
+    }
+
+    /// create / update / put を (存在しない・存在する) の両方で確認し、失敗時は何も書き込まれないことを確認
+    fn check_race_write_matrix<K: KeyValueStore>(engine: &mut BoatRaceEngine<K>, writes: impl Fn(&mut BoatRaceEngine<K>) -> usize) {
+        let get = |engine: &BoatRaceEngine<K>, timestamp| engine.get_race_data::<String>("tokyo_bay_cup", timestamp).ok();
+
+        // 存在しない
+        let before = writes(engine);
+        assert!(engine.update_race_data("tokyo_bay_cup", 1, &"updated").unwrap_err().is_not_found());
+        assert_eq!(writes(engine), before);
+        assert_eq!(get(engine, 1), None);
+        engine.create_race_data("tokyo_bay_cup", 1, &"created").unwrap();
+        assert_eq!(get(engine, 1).as_deref(), Some("created"));
+        engine.put_race_data("tokyo_bay_cup", 2, &"put").unwrap();
+        assert_eq!(get(engine, 2).as_deref(), Some("put"));
+
+        // 存在する
+        let before = writes(engine);
+        let error = engine.create_race_data("tokyo_bay_cup", 1, &"again").unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::KeyExists);
+        assert_eq!(writes(engine), before);
+        assert_eq!(get(engine, 1).as_deref(), Some("created"));
+        engine.update_race_data("tokyo_bay_cup", 1, &"updated").unwrap();
+        assert_eq!(get(engine, 1).as_deref(), Some("updated"));
+        engine.put_race_data("tokyo_bay_cup", 2, &"put again").unwrap();
+        assert_eq!(get(engine, 2).as_deref(), Some("put again"));
+        assert_eq!(writes(engine), before + 2);
+    }
+
+    #[test]
+    fn test_race_write_matrix_memory() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        // レースの書き込みごとに最終更新時刻も1件書き込む
+        check_race_write_matrix(&mut engine, |engine| engine.metrics().puts as usize / 2);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_race_write_matrix_file() {
+        let test_file = "test_engine_race_write_matrix.json";
+        std::fs::remove_file(test_file).ok();
+        {
+            let mut engine = BoatRaceEngine::new(crate::FileStore::new(test_file).unwrap());
+            check_race_write_matrix(&mut engine, |engine| engine.store().save_count());
+        }
+        let engine = BoatRaceEngine::new(crate::FileStore::new(test_file).unwrap());
+        assert_eq!(engine.get_race_data::<String>("tokyo_bay_cup", 1).unwrap(), "updated");
+        std::fs::remove_file(test_file).ok();
+    }
+
+    /// 同じ内容のストアなら挿入順によらず keys・エクスポート・ダンプが同じバイト列になること
+    #[test]
+    fn test_keys_order_and_outputs_are_deterministic() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        for timestamp in [3, 1, 2] {
+            engine.put_race_data("tokyo_bay_cup", timestamp, &format!("race{}", timestamp)).unwrap();
+        }
+        let keys = engine.store().keys().unwrap();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+
+        // 逆順に書き込んだ別のストア (HashMap の反復順も異なる)
+        let mut copy = MemoryStore::new();
+        for key in keys.iter().rev() {
+            copy.put(key.clone(), engine.store().get(key).unwrap().unwrap()).unwrap();
+        }
+        let mut copy = BoatRaceEngine::new(copy);
+        assert_eq!(copy.store().keys().unwrap(), keys);
+
+        let outputs = |engine: &mut BoatRaceEngine<MemoryStore>| {
+            let mut json = Vec::new();
+            engine.export_json(&mut json).unwrap();
+            let mut dump = Vec::new();
+            engine.dump(&mut dump, DumpOptions::default()).unwrap();
+            (json, dump, engine.store().debug_dump(usize::MAX))
+        };
+        assert_eq!(outputs(&mut engine), outputs(&mut copy));
+        assert_eq!(outputs(&mut engine), outputs(&mut engine));
+    }
+
+    /// Debug は件数とサイズだけを出力し、中身は debug_dump でのみ出力すること
+    #[test]
+    fn test_debug_output_is_summary() {
+        let mut store = MemoryStore::new();
+        store.put("Ukey".to_string(), "value".to_string()).unwrap();
+        store.put("Uother".to_string(), "x".repeat(10_000)).unwrap();
+        assert_eq!(format!("{:?}", store), "MemoryStore { entries: 2, approx_bytes: 10015 }");
+        assert_eq!(store.to_string(), "in-memory store (2 entries, 10015 bytes)");
+        assert_eq!(store.debug_dump(1), "\"Ukey\" => \"value\"\n... 1 more entries\n");
+        assert_eq!(store.debug_dump(0), "... 2 more entries\n");
+        assert_eq!(MemoryStore::new().debug_dump(10), "");
+
+        let engine = BoatRaceEngine::new(store);
+        #[cfg(feature = "compression")]
+        assert_eq!(
+            format!("{:?}", engine),
+            "BoatRaceEngine { store: MemoryStore { entries: 2, approx_bytes: 10015 }, codec: BincodeBase64, compression: None, \
+             indexes: IndexOptions { by_venue: false, by_name: false }, counters: None, hook: false, .. }"
+        );
+        assert!(format!("{:#?}", engine).len() < 500);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_debug_output() {
+        let test_file = "test_file_store_debug.json";
+        std::fs::remove_file(test_file).ok();
+        let mut store = crate::FileStore::new(test_file).unwrap();
+        store.put("Ukey".to_string(), "value".to_string()).unwrap();
+        assert_eq!(
+            format!("{:?}", store),
+            "FileStore { path: \"test_file_store_debug.json\", entries: 1, approx_bytes: 9, save_count: 1 }"
+        );
+        assert_eq!(store.to_string(), "file store at test_file_store_debug.json (1 entries, 9 bytes)");
+        assert_eq!(store.debug_dump(5), "\"Ukey\" => \"value\"\n");
+        std::fs::remove_file(test_file).ok();
+    }
+
+    /// テストごとに空の一時ディレクトリを用意する
+    #[cfg(feature = "fs")]
+    fn fresh_temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("norimaki_{}_{}", name, std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_creates_parent_dirs() {
+        let dir = fresh_temp_dir("create_dirs");
+        let path = dir.join("data").join("2025").join("db.json");
+        let mut engine = BoatRaceEngine::new(crate::FileStore::new(&path).unwrap());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert!(path.is_file());
+        let mut engine = BoatRaceEngine::new(crate::FileStore::new(&path).unwrap());
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 3);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_errors_mention_path() {
+        let dir = fresh_temp_dir("io_errors");
+
+        // 親ディレクトリを作らない設定
+        let missing = dir.join("missing").join("db.json");
+        let mut store = crate::FileStore::new(&missing).unwrap().with_create_dirs(false);
+        let error = store.put("Ukey".to_string(), "value".to_string()).unwrap_err();
+        assert!(error.is_io());
+        assert_eq!(error.path(), Some(missing.to_string_lossy().as_ref()));
+        assert!(error.to_string().starts_with("IO error: cannot write file "), "{}", error);
+        assert!(error.to_string().contains(missing.to_string_lossy().as_ref()), "{}", error);
+
+        // パスがディレクトリ
+        let error = crate::FileStore::new(&dir).unwrap_err();
+        assert!(error.to_string().starts_with("IO error: path is a directory "), "{}", error);
+        assert!(error.to_string().contains(dir.to_string_lossy().as_ref()), "{}", error);
+
+        // 読めないファイル (UTF-8 でない内容)
+        let unreadable = dir.join("binary.json");
+        std::fs::write(&unreadable, [0xff, 0xfe, 0x00]).unwrap();
+        let error = crate::FileStore::new(&unreadable).unwrap_err();
+        assert!(error.to_string().starts_with("IO error: cannot read file "), "{}", error);
+        assert!(error.to_string().contains(unreadable.to_string_lossy().as_ref()), "{}", error);
+
+        // 親ディレクトリの位置にファイルがある
+        let blocked = unreadable.join("db.json");
+        let mut store = crate::FileStore::new(&blocked).unwrap();
+        let error = store.put("Ukey".to_string(), "value".to_string()).unwrap_err();
+        assert!(error.to_string().starts_with("IO error: cannot create directory "), "{}", error);
+        assert_eq!(error.path(), Some(unreadable.to_string_lossy().as_ref()));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_reload_picks_up_external_changes() {
+        let dir = fresh_temp_dir("reload");
+        let path = dir.join("db.json");
+        let mut store = crate::FileStore::new(&path).unwrap();
+        store.put("Ua".to_string(), "1".to_string()).unwrap();
+        store.put("Ub".to_string(), "2".to_string()).unwrap();
+
+        // 変更がなければ何も反映されない
+        assert!(store.reload().unwrap().is_unchanged());
+
+        // 他のプロセスの書き込みを模してファイルを直接書き換える
+        std::fs::write(&path, r#"{"data": {"Ub": "20", "Uc": "3", "Ud": "4"}}"#).unwrap();
+        // 既定では自動で読み直さない
+        assert_eq!(store.get("Ua").unwrap(), Some("1".to_string()));
+        let stats = store.reload().unwrap();
+        assert_eq!(stats, crate::ReloadStats { added: 2, changed: 1, removed: 1 });
+        assert_eq!(store.keys().unwrap(), vec!["Ub", "Uc", "Ud"]);
+        assert_eq!(store.get("Ub").unwrap(), Some("20".to_string()));
+
+        // ファイルが消えた場合は空になる
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(store.reload().unwrap(), crate::ReloadStats { added: 0, changed: 0, removed: 3 });
+        assert!(store.keys().unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_auto_reload() {
+        let dir = fresh_temp_dir("auto_reload");
+        let path = dir.join("db.json");
+        let mut store = crate::FileStore::new(&path).unwrap().with_auto_reload(true);
+        store.put("Ua".to_string(), "1".to_string()).unwrap();
+        // 自分の書き出しでは読み直さない
+        assert_eq!(store.get("Ua").unwrap(), Some("1".to_string()));
+
+        // 読み取りの前に外部の変更が取り込まれる
+        std::fs::write(&path, r#"{"data": {"Ua": "1", "Ub": "external"}}"#).unwrap();
+        assert_eq!(store.get("Ub").unwrap(), Some("external".to_string()));
+        assert_eq!(store.keys().unwrap(), vec!["Ua", "Ub"]);
+
+        // 書き込みの前にも取り込まれるため、外部の変更を上書きで失わない
+        std::fs::write(&path, r#"{"data": {"Ua": "1", "Ub": "external", "Uc": "more external"}}"#).unwrap();
+        store.put("Ud".to_string(), "4".to_string()).unwrap();
+        let reopened = crate::FileStore::new(&path).unwrap();
+        assert_eq!(reopened.keys().unwrap(), vec!["Ua", "Ub", "Uc", "Ud"]);
+
+        // エンジン経由でも他のインスタンスの書き込みが見える
+        let mut reader = BoatRaceEngine::new(crate::FileStore::new(&path).unwrap().with_auto_reload(true));
+        assert!(reader.get_monthly_schedule(202509).unwrap().events.is_empty());
+        let mut writer = BoatRaceEngine::new(crate::FileStore::new(&path).unwrap());
+        writer.put_monthly_schedule(&sample_data()).unwrap();
+        assert_eq!(reader.get_monthly_schedule(202509).unwrap().events.len(), 3);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_reload_conflicts_with_unsaved_writes() {
+        let dir = fresh_temp_dir("reload_conflict");
+        let path = dir.join("missing").join("db.json");
+        let mut store = crate::FileStore::new(&path).unwrap().with_create_dirs(false).with_auto_reload(true);
+
+        // 書き出しに失敗した変更はメモリ上にだけ残る
+        assert!(store.put("Ua".to_string(), "local".to_string()).unwrap_err().is_io());
+        match store.reload() {
+            Err(StoreError::Conflict(conflict_path)) => assert_eq!(conflict_path, path.to_string_lossy()),
+            other => panic!("expected Conflict, got {:?}", other),
+        }
+
+        // ファイルが外部で作られても、未反映の変更とマージせずにエラーにする
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, r#"{"data": {"Ub": "external"}}"#).unwrap();
+        assert!(matches!(store.get("Ua"), Err(StoreError::Conflict(_))));
+        assert!(matches!(store.put("Uc".to_string(), "x".to_string()), Err(StoreError::Conflict(_))));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), r#"{"data": {"Ub": "external"}}"#);
+
+        // 自動再読み込みを使わなければ、書き出しでローカルの内容が採用される
+        let mut store = store.with_auto_reload(false);
+        store.put("Uc".to_string(), "x".to_string()).unwrap();
+        assert_eq!(store.reload().unwrap(), crate::ReloadStats::default());
+        assert_eq!(store.keys().unwrap(), vec!["Ua", "Uc"]);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_sync_on_flush() {
+        use crate::SyncPolicy;
+        let dir = fresh_temp_dir("sync_on_flush");
+        let path = dir.join("db.json");
+        let mut store = crate::FileStore::new(&path).unwrap().with_sync_policy(SyncPolicy::OnFlush);
+
+        // 書き込みはメモリ上に溜まり、ファイルは作られない
+        for n in 0..50 {
+            store.put(format!("U{:02}", n), n.to_string()).unwrap();
+        }
+        store.delete("U00").unwrap();
+        assert_eq!(store.save_count(), 0);
+        assert!(store.has_pending_writes());
+        assert!(!path.exists());
+        assert_eq!(store.get("U01").unwrap(), Some("1".to_string()));
+
+        // flush で一度だけ書き出され、開き直しても残る
+        store.flush().unwrap();
+        assert_eq!(store.save_count(), 1);
+        assert!(!store.has_pending_writes());
+        store.flush().unwrap();
+        assert_eq!(store.save_count(), 1);
+        let reopened = crate::FileStore::new(&path).unwrap();
+        assert_eq!(reopened.keys().unwrap().len(), 49);
+        assert_eq!(reopened.get("U00").unwrap(), None);
+
+        // エンジンの一括書き込みと組み合わせる
+        let mut engine = BoatRaceEngine::new(store);
+        for tournament in ["cup_a", "cup_b", "cup_c"] {
+            let races: Vec<(u64, String)> = (1..=12).map(|n| (1000 + n, format!("race{}", n))).collect();
+            engine.put_races_bulk(tournament, &races).unwrap();
+        }
+        assert_eq!(engine.store().save_count(), 1);
+        engine.store_mut().flush().unwrap();
+        assert_eq!(engine.store().save_count(), 2);
+        let mut reopened = BoatRaceEngine::new(crate::FileStore::new(&path).unwrap());
+        assert_eq!(reopened.get_tournament_races::<String>("cup_b").unwrap().len(), 12);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_flushes_on_drop() {
+        use crate::SyncPolicy;
+        let dir = fresh_temp_dir("flush_on_drop");
+        let path = dir.join("db.json");
+        {
+            let mut engine = BoatRaceEngine::new(crate::FileStore::new(&path).unwrap().with_sync_policy(SyncPolicy::OnFlush));
+            engine.put_monthly_schedule(&sample_data()).unwrap();
+            assert!(!path.exists());
+        }
+        let mut engine = BoatRaceEngine::new(crate::FileStore::new(&path).unwrap());
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 3);
+
+        // Never は書き込みごとに書き出す (fsync は flush または Drop まで行わない)
+        let never = dir.join("never.json");
+        {
+            let mut store = crate::FileStore::new(&never).unwrap().with_sync_policy(SyncPolicy::Never);
+            store.put("Ua".to_string(), "1".to_string()).unwrap();
+            assert_eq!(store.save_count(), 1);
+            assert!(!store.has_pending_writes());
+            assert_eq!(crate::FileStore::new(&never).unwrap().get("Ua").unwrap(), Some("1".to_string()));
+            store.flush().unwrap();
+            assert_eq!(store.save_count(), 1);
+        }
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    /// ストアの全エントリ (キー順)
+    #[cfg(feature = "test-util")]
+    fn store_contents<S: KeyValueStore>(store: &S) -> Vec<(String, String)> {
+        store
+            .keys()
+            .unwrap()
+            .into_iter()
+            .map(|key| {
+                let value = store.get(&key).unwrap().unwrap();
+                (key, value)
+            })
+            .collect()
+    }
+
+    /// 変更後のスケジュール (既存の大会を1つ変更し、大会を1つ追加する)
+    #[cfg(feature = "test-util")]
+    fn updated_sample_data() -> MonthlySchedule {
+        let mut updated = sample_data();
+        updated.events[0].duration_days += 1;
+        updated.events.push(RaceEvent { event_name: "追加の大会".to_string(), ..updated.events[1].clone() });
+        updated
+    }
+
+    /// サンプルデータを入れたストアで、バッチの途中の書き込みが失敗するスケジュールの書き込みを行う
+    ///
+    /// 失敗した書き込みが何も残さないことを確かめ、書き込み前の内容とエンジンを返す
+    #[cfg(feature = "test-util")]
+    fn put_schedule_failing_mid_batch<S: KeyValueStore>(store: S) -> (Vec<(String, String)>, BoatRaceEngine<crate::testing::FailingStore<S>>) {
+        use crate::testing::FailingStore;
+        let mut setup = BoatRaceEngine::new(FailingStore::new(store));
+        setup.put_monthly_schedule(&sample_data()).unwrap();
+        setup.rebuild_statistics().unwrap();
+        let failing = setup.into_store();
+        let puts = failing.puts();
+        let before = store_contents(failing.inner());
+
+        let error = StoreError::from(std::io::Error::other("disk full"));
+        let mut engine = BoatRaceEngine::new(failing.fail_put_at(puts + 2, error));
+        engine.rebuild_statistics().unwrap();
+        let counters = engine.metrics().counters;
+        let notified = std::sync::Arc::new(std::sync::Mutex::new(0usize));
+        let seen = std::sync::Arc::clone(&notified);
+        engine.set_hook(Box::new(move |_| *seen.lock().unwrap() += 1));
+
+        let result = engine.put_monthly_schedule(&updated_sample_data());
+        assert!(matches!(result, Err(StoreError::IoError { .. })), "{:?}", result);
+
+        // 失敗前に書き込まれた分も書き戻され、フックもカウンタも変わらない
+        assert!(engine.store().puts() > puts + 2);
+        assert_eq!(store_contents(engine.store().inner()), before);
+        assert_eq!(engine.metrics().counters, counters);
+        assert_eq!(*notified.lock().unwrap(), 0);
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), sample_data().events.len());
+        assert!(engine.check_integrity().unwrap().is_clean());
+        (before, engine)
+    }
+
+    /// `get` / `scan` で値を複製して返した回数を数えるストア (借用で読む経路は数えない)
+    struct CloneCountingStore {
+        inner: MemoryStore,
+        copies: std::cell::Cell<usize>,
+    }
+
+    impl KeyValueStore for CloneCountingStore {
+        fn put(&mut self, key: String, value: String) -> Result<()> {
+            self.inner.put(key, value)
+        }
+
+        fn get(&self, key: &str) -> Result<Option<String>> {
+            let value = self.inner.get(key)?;
+            self.copies.set(self.copies.get() + usize::from(value.is_some()));
+            Ok(value)
+        }
+
+        fn delete(&mut self, key: &str) -> Result<()> {
+            self.inner.delete(key)
+        }
+
+        fn keys(&self) -> Result<Vec<String>> {
+            self.inner.keys()
+        }
+
+        fn clear(&mut self) -> Result<()> {
+            self.inner.clear()
+        }
+
+        fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+            let entries = self.inner.scan(start, end)?;
+            self.copies.set(self.copies.get() + entries.len());
+            Ok(entries)
+        }
+
+        fn contains_key(&self, key: &str) -> Result<bool> {
+            self.inner.contains_key(key)
+        }
+
+        fn with_value<R>(&self, key: &str, f: impl FnOnce(&str) -> R) -> Result<Option<R>> {
+            self.inner.with_value(key, f)
+        }
+
+        fn scan_with(&mut self, start: &str, end: &str, f: impl FnMut(&str, &str) -> Result<()>) -> Result<()> {
+            self.inner.scan_with(start, end, f)
+        }
+    }
+
+    #[test]
+    fn test_reads_do_not_clone_values() {
+        let mut engine = BoatRaceEngine::new(CloneCountingStore { inner: MemoryStore::new(), copies: Default::default() });
+        let events: Vec<RaceEvent> = (0..30)
+            .map(|i| RaceEvent {
+                venue_id: i % 24 + 1,
+                venue_name: format!("Venue {}", i % 24 + 1),
+                event_name: format!("Cup {}", i),
+                grade: Grade::Ippan,
+                start_date: format!("2025-09-{:02}", i + 1),
+                duration_days: 1,
+                notes: None,
+            })
+            .collect();
+        engine.put_monthly_schedule(&MonthlySchedule { year_month: "2025-09".to_string(), events }).unwrap();
+        engine.put_races_bulk("cup_0", &[(1, "R1".to_string()), (2, "R2".to_string())]).unwrap();
+        engine.store().copies.set(0);
+
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 30);
+        assert_eq!(engine.get_events_on_date("2025-09-03").unwrap().len(), 1);
+        assert_eq!(engine.get_tournament_races::<String>("cup_0").unwrap(), vec!["R1", "R2"]);
+        assert_eq!(engine.get_race_data::<String>("cup_0", 2).unwrap(), "R2");
+        assert_eq!(engine.store().copies.get(), 0);
+    }
+
+    #[test]
+    fn test_tournament_races_par_matches_sequential() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let races: Vec<(u64, String)> = (0..3000u64).map(|i| (1_694_524_800_000 + i * 1000, format!("R{}", i))).collect();
+        engine.put_races_bulk("big_cup", &races).unwrap();
+
+        let sequential = engine.get_tournament_races::<String>("big_cup").unwrap();
+        let parallel = engine.get_tournament_races_par::<String>("big_cup").unwrap();
+        assert_eq!(parallel.len(), 3000);
+        assert_eq!(parallel, sequential);
+        assert!(engine.get_tournament_races_par::<String>("no_such_cup").unwrap().is_empty());
     }
 
-    /// 個別レースデータを保存
-    /// 
-    /// # Arguments
-    /// * `tournament_id` - 大会ID
-    /// * `timestamp` - レースのタイムスタンプ
-    /// * `data` - レースデータ
-    /// 
-    /// # Returns
-    /// 操作結果
-    pub fn put_race_data<T: Serialize>(&mut self, tournament_id: &str, timestamp: u64, data: &T) -> Result<()> {
-        let key = tournament_key(tournament_id, timestamp);
-        let value = serialize_to_string(data)?;
-        self.store.put(key, value)
+    #[test]
+    fn test_monthly_schedule_par_matches_sequential() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let events: Vec<RaceEvent> = (0..2000)
+            .map(|i| RaceEvent {
+                venue_id: i % 24 + 1,
+                venue_name: format!("Venue {}", i % 24 + 1),
+                event_name: format!("Cup {}", i),
+                grade: Grade::Ippan,
+                start_date: format!("2025-09-{:02}", i % 28 + 1),
+                duration_days: 1,
+                notes: None,
+            })
+            .collect();
+        engine.put_monthly_schedule(&MonthlySchedule { year_month: "2025-09".to_string(), events }).unwrap();
+        // 同じイベントを別の大会IDで重複登録しても、順次版と同じく1件にまとめる
+        let duplicate = engine.get_monthly_schedule(202509).unwrap().events[0].clone();
+        engine.store_mut().put(crate::key::monthly_key(202509, "legacy_id"), serialize_to_string(&duplicate).unwrap()).unwrap();
+
+        let sequential = engine.get_monthly_schedule(202509).unwrap();
+        let parallel = engine.get_monthly_schedule_par(202509).unwrap();
+        assert_eq!(parallel.events.len(), 2000);
+        assert_eq!(parallel, sequential);
     }
 
-    /// 大会の全レースデータを取得
-    /// 
-    /// # Arguments
-    /// * `tournament_id` - 大会ID
-    /// 
-    /// # Returns
-    /// レースデータのベクター（タイムスタンプ順）
-    pub fn get_tournament_races<T: DeserializeOwned>(&mut self, tournament_id: &str) -> Result<Vec<T>> {
-        let (start, end) = tournament_scan_range(tournament_id);
-        let results = self.store.scan(&start, &end)?;
-        
-        let mut races = Vec::new();
-        for (_, value) in results {
-            let race: T = deserialize_from_string(&value)?;
-            races.push(race);
-        }
-        
-        Ok(races)
+    #[test]
+    fn test_for_each_race_streams_in_order() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_races_bulk("cup", &[(3, 30u32), (1, 10), (2, 20)]).unwrap();
+        engine.put_races_bulk("cup_other", &[(1, 1000u32)]).unwrap();
+
+        let mut seen = Vec::new();
+        engine.for_each_race("cup", |timestamp, race: u32| {
+            seen.push((timestamp, race));
+            ControlFlow::Continue(())
+        }).unwrap();
+        assert_eq!(seen, vec![(1, 10), (2, 20), (3, 30)]);
+        assert_eq!(seen, engine.get_tournament_races_with_keys::<u32>("cup").unwrap());
     }
 
-    /// 特定のレースデータを取得
-    /// 
-    /// # Arguments
-    /// * `tournament_id` - 大会ID
-    /// * `timestamp` - レースのタイムスタンプ
-    /// 
-    /// # Returns
-    /// レースデータ
-    pub fn get_race_data<T: DeserializeOwned>(&self, tournament_id: &str, timestamp: u64) -> Result<T> {
-        let key = tournament_key(tournament_id, timestamp);
-        let value = self.store.get(&key)?
-            .ok_or_else(|| crate::StoreError::NotFound)?;
-        deserialize_from_string(&value)
+    #[test]
+    fn test_for_each_race_early_exit() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let races: Vec<(u64, u64)> = (0..10).map(|i| (i, i * 100)).collect();
+        engine.put_races_bulk("cup", &races).unwrap();
+
+        let gets_before = engine.metrics().gets;
+        let mut seen = Vec::new();
+        engine.for_each_race("cup", |timestamp, _: u64| {
+            seen.push(timestamp);
+            if seen.len() == 3 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        }).unwrap();
+        assert_eq!(seen, vec![0, 1, 2]);
+        // 打ち切った後の値は読まない
+        assert_eq!(engine.metrics().gets - gets_before, 3);
     }
 
-    /// 大会を複数の月に登録（月跨ぎ大会対応）
-    /// 
-    /// # Arguments
-    /// * `tournament` - 登録する大会情報
-    /// 
-    /// # Returns
-    /// 操作結果
-    pub fn register_tournament_to_months(&mut self, tournament: &RaceEvent) -> Result<()> {
-        let start_date = NaiveDate::parse_from_str(&tournament.start_date, "%Y-%m-%d")
-            .map_err(|_| crate::StoreError::InvalidValue)?;
-        
-        let mut current_date = start_date;
-        let end_date = start_date + chrono::Duration::days(tournament.duration_days as i64 - 1);
-        
-        // 開始月から終了月まで、各月に登録
-        while current_date <= end_date {
-            let year_month = current_date.year() as u32 * 100 + current_date.month();
-            let tournament_id = generate_tournament_id(&tournament.venue_name, &tournament.event_name);
-            let key = monthly_key(year_month, &tournament_id);
-            let value = serialize_to_string(tournament)?;
-            self.store.put(key, value)?;
-            
-            // 次の月に移動
-            current_date = if current_date.month() == 12 {
-                NaiveDate::from_ymd_opt(current_date.year() + 1, 1, 1)
-                    .ok_or_else(|| crate::StoreError::InvalidValue)?
-            } else {
-                NaiveDate::from_ymd_opt(current_date.year(), current_date.month() + 1, 1)
-                    .ok_or_else(|| crate::StoreError::InvalidValue)?
-            };
-            
-            // 終了日の月を超えたら終了
-            if current_date.year() as u32 * 100 + current_date.month() > 
-               end_date.year() as u32 * 100 + end_date.month() {
-                break;
+    #[test]
+    fn test_try_for_each_race_propagates_closure_error() {
+        #[derive(Debug, PartialEq)]
+        enum AggregateError {
+            Store(String),
+            TooLarge(u64),
+        }
+        impl From<StoreError> for AggregateError {
+            fn from(error: StoreError) -> Self {
+                AggregateError::Store(error.to_string())
             }
         }
-        
-        Ok(())
-    }
 
-    /// データ統計を取得
-    /// 
-    /// # Returns
-    /// (月数, 大会数, レース数) のタプル
-    pub fn get_statistics(&mut self) -> Result<(usize, usize, usize)> {
-        let all_keys = self.store.keys()?;
-        
-        let monthly_keys = all_keys.iter().filter(|k| k.starts_with('M')).count();
-        let tournament_keys = all_keys.iter().filter(|k| k.starts_with('T')).count();
-        
-        // 月別ビューの数から大会数を推定
-        let unique_tournaments = all_keys
-            .iter()
-            .filter_map(|k| {
-                if k.starts_with('M') {
-                    k.split('\x00').nth(1)
-                } else {
-                    None
-                }
-            })
-            .collect::<std::collections::HashSet<_>>()
-            .len();
-        
-        Ok((monthly_keys, unique_tournaments, tournament_keys))
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_races_bulk("cup", &[(1, 10u64), (2, 500), (3, 20)]).unwrap();
+
+        let mut total = 0;
+        let result = engine.try_for_each_race("cup", |timestamp, race: u64| {
+            if race > 100 {
+                return Err(AggregateError::TooLarge(timestamp));
+            }
+            total += race;
+            Ok(ControlFlow::Continue(()))
+        });
+        assert_eq!(result, Err(AggregateError::TooLarge(2)));
+        assert_eq!(total, 10);
+
+        // ストア側のエラーは From で変換される
+        let result = engine.try_for_each_race("bad\0id", |_, _: u64| Ok::<_, AggregateError>(ControlFlow::Continue(())));
+        assert!(matches!(result, Err(AggregateError::Store(_))));
     }
-}
 
-/// 年月文字列をu32に変換 (例: "2025-09" -> 202509)
-fn parse_year_month(year_month: &str) -> Result<u32> {
-    let parts: Vec<&str> = year_month.split('-').collect();
-    if parts.len() != 2 {
-        return Err(crate::StoreError::InvalidValue);
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct TypedRaceData {
+        race_number: u32,
+        winner: String,
     }
-    
-    let year: u32 = parts[0].parse()
-        .map_err(|_| crate::StoreError::InvalidValue)?;
-    let month: u32 = parts[1].parse()
-        .map_err(|_| crate::StoreError::InvalidValue)?;
-    
-    if month < 1 || month > 12 {
-        return Err(crate::StoreError::InvalidValue);
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct OddsSnapshot {
+        // TypedRaceData と同じレイアウトなので、型名がなければ bincode では取り違えに気付けない
+        combination: u32,
+        label: String,
     }
-    
-    Ok(year * 100 + month)
-}
 
-/// u32を年月文字列に変換 (例: 202509 -> "2025-09")
-fn format_year_month(year_month: u32) -> String {
-    let year = year_month / 100;
-    let month = year_month % 100;
-    format!("{:04}-{:02}", year, month)
-}
+    #[test]
+    fn test_tournament_handle_round_trip() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_type_tag::<TypedRaceData>("RaceData");
+        let mut races = engine.tournament::<TypedRaceData>("cup");
+        for i in 1..=5 {
+            races.put(i * 100, &TypedRaceData { race_number: i as u32, winner: format!("racer {}", i) }).unwrap();
+        }
+        assert_eq!(races.tournament_id(), "cup");
+        assert_eq!(races.get(300).unwrap().race_number, 3);
+        assert!(matches!(races.get(301), Err(StoreError::NotFound { .. })));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::MemoryStore;
+        let all = races.all().unwrap();
+        assert_eq!(all.iter().map(|(timestamp, _)| *timestamp).collect::<Vec<_>>(), vec![100, 200, 300, 400, 500]);
+        let latest = races.latest(2).unwrap();
+        assert_eq!(latest.iter().map(|(timestamp, race)| (*timestamp, race.race_number)).collect::<Vec<_>>(), vec![(500, 5), (400, 4)]);
+        assert_eq!(races.latest(10).unwrap().len(), 5);
 
-    #[test]
-    fn test_parse_year_month() {
-        assert_eq!(parse_year_month("2025-09").unwrap(), 202509);
-        assert_eq!(parse_year_month("2024-12").unwrap(), 202412);
-        assert!(parse_year_month("invalid").is_err());
-        assert!(parse_year_month("2025-13").is_err());
+        // 型名付きの値も汎用のメソッドで読める
+        assert_eq!(engine.get_race_data::<TypedRaceData>("cup", 100).unwrap().winner, "racer 1");
+        assert_eq!(engine.get_tournament_races::<TypedRaceData>("cup").unwrap().len(), 5);
+        let stored = engine.store().get(&crate::key::tournament_key("cup", 100)).unwrap().unwrap();
+        assert!(stored.starts_with("t:RaceData:"));
     }
 
     #[test]
-    fn test_format_year_month() {
-        assert_eq!(format_year_month(202509), "2025-09");
-        assert_eq!(format_year_month(202412), "2024-12");
+    fn test_tournament_handle_reports_type_mismatch() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new())
+            .with_type_tag::<TypedRaceData>("RaceData")
+            .with_type_tag::<OddsSnapshot>("OddsSnapshot");
+        engine.tournament::<OddsSnapshot>("cup").put(100, &OddsSnapshot { combination: 123, label: "1-2-3".to_string() }).unwrap();
+
+        // 汎用のメソッドは型名を確かめないため、同じレイアウトの別の型として読めてしまう
+        assert_eq!(engine.get_race_data::<TypedRaceData>("cup", 100).unwrap().race_number, 123);
+
+        let error = engine.tournament::<TypedRaceData>("cup").get(100).unwrap_err();
+        assert!(matches!(error, StoreError::SerializationError { .. }));
+        assert!(error.to_string().contains("expected type RaceData, found OddsSnapshot"), "{}", error);
+        assert_eq!(error.key(), Some(crate::key::tournament_key("cup", 100).as_str()));
+        assert!(engine.tournament::<TypedRaceData>("cup").all().is_err());
+        assert_eq!(engine.metrics().serialization_failures, 2);
+        assert_eq!(engine.tournament::<OddsSnapshot>("cup").get(100).unwrap().label, "1-2-3");
     }
 
     #[test]
-    fn test_put_get_monthly_schedule() {
-        let store = MemoryStore::new();
-        let mut engine = BoatRaceEngine::new(store);
-
-        let schedule = MonthlySchedule {
-            year_month: "2025-09".to_string(),
-            events: vec![
-                RaceEvent {
-                    venue_id: 4,
-                    venue_name: "平和島".to_string(),
-                    event_name: "トーキョー・ベイ・カップ".to_string(),
-                    grade: "G1".to_string(),
-                    start_date: "2025-09-10".to_string(),
-                    duration_days: 7,
-                },
-            ],
-        };
+    fn test_tournament_handle_reads_untagged_values() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let race = TypedRaceData { race_number: 1, winner: "racer".to_string() };
+        // 登録していない型は型名を記録しない
+        engine.tournament::<TypedRaceData>("cup").put(100, &race).unwrap();
+        engine.put_race_data("cup", 200, &race).unwrap();
+        let stored = engine.store().get(&crate::key::tournament_key("cup", 100)).unwrap().unwrap();
+        assert!(!stored.starts_with(crate::value::TYPE_TAG_PREFIX));
 
-        // スケジュールを保存
-        engine.put_monthly_schedule(&schedule).unwrap();
+        // 後から登録しても、型名のない既存の値はそのまま読める
+        let mut engine = BoatRaceEngine::new(engine.into_store()).with_type_tag::<TypedRaceData>("RaceData");
+        assert_eq!(engine.tournament::<TypedRaceData>("cup").all().unwrap(), vec![(100, race.clone()), (200, race)]);
+    }
 
-        // スケジュールを取得
-        let retrieved = engine.get_monthly_schedule(202509).unwrap();
-        assert_eq!(retrieved.year_month, "2025-09");
-        assert_eq!(retrieved.events.len(), 1);
-        assert_eq!(retrieved.events[0].venue_name, "平和島");
+    #[test]
+    fn test_tournament_handle_rejects_invalid_type_tag() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_type_tag::<u32>("race:data");
+        assert!(matches!(engine.tournament::<u32>("cup").put(1, &1), Err(StoreError::InvalidValue { .. })));
+        assert!(engine.store().keys().unwrap().is_empty());
     }
 
     #[test]
-    fn test_race_data_operations() {
-        let store = MemoryStore::new();
-        let mut engine = BoatRaceEngine::new(store);
+    fn test_par_reads_report_corrupted_key() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let races: Vec<(u64, String)> = (0..100u64).map(|i| (1_694_524_800_000 + i, format!("R{}", i))).collect();
+        engine.put_races_bulk("big_cup", &races).unwrap();
+        let corrupted = crate::key::tournament_key("big_cup", 1_694_524_800_042);
+        engine.store_mut().put(corrupted.clone(), "b2:not base64!".to_string()).unwrap();
 
-        #[derive(Debug, Clone, PartialEq, Serialize, serde::Deserialize)]
-        struct RaceData {
-            race_number: u32,
-            participants: Vec<String>,
-        }
+        let error = engine.get_tournament_races_par::<String>("big_cup").unwrap_err();
+        assert_eq!(error.key(), Some(corrupted.as_str()));
+        assert_eq!(engine.metrics().serialization_failures, 1);
+    }
 
-        let race_data = RaceData {
-            race_number: 1,
-            participants: vec!["選手A".to_string(), "選手B".to_string()],
-        };
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn test_failed_batch_rolls_back_memory_store() {
+        let (_, mut engine) = put_schedule_failing_mid_batch(MemoryStore::new());
 
-        let tournament_id = "tokyo_bay_cup";
-        let timestamp = 1694524800000;
+        // 失敗させる回を過ぎた後の書き込みは成功する
+        engine.put_monthly_schedule(&updated_sample_data()).unwrap();
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 4);
+    }
 
-        // レースデータを保存
-        engine.put_race_data(tournament_id, timestamp, &race_data).unwrap();
+    #[cfg(all(feature = "test-util", feature = "fs"))]
+    #[test]
+    fn test_failed_batch_rolls_back_file_store() {
+        let dir = fresh_temp_dir("rollback");
+        let path = dir.join("db.json");
+        let (before, engine) = put_schedule_failing_mid_batch(crate::FileStore::new(&path).unwrap());
+        let saved_before = {
+            let mut store = crate::FileStore::new(dir.join("expected.json")).unwrap();
+            store.apply_batch(before.clone(), &[]).unwrap();
+            std::fs::read_to_string(dir.join("expected.json")).unwrap()
+        };
+        drop(engine);
 
-        // レースデータを取得
-        let retrieved: RaceData = engine.get_race_data(tournament_id, timestamp).unwrap();
-        assert_eq!(retrieved.race_number, 1);
-        assert_eq!(retrieved.participants.len(), 2);
+        // 失敗までの書き込みはファイルに書き出されていたが、書き戻しでファイルも元に戻っている
+        assert_eq!(store_contents(&crate::FileStore::new(&path).unwrap()), before);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), saved_before);
+        std::fs::remove_dir_all(&dir).ok();
+    }
 
-        // 大会の全レースを取得
-        let all_races: Vec<RaceData> = engine.get_tournament_races(tournament_id).unwrap();
-        assert_eq!(all_races.len(), 1);
-        assert_eq!(all_races[0], race_data);
+    /// 同じ内容の FileStore は挿入順によらず同じバイト列のファイルになること
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_output_is_byte_stable() {
+        let entries: Vec<(String, String)> = (0..50).map(|n| (format!("Ukey{:02}", n), format!("value{}", n))).collect();
+        let write = |path: &str, entries: &mut dyn Iterator<Item = &(String, String)>| {
+            std::fs::remove_file(path).ok();
+            let mut store = crate::FileStore::new(path).unwrap();
+            store.apply_batch(entries.cloned().collect(), &[]).unwrap();
+            assert_eq!(store.keys().unwrap().first().map(String::as_str), Some("Ukey00"));
+            let bytes = std::fs::read(path).unwrap();
+            std::fs::remove_file(path).ok();
+            bytes
+        };
+        let forward = write("test_file_store_stable_a.json", &mut entries.iter());
+        let backward = write("test_file_store_stable_b.json", &mut entries.iter().rev());
+        assert_eq!(forward, backward);
     }
 
     #[test]
-    fn test_register_tournament_to_months() {
+    fn test_race_data_rejects_separator_in_id() {
         let store = MemoryStore::new();
         let mut engine = BoatRaceEngine::new(store);
 
-        let tournament = RaceEvent {
-            venue_id: 4,
-            venue_name: "平和島".to_string(),
-            event_name: "年末年始杯".to_string(),
-            grade: "G1".to_string(),
-            start_date: "2025-12-28".to_string(),
-            duration_days: 10, // 2026-01-06まで
-        };
-
-        // 月跨ぎ大会を登録
-        engine.register_tournament_to_months(&tournament).unwrap();
+        let result = engine.put_race_data("tokyo\x00bay", 1694524800000, &"race1");
+        assert!(matches!(result, Err(crate::StoreError::InvalidKey)));
+        assert!(engine.store().keys().unwrap().is_empty());
 
-        // 12月のスケジュールを確認
-        let dec_schedule = engine.get_monthly_schedule(202512).unwrap();
-        assert_eq!(dec_schedule.events.len(), 1);
-        assert_eq!(dec_schedule.events[0].event_name, "年末年始杯");
+        let result: Result<String> = engine.get_race_data("tokyo\x00bay", 1694524800000);
+        assert!(matches!(result, Err(crate::StoreError::InvalidKey)));
 
-        // 1月のスケジュールを確認
-        let jan_schedule = engine.get_monthly_schedule(202601).unwrap();
-        assert_eq!(jan_schedule.events.len(), 1);
-        assert_eq!(jan_schedule.events[0].event_name, "年末年始杯");
+        let result: Result<Vec<String>> = engine.get_tournament_races("\x00");
+        assert!(matches!(result, Err(crate::StoreError::InvalidKey)));
     }
 
     #[test]
@@ -337,9 +8867,10 @@ mod tests {
                     venue_id: 4,
                     venue_name: "平和島".to_string(),
                     event_name: "トーキョー・ベイ・カップ".to_string(),
-                    grade: "G1".to_string(),
+                    grade: Grade::G1,
                     start_date: "2025-09-10".to_string(),
                     duration_days: 7,
+                    notes: None,
                 },
             ],
         };
@@ -353,4 +8884,248 @@ mod tests {
         assert_eq!(tournament_count, 1); // 1つのユニーク大会
         assert_eq!(race_count, 2); // 2つのレース
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_json_codec() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_codec(Codec::Json);
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1694524800000, &"race1").unwrap();
+
+        let (start, end) = monthly_scan_range(202509);
+        let monthly = engine.store_mut().scan(&start, &end).unwrap();
+        assert_eq!(monthly.len(), sample_data().events.len());
+        assert!(monthly.iter().all(|(_, value)| value.starts_with('{')));
+        assert!(monthly.iter().any(|(_, value)| value.contains("平和島")));
+        let race = engine.store().get(&crate::key::tournament_key("tokyo_bay_cup", 1694524800000)).unwrap().unwrap();
+        assert_eq!(race, "\"race1\"");
+
+        // 既定の形式のエンジンでもそのまま読める
+        let mut engine = BoatRaceEngine::new(engine.into_store());
+        let expected = engine.get_monthly_schedule(202509).unwrap();
+        assert_eq!(expected.events.len(), sample_data().events.len());
+
+        // 形式が混在していても読める
+        engine.put_race_data("tokyo_bay_cup", 1694524800001, &"race2").unwrap();
+        let mut engine = engine.with_codec(Codec::Json);
+        let races: Vec<String> = engine.get_tournament_races("tokyo_bay_cup").unwrap();
+        assert_eq!(races, vec!["race1", "race2"]);
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap(), expected);
+        assert_eq!(engine.get_statistics().unwrap().2, 2);
+    }
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compression() {
+        // 参加者一覧のような繰り返しの多い 10 KB 程度の値
+        let payload: Vec<(u32, String)> = (0..400).map(|i| (4000 + i % 6, "トーキョー・ベイ".to_string())).collect();
+        let plain = serialize_to_string(&payload).unwrap();
+        assert!(plain.len() > 10_000);
+
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_race_data("tokyo_bay_cup", 1694524800000, &payload).unwrap();
+        let mut engine = engine.with_compression(crate::value::Compression::default());
+        engine.put_race_data("tokyo_bay_cup", 1694524800001, &payload).unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1694524800002, &"race3").unwrap();
+
+        let (start, end) = tournament_scan_range("tokyo_bay_cup");
+        let mut stored = engine.store_mut().scan(&start, &end).unwrap();
+        stored.sort();
+        assert_eq!(stored[0].1, plain);
+        assert!(stored[1].1.starts_with(crate::value::COMPRESSED_PREFIX));
+        assert!(stored[1].1.len() * 10 < plain.len());
+        // 閾値以下の値は圧縮しない
+        assert_eq!(stored[2].1, serialize_to_string(&"race3").unwrap());
+
+        // 圧縮の有無にかかわらず読める
+        for timestamp in [1694524800000, 1694524800001] {
+            let race: Vec<(u32, String)> = engine.get_race_data("tokyo_bay_cup", timestamp).unwrap();
+            assert_eq!(race, payload);
+        }
+        let mut engine = BoatRaceEngine::new(engine.into_store());
+        let race: Vec<(u32, String)> = engine.get_race_data("tokyo_bay_cup", 1694524800001).unwrap();
+        assert_eq!(race, payload);
+        assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), 3);
+    }
+
+    #[test]
+    fn test_errors_carry_key() {
+        use std::error::Error;
+
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let (start, end) = monthly_scan_range(202509);
+        let corrupt_key = engine.store_mut().scan(&start, &end).unwrap()[0].0.clone();
+        engine.store_mut().put(corrupt_key.clone(), "not base64!".to_string()).unwrap();
+
+        // 月の読み込みに失敗した原因のキーが分かる
+        let error = engine.get_monthly_schedule(202509).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Serialization);
+        assert_eq!(error.key(), Some(corrupt_key.as_str()));
+        assert!(error.to_string().contains(&format!("{:?}", corrupt_key)));
+        assert!(error.source().is_some());
+
+        let error = engine.get_race_data::<String>("tokyo_bay_cup", 1).unwrap_err();
+        assert!(matches!(&error, crate::StoreError::NotFound { key } if key.contains("tokyo_bay_cup")));
+        assert_eq!(error.kind(), crate::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_value_size_limit() {
+        let payload = "x".repeat(1000);
+        let size = serialize_to_string(&payload).unwrap().len();
+
+        // ちょうど上限の値は書き込める
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_max_value_bytes(size);
+        engine.put_race_data("tokyo_bay_cup", 1, &payload).unwrap();
+        engine.put_races_bulk("tokyo_bay_cup", &[(2, payload.clone())]).unwrap();
+
+        let mut engine = engine.with_max_value_bytes(size - 1);
+        let result = engine.put_race_data("tokyo_bay_cup", 3, &payload);
+        assert!(matches!(result, Err(crate::StoreError::ValueTooLarge { size: s, limit }) if s == size && limit == size - 1));
+        // 一括書き込みは1件でも超えればストアに触れない
+        let result = engine.put_races_bulk("tokyo_bay_cup", &[(4, "small".to_string()), (5, payload.clone())]);
+        assert!(matches!(result, Err(crate::StoreError::ValueTooLarge { .. })));
+        assert_eq!(engine.count_races("tokyo_bay_cup").unwrap(), 2);
+
+        let racer = Racer {
+            racer_id: 4444,
+            name: "x".repeat(size),
+            branch: "東京".to_string(),
+            class: "A1".to_string(),
+        };
+        assert!(matches!(engine.put_racer(&racer), Err(crate::StoreError::ValueTooLarge { .. })));
+        assert!(engine.get_racer(4444).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_key_size_limit() {
+        let key = crate::key::tournament_key("tokyo_bay_cup", 1);
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_max_key_bytes(key.len());
+        engine.put_race_data("tokyo_bay_cup", 1, &"race1").unwrap();
+
+        let result = engine.put_race_data("tokyo_bay_cup_2", 1, &"race1");
+        assert!(matches!(result, Err(crate::StoreError::KeyTooLarge { size, limit }) if size == key.len() + 2 && limit == key.len()));
+
+        // 既定の上限でも極端に長いキーは拒否する
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        let result = engine.put_race_data(&"a".repeat(DEFAULT_MAX_KEY_BYTES), 1, &"race1");
+        assert!(matches!(result, Err(crate::StoreError::KeyTooLarge { .. })));
+        assert!(engine.store().keys().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "rmp")]
+    #[test]
+    fn test_msgpack_codec() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_codec(Codec::MessagePack);
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1694524800000, &"race1").unwrap();
+
+        let (start, end) = monthly_scan_range(202509);
+        assert!(engine.store_mut().scan(&start, &end).unwrap().iter().all(|(_, value)| value.starts_with(crate::value::MSGPACK_PREFIX)));
+
+        let mut engine = BoatRaceEngine::new(engine.into_store());
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), sample_data().events.len());
+        assert_eq!(engine.get_race_data::<String>("tokyo_bay_cup", 1694524800000).unwrap(), "race1");
+    }
+}
+
+/// `tracing` フィーチャーのスパン・イベント出力のテスト
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_tests {
+    use super::*;
+    use crate::MemoryStore;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    include!("../testdata/sample.rs");
+
+    /// 出力されたスパンとイベントを記録するサブスクライバ
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Recorded>);
+
+    #[derive(Default)]
+    struct Recorded {
+        /// (スパン名, フィールド)
+        spans: Mutex<Vec<(&'static str, String)>>,
+        /// (レベル, フィールド, 入っていたスパンの ID)
+        events: Mutex<Vec<(Level, String, Option<u64>)>>,
+        entered: Mutex<Vec<u64>>,
+    }
+
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push_str(&format!("{}={:?} ", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = Fields(String::new());
+            span.record(&mut fields);
+            let mut spans = self.0.spans.lock().unwrap();
+            spans.push((span.metadata().name(), fields.0));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(String::new());
+            event.record(&mut fields);
+            let span = self.0.entered.lock().unwrap().last().copied();
+            self.0.events.lock().unwrap().push((*event.metadata().level(), fields.0, span));
+        }
+
+        fn enter(&self, span: &Id) {
+            self.0.entered.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.0.entered.lock().unwrap().pop();
+        }
+    }
+
+    #[test]
+    fn test_put_monthly_schedule_spans() {
+        let recorder = Recorder::default();
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        tracing::subscriber::with_default(recorder.clone(), || engine.put_monthly_schedule(&sample_data())).unwrap();
+
+        let spans = recorder.0.spans.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].0, "put_monthly_schedule");
+        assert!(spans[0].1.contains("year_month=2025-09"));
+        assert!(spans[0].1.contains("events=3"));
+
+        let written = engine.store().keys().unwrap().len();
+        let events = recorder.0.events.lock().unwrap();
+        let puts: Vec<_> = events.iter().filter(|(_, fields, _)| fields.starts_with("message=put ")).collect();
+        assert_eq!(puts.len(), written);
+        assert!(puts.iter().all(|(level, _, span)| *level == Level::DEBUG && *span == Some(1)));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_slow_file_store_save_warns() {
+        let path = std::env::temp_dir().join(format!("norimaki_tracing_{}.json", std::process::id()));
+        let store = crate::FileStore::new(&path).unwrap().with_slow_save_threshold(std::time::Duration::ZERO);
+        let recorder = Recorder::default();
+        let mut engine = BoatRaceEngine::new(store);
+        tracing::subscriber::with_default(recorder.clone(), || engine.put_race_data("tokyo_bay_cup", 1, &"race")).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let events = recorder.0.events.lock().unwrap();
+        let warnings: Vec<_> = events.iter().filter(|(level, _, _)| *level == Level::WARN).collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].1.contains("slow FileStore save"));
+    }
+}
@@ -1,38 +1,450 @@
+use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
+
+/// 元のエラー (エラーを Clone できるよう共有する)
+pub type ErrorSource = Arc<dyn Error + Send + Sync>;
 
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum StoreError {
-    IoError(String),
-    SerializationError(String),
+    /// 入出力エラー (source: 元のエラー, path: 対象のファイル・ディレクトリ, context: 失敗した操作の説明)
+    IoError { source: Arc<std::io::Error>, path: Option<String>, context: Option<String> },
+    /// 値のエンコード・デコードに失敗 (key: 対象のキー, source: 元のエラー)
+    SerializationError { key: Option<String>, source: ErrorSource },
+    /// キーが存在しない
+    NotFound { key: String },
+    InvalidKey,
+    /// 値が不正 (key: 対象のキー, reason: 原因の説明)
+    InvalidValue { key: Option<String>, reason: String },
+    /// 日付が不正 (input: 解析しようとした文字列, reason: 原因の説明)
+    InvalidDate { input: String, reason: String },
+    /// 会場マスタと一致しない (field: 問題のあるフィールド名, value: その値)
+    InvalidVenue { field: String, value: String },
+    /// 入力値の検証に失敗 (field: 問題のあるフィールド名, message: 原因の説明)
+    ValidationError { field: String, message: String },
+    /// 既存データと内容が食い違う (値: 対象のキー。`FileStore` の再読み込みではファイルパス)
+    Conflict(String),
+    /// キーが上限より大きい (size: キーのバイト数, limit: 上限)
+    KeyTooLarge { size: usize, limit: usize },
+    /// 値が上限より大きい (size: 格納する値のバイト数, limit: 上限)
+    ValueTooLarge { size: usize, limit: usize },
+    /// 上書きしない書き込みで、キーが既に存在する
+    KeyExists { key: String },
+    /// 書き込むとストアの上限を超える (quota: 超えた上限の種類, limit: 上限, attempted: 書き込んだ場合の値)
+    QuotaExceeded { quota: Quota, limit: usize, attempted: usize },
+}
+
+/// `StoreError::QuotaExceeded` で超えた上限の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quota {
+    /// エントリ数
+    Entries,
+    /// キーと値の合計バイト数
+    Bytes,
+}
+
+impl fmt::Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Quota::Entries => write!(f, "entries"),
+            Quota::Bytes => write!(f, "bytes"),
+        }
+    }
+}
+
+/// エラーの種類 (処理を分岐するだけの呼び出し側向け)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    Io,
+    Serialization,
     NotFound,
     InvalidKey,
     InvalidValue,
+    InvalidDate,
+    InvalidVenue,
+    Validation,
+    Conflict,
+    KeyTooLarge,
+    ValueTooLarge,
+    KeyExists,
+    QuotaExceeded,
+}
+
+impl StoreError {
+    /// キーの分からないエンコード・デコードのエラーを作成
+    ///
+    /// # Arguments
+    /// * `source` - 元のエラー、または原因の説明
+    pub fn serialization(source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        StoreError::SerializationError { key: None, source: Arc::from(source.into()) }
+    }
+
+    /// キーの分からない不正な値のエラーを作成
+    pub fn invalid_value(reason: impl Into<String>) -> Self {
+        StoreError::InvalidValue { key: None, reason: reason.into() }
+    }
+
+    /// 不正な日付のエラーを作成
+    ///
+    /// # Arguments
+    /// * `input` - 解析しようとした文字列
+    /// * `reason` - 原因 (chrono のエラーなど)
+    pub fn invalid_date(input: impl Into<String>, reason: impl fmt::Display) -> Self {
+        StoreError::InvalidDate { input: input.into(), reason: reason.to_string() }
+    }
+
+    /// 日付のエラーに解析しようとした文字列を付け加える (`From<chrono::ParseError>` で作成した場合など)
+    pub fn with_input(self, input: impl Into<String>) -> Self {
+        match self {
+            StoreError::InvalidDate { input: current, reason } if current.is_empty() => {
+                StoreError::InvalidDate { input: input.into(), reason }
+            }
+            other => other,
+        }
+    }
+
+    /// パスと操作の説明を付けた入出力エラーを作成
+    ///
+    /// # Arguments
+    /// * `source` - 元のエラー
+    /// * `context` - 失敗した操作の説明 (例: "cannot read file")
+    /// * `path` - 対象のファイル・ディレクトリのパス
+    pub fn io_at(source: std::io::Error, context: impl Into<String>, path: impl Into<String>) -> Self {
+        StoreError::IoError { source: Arc::new(source), path: Some(path.into()), context: Some(context.into()) }
+    }
+
+    /// 入出力エラーの対象のパス
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            StoreError::IoError { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// キーが存在しないエラーを作成
+    pub fn not_found(key: impl Into<String>) -> Self {
+        StoreError::NotFound { key: key.into() }
+    }
+
+    /// 対象のキーを付け加える (キーを持つ種類のエラーで、まだキーがない場合のみ)
+    pub fn with_key(self, key: impl Into<String>) -> Self {
+        match self {
+            StoreError::SerializationError { key: None, source } => {
+                StoreError::SerializationError { key: Some(key.into()), source }
+            }
+            StoreError::InvalidValue { key: None, reason } => StoreError::InvalidValue { key: Some(key.into()), reason },
+            other => other,
+        }
+    }
+
+    /// エラーの対象のキー
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            StoreError::SerializationError { key, .. } | StoreError::InvalidValue { key, .. } => key.as_deref(),
+            StoreError::NotFound { key } | StoreError::Conflict(key) | StoreError::KeyExists { key } => Some(key),
+            _ => None,
+        }
+    }
+
+    /// キーが存在しないエラーか
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, StoreError::NotFound { .. })
+    }
+
+    /// 呼び出し側の入力が不正なエラーか (キー・値・日付・会場・検証・大きさの上限)
+    pub fn is_invalid_input(&self) -> bool {
+        matches!(
+            self,
+            StoreError::InvalidKey
+                | StoreError::InvalidValue { .. }
+                | StoreError::InvalidDate { .. }
+                | StoreError::InvalidVenue { .. }
+                | StoreError::ValidationError { .. }
+                | StoreError::KeyTooLarge { .. }
+                | StoreError::ValueTooLarge { .. }
+        )
+    }
+
+    /// 入出力エラーか
+    pub fn is_io(&self) -> bool {
+        matches!(self, StoreError::IoError { .. })
+    }
+
+    /// 同じ操作を再試行すれば成功し得るか
+    ///
+    /// 一時的な入出力エラー (割り込み・タイムアウト・ロック競合など) のみ true。
+    /// デコード・検証などのエラーは何度試しても同じ結果になるため false
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            StoreError::IoError { source, .. } => matches!(
+                source.kind(),
+                std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ResourceBusy
+            ),
+            _ => false,
+        }
+    }
+
+    /// エラーの種類ごとの数値コード
+    ///
+    /// 外部に公開するエラー応答などで使う。一度割り当てたコードの意味はバージョンをまたいで変えず、
+    /// 新しい種類には新しいコードを割り当てる
+    ///
+    /// | コード | 種類 |
+    /// |-------|------|
+    /// | 1 | `IoError` |
+    /// | 2 | `SerializationError` |
+    /// | 3 | `NotFound` |
+    /// | 4 | `InvalidKey` |
+    /// | 5 | `InvalidValue` |
+    /// | 6 | `InvalidDate` |
+    /// | 7 | `InvalidVenue` |
+    /// | 8 | `ValidationError` |
+    /// | 9 | `Conflict` |
+    /// | 10 | `KeyTooLarge` |
+    /// | 11 | `ValueTooLarge` |
+    /// | 12 | `KeyExists` |
+    /// | 13 | `QuotaExceeded` |
+    pub fn code(&self) -> u16 {
+        match self {
+            StoreError::IoError { .. } => 1,
+            StoreError::SerializationError { .. } => 2,
+            StoreError::NotFound { .. } => 3,
+            StoreError::InvalidKey => 4,
+            StoreError::InvalidValue { .. } => 5,
+            StoreError::InvalidDate { .. } => 6,
+            StoreError::InvalidVenue { .. } => 7,
+            StoreError::ValidationError { .. } => 8,
+            StoreError::Conflict(_) => 9,
+            StoreError::KeyTooLarge { .. } => 10,
+            StoreError::ValueTooLarge { .. } => 11,
+            StoreError::KeyExists { .. } => 12,
+            StoreError::QuotaExceeded { .. } => 13,
+        }
+    }
+
+    /// エラーの種類
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            StoreError::IoError { .. } => ErrorKind::Io,
+            StoreError::SerializationError { .. } => ErrorKind::Serialization,
+            StoreError::NotFound { .. } => ErrorKind::NotFound,
+            StoreError::InvalidKey => ErrorKind::InvalidKey,
+            StoreError::InvalidValue { .. } => ErrorKind::InvalidValue,
+            StoreError::InvalidDate { .. } => ErrorKind::InvalidDate,
+            StoreError::InvalidVenue { .. } => ErrorKind::InvalidVenue,
+            StoreError::ValidationError { .. } => ErrorKind::Validation,
+            StoreError::Conflict(_) => ErrorKind::Conflict,
+            StoreError::KeyTooLarge { .. } => ErrorKind::KeyTooLarge,
+            StoreError::ValueTooLarge { .. } => ErrorKind::ValueTooLarge,
+            StoreError::KeyExists { .. } => ErrorKind::KeyExists,
+            StoreError::QuotaExceeded { .. } => ErrorKind::QuotaExceeded,
+        }
+    }
 }
 
 impl fmt::Display for StoreError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            StoreError::IoError(msg) => write!(f, "IO error: {}", msg),
-            StoreError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
-            StoreError::NotFound => write!(f, "Key not found"),
+            StoreError::IoError { source, path: Some(path), context: Some(context) } => {
+                write!(f, "IO error: {} {:?}: {}", context, path, source)
+            }
+            StoreError::IoError { source, path: Some(path), context: None } => {
+                write!(f, "IO error at {:?}: {}", path, source)
+            }
+            StoreError::IoError { source, .. } => write!(f, "IO error: {}", source),
+            StoreError::SerializationError { key: Some(key), source } => {
+                write!(f, "Serialization error for key {:?}: {}", key, source)
+            }
+            StoreError::SerializationError { key: None, source } => write!(f, "Serialization error: {}", source),
+            StoreError::NotFound { key } => write!(f, "Key not found: {:?}", key),
             StoreError::InvalidKey => write!(f, "Invalid key"),
-            StoreError::InvalidValue => write!(f, "Invalid value"),
+            StoreError::InvalidValue { key: Some(key), reason } => {
+                write!(f, "Invalid value for key {:?}: {}", key, reason)
+            }
+            StoreError::InvalidValue { key: None, reason } => write!(f, "Invalid value: {}", reason),
+            StoreError::InvalidDate { input, reason } if input.is_empty() => write!(f, "Invalid date: {}", reason),
+            StoreError::InvalidDate { input, reason } => write!(f, "Invalid date {:?}: {}", input, reason),
+            StoreError::InvalidVenue { field, value } => {
+                write!(f, "Invalid venue: {} = {:?}", field, value)
+            }
+            StoreError::ValidationError { field, message } => {
+                write!(f, "Validation error: {}: {}", field, message)
+            }
+            StoreError::Conflict(key) => write!(f, "Conflicting data for key: {:?}", key),
+            StoreError::KeyTooLarge { size, limit } => {
+                write!(f, "Key too large: {} bytes (limit {})", size, limit)
+            }
+            StoreError::ValueTooLarge { size, limit } => {
+                write!(f, "Value too large: {} bytes (limit {})", size, limit)
+            }
+            StoreError::KeyExists { key } => write!(f, "Key already exists: {:?}", key),
+            StoreError::QuotaExceeded { quota, limit, attempted } => {
+                write!(f, "Quota exceeded: {} {} (limit {})", attempted, quota, limit)
+            }
         }
     }
 }
 
-impl std::error::Error for StoreError {}
+impl Error for StoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StoreError::IoError { source, .. } => Some(source.as_ref()),
+            StoreError::SerializationError { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl From<std::io::Error> for StoreError {
     fn from(error: std::io::Error) -> Self {
-        StoreError::IoError(error.to_string())
+        StoreError::IoError { source: Arc::new(error), path: None, context: None }
+    }
+}
+
+/// パスを持たない入出力エラーは元の `std::io::Error` に戻し、それ以外は対応する種類の `std::io::Error` で包む
+impl From<StoreError> for std::io::Error {
+    fn from(error: StoreError) -> Self {
+        let kind = match &error {
+            StoreError::IoError { path: None, context: None, .. } => {
+                let StoreError::IoError { source, .. } = error else { unreachable!() };
+                return Arc::try_unwrap(source).unwrap_or_else(|shared| std::io::Error::new(shared.kind(), shared.to_string()));
+            }
+            StoreError::IoError { source, .. } => source.kind(),
+            StoreError::NotFound { .. } => std::io::ErrorKind::NotFound,
+            StoreError::SerializationError { .. } => std::io::ErrorKind::InvalidData,
+            StoreError::Conflict(_) | StoreError::KeyExists { .. } => std::io::ErrorKind::AlreadyExists,
+            StoreError::QuotaExceeded { .. } => std::io::ErrorKind::QuotaExceeded,
+            _ if error.is_invalid_input() => std::io::ErrorKind::InvalidInput,
+            _ => std::io::ErrorKind::Other,
+        };
+        std::io::Error::new(kind, error)
+    }
+}
+
+/// 入力文字列は `with_input` で付け加える
+impl From<chrono::ParseError> for StoreError {
+    fn from(error: chrono::ParseError) -> Self {
+        StoreError::invalid_date("", error)
     }
 }
 
 impl From<serde_json::Error> for StoreError {
     fn from(error: serde_json::Error) -> Self {
-        StoreError::SerializationError(error.to_string())
+        StoreError::serialization(error)
     }
 }
 
-pub type Result<T> = std::result::Result<T, StoreError>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, StoreError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_and_kind() {
+        let error: StoreError = serde_json::from_str::<u32>("x").unwrap_err().into();
+        assert_eq!(error.kind(), ErrorKind::Serialization);
+        assert!(error.source().unwrap().downcast_ref::<serde_json::Error>().is_some());
+
+        let error = error.with_key("M:202509:tokyo_bay_cup");
+        assert_eq!(error.key(), Some("M:202509:tokyo_bay_cup"));
+        assert!(error.to_string().contains("M:202509:tokyo_bay_cup"));
+        // 先に付けたキーは上書きしない
+        assert_eq!(error.clone().with_key("other").key(), Some("M:202509:tokyo_bay_cup"));
+
+        let error: StoreError = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied").into();
+        assert_eq!(error.kind(), ErrorKind::Io);
+        let io = error.source().unwrap().downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io.kind(), std::io::ErrorKind::PermissionDenied);
+
+        assert_eq!(StoreError::not_found("key").kind(), ErrorKind::NotFound);
+        assert_eq!(StoreError::invalid_value("bad").to_string(), "Invalid value: bad");
+        assert!(StoreError::InvalidKey.source().is_none());
+    }
+
+    #[test]
+    fn test_io_error_with_path() {
+        let source = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let error = StoreError::io_at(source, "cannot read file", "data/db.json");
+        assert_eq!(error.path(), Some("data/db.json"));
+        assert!(error.to_string().starts_with("IO error: cannot read file \"data/db.json\": "), "{}", error);
+        assert_eq!(error.kind(), ErrorKind::Io);
+
+        // パス付きのエラーは std::io::Error に戻しても説明を失わない
+        let io = std::io::Error::from(error);
+        assert_eq!(io.kind(), std::io::ErrorKind::PermissionDenied);
+        assert!(io.to_string().contains("data/db.json"));
+        assert_eq!(StoreError::from(std::io::Error::from(std::io::ErrorKind::NotFound)).path(), None);
+    }
+
+    #[test]
+    fn test_classification() {
+        let io = |kind| StoreError::from(std::io::Error::from(kind));
+        let serialization = StoreError::serialization("bad");
+        let cases = [
+            // (エラー, code, not_found, invalid_input, io, retryable)
+            (io(std::io::ErrorKind::PermissionDenied), 1, false, false, true, false),
+            (io(std::io::ErrorKind::Interrupted), 1, false, false, true, true),
+            (io(std::io::ErrorKind::TimedOut), 1, false, false, true, true),
+            (io(std::io::ErrorKind::ResourceBusy), 1, false, false, true, true),
+            (serialization, 2, false, false, false, false),
+            (StoreError::not_found("key"), 3, true, false, false, false),
+            (StoreError::InvalidKey, 4, false, true, false, false),
+            (StoreError::invalid_value("bad"), 5, false, true, false, false),
+            (StoreError::invalid_date("2025-13-01", "input is out of range"), 6, false, true, false, false),
+            (StoreError::InvalidVenue { field: "venue_id".into(), value: "99".into() }, 7, false, true, false, false),
+            (StoreError::ValidationError { field: "grade".into(), message: "empty".into() }, 8, false, true, false, false),
+            (StoreError::Conflict("key".into()), 9, false, false, false, false),
+            (StoreError::KeyTooLarge { size: 2000, limit: 1024 }, 10, false, true, false, false),
+            (StoreError::ValueTooLarge { size: 2000, limit: 1024 }, 11, false, true, false, false),
+            (StoreError::KeyExists { key: "key".into() }, 12, false, false, false, false),
+            (StoreError::QuotaExceeded { quota: Quota::Entries, limit: 10, attempted: 11 }, 13, false, false, false, false),
+        ];
+        for (error, code, not_found, invalid_input, is_io, retryable) in cases {
+            assert_eq!(error.code(), code, "{:?}", error);
+            assert_eq!(error.is_not_found(), not_found, "{:?}", error);
+            assert_eq!(error.is_invalid_input(), invalid_input, "{:?}", error);
+            assert_eq!(error.is_io(), is_io, "{:?}", error);
+            assert_eq!(error.is_retryable(), retryable, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_into_io_error() {
+        let original = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        let error: std::io::Error = StoreError::from(original).into();
+        assert_eq!(error.kind(), std::io::ErrorKind::PermissionDenied);
+        assert_eq!(error.to_string(), "denied");
+
+        let error: std::io::Error = StoreError::not_found("key").into();
+        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+        assert!(error.get_ref().unwrap().downcast_ref::<StoreError>().unwrap().is_not_found());
+        assert_eq!(std::io::Error::from(StoreError::InvalidKey).kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(std::io::Error::from(StoreError::serialization("bad")).kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(std::io::Error::from(StoreError::KeyExists { key: "key".into() }).kind(), std::io::ErrorKind::AlreadyExists);
+        let quota = StoreError::QuotaExceeded { quota: Quota::Bytes, limit: 100, attempted: 120 };
+        assert_eq!(quota.to_string(), "Quota exceeded: 120 bytes (limit 100)");
+        assert_eq!(std::io::Error::from(quota).kind(), std::io::ErrorKind::QuotaExceeded);
+    }
+
+    #[test]
+    fn test_invalid_date_from_chrono() {
+        let parse = |input: &str| chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d");
+
+        let error = StoreError::from(parse("2025-02-30").unwrap_err()).with_input("2025-02-30");
+        assert_eq!(error.kind(), ErrorKind::InvalidDate);
+        assert_eq!(error.to_string(), "Invalid date \"2025-02-30\": input is out of range");
+
+        let error = StoreError::from(parse("x025-09-10").unwrap_err());
+        assert_eq!(error.to_string(), "Invalid date: input contains invalid characters");
+        let error = error.with_input("x025-09-10");
+        assert!(error.to_string().contains("x025-09-10"));
+        // 既に入力を持つエラーはそのまま
+        assert!(!error.with_input("other").to_string().contains("other"));
+    }
+}
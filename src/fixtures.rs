@@ -0,0 +1,404 @@
+//! 合成データモジュール (`test-util` フィーチャー)
+//!
+//! ベンチマークや回帰テスト向けに、シードから決定的に月別スケジュールとレースデータを生成する。
+//! 同じシードからはどのマシンでも同じデータが得られる (乱数は外部クレートに依存しない SplitMix64)
+
+use crate::venues::STANDARD_VENUES;
+use crate::{generate_tournament_id, BoatRaceEngine, Grade, KeyValueStore, MemoryStore, MonthlySchedule, RaceEvent, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// `seed_engine` が生成する最初の月
+pub const SEED_START_MONTH: u32 = 202501;
+
+/// グレードごとの大会名
+const SG_NAMES: [&str; 8] = [
+    "ボートレースクラシック",
+    "ボートレースオールスター",
+    "グランドチャンピオン",
+    "オーシャンカップ",
+    "ボートレースメモリアル",
+    "ボートレースダービー",
+    "チャレンジカップ",
+    "グランプリ",
+];
+const G1_NAMES: [&str; 4] = ["周年記念", "ダイヤモンドカップ", "地区選手権", "レディースチャンピオン"];
+const G2_NAMES: [&str; 3] = ["モーターボート大賞", "秩父宮妃記念杯", "全国ボートレース甲子園"];
+const G3_NAMES: [&str; 4] = ["オールレディース", "マスターズリーグ", "イースタンヤング", "企業杯"];
+const IPPAN_NAMES: [&str; 10] = [
+    "スポーツニッポン杯",
+    "日刊スポーツ杯",
+    "報知新聞社杯",
+    "サンケイスポーツ杯",
+    "中日スポーツ賞",
+    "デイリースポーツ杯",
+    "西日本スポーツ杯",
+    "市長杯",
+    "ルーキーシリーズ",
+    "夏の特選競走",
+];
+
+/// `seed_engine` が書き込むレースデータ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyntheticRace {
+    /// その日のレース番号 (1-12)
+    pub race_number: u32,
+    /// レース名 ("予選"、"準優勝戦"、"優勝戦")
+    pub race_name: String,
+    /// 出走表 (枠番, 登録番号)
+    pub entries: Vec<(u8, u32)>,
+}
+
+/// `seed_engine` で書き込んだ件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeedStats {
+    /// 書き込んだ月別スケジュールの数
+    pub months: usize,
+    /// 書き込んだ大会の数
+    pub tournaments: usize,
+    /// 書き込んだレースの数
+    pub races: usize,
+}
+
+/// シードから決定的に値を生成する乱数生成器 (SplitMix64)
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// 0..bound の値 (bound は 1 以上)
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// シードと月から、その月用のシードを作る (月ごとに独立した列になるようにする)
+fn month_seed(seed: u64, year_month: u32) -> u64 {
+    SplitMix64(seed ^ (u64::from(year_month) << 32)).next_u64()
+}
+
+/// 月の日数
+fn days_in_month(year_month: u32) -> u32 {
+    let (year, month) = ((year_month / 100) as i32, year_month % 100);
+    let first = NaiveDate::from_ymd_opt(year, month, 1).expect("valid year_month");
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .expect("valid year_month");
+    (next - first).num_days() as u32
+}
+
+/// 月別スケジュールを生成
+///
+/// 会場は全国24場から、グレードは一般戦が多くなるよう重み付けして選び、大会名はグレードに応じた
+/// 実在しそうな名称に会場名と節番号 (`2025-09 第3節` の形式) を付ける。節番号は月と順番から決まるため、
+/// 大会IDは月をまたいでも重ならない。イベントは開始日順に並ぶ
+///
+/// # Arguments
+/// * `year_month` - 年月 (例: 202509)
+/// * `tournaments` - 大会数
+/// * `rng_seed` - シード (同じシードからは同じスケジュールを生成する)
+///
+/// # Returns
+/// 月別スケジュール
+pub fn generate_schedule(year_month: u32, tournaments: usize, rng_seed: u64) -> MonthlySchedule {
+    let mut rng = SplitMix64(month_seed(rng_seed, year_month));
+    let days = days_in_month(year_month);
+    let (year, month) = (year_month / 100, year_month % 100);
+
+    let mut events: Vec<RaceEvent> = (0..tournaments)
+        .map(|index| {
+            let (venue_id, venue_name, _, _) = STANDARD_VENUES[rng.below(STANDARD_VENUES.len() as u64) as usize];
+            let (grade, name, duration_days) = match rng.below(100) {
+                0..=2 => (Grade::SG, rng.pick(&SG_NAMES), 6),
+                3..=12 => (Grade::G1, rng.pick(&G1_NAMES), 6),
+                13..=19 => (Grade::G2, rng.pick(&G2_NAMES), 6),
+                20..=39 => (Grade::G3, rng.pick(&G3_NAMES), 5),
+                _ => (Grade::Ippan, rng.pick(&IPPAN_NAMES), 4 + rng.below(3) as u32),
+            };
+            let start_day = 1 + rng.below(u64::from(days)) as u32;
+            RaceEvent {
+                venue_id,
+                venue_name: venue_name.to_string(),
+                event_name: format!("{}{} {}-{:02} 第{}節", venue_name, name, year, month, index + 1),
+                grade,
+                start_date: format!("{}-{:02}-{:02}", year, month, start_day),
+                duration_days,
+                notes: None,
+            }
+        })
+        .collect();
+    events.sort_by(|a, b| a.start_date.cmp(&b.start_date).then(a.venue_id.cmp(&b.venue_id)));
+    MonthlySchedule { year_month: format!("{}-{:02}", year, month), events }
+}
+
+/// 大会のレースデータを生成
+///
+/// 1日12レースとして開始日から順に並べる (10:30 JST から30分間隔)。
+/// 最終日前日の11Rを準優勝戦、最終日の12Rを優勝戦とする
+fn generate_races(event: &RaceEvent, races: usize, rng: &mut SplitMix64) -> Vec<(u64, SyntheticRace)> {
+    let start = NaiveDate::parse_from_str(&event.start_date, "%Y-%m-%d").expect("generated start_date");
+    (0..races)
+        .map(|index| {
+            let day = (index / 12) as u64;
+            let race_number = (index % 12) as u32 + 1;
+            let date = start + chrono::Days::new(day);
+            // 10:30 JST = 01:30 UTC
+            let time = date.and_hms_opt(1, 30, 0).expect("valid time") + chrono::Duration::minutes(30 * i64::from(race_number - 1));
+            let race_name = match race_number {
+                12 if day + 1 == u64::from(event.duration_days) => "優勝戦",
+                11 if day + 2 == u64::from(event.duration_days) => "準優勝戦",
+                _ => "予選",
+            };
+            let entries = (1..=6).map(|lane| (lane, 3000 + rng.below(2400) as u32)).collect();
+            (
+                time.and_utc().timestamp_millis() as u64,
+                SyntheticRace { race_number, race_name: race_name.to_string(), entries },
+            )
+        })
+        .collect()
+}
+
+/// `seed_engine` が書き込む月 (`SEED_START_MONTH` から順に `months` か月)
+///
+/// # Returns
+/// 年月 (例: 202501) の一覧
+pub fn seed_months(months: usize) -> Vec<u32> {
+    let first = NaiveDate::from_ymd_opt((SEED_START_MONTH / 100) as i32, SEED_START_MONTH % 100, 1).expect("valid start month");
+    (0..months)
+        .map(|offset| {
+            let date = first + chrono::Months::new(offset as u32);
+            date.year() as u32 * 100 + date.month()
+        })
+        .collect()
+}
+
+/// 合成データをエンジンに書き込む
+///
+/// `SEED_START_MONTH` から `months` か月分、各月 `tournaments_per_month` 大会のスケジュールを
+/// `generate_schedule` で作り、各大会に `races_per_tournament` 件の `SyntheticRace` を書き込む。
+/// 月ごとにスケジュール1回とレースの一括書き込み1回でまとめて書き込む。
+///
+/// 最終更新時刻はエンジンの時計で記録されるため、ストアの内容をバイト単位で比べる場合は
+/// `BoatRaceEngine::with_clock` で時計を固定する
+///
+/// # Arguments
+/// * `engine` - 書き込み先のエンジン
+/// * `months` - 月数
+/// * `tournaments_per_month` - 1か月あたりの大会数
+/// * `races_per_tournament` - 1大会あたりのレース数
+/// * `seed` - シード
+///
+/// # Returns
+/// 書き込んだ月・大会・レースの数
+///
+/// ```
+/// use norimaki_db::fixtures::seed_engine;
+/// use norimaki_db::{BoatRaceEngine, MemoryStore};
+///
+/// let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_clock(|| 0);
+/// let stats = seed_engine(&mut engine, 2, 5, 24, 42)?;
+/// assert_eq!((stats.months, stats.tournaments, stats.races), (2, 10, 240));
+/// assert_eq!(engine.get_monthly_schedule(202502)?.events.len(), 5);
+/// # Ok::<(), norimaki_db::StoreError>(())
+/// ```
+pub fn seed_engine<K: KeyValueStore>(
+    engine: &mut BoatRaceEngine<K>,
+    months: usize,
+    tournaments_per_month: usize,
+    races_per_tournament: usize,
+    seed: u64,
+) -> Result<SeedStats> {
+    let mut stats = SeedStats::default();
+    for year_month in seed_months(months) {
+        let schedule = generate_schedule(year_month, tournaments_per_month, seed);
+        engine.put_monthly_schedule(&schedule)?;
+
+        let mut rng = SplitMix64(month_seed(seed, year_month).wrapping_add(1));
+        let mut races = Vec::with_capacity(schedule.events.len() * races_per_tournament);
+        for event in &schedule.events {
+            let tournament_id = generate_tournament_id(&event.venue_name, &event.event_name);
+            for (timestamp, race) in generate_races(event, races_per_tournament, &mut rng) {
+                races.push((tournament_id.clone(), timestamp, race));
+            }
+        }
+        stats.races += engine.put_races_bulk_multi(&races)?;
+        stats.tournaments += schedule.events.len();
+        stats.months += 1;
+    }
+    Ok(stats)
+}
+
+/// ディレクトリの中に空の状態で作れるストア
+///
+/// ベンチマークや比較テストで、バックエンドを同じ手順で作るために使う
+pub trait DirStore: KeyValueStore + Sized {
+    /// バックエンドの名前 (ベンチマークの ID などに使う)
+    const NAME: &'static str;
+
+    /// 空のストアを作成
+    ///
+    /// # Arguments
+    /// * `dir` - ストアが使ってよい空のディレクトリ (ファイルを使わないストアは無視する)
+    fn create_in(dir: &Path) -> Result<Self>;
+}
+
+impl DirStore for MemoryStore {
+    const NAME: &'static str = "memory";
+
+    fn create_in(_dir: &Path) -> Result<Self> {
+        Ok(MemoryStore::new())
+    }
+}
+
+impl DirStore for crate::CompactMemoryStore {
+    const NAME: &'static str = "compact";
+
+    fn create_in(_dir: &Path) -> Result<Self> {
+        Ok(crate::CompactMemoryStore::new())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl DirStore for crate::FileStore {
+    const NAME: &'static str = "file";
+
+    fn create_in(dir: &Path) -> Result<Self> {
+        crate::FileStore::new(dir.join("norimaki.json"))
+    }
+}
+
+/// 一時ディレクトリ (Drop 時に中身ごと削除する)
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// システムの一時ディレクトリの下に、このプロセスで重ならない空のディレクトリを作成
+    ///
+    /// # Arguments
+    /// * `label` - ディレクトリ名に含める文字列
+    pub fn new(label: &str) -> Result<Self> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!("norimaki_{}_{}_{}", label, std::process::id(), NEXT.fetch_add(1, Ordering::Relaxed));
+        let path = std::env::temp_dir().join(name);
+        std::fs::remove_dir_all(&path).ok();
+        std::fs::create_dir_all(&path)
+            .map_err(|error| crate::StoreError::io_at(error, "cannot create directory", path.to_string_lossy()))?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// ディレクトリの中に `S` の空のストアを作成
+    pub fn create_store<S: DirStore>(&self) -> Result<S> {
+        S::create_in(&self.path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.path).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryStore;
+    use std::collections::BTreeSet;
+
+    fn store_contents<K: KeyValueStore>(store: &K) -> Vec<(String, String)> {
+        store.keys().unwrap().into_iter().map(|key| {
+            let value = store.get(&key).unwrap().unwrap();
+            (key, value)
+        }).collect()
+    }
+
+    #[test]
+    fn test_generate_schedule_is_deterministic_and_valid() {
+        let schedule = generate_schedule(202502, 40, 7);
+        assert_eq!(schedule, generate_schedule(202502, 40, 7));
+        assert_ne!(schedule, generate_schedule(202502, 40, 8));
+        assert_ne!(schedule.events, generate_schedule(202503, 40, 7).events);
+
+        assert_eq!(schedule.year_month, "2025-02");
+        assert_eq!(schedule.events.len(), 40);
+        assert_eq!(seed_months(14)[11..], [202512, 202601, 202602]);
+        let ids: BTreeSet<String> = schedule.events.iter().map(|event| generate_tournament_id(&event.venue_name, &event.event_name)).collect();
+        assert_eq!(ids.len(), 40);
+        for event in &schedule.events {
+            assert!(event.start_date.starts_with("2025-02-"), "{}", event.start_date);
+            assert_eq!(crate::venues::venue_name(event.venue_id), Some(event.venue_name.as_str()));
+        }
+        assert!(schedule.events.windows(2).all(|pair| pair[0].start_date <= pair[1].start_date));
+    }
+
+    #[test]
+    fn test_seed_engine_is_byte_identical_for_same_seed() {
+        let seeded = |seed| {
+            let mut engine = BoatRaceEngine::new(MemoryStore::new()).with_clock(|| 1_700_000_000_000);
+            let stats = seed_engine(&mut engine, 3, 6, 30, seed).unwrap();
+            assert_eq!(stats, SeedStats { months: 3, tournaments: 18, races: 540 });
+            store_contents(engine.store())
+        };
+        let first = seeded(2025);
+        assert_eq!(first, seeded(2025));
+        assert_ne!(first, seeded(2026));
+
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        seed_engine(&mut engine, 3, 6, 30, 2025).unwrap();
+        assert_eq!(engine.get_monthly_schedule(202503).unwrap().events.len(), 6);
+        let tournament_id = engine.get_monthly_schedule(202501).unwrap().events.iter().map(|event| generate_tournament_id(&event.venue_name, &event.event_name)).next().unwrap();
+        let races: Vec<SyntheticRace> = engine.get_tournament_races(&tournament_id).unwrap();
+        assert_eq!(races.len(), 30);
+        assert_eq!(races[0].race_number, 1);
+        assert_eq!(races[0].entries.len(), 6);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_seed_engine_writes_identical_files() {
+        let write = || {
+            let dir = TempDir::new("fixtures").unwrap();
+            let store: crate::FileStore = dir.create_store().unwrap();
+            let mut engine = BoatRaceEngine::new(store.with_sync_policy(crate::SyncPolicy::OnFlush)).with_clock(|| 0);
+            seed_engine(&mut engine, 2, 4, 12, 99).unwrap();
+            engine.store_mut().flush().unwrap();
+            std::fs::read(dir.path().join("norimaki.json")).unwrap()
+        };
+        assert_eq!(write(), write());
+    }
+
+    #[test]
+    fn test_temp_dir_is_fresh_and_removed() {
+        let dir = TempDir::new("fixtures").unwrap();
+        let other = TempDir::new("fixtures").unwrap();
+        assert_ne!(dir.path(), other.path());
+        std::fs::write(dir.path().join("file"), "x").unwrap();
+        let path = dir.path().to_path_buf();
+        drop(dir);
+        assert!(!path.exists());
+
+        let mut store: MemoryStore = other.create_store().unwrap();
+        assert_eq!(<MemoryStore as DirStore>::NAME, "memory");
+        store.put("Ua".to_string(), "1".to_string()).unwrap();
+    }
+}
@@ -0,0 +1,78 @@
+//! iCalendar 処理モジュール
+//!
+//! スケジュールの .ics 出力で使う RFC 5545 のテキストエスケープと行の折り返しを提供
+
+use std::io::Write;
+
+use crate::Result;
+
+/// 1行の最大オクテット数 (改行を除く)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// TEXT 型の値をエスケープする (バックスラッシュ・セミコロン・カンマ・改行)
+pub(crate) fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// コンテンツ行を 75 オクテットで折り返して書き込む
+///
+/// マルチバイト文字の途中では折り返さない。継続行は空白1文字で始まり、その空白も 75 オクテットに含める
+///
+/// # Arguments
+/// * `writer` - 出力先
+/// * `line` - 折り返し前のコンテンツ行 (改行を含まない)
+pub(crate) fn write_line<W: Write>(writer: &mut W, line: &str) -> Result<()> {
+    let mut limit = MAX_LINE_OCTETS;
+    let mut rest = line;
+    loop {
+        if rest.len() <= limit {
+            writer.write_all(rest.as_bytes())?;
+            writer.write_all(b"\r\n")?;
+            return Ok(());
+        }
+        let mut split = limit;
+        while !rest.is_char_boundary(split) {
+            split -= 1;
+        }
+        writer.write_all(&rest.as_bytes()[..split])?;
+        writer.write_all(b"\r\n ")?;
+        rest = &rest[split..];
+        limit = MAX_LINE_OCTETS - 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+    }
+
+    #[test]
+    fn test_write_line_folds_on_char_boundaries() {
+        let line = format!("SUMMARY:{}", "競".repeat(40));
+        let mut buffer = Vec::new();
+        write_line(&mut buffer, &line).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+
+        let lines: Vec<&str> = text.split("\r\n").filter(|line| !line.is_empty()).collect();
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert!(lines[1..].iter().all(|line| line.starts_with(' ')));
+        let unfolded: String = lines.iter().enumerate().map(|(i, line)| if i == 0 { *line } else { &line[1..] }).collect();
+        assert_eq!(unfolded, line);
+    }
+}
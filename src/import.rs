@@ -0,0 +1,149 @@
+//! 外部データ取り込みモジュール
+//!
+//! 公式サイトから取得したスケジュール JSON を `MonthlySchedule` に変換する機能を提供
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::{Grade, MonthlySchedule, RaceEvent, Result, StoreError};
+
+/// 公式データで使われる会場コード (jcd) の範囲
+const VENUE_CODES: std::ops::RangeInclusive<u64> = 1..=24;
+
+#[derive(Debug, Deserialize)]
+struct OfficialSchedule {
+    month: String,
+    races: Vec<Value>,
+}
+
+/// 公式サイト形式のスケジュール JSON を解析
+///
+/// 形式は `{"month":"2025-09","races":[{"jcd":4,"place":"平和島","title":"...","grade":"G1","start":"20250910","days":7}]}`。
+/// jcd → venue_id, place → venue_name, title → event_name, start (YYYYMMDD) → start_date (YYYY-MM-DD),
+/// days → duration_days に対応付ける
+///
+/// # Arguments
+/// * `json` - 公式サイト形式の JSON 文字列
+///
+/// # Returns
+/// 月別スケジュール。不正なエントリは `StoreError::ValidationError` で、field に `races[1].jcd` のような位置を含む
+pub fn parse_official_schedule(json: &str) -> Result<MonthlySchedule> {
+    let schedule: OfficialSchedule = serde_json::from_str(json)?;
+    if chrono::NaiveDate::parse_from_str(&format!("{}-01", schedule.month), "%Y-%m-%d").is_err() {
+        return Err(StoreError::ValidationError {
+            field: "month".to_string(),
+            message: format!("{:?} is not a YYYY-MM month", schedule.month),
+        });
+    }
+
+    let events = schedule
+        .races
+        .iter()
+        .enumerate()
+        .map(|(index, race)| parse_race(index, race))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(MonthlySchedule {
+        year_month: schedule.month,
+        events,
+    })
+}
+
+/// races 配列の1要素を RaceEvent に変換
+fn parse_race(index: usize, race: &Value) -> Result<RaceEvent> {
+    let error = |field: &str, message: String| StoreError::ValidationError {
+        field: format!("races[{}].{}", index, field),
+        message,
+    };
+    let race = race
+        .as_object()
+        .ok_or_else(|| StoreError::ValidationError {
+            field: format!("races[{}]", index),
+            message: "entry is not an object".to_string(),
+        })?;
+
+    let jcd = number_field(race, "jcd").map_err(|message| error("jcd", message))?;
+    if !VENUE_CODES.contains(&jcd) {
+        return Err(error("jcd", format!("venue code {} is outside {:?}", jcd, VENUE_CODES)));
+    }
+    let start = string_field(race, "start").map_err(|message| error("start", message))?;
+    let start_date = chrono::NaiveDate::parse_from_str(start, "%Y%m%d")
+        .map_err(|e| error("start", format!("{:?} is not a valid YYYYMMDD date ({})", start, e)))?;
+    let days = number_field(race, "days").map_err(|message| error("days", message))?;
+    let days = u32::try_from(days).map_err(|_| error("days", format!("{} is too large", days)))?;
+
+    Ok(RaceEvent {
+        venue_id: jcd as u32,
+        venue_name: string_field(race, "place").map_err(|message| error("place", message))?.to_string(),
+        event_name: string_field(race, "title").map_err(|message| error("title", message))?.to_string(),
+        grade: string_field(race, "grade")
+            .map_err(|message| error("grade", message))?
+            .parse::<Grade>()
+            .map_err(|e| match e {
+                StoreError::ValidationError { message, .. } => error("grade", message),
+                e => e,
+            })?,
+        start_date: start_date.format("%Y-%m-%d").to_string(),
+        duration_days: days,
+        notes: None,
+    })
+}
+
+fn string_field<'a>(race: &'a Map<String, Value>, name: &str) -> std::result::Result<&'a str, String> {
+    match race.get(name) {
+        Some(Value::String(value)) => Ok(value),
+        Some(other) => Err(format!("expected a string, found {}", other)),
+        None => Err("field is missing".to_string()),
+    }
+}
+
+fn number_field(race: &Map<String, Value>, name: &str) -> std::result::Result<u64, String> {
+    match race.get(name) {
+        Some(Value::Number(value)) => value.as_u64().ok_or_else(|| format!("expected a non-negative integer, found {}", value)),
+        // スクレイピング結果では数値が文字列になっていることがある
+        Some(Value::String(value)) => value.trim().parse().map_err(|_| format!("expected a non-negative integer, found {:?}", value)),
+        Some(other) => Err(format!("expected a non-negative integer, found {}", other)),
+        None => Err("field is missing".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    include!("../testdata/sample.rs");
+
+    fn field_error(result: Result<MonthlySchedule>) -> (String, String) {
+        match result {
+            Err(StoreError::ValidationError { field, message }) => (field, message),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_official_schedule() {
+        let schedule = parse_official_schedule(include_str!("../testdata/official_schedule.json")).unwrap();
+        assert_eq!(schedule, sample_data());
+    }
+
+    #[test]
+    fn test_parse_official_schedule_reports_malformed_entries() {
+        let malformed = include_str!("../testdata/official_schedule_malformed.json");
+        let (field, message) = field_error(parse_official_schedule(malformed));
+        assert_eq!(field, "races[1].jcd");
+        assert!(message.contains("25"), "{}", message);
+
+        // 残りの不正なエントリも1件ずつ確認する
+        let document: Value = serde_json::from_str(malformed).unwrap();
+        let single = |index: usize| {
+            let race = document["races"][index].clone();
+            serde_json::json!({ "month": "2025-09", "races": [race] }).to_string()
+        };
+        assert_eq!(field_error(parse_official_schedule(&single(2))).0, "races[0].start");
+        assert_eq!(field_error(parse_official_schedule(&single(3))).0, "races[0].title");
+
+        let (field, _) = field_error(parse_official_schedule(r#"{"month":"2025-13","races":[]}"#));
+        assert_eq!(field, "month");
+        assert!(parse_official_schedule("not json").is_err());
+    }
+}
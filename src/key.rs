@@ -1,14 +1,631 @@
-/// 競艇データ用のキー管理モジュール
-/// 
-/// キー設計:
-/// - 月別ビュー: M + YYYYMM + 0x00 + tournament_id
-/// - 大会データ: T + tournament_id + 0x00 + timestamp_be
+//! 競艇データ用のキー管理モジュール
+//! 
+//! キー設計:
+//! - 月別ビュー: M + YYYYMM + 0x00 + tournament_id
+//! - 大会データ: T + tournament_id + 0x00 + timestamp_be
+//! - 日別ビュー: D + YYYYMMDD + 0x00 + tournament_id
+//! - 会場マスタ: V + venue_id (10桁ゼロ埋め)
+//! - 選手マスタ: R + racer_id (10桁ゼロ埋め)
+//! - レース結果: F + tournament_id + 0x00 + timestamp_be
+//! - 気象条件: W + venue_id (10桁ゼロ埋め) + YYYYMMDD
+//! - 払戻金: P + tournament_id + 0x00 + timestamp_be
+//! - オッズ履歴: O + tournament_id + 0x00 + race_timestamp_be + 0x00 + snapshot_time_be
+//! - 会場インデックス: I + "_venue" + 0x00 + venue_id (10桁ゼロ埋め) + 0x00 + YYYYMM + 0x00 + tournament_id
+//! - 名前インデックス: I + "_name" + 0x00 + token + 0x00 + YYYYMM + 0x00 + tournament_id
+//! - 出走者インデックス: I + "_racer" + 0x00 + racer_id (10桁ゼロ埋め) + 0x00 + tournament_id + 0x00 + timestamp_be
+//! - 出走者一覧: I + "_participants" + 0x00 + tournament_id + 0x00 + timestamp_be
+//! - 大会メタデータ: X + tournament_id + 0x00 + meta_key
+//! - メタデータ: 0x01 + "meta" + 0x00 + name (エンジン内部用。空の月のマーカーは name = "empty_month" + 0x00 + YYYYMM、
+//!   大会の最終更新時刻は name = "last_modified" + 0x00 + tournament_id)
+//! - ユーザー領域: U + 任意の文字列 (エンジンは読み書きも集計もしない)
+
+use crate::StoreError;
+use std::fmt;
+use std::str::FromStr;
 
 // キープレフィックス定義
 pub const PREFIX_MONTHLY: u8 = b'M';     // 月別ビュー
 pub const PREFIX_TOURNAMENT: u8 = b'T';  // 大会データ
+pub const PREFIX_DAILY: u8 = b'D';       // 日別ビュー
+pub const PREFIX_VENUE: u8 = b'V';       // 会場マスタ
+pub const PREFIX_RACER: u8 = b'R';       // 選手マスタ
+pub const PREFIX_RESULT: u8 = b'F';      // レース結果
+pub const PREFIX_ODDS: u8 = b'O';        // オッズ履歴
+pub const PREFIX_PAYOUT: u8 = b'P';      // 払戻金
+pub const PREFIX_CONDITIONS: u8 = b'W';  // 気象条件
+pub const PREFIX_INDEX: u8 = b'I';       // セカンダリインデックス
+pub const PREFIX_TOURNAMENT_META: u8 = b'X'; // 大会メタデータ
+pub const PREFIX_META: u8 = 0x01;        // エンジン内部のメタデータ
+pub const PREFIX_USER: u8 = b'U';        // ユーザー領域 (エンジンは触れない)
 pub const SEPARATOR: u8 = 0x00;          // セパレータ
 
+/// エンジン内部のメタデータキーの共通部分
+const META_KEY_PREFIX: &str = "\x01meta\x00";
+
+/// 会場インデックスの名前 (インデックスプレフィックスの直後に続く)
+const VENUE_INDEX_NAME: &str = "_venue";
+/// 名前インデックスの名前 (インデックスプレフィックスの直後に続く)
+const NAME_INDEX_NAME: &str = "_name";
+/// 出走者インデックスの名前 (インデックスプレフィックスの直後に続く)
+const RACER_INDEX_NAME: &str = "_racer";
+/// 出走者一覧の名前 (インデックスプレフィックスの直後に続く)
+const PARTICIPANTS_INDEX_NAME: &str = "_participants";
+/// 会場インデックスに入れられる会場IDの上限 (3桁)
+const MAX_VENUE_INDEX_ID: u32 = 999;
+/// 出走者インデックスに入れられる登録番号の上限 (6桁)
+const MAX_RACER_INDEX_ID: u32 = 999_999;
+
+/// 統計カウンタを保存するメタデータキー
+pub(crate) const STATISTICS_META_KEY: &str = "\x01meta\x00statistics";
+
+/// 空のスケジュールとして取り込まれた月を示すメタデータキーを生成
+///
+/// # Arguments
+/// * `year_month` - YYYYMM形式の年月
+///
+/// # Returns
+/// "\x01meta\x00empty_month\x00202509" のようなキー
+pub(crate) fn empty_month_marker_key(year_month: u32) -> String {
+    format!("{}meta{}empty_month{}{:06}", PREFIX_META as char, SEPARATOR as char, SEPARATOR as char, year_month)
+}
+
+/// 大会の最終更新時刻を保存するメタデータキーの共通部分
+const LAST_MODIFIED_META_PREFIX: &str = "\x01meta\x00last_modified\x00";
+
+/// 大会の最終更新時刻を保存するメタデータキーを生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// "\x01meta\x00last_modified\x00tokyo_bay_cup" のようなキー
+pub(crate) fn last_modified_key(tournament_id: &str) -> String {
+    format!("{}{}", LAST_MODIFIED_META_PREFIX, tournament_id)
+}
+
+/// 最終更新時刻のメタデータキーから大会IDを取り出す
+///
+/// # Returns
+/// 大会ID（最終更新時刻のキーでない場合は None）
+pub(crate) fn parse_last_modified_key(key: &str) -> Option<&str> {
+    key.strip_prefix(LAST_MODIFIED_META_PREFIX)
+}
+
+/// 月別ビュー・レースデータのキーから大会IDを取り出す (最終更新時刻の対象の判定に使う)
+///
+/// `Key` として解析した場合と同じ形式の検査を行うが、大会IDは複製しない
+///
+/// # Returns
+/// 大会ID（月別ビュー・レースデータのキーでない場合は None）
+pub(crate) fn parse_written_tournament_id(key: &str) -> Option<&str> {
+    let body = key.get(1..)?;
+    match *key.as_bytes().first()? {
+        PREFIX_MONTHLY => {
+            let (digits, tournament_id) = body.split_once(SEPARATOR as char)?;
+            parse_digits(digits, 6).ok().map(|_| tournament_id)
+        }
+        PREFIX_TOURNAMENT => {
+            let (tournament_id, timestamp) = body.rsplit_once(SEPARATOR as char)?;
+            decode_timestamp(timestamp).ok().map(|_| tournament_id)
+        }
+        _ => None,
+    }
+}
+
+/// キーの名前空間
+///
+/// `User` 以外はエンジンが予約しており、キーの形式も決まっている
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Namespace {
+    /// 月別ビュー (M)
+    Monthly,
+    /// 大会データ (T)
+    Tournament,
+    /// 日別ビュー (D)
+    Daily,
+    /// 会場マスタ (V)
+    Venue,
+    /// 選手マスタ (R)
+    Racer,
+    /// レース結果 (F)
+    Result,
+    /// 気象条件 (W)
+    Conditions,
+    /// 払戻金 (P)
+    Payout,
+    /// オッズ履歴 (O)
+    Odds,
+    /// セカンダリインデックス・出走者一覧 (I)
+    Index,
+    /// 大会メタデータ (X)
+    TournamentMeta,
+    /// エンジン内部のメタデータ (0x01)
+    Meta,
+    /// ユーザー領域 (U)
+    User,
+}
+
+impl Namespace {
+    /// 全ての名前空間
+    pub const ALL: [Namespace; 13] = [
+        Namespace::Monthly,
+        Namespace::Tournament,
+        Namespace::Daily,
+        Namespace::Venue,
+        Namespace::Racer,
+        Namespace::Result,
+        Namespace::Conditions,
+        Namespace::Payout,
+        Namespace::Odds,
+        Namespace::Index,
+        Namespace::TournamentMeta,
+        Namespace::Meta,
+        Namespace::User,
+    ];
+
+    /// キーの先頭バイト
+    pub fn prefix(self) -> u8 {
+        match self {
+            Namespace::Monthly => PREFIX_MONTHLY,
+            Namespace::Tournament => PREFIX_TOURNAMENT,
+            Namespace::Daily => PREFIX_DAILY,
+            Namespace::Venue => PREFIX_VENUE,
+            Namespace::Racer => PREFIX_RACER,
+            Namespace::Result => PREFIX_RESULT,
+            Namespace::Conditions => PREFIX_CONDITIONS,
+            Namespace::Payout => PREFIX_PAYOUT,
+            Namespace::Odds => PREFIX_ODDS,
+            Namespace::Index => PREFIX_INDEX,
+            Namespace::TournamentMeta => PREFIX_TOURNAMENT_META,
+            Namespace::Meta => PREFIX_META,
+            Namespace::User => PREFIX_USER,
+        }
+    }
+
+    /// 先頭バイトから名前空間を取得
+    ///
+    /// # Returns
+    /// 予約済み・ユーザー領域のプレフィックスでない場合は None
+    pub fn from_prefix(prefix: u8) -> Option<Namespace> {
+        Namespace::ALL.into_iter().find(|namespace| namespace.prefix() == prefix)
+    }
+
+    /// エンジンが予約している名前空間か
+    pub fn is_reserved(self) -> bool {
+        self != Namespace::User
+    }
+}
+
+/// キーを名前空間に分類
+///
+/// 先頭バイトだけでなく、その名前空間のキー形式 (セパレータの位置・固定桁の数値など) を満たす場合に限り分類する。
+/// "Manual_note" のように予約済みのプレフィックスで始まるだけのキーは None。
+/// ユーザー領域 (U) のキーは形式を問わない
+///
+/// # Arguments
+/// * `key` - ストアのキー
+///
+/// # Returns
+/// 名前空間（形式を満たさない場合は None）
+pub fn classify_key(key: &str) -> Option<Namespace> {
+    match key.as_bytes().first() {
+        Some(&PREFIX_USER) => Some(Namespace::User),
+        Some(&PREFIX_META) => key
+            .strip_prefix(META_KEY_PREFIX)
+            .filter(|name| !name.is_empty())
+            .map(|_| Namespace::Meta),
+        _ => key.parse::<Key>().ok().map(|key| key.namespace()),
+    }
+}
+
+/// ユーザー領域のキーを生成
+///
+/// ユーザー領域のキーはエンジンのスキャン・統計・整合性チェック・修復の対象にならない
+///
+/// # Arguments
+/// * `name` - 任意の名前
+///
+/// # Returns
+/// "Umy_note" のようなキー
+pub fn user_key(name: &str) -> String {
+    format!("{}{}", PREFIX_USER as char, name)
+}
+
+/// 型付きキー
+///
+/// `Display` で現行の文字列形式を生成し、`FromStr` でその文字列から復元する
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// 月別ビュー: M + YYYYMM + 0x00 + tournament_id
+    Monthly { year_month: u32, tournament_id: String },
+    /// 大会データ: T + tournament_id + 0x00 + timestamp_be
+    Tournament { tournament_id: String, timestamp: u64 },
+    /// 日別ビュー: D + YYYYMMDD + 0x00 + tournament_id
+    Daily { date: u32, tournament_id: String },
+    /// 会場マスタ: V + venue_id
+    Venue { venue_id: u32 },
+    /// 選手マスタ: R + racer_id
+    Racer { racer_id: u32 },
+    /// レース結果: F + tournament_id + 0x00 + timestamp_be
+    Result { tournament_id: String, timestamp: u64 },
+    /// 気象条件: W + venue_id + YYYYMMDD
+    Conditions { venue_id: u32, date: u32 },
+    /// 払戻金: P + tournament_id + 0x00 + timestamp_be
+    Payout { tournament_id: String, timestamp: u64 },
+    /// オッズ履歴: O + tournament_id + 0x00 + race_timestamp_be + 0x00 + snapshot_time_be
+    Odds { tournament_id: String, race_timestamp: u64, snapshot_time: u64 },
+    /// 会場インデックス: I + "_venue" + 0x00 + venue_id + 0x00 + YYYYMM + 0x00 + tournament_id
+    VenueIndex { venue_id: u32, year_month: u32, tournament_id: String },
+    /// 名前インデックス: I + "_name" + 0x00 + token + 0x00 + YYYYMM + 0x00 + tournament_id
+    NameIndex { token: String, year_month: u32, tournament_id: String },
+    /// 出走者インデックス: I + "_racer" + 0x00 + racer_id + 0x00 + tournament_id + 0x00 + timestamp_be
+    RacerIndex { racer_id: u32, tournament_id: String, timestamp: u64 },
+    /// 出走者一覧: I + "_participants" + 0x00 + tournament_id + 0x00 + timestamp_be
+    Participants { tournament_id: String, timestamp: u64 },
+    /// 大会メタデータ: X + tournament_id + 0x00 + meta_key
+    TournamentMeta { tournament_id: String, meta_key: String },
+}
+
+impl Key {
+    /// このキーが属する名前空間のスキャン範囲を取得
+    ///
+    /// # Returns
+    /// 月別ビューならその月全体、大会データならその大会全体の (開始キー, 終了キー)
+    pub fn scan_range(&self) -> (String, String) {
+        match self {
+            Key::Monthly { year_month, .. } => monthly_scan_range(*year_month),
+            Key::Tournament { tournament_id, .. } => tournament_scan_range(tournament_id),
+            Key::Daily { date, .. } => daily_scan_range(*date),
+            Key::Venue { .. } => venue_scan_range(),
+            Key::Racer { .. } => racer_scan_range(),
+            Key::Result { tournament_id, .. } => result_scan_range(tournament_id),
+            Key::Conditions { venue_id, .. } => conditions_scan_range(*venue_id, 0, u32::MAX),
+            Key::Payout { tournament_id, .. } => payout_scan_range(tournament_id),
+            Key::Odds { tournament_id, race_timestamp, .. } => odds_scan_range(tournament_id, *race_timestamp),
+            Key::VenueIndex { venue_id, .. } => venue_index_scan_range(*venue_id),
+            Key::NameIndex { token, .. } => name_index_scan_range(token, None),
+            Key::RacerIndex { racer_id, .. } => racer_index_scan_range(*racer_id),
+            Key::Participants { .. } => index_namespace_range(PARTICIPANTS_INDEX_NAME),
+            Key::TournamentMeta { tournament_id, .. } => tournament_meta_scan_range(tournament_id),
+        }
+    }
+
+    /// キーが属する名前空間を取得
+    pub fn namespace(&self) -> Namespace {
+        match self {
+            Key::Monthly { .. } => Namespace::Monthly,
+            Key::Tournament { .. } => Namespace::Tournament,
+            Key::Daily { .. } => Namespace::Daily,
+            Key::Venue { .. } => Namespace::Venue,
+            Key::Racer { .. } => Namespace::Racer,
+            Key::Result { .. } => Namespace::Result,
+            Key::Conditions { .. } => Namespace::Conditions,
+            Key::Payout { .. } => Namespace::Payout,
+            Key::Odds { .. } => Namespace::Odds,
+            Key::VenueIndex { .. } | Key::NameIndex { .. } | Key::RacerIndex { .. } | Key::Participants { .. } => {
+                Namespace::Index
+            }
+            Key::TournamentMeta { .. } => Namespace::TournamentMeta,
+        }
+    }
+
+    /// キーが属する大会IDを取得
+    ///
+    /// # Returns
+    /// 大会IDを含むキーならその大会ID、会場・選手マスタや気象条件なら None
+    pub fn tournament_id(&self) -> Option<&str> {
+        match self {
+            Key::Monthly { tournament_id, .. }
+            | Key::Tournament { tournament_id, .. }
+            | Key::Daily { tournament_id, .. }
+            | Key::Result { tournament_id, .. }
+            | Key::Payout { tournament_id, .. }
+            | Key::Odds { tournament_id, .. }
+            | Key::VenueIndex { tournament_id, .. }
+            | Key::NameIndex { tournament_id, .. }
+            | Key::RacerIndex { tournament_id, .. }
+            | Key::Participants { tournament_id, .. }
+            | Key::TournamentMeta { tournament_id, .. } => Some(tournament_id),
+            Key::Venue { .. } | Key::Racer { .. } | Key::Conditions { .. } => None,
+        }
+    }
+
+    /// キー構造が一意に復元できるか検証
+    ///
+    /// # Returns
+    /// 大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+    pub fn validate(&self) -> crate::Result<()> {
+        match self {
+            Key::Monthly { tournament_id, .. }
+            | Key::Tournament { tournament_id, .. }
+            | Key::Daily { tournament_id, .. }
+            | Key::Result { tournament_id, .. }
+            | Key::Payout { tournament_id, .. }
+            | Key::Odds { tournament_id, .. }
+            | Key::Participants { tournament_id, .. } => {
+                validate_tournament_id(tournament_id)
+            }
+            // インデックスキーの会場IDは3桁、登録番号は6桁の固定幅
+            Key::VenueIndex { venue_id, .. } if *venue_id > MAX_VENUE_INDEX_ID => Err(StoreError::InvalidKey),
+            Key::VenueIndex { tournament_id, .. } => validate_tournament_id(tournament_id),
+            Key::RacerIndex { racer_id, .. } if *racer_id == 0 || *racer_id > MAX_RACER_INDEX_ID => {
+                Err(StoreError::InvalidKey)
+            }
+            Key::RacerIndex { tournament_id, .. } => validate_tournament_id(tournament_id),
+            Key::NameIndex { token, tournament_id, .. } => {
+                validate_tournament_id(token)?;
+                validate_tournament_id(tournament_id)
+            }
+            // メタデータキーは空にできず、セパレータも含められない
+            Key::TournamentMeta { meta_key, .. } if meta_key.is_empty() || meta_key.as_bytes().contains(&SEPARATOR) => {
+                Err(StoreError::InvalidKey)
+            }
+            Key::TournamentMeta { tournament_id, .. } => validate_tournament_id(tournament_id),
+            Key::Venue { .. } | Key::Conditions { .. } => Ok(()),
+            // 登録番号 0 は未割り当てを意味するため使用不可
+            Key::Racer { racer_id } if *racer_id == 0 => Err(StoreError::InvalidKey),
+            Key::Racer { .. } => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Monthly { year_month, tournament_id } => write!(
+                f,
+                "{}{:06}{}{}",
+                PREFIX_MONTHLY as char,
+                year_month,
+                SEPARATOR as char,
+                tournament_id
+            ),
+            Key::Tournament { tournament_id, timestamp } => write!(
+                f,
+                "{}{}{}{}",
+                PREFIX_TOURNAMENT as char,
+                tournament_id,
+                SEPARATOR as char,
+                encode_timestamp(*timestamp)
+            ),
+            Key::Daily { date, tournament_id } => write!(
+                f,
+                "{}{:08}{}{}",
+                PREFIX_DAILY as char,
+                date,
+                SEPARATOR as char,
+                tournament_id
+            ),
+            Key::Venue { venue_id } => write!(f, "{}{:010}", PREFIX_VENUE as char, venue_id),
+            Key::Racer { racer_id } => write!(f, "{}{:010}", PREFIX_RACER as char, racer_id),
+            Key::Result { tournament_id, timestamp } => write!(
+                f,
+                "{}{}{}{}",
+                PREFIX_RESULT as char,
+                tournament_id,
+                SEPARATOR as char,
+                encode_timestamp(*timestamp)
+            ),
+            Key::Conditions { venue_id, date } => {
+                write!(f, "{}{:010}{:08}", PREFIX_CONDITIONS as char, venue_id, date)
+            }
+            Key::Payout { tournament_id, timestamp } => write!(
+                f,
+                "{}{}{}{}",
+                PREFIX_PAYOUT as char,
+                tournament_id,
+                SEPARATOR as char,
+                encode_timestamp(*timestamp)
+            ),
+            Key::Odds { tournament_id, race_timestamp, snapshot_time } => write!(
+                f,
+                "{}{}{}{}{}{}",
+                PREFIX_ODDS as char,
+                tournament_id,
+                SEPARATOR as char,
+                encode_timestamp(*race_timestamp),
+                SEPARATOR as char,
+                encode_timestamp(*snapshot_time)
+            ),
+            Key::VenueIndex { venue_id, year_month, tournament_id } => write!(
+                f,
+                "{}{}{}{:03}{}{:06}{}{}",
+                PREFIX_INDEX as char,
+                VENUE_INDEX_NAME,
+                SEPARATOR as char,
+                venue_id,
+                SEPARATOR as char,
+                year_month,
+                SEPARATOR as char,
+                tournament_id
+            ),
+            Key::NameIndex { token, year_month, tournament_id } => write!(
+                f,
+                "{}{}{}{}{}{:06}{}{}",
+                PREFIX_INDEX as char,
+                NAME_INDEX_NAME,
+                SEPARATOR as char,
+                token,
+                SEPARATOR as char,
+                year_month,
+                SEPARATOR as char,
+                tournament_id
+            ),
+            Key::RacerIndex { racer_id, tournament_id, timestamp } => write!(
+                f,
+                "{}{}{}{:06}{}{}{}{}",
+                PREFIX_INDEX as char,
+                RACER_INDEX_NAME,
+                SEPARATOR as char,
+                racer_id,
+                SEPARATOR as char,
+                tournament_id,
+                SEPARATOR as char,
+                encode_timestamp(*timestamp)
+            ),
+            Key::Participants { tournament_id, timestamp } => write!(
+                f,
+                "{}{}{}{}{}{}",
+                PREFIX_INDEX as char,
+                PARTICIPANTS_INDEX_NAME,
+                SEPARATOR as char,
+                tournament_id,
+                SEPARATOR as char,
+                encode_timestamp(*timestamp)
+            ),
+            Key::TournamentMeta { tournament_id, meta_key } => write!(
+                f,
+                "{}{}{}{}",
+                PREFIX_TOURNAMENT_META as char,
+                tournament_id,
+                SEPARATOR as char,
+                meta_key
+            ),
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = StoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.as_bytes();
+        let prefix = *bytes.first().ok_or(StoreError::InvalidKey)?;
+        let body = s.get(1..).ok_or(StoreError::InvalidKey)?;
+
+        match prefix {
+            PREFIX_MONTHLY => {
+                let (year_month, tournament_id) = parse_dated(body, 6)?;
+                Ok(Key::Monthly { year_month, tournament_id })
+            }
+            PREFIX_DAILY => {
+                let (date, tournament_id) = parse_dated(body, 8)?;
+                Ok(Key::Daily { date, tournament_id })
+            }
+            PREFIX_VENUE => Ok(Key::Venue { venue_id: parse_digits(body, 10)? }),
+            PREFIX_RACER => Ok(Key::Racer { racer_id: parse_digits(body, 10)? }),
+            PREFIX_TOURNAMENT => {
+                let (tournament_id, timestamp) = parse_timestamped(body)?;
+                Ok(Key::Tournament { tournament_id, timestamp })
+            }
+            PREFIX_RESULT => {
+                let (tournament_id, timestamp) = parse_timestamped(body)?;
+                Ok(Key::Result { tournament_id, timestamp })
+            }
+            PREFIX_CONDITIONS => {
+                let (venue, date) = body.split_at_checked(10).ok_or(StoreError::InvalidKey)?;
+                let venue_id = parse_digits(venue, 10)?;
+                let date = parse_digits(date, 8)?;
+                Ok(Key::Conditions { venue_id, date })
+            }
+            PREFIX_PAYOUT => {
+                let (tournament_id, timestamp) = parse_timestamped(body)?;
+                Ok(Key::Payout { tournament_id, timestamp })
+            }
+            PREFIX_ODDS => {
+                let (race, snapshot) = body
+                    .rsplit_once(SEPARATOR as char)
+                    .ok_or(StoreError::InvalidKey)?;
+                let (tournament_id, race_timestamp) = parse_timestamped(race)?;
+                let snapshot_time = decode_timestamp(snapshot)?;
+                Ok(Key::Odds { tournament_id, race_timestamp, snapshot_time })
+            }
+            PREFIX_INDEX => {
+                let (name, rest) = body.split_once(SEPARATOR as char).ok_or(StoreError::InvalidKey)?;
+                if name == PARTICIPANTS_INDEX_NAME {
+                    let (tournament_id, timestamp) = parse_timestamped(rest)?;
+                    return Ok(Key::Participants { tournament_id, timestamp });
+                }
+
+                let (head, rest) = rest.split_once(SEPARATOR as char).ok_or(StoreError::InvalidKey)?;
+                match name {
+                    VENUE_INDEX_NAME => {
+                        let venue_id = parse_digits(head, 3)?;
+                        let (year_month, tournament_id) = parse_dated(rest, 6)?;
+                        Ok(Key::VenueIndex { venue_id, year_month, tournament_id })
+                    }
+                    NAME_INDEX_NAME => {
+                        let (year_month, tournament_id) = parse_dated(rest, 6)?;
+                        Ok(Key::NameIndex { token: head.to_string(), year_month, tournament_id })
+                    }
+                    RACER_INDEX_NAME => {
+                        let racer_id = parse_digits(head, 6)?;
+                        let (tournament_id, timestamp) = parse_timestamped(rest)?;
+                        Ok(Key::RacerIndex { racer_id, tournament_id, timestamp })
+                    }
+                    _ => Err(StoreError::InvalidKey),
+                }
+            }
+            PREFIX_TOURNAMENT_META => {
+                let (tournament_id, meta_key) = body.split_once(SEPARATOR as char).ok_or(StoreError::InvalidKey)?;
+                if meta_key.contains(SEPARATOR as char) {
+                    return Err(StoreError::InvalidKey);
+                }
+                Ok(Key::TournamentMeta {
+                    tournament_id: tournament_id.to_string(),
+                    meta_key: meta_key.to_string(),
+                })
+            }
+            _ => Err(StoreError::InvalidKey),
+        }
+    }
+}
+
+/// 固定桁の数値 + セパレータ + 大会ID の形式を解析
+fn parse_dated(body: &str, width: usize) -> Result<(u32, String), StoreError> {
+    let (digits, tournament_id) = body
+        .split_once(SEPARATOR as char)
+        .ok_or(StoreError::InvalidKey)?;
+    Ok((parse_digits(digits, width)?, tournament_id.to_string()))
+}
+
+/// 大会ID + セパレータ + 16桁の16進タイムスタンプ の形式を解析
+fn parse_timestamped(body: &str) -> Result<(String, u64), StoreError> {
+    let (tournament_id, ts) = body
+        .rsplit_once(SEPARATOR as char)
+        .ok_or(StoreError::InvalidKey)?;
+    Ok((tournament_id.to_string(), decode_timestamp(ts)?))
+}
+
+/// タイムスタンプをキー用の文字列に変換
+///
+/// 16桁固定・小文字の16進数で出力する。桁数が固定のため、文字列の辞書順は数値の大小順と一致する
+/// (0 は "0000000000000000"、u64::MAX は "ffffffffffffffff")。
+/// 10進数や可変長にすると "9" > "10" のように順序が崩れ、範囲スキャンが壊れる
+///
+/// # Arguments
+/// * `timestamp` - タイムスタンプ
+///
+/// # Returns
+/// 16文字の文字列
+pub fn encode_timestamp(timestamp: u64) -> String {
+    format!("{:016x}", timestamp)
+}
+
+/// `encode_timestamp` で変換した文字列をタイムスタンプに戻す
+///
+/// 16桁・小文字の16進数以外は受け付けない (大文字を許すと辞書順と数値順が一致しなくなる)
+///
+/// # Arguments
+/// * `encoded` - キー中のタイムスタンプ部分
+///
+/// # Returns
+/// タイムスタンプ。形式が不正な場合は `StoreError::InvalidKey`
+pub fn decode_timestamp(encoded: &str) -> crate::Result<u64> {
+    if encoded.len() != 16 || !encoded.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return Err(StoreError::InvalidKey);
+    }
+    u64::from_str_radix(encoded, 16).map_err(|_| StoreError::InvalidKey)
+}
+
+/// 固定桁の10進数を解析
+fn parse_digits(digits: &str, width: usize) -> Result<u32, StoreError> {
+    if digits.len() != width || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(StoreError::InvalidKey);
+    }
+    digits.parse().map_err(|_| StoreError::InvalidKey)
+}
+
 /// 月別ビューキーを生成
 /// 
 /// # Arguments
@@ -18,12 +635,11 @@ pub const SEPARATOR: u8 = 0x00;          // セパレータ
 /// # Returns
 /// "M202509\x00tokyo_bay_cup" のようなキー
 pub fn monthly_key(year_month: u32, tournament_id: &str) -> String {
-    format!("{}{:06}{}{}", 
-        PREFIX_MONTHLY as char,
+    Key::Monthly {
         year_month,
-        SEPARATOR as char,
-        tournament_id
-    )
+        tournament_id: tournament_id.to_string(),
+    }
+    .to_string()
 }
 
 /// 大会データキーを生成
@@ -35,27 +651,425 @@ pub fn monthly_key(year_month: u32, tournament_id: &str) -> String {
 /// # Returns
 /// "Ttokyo_bay_cup\x00<timestamp_be>" のようなキー
 pub fn tournament_key(tournament_id: &str, timestamp: u64) -> String {
-    format!("{}{}{}{:016x}", 
-        PREFIX_TOURNAMENT as char,
-        tournament_id,
-        SEPARATOR as char,
-        timestamp
-    )
+    Key::Tournament {
+        tournament_id: tournament_id.to_string(),
+        timestamp,
+    }
+    .to_string()
+}
+
+/// 大会IDがキーに埋め込めるか検証
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// セパレータ (0x00) を含む場合は `StoreError::InvalidKey`
+pub fn validate_tournament_id(tournament_id: &str) -> crate::Result<()> {
+    if tournament_id.as_bytes().contains(&SEPARATOR) {
+        return Err(StoreError::InvalidKey);
+    }
+    Ok(())
+}
+
+/// 検証付きで月別ビューキーを生成
+///
+/// # Arguments
+/// * `year_month` - YYYYMM形式の年月
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// 月別ビューキー。大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_monthly_key(year_month: u32, tournament_id: &str) -> crate::Result<String> {
+    let key = Key::Monthly {
+        year_month,
+        tournament_id: tournament_id.to_string(),
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 検証付きで大会データキーを生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+/// * `timestamp` - タイムスタンプ（エポックミリ秒）
+///
+/// # Returns
+/// 大会データキー。大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_tournament_key(tournament_id: &str, timestamp: u64) -> crate::Result<String> {
+    let key = Key::Tournament {
+        tournament_id: tournament_id.to_string(),
+        timestamp,
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 日別ビューキーを生成
+///
+/// # Arguments
+/// * `date` - YYYYMMDD形式の日付 (例: 20250913)
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// "D20250913\x00tokyo_bay_cup" のようなキー
+pub fn daily_key(date: u32, tournament_id: &str) -> String {
+    Key::Daily {
+        date,
+        tournament_id: tournament_id.to_string(),
+    }
+    .to_string()
+}
+
+/// 検証付きで日別ビューキーを生成
+///
+/// # Arguments
+/// * `date` - YYYYMMDD形式の日付
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// 日別ビューキー。大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_daily_key(date: u32, tournament_id: &str) -> crate::Result<String> {
+    let key = Key::Daily {
+        date,
+        tournament_id: tournament_id.to_string(),
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 日別スキャン範囲を生成
+///
+/// 範囲の両端にセパレータを含めるため、セパレータのない不正なキー ("D202509131" など) は含まない
+///
+/// # Arguments
+/// * `date` - YYYYMMDD形式の日付
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル (例: ("D20250913\x00", "D20250913\x01"))
+pub fn daily_scan_range(date: u32) -> (String, String) {
+    separated_range(&format!("{}{:08}", PREFIX_DAILY as char, date))
+}
+
+/// 指定月の日別ビュー全体のスキャン範囲を生成
+///
+/// 日別キーはセパレータの前に日付が入るため、`monthly_scan_range` と同じく 1日と 31日の
+/// セパレータを両端にする。翌月の年月 (12月なら "D202513") には依存しない。
+/// 範囲内にある日付部分が不正なキー ("D2025091x\x00" など) は呼び出し側で検証すること
+///
+/// # Arguments
+/// * `year_month` - YYYYMM形式の年月
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル (例: ("D20250901\x00", "D20250931\x01"))
+pub fn daily_month_scan_range(year_month: u32) -> (String, String) {
+    let (start, _) = daily_scan_range(year_month * 100 + 1);
+    let (_, end) = daily_scan_range(year_month * 100 + 31);
+    (start, end)
+}
+
+/// `prefix` + セパレータで始まるキー全体の範囲 (`prefix` + 0x00 以上、`prefix` + 0x01 未満)
+fn separated_range(prefix: &str) -> (String, String) {
+    (format!("{}{}", prefix, SEPARATOR as char), format!("{}{}", prefix, (SEPARATOR + 1) as char))
+}
+
+/// 月別ビューキーの年月までの部分
+fn monthly_prefix(year_month: u32) -> String {
+    format!("{}{:06}", PREFIX_MONTHLY as char, year_month)
+}
+
+/// 会場マスタキーを生成
+///
+/// # Arguments
+/// * `venue_id` - 会場ID (例: 4)
+///
+/// # Returns
+/// "V0000000004" のようなキー
+pub fn venue_key(venue_id: u32) -> String {
+    Key::Venue { venue_id }.to_string()
+}
+
+/// 名前空間全体のスキャン範囲を生成
+///
+/// # Arguments
+/// * `prefix` - キープレフィックス (例: `PREFIX_MONTHLY`)
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル (例: ("M", "N"))
+pub fn namespace_scan_range(prefix: u8) -> (String, String) {
+    let start = (prefix as char).to_string();
+    let end = ((prefix + 1) as char).to_string();
+    (start, end)
+}
+
+/// 会場マスタ全体のスキャン範囲を生成
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn venue_scan_range() -> (String, String) {
+    namespace_scan_range(PREFIX_VENUE)
+}
+
+/// 会場インデックスキーを生成
+///
+/// # Arguments
+/// * `venue_id` - 会場ID
+/// * `year_month` - YYYYMM形式の年月
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// "I_venue\x00004\x00202509\x00tokyo_bay_cup" のようなキー
+pub fn venue_index_key(venue_id: u32, year_month: u32, tournament_id: &str) -> String {
+    Key::VenueIndex {
+        venue_id,
+        year_month,
+        tournament_id: tournament_id.to_string(),
+    }
+    .to_string()
+}
+
+/// 検証付きで会場インデックスキーを生成
+///
+/// # Arguments
+/// * `venue_id` - 会場ID
+/// * `year_month` - YYYYMM形式の年月
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// 会場インデックスキー。会場IDが3桁に収まらない、または大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_venue_index_key(venue_id: u32, year_month: u32, tournament_id: &str) -> crate::Result<String> {
+    let key = Key::VenueIndex {
+        venue_id,
+        year_month,
+        tournament_id: tournament_id.to_string(),
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 会場インデックスのスキャン範囲を生成
+///
+/// # Arguments
+/// * `venue_id` - 会場ID
+///
+/// # Returns
+/// 指定会場の全インデックスを含む (開始キー, 終了キー) のタプル
+pub fn venue_index_scan_range(venue_id: u32) -> (String, String) {
+    let prefix = format!("{}{}{}{:03}", PREFIX_INDEX as char, VENUE_INDEX_NAME, SEPARATOR as char, venue_id);
+    (format!("{}{}", prefix, SEPARATOR as char), format!("{}\x01", prefix))
+}
+
+/// 会場インデックス全体のスキャン範囲を生成
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn venue_index_namespace_range() -> (String, String) {
+    index_namespace_range(VENUE_INDEX_NAME)
+}
+
+/// 名前インデックス全体のスキャン範囲を生成
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn name_index_namespace_range() -> (String, String) {
+    index_namespace_range(NAME_INDEX_NAME)
+}
+
+/// 指定した名前のインデックス全体のスキャン範囲を生成
+fn index_namespace_range(name: &str) -> (String, String) {
+    let prefix = format!("{}{}", PREFIX_INDEX as char, name);
+    (format!("{}{}", prefix, SEPARATOR as char), format!("{}\x01", prefix))
+}
+
+/// 検証付きで出走者インデックスキーを生成
+///
+/// # Arguments
+/// * `racer_id` - 選手の登録番号
+/// * `tournament_id` - 大会ID
+/// * `timestamp` - レースのタイムスタンプ
+///
+/// # Returns
+/// 出走者インデックスキー。登録番号が 0 か6桁に収まらない、または大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_racer_index_key(racer_id: u32, tournament_id: &str, timestamp: u64) -> crate::Result<String> {
+    let key = Key::RacerIndex {
+        racer_id,
+        tournament_id: tournament_id.to_string(),
+        timestamp,
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 出走者インデックスのスキャン範囲を生成
+///
+/// # Arguments
+/// * `racer_id` - 選手の登録番号
+///
+/// # Returns
+/// 指定選手の全インデックスを含む (開始キー, 終了キー) のタプル
+pub fn racer_index_scan_range(racer_id: u32) -> (String, String) {
+    let prefix = format!("{}{}{}{:06}", PREFIX_INDEX as char, RACER_INDEX_NAME, SEPARATOR as char, racer_id);
+    (format!("{}{}", prefix, SEPARATOR as char), format!("{}\x01", prefix))
+}
+
+/// 検証付きで出走者一覧キーを生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+/// * `timestamp` - レースのタイムスタンプ
+///
+/// # Returns
+/// 出走者一覧キー。大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_participants_key(tournament_id: &str, timestamp: u64) -> crate::Result<String> {
+    let key = Key::Participants {
+        tournament_id: tournament_id.to_string(),
+        timestamp,
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 名前インデックスキーを生成
+///
+/// # Arguments
+/// * `token` - 名前から切り出したトークン (文字バイグラム)
+/// * `year_month` - YYYYMM形式の年月
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// "I_name\x00高松\x00202509\x00sumin_takamatsu" のようなキー
+pub fn name_index_key(token: &str, year_month: u32, tournament_id: &str) -> String {
+    Key::NameIndex {
+        token: token.to_string(),
+        year_month,
+        tournament_id: tournament_id.to_string(),
+    }
+    .to_string()
+}
+
+/// 名前インデックスのスキャン範囲を生成
+///
+/// # Arguments
+/// * `token` - 検索するトークン
+/// * `year_month` - 指定した場合はその月のエントリのみを対象にする
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn name_index_scan_range(token: &str, year_month: Option<u32>) -> (String, String) {
+    let mut prefix = format!("{}{}{}{}", PREFIX_INDEX as char, NAME_INDEX_NAME, SEPARATOR as char, token);
+    if let Some(year_month) = year_month {
+        prefix = format!("{}{}{:06}", prefix, SEPARATOR as char, year_month);
+    }
+    (format!("{}{}", prefix, SEPARATOR as char), format!("{}\x01", prefix))
+}
+
+/// 選手マスタキーを生成
+///
+/// # Arguments
+/// * `racer_id` - 選手の登録番号 (例: 4444)
+///
+/// # Returns
+/// "R0000004444" のようなキー
+pub fn racer_key(racer_id: u32) -> String {
+    Key::Racer { racer_id }.to_string()
+}
+
+/// 検証付きで選手マスタキーを生成
+///
+/// # Arguments
+/// * `racer_id` - 選手の登録番号
+///
+/// # Returns
+/// 選手マスタキー。登録番号が 0 の場合は `StoreError::InvalidKey`
+pub fn try_racer_key(racer_id: u32) -> crate::Result<String> {
+    let key = Key::Racer { racer_id };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 選手マスタ全体のスキャン範囲を生成
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn racer_scan_range() -> (String, String) {
+    namespace_scan_range(PREFIX_RACER)
 }
 
 /// 月別スキャン範囲を生成
+///
+/// 範囲の両端にセパレータを含めるため、セパレータのない不正なキー ("M2025091" など) は含まない
 /// 
 /// # Arguments
 /// * `year_month` - YYYYMM形式の年月
 /// 
 /// # Returns
-/// (開始キー, 終了キー) のタプル
+/// (開始キー, 終了キー) のタプル (例: ("M202509\x00", "M202509\x01"))
 pub fn monthly_scan_range(year_month: u32) -> (String, String) {
-    let start = format!("{}{:06}", PREFIX_MONTHLY as char, year_month);
-    let end = format!("{}{:06}", PREFIX_MONTHLY as char, year_month + 1);
+    separated_range(&monthly_prefix(year_month))
+}
+
+/// 複数月にまたがる月別スキャン範囲を生成
+///
+/// # Arguments
+/// * `from` - 開始年月 (YYYYMM形式)
+/// * `to_inclusive` - 終了年月 (YYYYMM形式、この月を含む)
+///
+/// 複数月の範囲には月の間にある不正なキー ("M2025081" など) も含まれるため、呼び出し側でキーを検証すること
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル。年月が不正、または `from > to_inclusive` の場合は `StoreError::InvalidKey`
+pub fn months_scan_range(from: u32, to_inclusive: u32) -> crate::Result<(String, String)> {
+    validate_year_month(from)?;
+    validate_year_month(to_inclusive)?;
+    if from > to_inclusive {
+        return Err(StoreError::InvalidKey);
+    }
+    let (start, _) = monthly_scan_range(from);
+    let (_, end) = monthly_scan_range(to_inclusive);
+    Ok((start, end))
+}
+
+/// 年間の月別スキャン範囲を生成
+///
+/// # Arguments
+/// * `year` - 対象の年 (例: 2025)
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル (例: ("M202501\x00", "M202512\x01"))
+pub fn yearly_scan_range(year: u32) -> (String, String) {
+    let (start, _) = monthly_scan_range(year * 100 + 1);
+    let (_, end) = monthly_scan_range(year * 100 + 12);
     (start, end)
 }
 
+/// YYYYMM形式の年月として妥当か検証
+pub(crate) fn validate_year_month(year_month: u32) -> crate::Result<()> {
+    let month = year_month % 100;
+    if !(1..=12).contains(&month) || year_month / 100 > 9999 {
+        return Err(StoreError::InvalidKey);
+    }
+    Ok(())
+}
+
+/// 翌月をYYYYMM形式で取得 (例: 202512 -> 202601)
+pub(crate) fn next_year_month(year_month: u32) -> u32 {
+    if year_month % 100 >= 12 {
+        (year_month / 100 + 1) * 100 + 1
+    } else {
+        year_month + 1
+    }
+}
+
+/// 前月をYYYYMM形式で取得 (例: 202601 -> 202512)
+pub(crate) fn prev_year_month(year_month: u32) -> u32 {
+    if year_month % 100 <= 1 {
+        (year_month / 100 - 1) * 100 + 12
+    } else {
+        year_month - 1
+    }
+}
+
 /// 大会スキャン範囲を生成
 /// 
 /// # Arguments
@@ -77,6 +1091,205 @@ pub fn tournament_scan_range(tournament_id: &str) -> (String, String) {
     (start, end)
 }
 
+/// キーが指定した大会のものか判定
+///
+/// キーをセパレータで分解して大会IDを完全一致で比較するため、"tokyo" のキーと
+/// "tokyo_bay_cup" のキーを取り違えない
+///
+/// # Arguments
+/// * `key` - ストアのキー
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// 大会IDを含むキーで、その大会IDが一致する場合は true（解析できないキーは false）
+pub fn tournament_key_matches(key: &str, tournament_id: &str) -> bool {
+    key.parse::<Key>()
+        .is_ok_and(|key| key.tournament_id() == Some(tournament_id))
+}
+
+/// 検証付きでレース結果キーを生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+/// * `timestamp` - レースのタイムスタンプ（エポックミリ秒）
+///
+/// # Returns
+/// "Ftokyo_bay_cup\x00<timestamp_be>" のようなキー。大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_result_key(tournament_id: &str, timestamp: u64) -> crate::Result<String> {
+    let key = Key::Result {
+        tournament_id: tournament_id.to_string(),
+        timestamp,
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 大会のレース結果スキャン範囲を生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn result_scan_range(tournament_id: &str) -> (String, String) {
+    let start = format!("{}{}{}", PREFIX_RESULT as char, tournament_id, SEPARATOR as char);
+    let end = format!("{}{}{}", PREFIX_RESULT as char, tournament_id, (SEPARATOR + 1) as char);
+    (start, end)
+}
+
+/// 検証付きで大会メタデータキーを生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+/// * `meta_key` - メタデータのキー
+///
+/// # Returns
+/// "Xtokyo_bay_cup\x00sponsor" のようなキー。大会IDにセパレータが含まれる場合や、
+/// メタデータのキーが空またはセパレータを含む場合は `StoreError::InvalidKey`
+pub fn try_tournament_meta_key(tournament_id: &str, meta_key: &str) -> crate::Result<String> {
+    let key = Key::TournamentMeta {
+        tournament_id: tournament_id.to_string(),
+        meta_key: meta_key.to_string(),
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 大会のメタデータスキャン範囲を生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn tournament_meta_scan_range(tournament_id: &str) -> (String, String) {
+    let start = format!("{}{}{}", PREFIX_TOURNAMENT_META as char, tournament_id, SEPARATOR as char);
+    let end = format!("{}{}{}", PREFIX_TOURNAMENT_META as char, tournament_id, (SEPARATOR + 1) as char);
+    (start, end)
+}
+
+/// 気象条件キーを生成
+///
+/// # Arguments
+/// * `venue_id` - 会場ID
+/// * `date` - YYYYMMDD形式の日付
+///
+/// # Returns
+/// "W000000000420250913" のようなキー
+pub fn conditions_key(venue_id: u32, date: u32) -> String {
+    Key::Conditions { venue_id, date }.to_string()
+}
+
+/// 会場の気象条件スキャン範囲を生成
+///
+/// # Arguments
+/// * `venue_id` - 会場ID
+/// * `from_date` - 開始日 (YYYYMMDD形式)
+/// * `to_date` - 終了日 (YYYYMMDD形式、この日を含む。99999999 以上なら会場の全期間)
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn conditions_scan_range(venue_id: u32, from_date: u32, to_date: u32) -> (String, String) {
+    let prefix = format!("{}{:010}", PREFIX_CONDITIONS as char, venue_id);
+    let start = format!("{}{:08}", prefix, from_date);
+    // 翌日が8桁に収まらない場合は桁が増えて辞書順が崩れるため、会場の終端 (数字より後ろの文字) を使う
+    let end = match to_date.checked_add(1).filter(|next| *next <= 99_999_999) {
+        Some(next) => format!("{}{:08}", prefix, next),
+        None => format!("{}\u{10FFFF}", prefix),
+    };
+    (start, end)
+}
+
+/// 検証付きで払戻金キーを生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+/// * `timestamp` - レースのタイムスタンプ（エポックミリ秒）
+///
+/// # Returns
+/// 払戻金キー。大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_payout_key(tournament_id: &str, timestamp: u64) -> crate::Result<String> {
+    let key = Key::Payout {
+        tournament_id: tournament_id.to_string(),
+        timestamp,
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// 大会の払戻金スキャン範囲を生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn payout_scan_range(tournament_id: &str) -> (String, String) {
+    let start = format!("{}{}{}", PREFIX_PAYOUT as char, tournament_id, SEPARATOR as char);
+    let end = format!("{}{}{}", PREFIX_PAYOUT as char, tournament_id, (SEPARATOR + 1) as char);
+    (start, end)
+}
+
+/// 検証付きでオッズスナップショットキーを生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+/// * `race_timestamp` - レースのタイムスタンプ（エポックミリ秒）
+/// * `snapshot_time` - スナップショット取得時刻（エポックミリ秒）
+///
+/// # Returns
+/// オッズスナップショットキー。大会IDにセパレータが含まれる場合は `StoreError::InvalidKey`
+pub fn try_odds_key(tournament_id: &str, race_timestamp: u64, snapshot_time: u64) -> crate::Result<String> {
+    let key = Key::Odds {
+        tournament_id: tournament_id.to_string(),
+        race_timestamp,
+        snapshot_time,
+    };
+    key.validate()?;
+    Ok(key.to_string())
+}
+
+/// レース単位のオッズ履歴スキャン範囲を生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+/// * `race_timestamp` - レースのタイムスタンプ
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn odds_scan_range(tournament_id: &str, race_timestamp: u64) -> (String, String) {
+    let race = format!("{}{}{}{}", PREFIX_ODDS as char, tournament_id, SEPARATOR as char, encode_timestamp(race_timestamp));
+    let start = format!("{}{}", race, SEPARATOR as char);
+    let end = format!("{}{}", race, (SEPARATOR + 1) as char);
+    (start, end)
+}
+
+/// 大会の全オッズ履歴のスキャン範囲を生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+///
+/// # Returns
+/// (開始キー, 終了キー) のタプル
+pub fn odds_tournament_scan_range(tournament_id: &str) -> (String, String) {
+    let start = format!("{}{}{}", PREFIX_ODDS as char, tournament_id, SEPARATOR as char);
+    let end = format!("{}{}{}", PREFIX_ODDS as char, tournament_id, (SEPARATOR + 1) as char);
+    (start, end)
+}
+
+/// 大会内のタイムスタンプ範囲のスキャン範囲を生成
+///
+/// # Arguments
+/// * `tournament_id` - 大会ID
+/// * `from_ts` - 開始タイムスタンプ（含む）
+/// * `to_ts` - 終了タイムスタンプ（含まない）
+///
+/// # Returns
+/// [from_ts, to_ts) に対応する (開始キー, 終了キー) のタプル
+pub fn tournament_time_range(tournament_id: &str, from_ts: u64, to_ts: u64) -> (String, String) {
+    (tournament_key(tournament_id, from_ts), tournament_key(tournament_id, to_ts))
+}
+
 /// 大会IDから一意のキー識別子を生成
 /// 
 /// # Arguments
@@ -86,56 +1299,69 @@ pub fn tournament_scan_range(tournament_id: &str) -> (String, String) {
 /// # Returns
 /// 安全なキー識別子 (例: "venue_4_event_tokyo_bay_cup")
 pub fn generate_tournament_id(venue_name: &str, event_name: &str) -> String {
-    // ASCII文字のみを抽出
-    let venue_ascii: String = venue_name
-        .chars()
-        .filter_map(|c| match c {
-            'a'..='z' | 'A'..='Z' | '0'..='9' => Some(c.to_ascii_lowercase()),
-            ' ' => Some('_'),
-            _ => None,
-        })
-        .collect();
-
-    let event_ascii: String = event_name
-        .chars()
-        .filter_map(|c| match c {
-            'a'..='z' | 'A'..='Z' | '0'..='9' => Some(c.to_ascii_lowercase()),
-            ' ' => Some('_'),
-            _ => None,
-        })
-        .collect();
-
-    // ASCII文字が少ない場合は、ハッシュベースのIDを生成
-    let venue_part = if venue_ascii.len() > 2 {
-        venue_ascii
-    } else {
-        format!("venue_{}", venue_name.len())
+    // 出力は会場名・イベント名の長さを超えない (ASCII文字が少ない場合の "venue_N" などを除く)
+    let mut id = TournamentIdWriter {
+        id: String::with_capacity(venue_name.len() + event_name.len() + 32),
+        prev_underscore: false,
     };
+    id.push_part(venue_name, "venue");
+    id.push('_');
+    id.push_part(event_name, "event");
+    id.finish()
+}
 
-    let event_part = if event_ascii.len() > 2 {
-        event_ascii
-    } else {
-        format!("event_{}", event_name.len())
-    };
+/// 大会IDに使う文字 (ASCII英数字は小文字に、空白は '_' に変換し、それ以外は捨てる)
+fn tournament_id_char(c: char) -> Option<char> {
+    match c {
+        'a'..='z' | 'A'..='Z' | '0'..='9' => Some(c.to_ascii_lowercase()),
+        ' ' => Some('_'),
+        _ => None,
+    }
+}
 
-    // 連続する_を1つにまとめ
-    let combined = format!("{}_{}", venue_part, event_part);
-    let mut result = String::new();
-    let mut prev_underscore = false;
+/// 大会IDを1つの文字列に直接書き込む (連続する '_' と先頭の '_' は書かない)
+struct TournamentIdWriter {
+    id: String,
+    prev_underscore: bool,
+}
 
-    for c in combined.chars() {
+impl TournamentIdWriter {
+    fn push(&mut self, c: char) {
         if c == '_' {
-            if !prev_underscore && !result.is_empty() {
-                result.push(c);
+            if !self.prev_underscore && !self.id.is_empty() {
+                self.id.push(c);
             }
-            prev_underscore = true;
+            self.prev_underscore = true;
         } else {
-            result.push(c);
-            prev_underscore = false;
+            self.id.push(c);
+            self.prev_underscore = false;
         }
     }
-    
-    result.trim_matches('_').to_string()
+
+    /// 名前から取り出したASCII文字を書く。3文字未満なら "{label}_{名前のバイト数}" を書く
+    fn push_part(&mut self, name: &str, label: &str) {
+        if name.chars().filter_map(tournament_id_char).count() > 2 {
+            name.chars().filter_map(tournament_id_char).for_each(|c| self.push(c));
+        } else {
+            use std::fmt::Write as _;
+            let _ = write!(self, "{}_{}", label, name.len());
+        }
+    }
+
+    fn finish(mut self) -> String {
+        // 先頭の '_' は書かず、連続する '_' もまとめているため、取り除くのは末尾の1文字だけ
+        if self.id.ends_with('_') {
+            self.id.pop();
+        }
+        self.id
+    }
+}
+
+impl std::fmt::Write for TournamentIdWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        s.chars().for_each(|c| self.push(c));
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -157,8 +1383,401 @@ mod tests {
     #[test]
     fn test_monthly_scan_range() {
         let (start, end) = monthly_scan_range(202509);
-        assert_eq!(start, "M202509");
-        assert_eq!(end, "M202510");
+        assert_eq!(start, "M202509\x00");
+        assert_eq!(end, "M202509\x01");
+    }
+
+    #[test]
+    fn test_classify_key() {
+        assert_eq!(classify_key(&monthly_key(202509, "tokyo")), Some(Namespace::Monthly));
+        assert_eq!(classify_key(&tournament_key("tokyo", 1)), Some(Namespace::Tournament));
+        assert_eq!(classify_key(&daily_key(20250913, "tokyo")), Some(Namespace::Daily));
+        assert_eq!(classify_key(&venue_key(4)), Some(Namespace::Venue));
+        assert_eq!(classify_key(&racer_key(4444)), Some(Namespace::Racer));
+        assert_eq!(classify_key(&try_result_key("tokyo", 1).unwrap()), Some(Namespace::Result));
+        assert_eq!(classify_key(&conditions_key(4, 20250913)), Some(Namespace::Conditions));
+        assert_eq!(classify_key(&try_payout_key("tokyo", 1).unwrap()), Some(Namespace::Payout));
+        assert_eq!(classify_key(&try_odds_key("tokyo", 1, 2).unwrap()), Some(Namespace::Odds));
+        assert_eq!(classify_key(&venue_index_key(4, 202509, "tokyo")), Some(Namespace::Index));
+        assert_eq!(classify_key(&try_participants_key("tokyo", 1).unwrap()), Some(Namespace::Index));
+        assert_eq!(classify_key(&try_tournament_meta_key("tokyo", "status").unwrap()), Some(Namespace::TournamentMeta));
+        assert_eq!(classify_key(STATISTICS_META_KEY), Some(Namespace::Meta));
+        assert_eq!(classify_key(&last_modified_key("tokyo")), Some(Namespace::Meta));
+        assert_eq!(classify_key(&user_key("Manual_note")), Some(Namespace::User));
+        assert_eq!(classify_key("U"), Some(Namespace::User));
+
+        // 予約済みのプレフィックスで始まるだけのキー・予約されていないプレフィックス
+        for key in ["Manual_note", "M202509", "Tokyo", "Venue", "Rx", "I_unknown\x00x", "Xtokyo", "\x01other", "\x01meta\x00", "a", "Z123", ""] {
+            assert_eq!(classify_key(key), None, "{:?}", key);
+        }
+
+        for namespace in Namespace::ALL {
+            assert_eq!(Namespace::from_prefix(namespace.prefix()), Some(namespace));
+            assert_eq!(namespace.is_reserved(), namespace != Namespace::User);
+        }
+        assert_eq!(Namespace::from_prefix(b'Z'), None);
+    }
+
+    /// 境界付近の値でタイムスタンプの数値順とキーの辞書順が一致すること
+    #[test]
+    fn test_timestamp_encoding_preserves_order() {
+        let mut values = vec![0, 1, 9, 10, 15, 16, 255, 256, u32::MAX as u64, u32::MAX as u64 + 1, i64::MAX as u64, u64::MAX - 1, u64::MAX];
+        // 最上位の4ビットだけが異なる値、最上位の4ビットが同じで下位が異なる値
+        for nibble in 0..16u64 {
+            values.push(nibble << 60);
+            values.push((nibble << 60) | 0x0fff_ffff_ffff_ffff);
+            values.push(nibble);
+        }
+        for shift in 0..64 {
+            values.push(1u64 << shift);
+            values.push((1u64 << shift) - 1);
+        }
+        // 決定的な擬似乱数 (xorshift) で間の値も埋める
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        for _ in 0..200 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            values.push(state);
+        }
+
+        for &value in &values {
+            let encoded = encode_timestamp(value);
+            assert_eq!(encoded.len(), 16);
+            assert_eq!(decode_timestamp(&encoded).unwrap(), value);
+        }
+        for &a in &values {
+            for &b in &values {
+                assert_eq!(encode_timestamp(a).cmp(&encode_timestamp(b)), a.cmp(&b), "{} {}", a, b);
+                assert_eq!(tournament_key("tokyo", a).cmp(&tournament_key("tokyo", b)), a.cmp(&b), "{} {}", a, b);
+            }
+        }
+
+        assert_eq!(encode_timestamp(0), "0000000000000000");
+        assert_eq!(encode_timestamp(u64::MAX), "ffffffffffffffff");
+        for invalid in ["", "0", "fffffffffffffffff", "FFFFFFFFFFFFFFFF", "000000000000000g", "+000000000000001"] {
+            assert!(decode_timestamp(invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_tournament_key_matches() {
+        let ids = ["tokyo", "tokyo_bay_cup", "tokyo_bay"];
+        let keys = [
+            monthly_key(202509, "tokyo"),
+            daily_key(20250913, "tokyo"),
+            tournament_key("tokyo", 1),
+            try_result_key("tokyo", u64::MAX).unwrap(),
+            try_payout_key("tokyo", 1).unwrap(),
+            try_odds_key("tokyo", 1, 2).unwrap(),
+            venue_index_key(4, 202509, "tokyo"),
+            name_index_key("ベイ", 202509, "tokyo"),
+            try_racer_index_key(4444, "tokyo", 1).unwrap(),
+            try_participants_key("tokyo", 1).unwrap(),
+            try_tournament_meta_key("tokyo", "status").unwrap(),
+        ];
+        for key in &keys {
+            assert!(tournament_key_matches(key, "tokyo"), "{:?}", key);
+            for other in &ids[1..] {
+                assert!(!tournament_key_matches(key, other), "{:?} {:?}", key, other);
+                let other_key = key.replacen("tokyo", other, 1);
+                assert!(!tournament_key_matches(&other_key, "tokyo"), "{:?}", other_key);
+            }
+        }
+
+        assert!(!tournament_key_matches(&venue_key(4), "tokyo"));
+        assert!(!tournament_key_matches("Ttokyo", "tokyo"));
+        assert!(!tournament_key_matches("Ttokyo\x00zz", "tokyo"));
+        assert!(!tournament_key_matches(&tournament_key("tokyo_bay_cup", 1), "tokyo_bay"));
+    }
+
+    /// 範囲の境界に置いた不正なキー・他の月のキーが含まれないこと
+    #[test]
+    fn test_scan_ranges_exclude_adversarial_keys() {
+        let within = |key: &str, (start, end): &(String, String)| key >= start.as_str() && key < end.as_str();
+
+        let september = monthly_scan_range(202509);
+        for key in ["M202509\x00", "M202509\x00a", "M202509\x00\u{10ffff}"] {
+            assert!(within(key, &september), "{:?}", key);
+        }
+        for key in ["M202509", "M2025091", "M20250900", "M202509\x01", "M202509\x01x", "M202510", "M202510\x00a", "M202508\x00z", "M20250"] {
+            assert!(!within(key, &september), "{:?}", key);
+        }
+
+        let december = monthly_scan_range(202512);
+        assert!(within(&monthly_key(202512, "x"), &december));
+        assert!(!within("M202513", &december));
+        assert!(!within(&monthly_key(202601, "x"), &december));
+
+        let day = daily_scan_range(20250913);
+        assert!(within(&daily_key(20250913, "x"), &day));
+        for key in ["D20250913", "D202509131", "D20250914", "D20250913\x01", "D20250912\x00x"] {
+            assert!(!within(key, &day), "{:?}", key);
+        }
+
+        // 複数月・年間の範囲は両端の外側を含まない (月の間の不正なキーはエンジンで読み飛ばす)
+        let months = months_scan_range(202507, 202509).unwrap();
+        for key in ["M202507", "M2025069", "M202509\x01", "M2025091", "M202510"] {
+            assert!(!within(key, &months), "{:?}", key);
+        }
+        assert!(within("M2025081", &months));
+        let year = yearly_scan_range(2025);
+        for key in ["M202501", "M2024129", "M202512\x01", "M2025121", "M202601\x00x"] {
+            assert!(!within(key, &year), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_months_scan_range() {
+        let (start, end) = months_scan_range(202507, 202509).unwrap();
+        assert_eq!(start, "M202507\x00");
+        assert_eq!(end, "M202509\x01");
+
+        // 単月は monthly_scan_range と同じ範囲になる
+        assert_eq!(months_scan_range(202509, 202509).unwrap(), monthly_scan_range(202509));
+    }
+
+    #[test]
+    fn test_months_scan_range_year_wrap() {
+        let (start, end) = months_scan_range(202511, 202602).unwrap();
+        assert_eq!(start, "M202511\x00");
+        assert_eq!(end, "M202602\x01");
+
+        let (_, dec_end) = months_scan_range(202510, 202512).unwrap();
+        assert_eq!(dec_end, "M202512\x01");
+
+        // 範囲内の全月のキーが含まれる
+        for ym in [202511, 202512, 202601, 202602] {
+            let key = monthly_key(ym, "tokyo_bay_cup");
+            assert!(key >= start && key < end);
+        }
+        assert!(monthly_key(202603, "tokyo_bay_cup") >= end);
+    }
+
+    #[test]
+    fn test_yearly_scan_range() {
+        let (start, end) = yearly_scan_range(2025);
+        assert_eq!(start, "M202501\x00");
+        assert_eq!(end, "M202512\x01");
+        assert!(monthly_key(202512, "x") < end);
+        assert!(monthly_key(202601, "x") >= end);
+    }
+
+    #[test]
+    fn test_months_scan_range_invalid() {
+        assert!(months_scan_range(202500, 202509).is_err());
+        assert!(months_scan_range(202509, 202513).is_err());
+        assert!(months_scan_range(202510, 202509).is_err());
+    }
+
+    #[test]
+    fn test_daily_month_scan_range() {
+        let (start, end) = daily_month_scan_range(202509);
+        for date in [20250901, 20250930] {
+            let key = daily_key(date, "tokyo_bay_cup");
+            assert!(start.as_str() <= key.as_str() && key.as_str() < end.as_str());
+        }
+        assert!(daily_key(20251001, "tokyo_bay_cup") >= end);
+        assert!(daily_key(20250831, "tokyo_bay_cup") < start);
+        assert!(monthly_key(202509, "tokyo_bay_cup") >= end);
+        assert_eq!(daily_month_scan_range(202509), ("D20250901\x00".to_string(), "D20250931\x01".to_string()));
+
+        // 12月も翌月の年月を使わずに閉じる
+        let december = daily_month_scan_range(202512);
+        assert!(december.0 <= daily_key(20251231, "x") && daily_key(20251231, "x") < december.1);
+        for key in ["D202512", "D2025120", "D20251201", "D20251231\x01", "D202513", "D20260101\x00x"] {
+            assert!(!(december.0.as_str() <= key && key < december.1.as_str()), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_daily_key_and_scan_range() {
+        let key = daily_key(20250913, "tokyo_bay_cup");
+        assert_eq!(key, "D20250913\x00tokyo_bay_cup");
+
+        let (start, end) = daily_scan_range(20250913);
+        assert_eq!(start, "D20250913\x00");
+        assert_eq!(end, "D20250913\x01");
+        assert!(key >= start && key < end);
+        assert!(daily_key(20250914, "tokyo_bay_cup") >= end);
+
+        assert!(try_daily_key(20250913, "tokyo\x00bay").is_err());
+    }
+
+    #[test]
+    fn test_venue_key_and_scan_range() {
+        assert_eq!(venue_key(4), "V0000000004");
+        assert!(venue_key(4) < venue_key(24));
+
+        let (start, end) = venue_scan_range();
+        assert_eq!(start, "V");
+        assert_eq!(end, "W");
+        assert!(venue_key(u32::MAX) >= start && venue_key(u32::MAX) < end);
+        assert!("V12".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn test_racer_key_and_scan_range() {
+        assert_eq!(racer_key(4444), "R0000004444");
+        assert_eq!(try_racer_key(4444).unwrap(), racer_key(4444));
+        assert!(matches!(try_racer_key(0), Err(StoreError::InvalidKey)));
+
+        let (start, end) = racer_scan_range();
+        assert_eq!(start, "R");
+        assert_eq!(end, "S");
+        assert!(racer_key(1) >= start && racer_key(u32::MAX) < end);
+    }
+
+    #[test]
+    fn test_result_key_does_not_collide_with_race_data() {
+        let result = try_result_key("tokyo_bay_cup", 1694524800000).unwrap();
+        assert_eq!(result, "Ftokyo_bay_cup\x000000018a898c7c00");
+        assert_ne!(result, tournament_key("tokyo_bay_cup", 1694524800000));
+
+        let (start, end) = tournament_scan_range("tokyo_bay_cup");
+        assert!(result < start || result >= end);
+        let (start, end) = result_scan_range("tokyo_bay_cup");
+        assert!(result >= start && result < end);
+    }
+
+    #[test]
+    fn test_odds_scan_range_boundary() {
+        let race = 1694524800000;
+        let (start, end) = odds_scan_range("tokyo_bay_cup", race);
+
+        for snapshot in [0, race - 60_000, u64::MAX] {
+            let key = try_odds_key("tokyo_bay_cup", race, snapshot).unwrap();
+            assert!(key >= start && key < end);
+        }
+
+        // 隣接するレースのスナップショットは範囲外
+        for neighbor in [race - 1, race + 1] {
+            for snapshot in [0, u64::MAX] {
+                let key = try_odds_key("tokyo_bay_cup", neighbor, snapshot).unwrap();
+                assert!(key < start || key >= end);
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditions_key_and_scan_range() {
+        assert_eq!(conditions_key(4, 20250913), "W000000000420250913");
+
+        let (start, end) = conditions_scan_range(4, 20250910, 20250916);
+        assert!(conditions_key(4, 20250910) >= start);
+        assert!(conditions_key(4, 20250916) < end);
+        assert!(conditions_key(4, 20250917) >= end);
+        assert!(conditions_key(4, 20250909) < start);
+
+        // 他会場の同じ日付は範囲外
+        assert!(conditions_key(5, 20250913) >= end);
+        assert!(conditions_key(3, 20250913) < start);
+
+        // 終了日が最大値でも桁あふれせず、会場の全期間を含む
+        let (start, end) = conditions_scan_range(4, 0, u32::MAX);
+        assert!(conditions_key(4, 20250913) >= start && conditions_key(4, 20250913) < end);
+        assert!(conditions_key(4, 99999999) < end);
+        assert!(conditions_key(5, 0) >= end);
+        let (_, end) = conditions_scan_range(4, 0, 99999999);
+        assert!(conditions_key(4, 99999999) < end);
+    }
+
+    #[test]
+    fn test_every_key_is_inside_its_scan_range() {
+        let keys = [
+            Key::Monthly { year_month: 202509, tournament_id: "tokyo_bay_cup".to_string() },
+            Key::Tournament { tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::Daily { date: 20250913, tournament_id: "tokyo_bay_cup".to_string() },
+            Key::Venue { venue_id: 4 },
+            Key::Racer { racer_id: 4320 },
+            Key::Result { tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::Conditions { venue_id: 4, date: 20250913 },
+            Key::Payout { tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::Odds { tournament_id: "tokyo_bay_cup".to_string(), race_timestamp: 1694524800000, snapshot_time: 1694520000000 },
+            Key::VenueIndex { venue_id: 4, year_month: 202509, tournament_id: "tokyo_bay_cup".to_string() },
+            Key::NameIndex { token: "cup".to_string(), year_month: 202509, tournament_id: "tokyo_bay_cup".to_string() },
+            Key::RacerIndex { racer_id: 4320, tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::Participants { tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::TournamentMeta { tournament_id: "tokyo_bay_cup".to_string(), meta_key: "status".to_string() },
+        ];
+        for key in keys {
+            let encoded = key.to_string();
+            let (start, end) = key.scan_range();
+            assert!(start.as_str() <= encoded.as_str() && encoded < end, "{:?} is outside {:?}..{:?}", encoded, start, end);
+        }
+    }
+
+    #[test]
+    fn test_venue_index_key_and_scan_range() {
+        let key = venue_index_key(4, 202509, "tokyo_bay_cup");
+        assert_eq!(key, "I_venue\x00004\x00202509\x00tokyo_bay_cup");
+
+        let (start, end) = venue_index_scan_range(4);
+        assert!(key >= start && key < end);
+        // 別会場のインデックスは範囲外
+        let other = venue_index_key(40, 202509, "tokyo_bay_cup");
+        assert!(!(other >= start && other < end));
+
+        let (start, end) = venue_index_namespace_range();
+        assert!(key >= start && key < end && other >= start && other < end);
+        assert!("I_other\x00004\x00202509\x00x".parse::<Key>().is_err());
+        // 旧形式 (10桁) や 3桁に収まらない会場IDは扱わない
+        assert!("I_venue\x000000000004\x00202509\x00x".parse::<Key>().is_err());
+        assert!(try_venue_index_key(1000, 202509, "x").is_err());
+        assert_eq!(try_venue_index_key(24, 202509, "x").unwrap(), "I_venue\x00024\x00202509\x00x");
+    }
+
+    #[test]
+    fn test_name_index_scan_range() {
+        let key = name_index_key("高松", 202509, "sumino");
+        let (start, end) = name_index_scan_range("高松", None);
+        assert!(key >= start && key < end);
+        let (start, end) = name_index_scan_range("高松", Some(202509));
+        assert!(key >= start && key < end);
+        let (start, end) = name_index_scan_range("高松", Some(202510));
+        assert!(!(key >= start && key < end));
+        // トークンの前方一致では範囲に入らない
+        let (start, end) = name_index_scan_range("高", None);
+        assert!(!(key >= start && key < end));
+    }
+
+    #[test]
+    fn test_racer_index_key_and_scan_range() {
+        let key = try_racer_index_key(4444, "tokyo_bay_cup", 1694524800000).unwrap();
+        let (start, end) = racer_index_scan_range(4444);
+        assert!(key >= start && key < end);
+        let (start, end) = racer_index_scan_range(44);
+        assert!(!(key >= start && key < end));
+
+        assert!(key.starts_with("I_racer\x00004444\x00tokyo_bay_cup\x00"));
+        assert_eq!(key.parse::<Key>().unwrap().to_string(), key);
+        assert!(try_racer_index_key(0, "tokyo_bay_cup", 1).is_err());
+        assert!(try_racer_index_key(1_000_000, "tokyo_bay_cup", 1).is_err());
+        assert!(try_racer_index_key(4444, "bad\x00id", 1).is_err());
+        assert!(try_participants_key("bad\x00id", 1).is_err());
+
+        // 出走者一覧は他のインデックスの範囲に入らない
+        let participants = try_participants_key("tokyo_bay_cup", 1694524800000).unwrap();
+        for (start, end) in [venue_index_namespace_range(), name_index_namespace_range(), racer_index_scan_range(4444)] {
+            assert!(!(participants >= start && participants < end));
+        }
+    }
+
+    #[test]
+    fn test_namespace_scan_range() {
+        let (start, end) = namespace_scan_range(PREFIX_MONTHLY);
+        assert_eq!(start, "M");
+        assert_eq!(end, "N");
+        assert!(monthly_key(999912, "zzz") < end);
+    }
+
+    #[test]
+    fn test_tournament_time_range() {
+        let (start, end) = tournament_time_range("tokyo", 100, 200);
+        assert!(tournament_key("tokyo", 100) >= start);
+        assert!(tournament_key("tokyo", 199) < end);
+        assert!(tournament_key("tokyo", 200) >= end);
+        assert!(tournament_key("tokyo_bay", 150) >= end);
     }
 
     #[test]
@@ -185,4 +1804,148 @@ mod tests {
         let id = generate_tournament_id("Tokyo", "Bay Cup 2025");
         assert_eq!(id, "tokyo_bay_cup_2025");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_generate_tournament_id_collapses_underscores() {
+        // 前後・連続する空白は1つの '_' にまとめ、両端には残さない
+        assert_eq!(generate_tournament_id("  Tokyo  ", "  Bay  Cup  "), "tokyo_bay_cup");
+        assert_eq!(generate_tournament_id("Ab", "  Cup "), "venue_2_cup");
+        assert_eq!(generate_tournament_id("平和島 Cup", "G1"), "cup_event_2");
+    }
+
+    #[test]
+    fn test_parse_written_tournament_id() {
+        assert_eq!(parse_written_tournament_id(&monthly_key(202509, "tokyo_bay_cup")), Some("tokyo_bay_cup"));
+        assert_eq!(parse_written_tournament_id(&tournament_key("tokyo_bay_cup", 1)), Some("tokyo_bay_cup"));
+        assert_eq!(parse_written_tournament_id(&daily_key(20250913, "tokyo_bay_cup")), None);
+        assert_eq!(parse_written_tournament_id("M2025\0tokyo_bay_cup"), None);
+        assert_eq!(parse_written_tournament_id("Ttokyo_bay_cup\x000000018A898C7C00"), None);
+        assert_eq!(parse_written_tournament_id(""), None);
+    }
+
+    #[test]
+    fn test_key_display_matches_string_format() {
+        let monthly = Key::Monthly {
+            year_month: 202509,
+            tournament_id: "tokyo_bay_cup".to_string(),
+        };
+        assert_eq!(monthly.to_string(), monthly_key(202509, "tokyo_bay_cup"));
+
+        let tournament = Key::Tournament {
+            tournament_id: "tokyo_bay_cup".to_string(),
+            timestamp: 1694524800000,
+        };
+        assert_eq!(tournament.to_string(), tournament_key("tokyo_bay_cup", 1694524800000));
+    }
+
+    #[test]
+    fn test_key_round_trip() {
+        let keys = vec![
+            Key::Monthly { year_month: 202509, tournament_id: "tokyo_bay_cup".to_string() },
+            Key::Monthly { year_month: 202412, tournament_id: "venue_9_event_36".to_string() },
+            Key::Monthly { year_month: 1, tournament_id: "a".to_string() },
+            Key::Monthly { year_month: 202601, tournament_id: String::new() },
+            Key::Tournament { tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::Tournament { tournament_id: "kiryu".to_string(), timestamp: 0 },
+            Key::Tournament { tournament_id: "kiryu".to_string(), timestamp: u64::MAX },
+            Key::Tournament { tournament_id: String::new(), timestamp: 42 },
+            Key::Daily { date: 20250913, tournament_id: "tokyo_bay_cup".to_string() },
+            Key::Daily { date: 20251231, tournament_id: String::new() },
+            Key::Venue { venue_id: 4 },
+            Key::Venue { venue_id: u32::MAX },
+            Key::Racer { racer_id: 4444 },
+            Key::Racer { racer_id: 0 },
+            Key::Result { tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::Payout { tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::Conditions { venue_id: 4, date: 20250913 },
+            Key::Odds { tournament_id: "tokyo_bay_cup".to_string(), race_timestamp: 1694524800000, snapshot_time: 1694524740000 },
+            Key::Odds { tournament_id: String::new(), race_timestamp: 0, snapshot_time: u64::MAX },
+            Key::VenueIndex { venue_id: 4, year_month: 202509, tournament_id: "tokyo_bay_cup".to_string() },
+            Key::VenueIndex { venue_id: 999, year_month: 202601, tournament_id: String::new() },
+            Key::NameIndex { token: "高松".to_string(), year_month: 202509, tournament_id: "sumino".to_string() },
+            Key::NameIndex { token: "cu".to_string(), year_month: 202512, tournament_id: String::new() },
+            Key::RacerIndex { racer_id: 4444, tournament_id: "tokyo_bay_cup".to_string(), timestamp: 1694524800000 },
+            Key::Participants { tournament_id: "tokyo_bay_cup".to_string(), timestamp: u64::MAX },
+            Key::TournamentMeta { tournament_id: "tokyo_bay_cup".to_string(), meta_key: "sponsor".to_string() },
+            Key::TournamentMeta { tournament_id: String::new(), meta_key: "放送局".to_string() },
+        ];
+
+        for key in keys {
+            let encoded = key.to_string();
+            let decoded: Key = encoded.parse().unwrap();
+            assert_eq!(decoded, key);
+        }
+    }
+
+    #[test]
+    fn test_key_scan_range() {
+        let monthly = Key::Monthly {
+            year_month: 202509,
+            tournament_id: "tokyo_bay_cup".to_string(),
+        };
+        assert_eq!(monthly.scan_range(), monthly_scan_range(202509));
+
+        let tournament = Key::Tournament {
+            tournament_id: "tokyo_bay_cup".to_string(),
+            timestamp: 1694524800000,
+        };
+        assert_eq!(tournament.scan_range(), tournament_scan_range("tokyo_bay_cup"));
+    }
+
+    #[test]
+    fn test_key_from_str_invalid() {
+        // 未知のプレフィックス
+        assert!("Z202509\x00tokyo_bay_cup".parse::<Key>().is_err());
+        // 空文字列
+        assert!("".parse::<Key>().is_err());
+        // セパレータなし
+        assert!("M202509".parse::<Key>().is_err());
+        // 年月が数字でない
+        assert!("M2025ab\x00tokyo_bay_cup".parse::<Key>().is_err());
+        // タイムスタンプの桁数不正
+        assert!("Ttokyo_bay_cup\x00abc".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn test_try_keys_reject_separator() {
+        assert!(matches!(try_monthly_key(202509, "tokyo\x00bay"), Err(StoreError::InvalidKey)));
+        assert!(matches!(try_monthly_key(202509, "\x00tokyo"), Err(StoreError::InvalidKey)));
+        assert!(matches!(try_monthly_key(202509, "tokyo\x00"), Err(StoreError::InvalidKey)));
+        assert!(matches!(try_tournament_key("tokyo\x00bay", 0), Err(StoreError::InvalidKey)));
+        assert!(matches!(try_tournament_key("\x00tokyo", 0), Err(StoreError::InvalidKey)));
+        assert!(matches!(try_tournament_key("tokyo\x00", 0), Err(StoreError::InvalidKey)));
+        assert!(matches!(try_tournament_key("\x00", 0), Err(StoreError::InvalidKey)));
+    }
+
+    #[test]
+    fn test_tournament_meta_key() {
+        let key = try_tournament_meta_key("tokyo_bay_cup", "sponsor").unwrap();
+        assert_eq!(key, "Xtokyo_bay_cup\x00sponsor");
+        let (start, end) = tournament_meta_scan_range("tokyo_bay_cup");
+        assert!(key >= start && key < end);
+        // 大会データのスキャン範囲とは重ならない
+        let (start, end) = namespace_scan_range(PREFIX_TOURNAMENT);
+        assert!(key < start || key >= end);
+
+        assert!(matches!(try_tournament_meta_key("tokyo_bay_cup", ""), Err(StoreError::InvalidKey)));
+        assert!(matches!(try_tournament_meta_key("tokyo_bay_cup", "a\x00b"), Err(StoreError::InvalidKey)));
+        assert!(matches!(try_tournament_meta_key("tokyo\x00bay", "sponsor"), Err(StoreError::InvalidKey)));
+        assert!("Xtokyo\x00a\x00b".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn test_try_keys_accept_other_control_bytes() {
+        // 0x01 はセパレータではないのでキー構造は曖昧にならない
+        let key = try_tournament_key("tokyo\x01bay", 7).unwrap();
+        assert_eq!(key, tournament_key("tokyo\x01bay", 7));
+        let parsed: Key = key.parse().unwrap();
+        assert_eq!(parsed, Key::Tournament { tournament_id: "tokyo\x01bay".to_string(), timestamp: 7 });
+
+        // 0x01 を含むIDは別大会のスキャン範囲に入らない
+        let (start, end) = tournament_scan_range("tokyo");
+        assert!(key.as_str() >= end.as_str() || key.as_str() < start.as_str());
+
+        let key = try_monthly_key(202509, "\x01tokyo\x01").unwrap();
+        assert_eq!(key, monthly_key(202509, "\x01tokyo\x01"));
+    }
+}
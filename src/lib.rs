@@ -22,43 +22,105 @@
 //! let retrieved = engine.get_monthly_schedule(202509)?;
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! ## Feature Flags
+//!
+//! | Feature  | Default | Provides | wasm32-unknown-unknown |
+//! |----------|---------|----------|------------------------|
+//! | `memory` | yes     | `MemoryStore` (always compiled; name it explicitly when disabling defaults) | yes |
+//! | `fs`     | yes     | `FileStore` and the `norimaki-cli` binary | no (needs `std::fs`) |
+//! | `wasm`   | no      | `LocalStorageStore` over `web_sys::Storage` (localStorage / sessionStorage); chrono reads the clock from JS | yes |
+//! | `http`   | no      | `server` module with a read-only JSON API on `tiny_http` | no (needs `std::net`) |
+//! | `async`  | no      | `async_engine` module over tokio's `spawn_blocking` | no (needs threads) |
+//! | `compression` | yes | `BoatRaceEngine::with_compression`: large values are deflate-compressed with `flate2` | yes |
+//! | `rmp`    | no      | `Codec::MessagePack` / `MessagePackCodec` values readable by any msgpack library | yes |
+//! | `tracing` | no     | `tracing` spans for engine methods, debug events per written key, slow `FileStore` save warnings | yes |
+//! | `test-util` | no   | `testing` module with `NullStore`, `RecordingStore` and `FailingStore` test doubles; `fixtures` module with seeded synthetic data | yes |
+//! | `rayon`  | no      | `get_tournament_races_par` / `get_monthly_schedule_par` deserialize on all cores (sequential without it) | no (needs threads) |
+//!
+//! For the browser, build with `default-features = false, features = ["memory", "wasm"]`.
+//! The engine, key and value modules (bincode/base64) have no platform-specific code.
 
 pub mod error;
 pub mod store;
 pub mod key;
 pub mod value;
 pub mod engine;
+pub mod import;
+pub mod venues;
+pub mod metrics;
+pub mod dry_run;
+pub mod overlay;
+pub mod quota;
+pub mod compact;
+#[cfg(feature = "http")]
+pub mod server;
+#[cfg(feature = "async")]
+pub mod async_engine;
+#[cfg(feature = "wasm")]
+pub mod web_storage;
+#[cfg(feature = "test-util")]
+pub mod testing;
+#[cfg(feature = "test-util")]
+pub mod fixtures;
+#[cfg(feature = "compression")]
+mod compress;
+mod csv;
+mod ics;
+mod text;
 
 // Core types and results
-pub use error::{Result, StoreError};
+pub use error::{ErrorKind, Quota, Result, StoreError};
 
 // Storage backends
-pub use store::{FileStore, KeyValueStore, MemoryStore};
+pub use store::{KeyValueStore, MemoryStore};
+pub use metrics::{render_prometheus, EngineMetrics};
+pub use dry_run::DryRunReport;
+pub use overlay::{apply_changes, Change, OverlayStore};
+pub use quota::{QuotaStore, QuotaUsage};
+pub use compact::CompactMemoryStore;
+#[cfg(feature = "fs")]
+pub use store::{FileStore, ReloadStats, SyncPolicy};
+#[cfg(all(feature = "fs", feature = "tracing"))]
+pub use store::DEFAULT_SLOW_SAVE_THRESHOLD;
+#[cfg(feature = "wasm")]
+pub use web_storage::{LocalStorageStore, WebStorage};
 
 // Main engine
-pub use engine::BoatRaceEngine;
+pub use engine::{ArchiveReport, BoatRaceEngine, ClearReport, ConflictPolicy, CsvOptions, DumpOptions, DatabaseStatistics, DedupeReport, DeleteReport, EngineEvent, ExportStats, HookPanic, ImportConflict, ImportOptions, ImportReport, ImportStats, ScheduleDiff, ScheduleOptions, SyncOptions, SyncReport, IndexCheck, IndexOptions, IntegrityReport, MonthOverview, RepairOptions, StatisticsCounters, TournamentHandle, TournamentStats, TournamentSummary, YearOverview};
 
 // Key generation utilities (commonly used)
-pub use key::{generate_tournament_id, monthly_key, tournament_key};
+pub use key::{classify_key, conditions_key, daily_key, decode_timestamp, encode_timestamp, generate_tournament_id, monthly_key, tournament_key, racer_key, try_daily_key, try_monthly_key, try_odds_key, try_payout_key, try_racer_key, try_result_key, try_tournament_key, try_tournament_meta_key, tournament_key_matches, user_key, venue_key, Key, Namespace};
 
 // Serialization utilities (for custom data types)
-pub use value::{serialize_to_string, deserialize_from_string};
+pub use value::{serialize_to_string, deserialize_from_string, deserialize_tagged, BincodeBase64Codec, FORMAT_VERSION, Codec, JsonCodec, ValueCodec};
+#[cfg(feature = "compression")]
+pub use value::{serialize_to_string_compressed, Compression, CompressionAlgorithm};
+#[cfg(feature = "rmp")]
+pub use value::MessagePackCodec;
 
 // Re-export commonly used types from dependencies
 pub use serde::{Serialize, Deserialize};
 
+use chrono::{Datelike, NaiveDate};
+use std::ops::RangeInclusive;
+
 /// Monthly schedule containing a list of race events for a specific month
+///
+/// New fields follow the same compatibility rules as `RaceEvent` (`Option` with `#[serde(default)]`).
+///
+/// Prefer `MonthlySchedule::builder`, which checks that every event overlaps the month.
 /// 
 /// # Example
 /// ```rust
-/// use norimaki_db::{MonthlySchedule, RaceEvent};
+/// use norimaki_db::MonthlySchedule;
 /// 
-/// let schedule = MonthlySchedule {
-///     year_month: "2025-09".to_string(),
-///     events: vec![/* RaceEvent instances */],
-/// };
+/// let schedule = MonthlySchedule::builder(202509)
+///     .events(vec![/* RaceEvent instances */])
+///     .build()?;
+/// # Ok::<(), norimaki_db::StoreError>(())
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MonthlySchedule {
     /// Year and month in "YYYY-MM" format (e.g., "2025-09")
     pub year_month: String,
@@ -66,22 +128,465 @@ pub struct MonthlySchedule {
     pub events: Vec<RaceEvent>,
 }
 
+impl MonthlySchedule {
+    /// Start building a schedule for a YYYYMM month (e.g., 202509)
+    ///
+    /// # Example
+    /// ```rust
+    /// use norimaki_db::{Grade, MonthlySchedule, RaceEvent};
+    ///
+    /// let schedule = MonthlySchedule::builder(202509)
+    ///     .event(
+    ///         RaceEvent::builder()
+    ///             .venue_id(4)
+    ///             .venue_name("平和島")
+    ///             .event_name("トーキョー・ベイ・カップ")
+    ///             .grade(Grade::G1)
+    ///             .start_date("2025-09-10")
+    ///             .duration_days(7)
+    ///             .build()?,
+    ///     )
+    ///     .build()?;
+    /// assert_eq!(schedule.year_month, "2025-09");
+    /// # Ok::<(), norimaki_db::StoreError>(())
+    /// ```
+    pub fn builder(year_month: u32) -> MonthlyScheduleBuilder {
+        MonthlyScheduleBuilder {
+            year_month,
+            events: Vec::new(),
+        }
+    }
+
+    /// Sort events by start date (events starting on the same day are ordered by venue_id)
+    pub fn sort_by_start_date(&mut self) {
+        self.events.sort_by(|a, b| a.start_date.cmp(&b.start_date).then(a.venue_id.cmp(&b.venue_id)));
+    }
+
+    /// Multi-line table of the month's events, one row per event in start-date order
+    ///
+    /// Columns are padded by display width, counting full-width (CJK) characters as two columns,
+    /// so Japanese names line up in a monospace terminal. Ambiguous-width symbols are counted as one.
+    ///
+    /// # Example
+    /// ```rust
+    /// use norimaki_db::{Grade, MonthlySchedule, RaceEvent};
+    ///
+    /// let event = |venue_id, venue: &str, name: &str, grade, start: &str, days| {
+    ///     RaceEvent::builder()
+    ///         .venue_id(venue_id)
+    ///         .venue_name(venue)
+    ///         .event_name(name)
+    ///         .grade(grade)
+    ///         .start_date(start)
+    ///         .duration_days(days)
+    ///         .build()
+    /// };
+    /// let schedule = MonthlySchedule::builder(202509)
+    ///     .event(event(4, "平和島", "トーキョー・ベイ・カップ", Grade::G1, "2025-09-10", 7)?)
+    ///     .event(event(1, "桐生", "群馬クレインサンダーズカップ", Grade::Ippan, "2025-09-11", 6)?)
+    ///     .build()?;
+    /// assert_eq!(
+    ///     schedule.summary(),
+    ///     "2025-09: 2 events\n\
+    ///      2025-09-10〜09-16  平和島  G1    トーキョー・ベイ・カップ\n\
+    ///      2025-09-11〜09-16  桐生    一般  群馬クレインサンダーズカップ\n"
+    /// );
+    /// # Ok::<(), norimaki_db::StoreError>(())
+    /// ```
+    pub fn summary(&self) -> String {
+        let mut events: Vec<&RaceEvent> = self.events.iter().collect();
+        events.sort_by(|a, b| a.start_date.cmp(&b.start_date).then(a.venue_id.cmp(&b.venue_id)));
+        let rows: Vec<[String; 4]> = events
+            .iter()
+            .map(|event| [event.period(), event.venue_name.clone(), event.grade.to_string(), event.event_name.clone()])
+            .collect();
+
+        let mut widths = [0; 3];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(text::display_width(cell));
+            }
+        }
+        let count = match rows.len() {
+            0 => "no events".to_string(),
+            1 => "1 event".to_string(),
+            n => format!("{} events", n),
+        };
+        let mut summary = format!("{}: {}\n", self.year_month, count);
+        for [period, venue, grade, name] in &rows {
+            summary.push_str(&format!(
+                "{}  {}  {}  {}\n",
+                text::pad(period, widths[0]),
+                text::pad(venue, widths[1]),
+                text::pad(grade, widths[2]),
+                name
+            ));
+        }
+        summary
+    }
+
+    /// Copy of the schedule containing only events of the given grade
+    pub fn filter_grade(&self, grade: Grade) -> MonthlySchedule {
+        MonthlySchedule {
+            year_month: self.year_month.clone(),
+            events: self.events.iter().filter(|event| event.grade == grade).cloned().collect(),
+        }
+    }
+
+    /// Events held at the given venue, in schedule order
+    pub fn events_at_venue(&self, venue_id: u32) -> Vec<&RaceEvent> {
+        self.events.iter().filter(|event| event.venue_id == venue_id).collect()
+    }
+
+    /// First and last racing day across all events
+    ///
+    /// Events with an unparsable `start_date` or zero `duration_days` are ignored;
+    /// returns `None` if no event has a usable date range.
+    pub fn date_span(&self) -> Option<(NaiveDate, NaiveDate)> {
+        self.events
+            .iter()
+            .filter_map(|event| Some((event.start().ok()?, event.end().ok()?)))
+            .reduce(|(first, last), (start, end)| (first.min(start), last.max(end)))
+    }
+
+    /// Combine two schedules of the same month, keeping every event
+    ///
+    /// Shorthand for `merge_report(other)?.schedule`; see `merge_report` for how duplicates and
+    /// conflicting events are handled.
+    pub fn merge(&self, other: &MonthlySchedule) -> Result<MonthlySchedule> {
+        Ok(self.merge_report(other)?.schedule)
+    }
+
+    /// Combine two schedules of the same month and report what differed
+    ///
+    /// Events are matched by tournament id (`generate_tournament_id(venue_name, event_name)`, the
+    /// identity the engine stores them under):
+    /// - identical events are kept once and counted in `duplicates`;
+    /// - events with the same tournament id but different fields are all kept, and each pair is
+    ///   reported in `conflicts` so the caller can decide which source wins before importing
+    ///   (importing both would overwrite one with the other).
+    ///
+    /// Events from `self` come first, followed by new events from `other`, each in their original order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use norimaki_db::{Grade, MonthlySchedule, RaceEvent};
+    ///
+    /// let event = RaceEvent::builder()
+    ///     .venue_id(4)
+    ///     .venue_name("平和島")
+    ///     .event_name("トーキョー・ベイ・カップ")
+    ///     .grade(Grade::G1)
+    ///     .start_date("2025-09-10")
+    ///     .duration_days(7)
+    ///     .build()?;
+    /// let ours = MonthlySchedule::builder(202509).event(event.clone()).build()?;
+    /// let theirs = MonthlySchedule::builder(202509)
+    ///     .event(RaceEvent { duration_days: 6, ..event })
+    ///     .build()?;
+    ///
+    /// let report = ours.merge_report(&theirs)?;
+    /// assert_eq!(report.schedule.events.len(), 2);
+    /// assert_eq!(report.conflicts.len(), 1);
+    /// # Ok::<(), norimaki_db::StoreError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// `StoreError::ValidationError { field: "year_month", .. }` if the schedules are for different months.
+    pub fn merge_report(&self, other: &MonthlySchedule) -> Result<MergeReport> {
+        if self.year_month != other.year_month {
+            return Err(StoreError::ValidationError {
+                field: "year_month".to_string(),
+                message: format!("cannot merge {} with {}", self.year_month, other.year_month),
+            });
+        }
+
+        let mut report = MergeReport {
+            schedule: MonthlySchedule {
+                year_month: self.year_month.clone(),
+                events: Vec::new(),
+            },
+            duplicates: 0,
+            conflicts: Vec::new(),
+        };
+        for event in self.events.iter().chain(&other.events) {
+            let tournament_id = generate_tournament_id(&event.venue_name, &event.event_name);
+            let same_tournament: Vec<&RaceEvent> = report
+                .schedule
+                .events
+                .iter()
+                .filter(|kept| generate_tournament_id(&kept.venue_name, &kept.event_name) == tournament_id)
+                .collect();
+            if same_tournament.contains(&event) {
+                report.duplicates += 1;
+                continue;
+            }
+            let conflicts: Vec<MergeConflict> = same_tournament
+                .into_iter()
+                .map(|kept| MergeConflict {
+                    tournament_id: tournament_id.clone(),
+                    kept: kept.clone(),
+                    incoming: event.clone(),
+                })
+                .collect();
+            report.conflicts.extend(conflicts);
+            report.schedule.events.push(event.clone());
+        }
+        Ok(report)
+    }
+}
+
+/// Result of `MonthlySchedule::merge_report`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Merged schedule (conflicting events are all included)
+    pub schedule: MonthlySchedule,
+    /// Number of events dropped because an identical event was already present
+    pub duplicates: usize,
+    /// Events that share a tournament id with an earlier, different event
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Two different events with the same tournament id found while merging
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// Tournament id both events map to
+    pub tournament_id: String,
+    /// Event that was already in the merged schedule
+    pub kept: RaceEvent,
+    /// Later event with the same tournament id (also kept in the merged schedule)
+    pub incoming: RaceEvent,
+}
+
+/// Builder for `MonthlySchedule`, created by `MonthlySchedule::builder`
+#[derive(Debug, Clone)]
+pub struct MonthlyScheduleBuilder {
+    year_month: u32,
+    events: Vec<RaceEvent>,
+}
+
+impl MonthlyScheduleBuilder {
+    /// Add an event to the schedule
+    pub fn event(mut self, event: RaceEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Add several events to the schedule
+    pub fn events(mut self, events: impl IntoIterator<Item = RaceEvent>) -> Self {
+        self.events.extend(events);
+        self
+    }
+
+    /// Validate the month and every event, then build the schedule
+    ///
+    /// Each event is checked like `RaceEventBuilder::build`, and must also run on at least one day
+    /// of the month (`StoreError::ValidationError { field: "start_date", .. }` otherwise).
+    /// An invalid month is reported with `field: "year_month"`.
+    pub fn build(self) -> Result<MonthlySchedule> {
+        if key::validate_year_month(self.year_month).is_err() {
+            return Err(StoreError::ValidationError {
+                field: "year_month".to_string(),
+                message: format!("{} is not a YYYYMM month", self.year_month),
+            });
+        }
+        for event in &self.events {
+            event.validate(&DEFAULT_VENUE_IDS, Some(self.year_month))?;
+        }
+        Ok(MonthlySchedule {
+            year_month: format!("{:04}-{:02}", self.year_month / 100, self.year_month % 100),
+            events: self.events,
+        })
+    }
+}
+
+/// Grade of a race event, ordered by prestige (`SG` is the highest)
+///
+/// Parsing is lenient about the spellings found in the wild: full-width characters, lower case,
+/// surrounding spaces and roman numerals are all accepted (`"Ｇ１"`, `"g1"`, `"GⅠ"` and `"G1"` are
+/// the same grade). `Display` and serde always produce the canonical form (`"SG"`, `"G1"`, `"G2"`,
+/// `"G3"`, `"一般"`), which is also how grades were stored before this type existed.
+///
+/// # Example
+/// ```rust
+/// use norimaki_db::Grade;
+///
+/// let grade: Grade = "ＧⅠ".parse()?;
+/// assert_eq!(grade, Grade::G1);
+/// assert_eq!(grade.to_string(), "G1");
+/// assert!(Grade::SG > Grade::G1 && Grade::G3 > Grade::Ippan);
+/// # Ok::<(), norimaki_db::StoreError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Grade {
+    /// General races (一般)
+    #[default]
+    Ippan,
+    G3,
+    G2,
+    G1,
+    SG,
+}
+
+impl Grade {
+    /// All grades from the lowest to the highest
+    pub const ALL: [Grade; 5] = [Grade::Ippan, Grade::G3, Grade::G2, Grade::G1, Grade::SG];
+
+    /// Canonical spelling of the grade
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Grade::Ippan => "一般",
+            Grade::G3 => "G3",
+            Grade::G2 => "G2",
+            Grade::G1 => "G1",
+            Grade::SG => "SG",
+        }
+    }
+}
+
+impl std::fmt::Display for Grade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Grade {
+    type Err = StoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // 全角英数字を半角に、ローマ数字をアラビア数字にそろえ、空白を除いて大文字にする
+        let mut normalized = String::with_capacity(s.len());
+        for c in s.chars() {
+            match c {
+                '\u{ff01}'..='\u{ff5e}' => normalized.push(char::from_u32(c as u32 - 0xfee0).unwrap_or(c)),
+                'Ⅰ' | 'ⅰ' => normalized.push('1'),
+                'Ⅱ' | 'ⅱ' => normalized.push('2'),
+                'Ⅲ' | 'ⅲ' => normalized.push('3'),
+                c if c.is_whitespace() => {}
+                c => normalized.push(c),
+            }
+        }
+        match normalized.to_ascii_uppercase().as_str() {
+            "SG" => Ok(Grade::SG),
+            "G1" | "GI" => Ok(Grade::G1),
+            "G2" | "GII" => Ok(Grade::G2),
+            "G3" | "GIII" => Ok(Grade::G3),
+            "一般" | "IPPAN" => Ok(Grade::Ippan),
+            _ => Err(StoreError::ValidationError {
+                field: "grade".to_string(),
+                message: format!("{:?} is not a known grade (SG, G1, G2, G3 or 一般)", s),
+            }),
+        }
+    }
+}
+
+impl Serialize for Grade {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Grade {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Lifecycle status of a tournament
+///
+/// `Scheduled`, `Ongoing` and `Finished` follow from the event's date window (see
+/// `RaceEvent::status_on`); `Cancelled` and an early `Finished` can be recorded explicitly with
+/// `BoatRaceEngine::set_tournament_status`, and such an override wins over the dates.
+///
+/// # Example
+/// ```rust
+/// use norimaki_db::TournamentStatus;
+///
+/// let status: TournamentStatus = "cancelled".parse()?;
+/// assert_eq!(status, TournamentStatus::Cancelled);
+/// assert_eq!(TournamentStatus::Ongoing.to_string(), "ongoing");
+/// # Ok::<(), norimaki_db::StoreError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TournamentStatus {
+    /// The first day is still ahead
+    Scheduled,
+    /// Today is within the event's days
+    Ongoing,
+    /// The last day has passed, or the tournament was marked finished
+    Finished,
+    /// Marked cancelled
+    Cancelled,
+}
+
+impl TournamentStatus {
+    /// Lowercase name, also used as the stored representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TournamentStatus::Scheduled => "scheduled",
+            TournamentStatus::Ongoing => "ongoing",
+            TournamentStatus::Finished => "finished",
+            TournamentStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Returns true for the statuses that can be recorded as an override (`Finished`, `Cancelled`)
+    pub fn is_final(&self) -> bool {
+        matches!(self, TournamentStatus::Finished | TournamentStatus::Cancelled)
+    }
+}
+
+impl std::fmt::Display for TournamentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TournamentStatus {
+    type Err = StoreError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "scheduled" => Ok(TournamentStatus::Scheduled),
+            "ongoing" => Ok(TournamentStatus::Ongoing),
+            "finished" => Ok(TournamentStatus::Finished),
+            "cancelled" => Ok(TournamentStatus::Cancelled),
+            _ => Err(StoreError::ValidationError {
+                field: "status".to_string(),
+                message: format!("{:?} is not a known status (scheduled, ongoing, finished or cancelled)", s),
+            }),
+        }
+    }
+}
+
 /// Information about a single race event/tournament
+///
+/// Prefer `RaceEvent::builder`, which validates the fields the way the engine does.
 /// 
 /// # Example
 /// ```rust
-/// use norimaki_db::RaceEvent;
+/// use norimaki_db::{Grade, RaceEvent};
 /// 
-/// let event = RaceEvent {
-///     venue_id: 4,
-///     venue_name: "平和島".to_string(),
-///     event_name: "トーキョー・ベイ・カップ".to_string(),
-///     grade: "G1".to_string(),
-///     start_date: "2025-09-10".to_string(),
-///     duration_days: 7,
-/// };
+/// let event = RaceEvent::builder()
+///     .venue_id(4)
+///     .venue_name("平和島")
+///     .event_name("トーキョー・ベイ・カップ")
+///     .grade(Grade::G1)
+///     .start_date("2025-09-10")
+///     .duration_days(7)
+///     .build()?;
+/// # Ok::<(), norimaki_db::StoreError>(())
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// # Compatibility
+///
+/// Fields added after the first release are `Option`s that default to `None` when missing and are
+/// left out of self-describing formats (JSON, MessagePack) while unset, so older and newer binaries
+/// can read each other's exports; unknown fields are ignored. bincode reads fields by position, so
+/// each addition also bumps `FORMAT_VERSION` and keeps a reader for the previous layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RaceEvent {
     /// Unique venue identifier
     pub venue_id: u32,
@@ -89,19 +594,1001 @@ pub struct RaceEvent {
     pub venue_name: String,
     /// Name of the event/tournament
     pub event_name: String,
-    /// Grade of the event (e.g., "G1", "G2", "一般", "SG")
-    pub grade: String,
+    /// Grade of the event
+    pub grade: Grade,
     /// Start date in "YYYY-MM-DD" format
     pub start_date: String,
     /// Duration of the event in days
     pub duration_days: u32,
+    /// Free-form notes (e.g., a timetable URL or a cancellation remark); added in format version 2
+    pub notes: Option<String>,
+}
+
+/// Formats as "『G1』トーキョー・ベイ・カップ @ 平和島 2025-09-10〜09-16"
+impl std::fmt::Display for RaceEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "『{}』{} @ {} {}", self.grade, self.event_name, self.venue_name, self.period())
+    }
+}
+
+/// Format version 1 layout of `RaceEvent`, read from unprefixed bincode values
+#[derive(Deserialize)]
+#[serde(rename = "RaceEvent")]
+struct RaceEventV1 {
+    venue_id: u32,
+    venue_name: String,
+    event_name: String,
+    grade: Grade,
+    start_date: String,
+    duration_days: u32,
+}
+
+/// Current layout of `RaceEvent`
+#[derive(Deserialize)]
+#[serde(rename = "RaceEvent")]
+struct RaceEventV2 {
+    venue_id: u32,
+    venue_name: String,
+    event_name: String,
+    grade: Grade,
+    start_date: String,
+    duration_days: u32,
+    #[serde(default)]
+    notes: Option<String>,
+}
+
+impl Serialize for RaceEvent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        // bincode は位置で読むため常に全フィールドを書く。自己記述的な形式では未設定の追加フィールドを省く
+        let positional = !serializer.is_human_readable();
+        let write_notes = positional || self.notes.is_some();
+        let mut state = serializer.serialize_struct("RaceEvent", 6 + write_notes as usize)?;
+        state.serialize_field("venue_id", &self.venue_id)?;
+        state.serialize_field("venue_name", &self.venue_name)?;
+        state.serialize_field("event_name", &self.event_name)?;
+        state.serialize_field("grade", &self.grade)?;
+        state.serialize_field("start_date", &self.start_date)?;
+        state.serialize_field("duration_days", &self.duration_days)?;
+        if write_notes {
+            state.serialize_field("notes", &self.notes)?;
+        } else {
+            state.skip_field("notes")?;
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for RaceEvent {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        if !deserializer.is_human_readable() && value::decoding_format_version() < 2 {
+            let v1 = RaceEventV1::deserialize(deserializer)?;
+            return Ok(RaceEvent {
+                venue_id: v1.venue_id,
+                venue_name: v1.venue_name,
+                event_name: v1.event_name,
+                grade: v1.grade,
+                start_date: v1.start_date,
+                duration_days: v1.duration_days,
+                notes: None,
+            });
+        }
+        let v2 = RaceEventV2::deserialize(deserializer)?;
+        Ok(RaceEvent {
+            venue_id: v2.venue_id,
+            venue_name: v2.venue_name,
+            event_name: v2.event_name,
+            grade: v2.grade,
+            start_date: v2.start_date,
+            duration_days: v2.duration_days,
+            notes: v2.notes,
+        })
+    }
+}
+
+impl RaceEvent {
+    /// Start building an event
+    ///
+    /// Unset fields default to `Grade::Ippan` and a one-day event; venue, names and start date have
+    /// no default and must be set for `build` to succeed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use norimaki_db::{Grade, RaceEvent};
+    ///
+    /// let event = RaceEvent::builder()
+    ///     .venue_id(4)
+    ///     .venue_name("平和島")
+    ///     .event_name("トーキョー・ベイ・カップ")
+    ///     .grade(Grade::G1)
+    ///     .start_date("2025-09-10")
+    ///     .duration_days(7)
+    ///     .build()?;
+    /// assert_eq!(event.end()?.to_string(), "2025-09-16");
+    /// # Ok::<(), norimaki_db::StoreError>(())
+    /// ```
+    pub fn builder() -> RaceEventBuilder {
+        RaceEventBuilder::default()
+    }
+
+    /// Check the event the way the engine does when validation is enabled
+    ///
+    /// Names must not be blank, `start_date` must be a real date, `duration_days` must be in
+    /// 1..=30 and `venue_id` in `venue_ids`. With `year_month`, the event must also overlap that month.
+    pub(crate) fn validate(&self, venue_ids: &RangeInclusive<u32>, year_month: Option<u32>) -> Result<()> {
+        let invalid = |field: &str, message: String| StoreError::ValidationError {
+            field: field.to_string(),
+            message: format!("event {:?}: {}", self.event_name, message),
+        };
+
+        if self.event_name.trim().is_empty() {
+            return Err(invalid("event_name", "must not be empty".to_string()));
+        }
+        if self.venue_name.trim().is_empty() {
+            return Err(invalid("venue_name", "must not be empty".to_string()));
+        }
+        let start = NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d")
+            .map_err(|e| invalid("start_date", format!("{:?} is not a valid YYYY-MM-DD date ({})", self.start_date, e)))?;
+        if !(1..=30).contains(&self.duration_days) {
+            return Err(invalid("duration_days", format!("{} is outside 1..=30", self.duration_days)));
+        }
+        if !venue_ids.contains(&self.venue_id) {
+            return Err(invalid(
+                "venue_id",
+                format!("{} is outside {}..={}", self.venue_id, venue_ids.start(), venue_ids.end()),
+            ));
+        }
+        if let Some(year_month) = year_month {
+            if !self.months_covered()?.contains(&year_month) {
+                return Err(invalid(
+                    "start_date",
+                    format!(
+                        "{} to {} does not overlap {:04}-{:02}",
+                        start.format("%Y-%m-%d"),
+                        self.end()?.format("%Y-%m-%d"),
+                        year_month / 100,
+                        year_month % 100
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Make `venue_id` and `venue_name` agree with the national venue table (`venues`)
+    ///
+    /// A recognised name (including aliases such as "琵琶湖") fills in an unknown `venue_id` and is
+    /// rewritten to its canonical spelling; a valid `venue_id` fills in or replaces an unrecognised name.
+    ///
+    /// # Errors
+    /// `StoreError::InvalidVenue` if neither side identifies a venue (`field: "venue_id"`), or if both
+    /// do but name different venues (`field: "venue_name"`), since it is unclear which side is wrong.
+    ///
+    /// # Example
+    /// ```rust
+    /// use norimaki_db::RaceEvent;
+    ///
+    /// let mut event = RaceEvent::builder()
+    ///     .venue_id(11)
+    ///     .venue_name("琵琶湖")
+    ///     .event_name("びわこ大賞")
+    ///     .start_date("2025-09-10")
+    ///     .build()?;
+    /// event.normalize_venue()?;
+    /// assert_eq!(event.venue_name, "びわこ");
+    /// # Ok::<(), norimaki_db::StoreError>(())
+    /// ```
+    pub fn normalize_venue(&mut self) -> Result<()> {
+        match (venues::venue_id(&self.venue_name), venues::venue_name(self.venue_id)) {
+            (Some(id), Some(_)) if id != self.venue_id => Err(StoreError::InvalidVenue {
+                field: "venue_name".to_string(),
+                value: self.venue_name.clone(),
+            }),
+            (Some(id), _) => {
+                self.venue_id = id;
+                self.venue_name = venues::venue_name(id).unwrap_or_default().to_string();
+                Ok(())
+            }
+            (None, Some(name)) => {
+                self.venue_name = name.to_string();
+                Ok(())
+            }
+            (None, None) => Err(StoreError::InvalidVenue {
+                field: "venue_id".to_string(),
+                value: self.venue_id.to_string(),
+            }),
+        }
+    }
+
+    /// One-line label: venue, grade and event name (e.g., "平和島 G1 トーキョー・ベイ・カップ")
+    pub fn short_label(&self) -> String {
+        format!("{} {} {}", self.venue_name, self.grade, self.event_name)
+    }
+
+    /// Racing days as "2025-09-10〜09-16"
+    ///
+    /// The end keeps its year when it differs from the start ("2025-12-28〜2026-01-04"),
+    /// a one-day event shows a single date, and unusable dates fall back to the raw `start_date`
+    /// and day count.
+    fn period(&self) -> String {
+        match (self.start(), self.end()) {
+            (Ok(start), Ok(end)) if start == end => start.format("%Y-%m-%d").to_string(),
+            (Ok(start), Ok(end)) if start.year() == end.year() => {
+                format!("{}〜{}", start.format("%Y-%m-%d"), end.format("%m-%d"))
+            }
+            (Ok(start), Ok(end)) => format!("{}〜{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d")),
+            _ => format!("{} ({} days)", self.start_date, self.duration_days),
+        }
+    }
+
+    /// Returns true if the event is running on the given "YYYY-MM-DD" date
+    ///
+    /// The event runs on `[start_date, start_date + duration_days)`.
+    /// Unparsable dates are treated as not ongoing.
+    ///
+    /// # Example
+    /// ```rust
+    /// use norimaki_db::{Grade, RaceEvent};
+    ///
+    /// let event = RaceEvent::builder()
+    ///     .venue_id(4)
+    ///     .venue_name("平和島")
+    ///     .event_name("トーキョー・ベイ・カップ")
+    ///     .grade(Grade::G1)
+    ///     .start_date("2025-09-10")
+    ///     .duration_days(7)
+    ///     .build()?;
+    /// assert!(event.is_event_ongoing("2025-09-16"));
+    /// assert!(!event.is_event_ongoing("2025-09-17"));
+    /// # Ok::<(), norimaki_db::StoreError>(())
+    /// ```
+    pub fn is_event_ongoing(&self, date: &str) -> bool {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| self.contains_date(date).ok())
+            .unwrap_or(false)
+    }
+
+    /// First day of the event, parsed from `start_date`
+    ///
+    /// Fails with `StoreError::InvalidDate` if `start_date` is not a "YYYY-MM-DD" date.
+    pub fn start(&self) -> Result<NaiveDate> {
+        NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d")
+            .map_err(|e| StoreError::from(e).with_input(&self.start_date))
+    }
+
+    /// Last day of the event (inclusive): `start + duration_days - 1`
+    ///
+    /// An event must last at least one day, so `duration_days == 0` fails with
+    /// `StoreError::ValidationError { field: "duration_days", .. }`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use norimaki_db::{Grade, RaceEvent};
+    ///
+    /// let event = RaceEvent::builder()
+    ///     .venue_id(4)
+    ///     .venue_name("平和島")
+    ///     .event_name("トーキョー・ベイ・カップ")
+    ///     .grade(Grade::G1)
+    ///     .start_date("2025-09-28")
+    ///     .duration_days(7)
+    ///     .build()?;
+    /// assert_eq!(event.end()?.to_string(), "2025-10-04");
+    /// assert_eq!(event.months_covered()?, vec![202509, 202510]);
+    /// # Ok::<(), norimaki_db::StoreError>(())
+    /// ```
+    pub fn end(&self) -> Result<NaiveDate> {
+        let start = self.start()?;
+        if self.duration_days == 0 {
+            return Err(StoreError::ValidationError {
+                field: "duration_days".to_string(),
+                message: format!("event {:?} lasts 0 days", self.event_name),
+            });
+        }
+        start
+            .checked_add_days(chrono::Days::new(self.duration_days as u64 - 1))
+            .ok_or_else(|| StoreError::invalid_date(&self.start_date, "end date is out of range"))
+    }
+
+    /// Returns true if `date` falls within `[start(), end()]`
+    pub fn contains_date(&self, date: NaiveDate) -> Result<bool> {
+        Ok(self.start()? <= date && date <= self.end()?)
+    }
+
+    /// Status derived from the date window: `Scheduled` before the first day, `Ongoing` from the
+    /// first through the last day (inclusive) and `Finished` afterwards
+    ///
+    /// Explicit overrides are not considered; use `BoatRaceEngine::get_tournament_status` for those.
+    pub fn status_on(&self, today: NaiveDate) -> Result<TournamentStatus> {
+        if today < self.start()? {
+            Ok(TournamentStatus::Scheduled)
+        } else if today <= self.end()? {
+            Ok(TournamentStatus::Ongoing)
+        } else {
+            Ok(TournamentStatus::Finished)
+        }
+    }
+
+    /// Returns true if the two events share at least one day
+    pub fn overlaps(&self, other: &RaceEvent) -> Result<bool> {
+        Ok(self.start()? <= other.end()? && other.start()? <= self.end()?)
+    }
+
+    /// Months the event runs in, as YYYYMM values in ascending order
+    pub fn months_covered(&self) -> Result<Vec<u32>> {
+        let month_of = |date: NaiveDate| date.year() as u32 * 100 + date.month();
+        let last = month_of(self.end()?);
+        let mut month = month_of(self.start()?);
+        let mut months = vec![month];
+        while month < last {
+            month = key::next_year_month(month);
+            months.push(month);
+        }
+        Ok(months)
+    }
+}
+
+/// Builder for `RaceEvent`, created by `RaceEvent::builder`
+#[derive(Debug, Clone)]
+pub struct RaceEventBuilder {
+    event: RaceEvent,
+}
+
+impl Default for RaceEventBuilder {
+    fn default() -> Self {
+        Self {
+            event: RaceEvent {
+                venue_id: 0,
+                venue_name: String::new(),
+                event_name: String::new(),
+                grade: Grade::default(),
+                start_date: String::new(),
+                duration_days: 1,
+                notes: None,
+            },
+        }
+    }
+}
+
+impl RaceEventBuilder {
+    /// Venue identifier (1..=24)
+    pub fn venue_id(mut self, venue_id: u32) -> Self {
+        self.event.venue_id = venue_id;
+        self
+    }
+
+    /// Name of the racing venue
+    pub fn venue_name(mut self, venue_name: impl Into<String>) -> Self {
+        self.event.venue_name = venue_name.into();
+        self
+    }
+
+    /// Name of the event/tournament
+    pub fn event_name(mut self, event_name: impl Into<String>) -> Self {
+        self.event.event_name = event_name.into();
+        self
+    }
+
+    /// Grade of the event (default `Grade::Ippan`)
+    pub fn grade(mut self, grade: Grade) -> Self {
+        self.event.grade = grade;
+        self
+    }
+
+    /// Start date in "YYYY-MM-DD" format
+    pub fn start_date(mut self, start_date: impl Into<String>) -> Self {
+        self.event.start_date = start_date.into();
+        self
+    }
+
+    /// Start date as a `NaiveDate`
+    pub fn start(self, start: NaiveDate) -> Self {
+        self.start_date(start.format("%Y-%m-%d").to_string())
+    }
+
+    /// Duration of the event in days (default 1)
+    pub fn duration_days(mut self, duration_days: u32) -> Self {
+        self.event.duration_days = duration_days;
+        self
+    }
+
+    /// Free-form notes (default none)
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.event.notes = Some(notes.into());
+        self
+    }
+
+    /// Validate and build the event
+    ///
+    /// Runs the same checks as `BoatRaceEngine` with validation enabled and the default venue range
+    /// (1..=24). Failures are `StoreError::ValidationError` naming the offending field.
+    pub fn build(self) -> Result<RaceEvent> {
+        self.event.validate(&DEFAULT_VENUE_IDS, None)?;
+        Ok(self.event)
+    }
+}
+
+/// Venue ids accepted by default (the 24 official venues)
+pub(crate) const DEFAULT_VENUE_IDS: RangeInclusive<u32> = 1..=24;
+
+/// Partial update for a `RaceEvent`
+///
+/// Fields left as `None` keep their current value.
+///
+/// # Example
+/// ```rust
+/// use norimaki_db::{Grade, RaceEventUpdate};
+///
+/// let update = RaceEventUpdate {
+///     grade: Some(Grade::SG),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RaceEventUpdate {
+    /// New venue identifier
+    pub venue_id: Option<u32>,
+    /// New venue name (changes the tournament id)
+    pub venue_name: Option<String>,
+    /// New event name (changes the tournament id)
+    pub event_name: Option<String>,
+    /// New grade
+    pub grade: Option<Grade>,
+    /// New start date in "YYYY-MM-DD" format
+    pub start_date: Option<String>,
+    /// New duration in days
+    pub duration_days: Option<u32>,
+    /// New notes (`Some(None)` clears them)
+    pub notes: Option<Option<String>>,
+}
+
+impl RaceEventUpdate {
+    /// Apply this update to an event, returning the updated copy
+    pub fn apply(&self, event: &RaceEvent) -> RaceEvent {
+        RaceEvent {
+            venue_id: self.venue_id.unwrap_or(event.venue_id),
+            venue_name: self.venue_name.clone().unwrap_or_else(|| event.venue_name.clone()),
+            event_name: self.event_name.clone().unwrap_or_else(|| event.event_name.clone()),
+            grade: self.grade.unwrap_or(event.grade),
+            start_date: self.start_date.clone().unwrap_or_else(|| event.start_date.clone()),
+            duration_days: self.duration_days.unwrap_or(event.duration_days),
+            notes: self.notes.clone().unwrap_or_else(|| event.notes.clone()),
+        }
+    }
+}
+
+/// Master data for a racing venue
+///
+/// # Example
+/// ```rust
+/// use norimaki_db::VenueInfo;
+///
+/// let venue = VenueInfo {
+///     venue_id: 4,
+///     name: "平和島".to_string(),
+///     prefecture: "東京都".to_string(),
+///     water: "海水".to_string(),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueInfo {
+    /// Unique venue identifier (matches `RaceEvent::venue_id`)
+    pub venue_id: u32,
+    /// Canonical name of the venue
+    pub name: String,
+    /// Prefecture the venue is located in
+    pub prefecture: String,
+    /// Water type of the course (e.g., "淡水", "海水", "汽水")
+    pub water: String,
+}
+
+impl VenueInfo {
+    /// The 24 official boat racing venues, ordered by venue_id
+    ///
+    /// Useful for seeding a fresh store with `BoatRaceEngine::put_venue`.
+    pub fn standard_venues() -> Vec<VenueInfo> {
+        venues::STANDARD_VENUES
+            .iter()
+            .map(|&(venue_id, name, prefecture, water)| VenueInfo {
+                venue_id,
+                name: name.to_string(),
+                prefecture: prefecture.to_string(),
+                water: water.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Racer master data
+///
+/// # Example
+/// ```rust
+/// use norimaki_db::Racer;
+///
+/// let racer = Racer {
+///     racer_id: 4444,
+///     name: "山田太郎".to_string(),
+///     branch: "東京".to_string(),
+///     class: "A1".to_string(),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Racer {
+    /// Registration number (登録番号), must be non-zero
+    pub racer_id: u32,
+    /// Full name of the racer
+    pub name: String,
+    /// Branch the racer belongs to (e.g., "東京", "福岡")
+    pub branch: String,
+    /// Racer class (e.g., "A1", "A2", "B1", "B2")
+    pub class: String,
+}
+
+/// Outcome of a single race
+///
+/// # Example
+/// ```rust
+/// use norimaki_db::RaceResult;
+///
+/// let result = RaceResult {
+///     tournament_id: "tokyo_bay_cup".to_string(),
+///     timestamp: 1694524800000,
+///     race_number: 12,
+///     finishing_order: vec![(1, 4444), (3, 3333), (2, 5555)],
+///     winning_technique: "逃げ".to_string(),
+///     decided: true,
+/// };
+/// assert!(result.is_decided());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RaceResult {
+    /// Tournament this race belongs to
+    pub tournament_id: String,
+    /// Race start time in epoch milliseconds
+    pub timestamp: u64,
+    /// Race number within the day (1-12)
+    pub race_number: u32,
+    /// Finishing order as (lane, racer_id), winner first
+    pub finishing_order: Vec<(u8, u32)>,
+    /// Winning technique (e.g., "逃げ", "差し", "まくり")
+    pub winning_technique: String,
+    /// Whether the result has been officially finalized
+    pub decided: bool,
+}
+
+impl RaceResult {
+    /// Returns true if the result has been officially finalized
+    pub fn is_decided(&self) -> bool {
+        self.decided
+    }
+}
+
+/// A single winning combination and its payout
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutEntry {
+    /// Winning combination of lanes (e.g., "1-3-2")
+    pub combination: String,
+    /// Payout in yen per 100-yen ticket
+    pub amount: u64,
+}
+
+/// Payouts of a race for each standard bet type
+///
+/// Each bet type is a list because place/wide pay out on multiple
+/// combinations and dead heats can produce more than one winner.
+///
+/// # Example
+/// ```rust
+/// use norimaki_db::{Payout, PayoutEntry};
+///
+/// let payout = Payout {
+///     trifecta: vec![PayoutEntry { combination: "1-3-2".to_string(), amount: 2450 }],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payout {
+    /// 単勝
+    pub win: Vec<PayoutEntry>,
+    /// 複勝
+    pub place: Vec<PayoutEntry>,
+    /// 2連単
+    pub exacta: Vec<PayoutEntry>,
+    /// 2連複
+    pub quinella: Vec<PayoutEntry>,
+    /// 3連単
+    pub trifecta: Vec<PayoutEntry>,
+    /// 3連複
+    pub trio: Vec<PayoutEntry>,
+    /// 拡連複
+    pub wide: Vec<PayoutEntry>,
+}
+
+/// Weather and water conditions at a venue on a race day
+///
+/// # Example
+/// ```rust
+/// use norimaki_db::RaceDayConditions;
+///
+/// let conditions = RaceDayConditions {
+///     date: "2025-09-13".to_string(),
+///     venue_id: 4,
+///     weather: "晴".to_string(),
+///     wind_speed_mps: 3.0,
+///     wind_direction: "北西".to_string(),
+///     wave_height_cm: 5,
+///     temperature: 27.5,
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RaceDayConditions {
+    /// Race day in "YYYY-MM-DD" format
+    pub date: String,
+    /// Venue identifier
+    pub venue_id: u32,
+    /// Weather (e.g., "晴", "曇り", "雨")
+    pub weather: String,
+    /// Wind speed in meters per second
+    pub wind_speed_mps: f64,
+    /// Wind direction (e.g., "北西")
+    pub wind_direction: String,
+    /// Wave height in centimeters
+    pub wave_height_cm: u32,
+    /// Air temperature in degrees Celsius
+    pub temperature: f64,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "fs")]
     use std::fs;
 
+    #[test]
+    fn test_grade_parsing() {
+        let spellings = [
+            (Grade::SG, &["SG", "sg", "ＳＧ", "ｓｇ", " SG "][..]),
+            (Grade::G1, &["G1", "Ｇ１", "g1", "GⅠ", "ＧⅠ", "GI", "G1 ", "G 1"][..]),
+            (Grade::G2, &["G2", "Ｇ２", "g2", "GⅡ", "GII"][..]),
+            (Grade::G3, &["G3", "Ｇ３", "g3", "GⅢ", "GIII"][..]),
+            (Grade::Ippan, &["一般", " 一般\u{3000}", "ippan"][..]),
+        ];
+        for (grade, inputs) in spellings {
+            for input in inputs {
+                assert_eq!(input.parse::<Grade>().unwrap(), grade, "{:?}", input);
+            }
+        }
+        for input in ["", "G4", "PG1", "Ｇ", "女子戦"] {
+            let error = input.parse::<Grade>().unwrap_err();
+            assert!(matches!(error, StoreError::ValidationError { ref field, .. } if field == "grade"), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_grade_display_order_and_serde() {
+        for grade in Grade::ALL {
+            assert_eq!(grade.to_string().parse::<Grade>().unwrap(), grade);
+        }
+        assert_eq!(Grade::Ippan.to_string(), "一般");
+        let mut grades = vec![Grade::G2, Grade::SG, Grade::Ippan, Grade::G1, Grade::G3];
+        grades.sort();
+        assert_eq!(grades, Grade::ALL);
+        assert_eq!(grades.iter().max(), Some(&Grade::SG));
+
+        assert_eq!(serde_json::to_string(&Grade::G1).unwrap(), "\"G1\"");
+        assert_eq!(serde_json::from_str::<Grade>("\"ＧⅠ\"").unwrap(), Grade::G1);
+        assert!(serde_json::from_str::<Grade>("\"G9\"").is_err());
+        // 文字列として保存していた頃の値とバイナリ表現が変わらない
+        assert_eq!(crate::value::serialize(&Grade::SG).unwrap(), crate::value::serialize(&"SG").unwrap());
+    }
+
+    fn event_from(start_date: &str, duration_days: u32) -> RaceEvent {
+        RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: format!("{} から {} 日間", start_date, duration_days),
+            grade: Grade::G1,
+            start_date: start_date.to_string(),
+            duration_days,
+            notes: None,
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_race_event_start_and_end() {
+        let cases = [
+            ("2025-09-10", 1, "2025-09-10"),
+            ("2025-09-10", 7, "2025-09-16"),
+            ("2025-09-28", 3, "2025-09-30"),
+            ("2025-09-28", 4, "2025-10-01"),
+            ("2025-12-30", 5, "2026-01-03"),
+            ("2024-02-27", 4, "2024-03-01"),
+            ("2025-02-27", 3, "2025-03-01"),
+        ];
+        for (start, days, end) in cases {
+            let event = event_from(start, days);
+            assert_eq!(event.start().unwrap(), date(start));
+            assert_eq!(event.end().unwrap(), date(end), "{} + {} days", start, days);
+        }
+
+        let zero = event_from("2025-09-10", 0);
+        assert!(matches!(zero.end(), Err(StoreError::ValidationError { ref field, .. }) if field == "duration_days"));
+        assert!(zero.contains_date(date("2025-09-10")).is_err());
+        assert!(zero.months_covered().is_err());
+
+        let invalid = event_from("2025-09-31", 3);
+        assert!(matches!(invalid.start(), Err(StoreError::InvalidDate { ref input, .. }) if input == "2025-09-31"));
+        assert!(invalid.end().is_err());
+        assert!(event_from("+262142-12-31", 2).end().is_err());
+    }
+
+    #[test]
+    fn test_race_event_contains_date() {
+        let event = event_from("2025-12-30", 5);
+        assert!(!event.contains_date(date("2025-12-29")).unwrap());
+        assert!(event.contains_date(date("2025-12-30")).unwrap());
+        assert!(event.contains_date(date("2026-01-01")).unwrap());
+        assert!(event.contains_date(date("2026-01-03")).unwrap());
+        assert!(!event.contains_date(date("2026-01-04")).unwrap());
+        assert!(event.is_event_ongoing("2026-01-03"));
+        assert!(!event.is_event_ongoing("2026-01-04"));
+        assert!(!event_from("2025-09-10", 0).is_event_ongoing("2025-09-10"));
+    }
+
+    #[test]
+    fn test_race_event_status_on() {
+        let event = event_from("2025-12-30", 5); // 2026-01-03まで
+        assert_eq!(event.status_on(date("2025-12-29")).unwrap(), TournamentStatus::Scheduled);
+        assert_eq!(event.status_on(date("2025-12-30")).unwrap(), TournamentStatus::Ongoing);
+        assert_eq!(event.status_on(date("2026-01-03")).unwrap(), TournamentStatus::Ongoing);
+        assert_eq!(event.status_on(date("2026-01-04")).unwrap(), TournamentStatus::Finished);
+
+        let single_day = event_from("2025-09-10", 1);
+        assert_eq!(single_day.status_on(date("2025-09-10")).unwrap(), TournamentStatus::Ongoing);
+        assert_eq!(single_day.status_on(date("2025-09-11")).unwrap(), TournamentStatus::Finished);
+        assert!(event_from("2025-09-10", 0).status_on(date("2025-09-10")).is_err());
+    }
+
+    #[test]
+    fn test_tournament_status_parsing() {
+        for status in [TournamentStatus::Scheduled, TournamentStatus::Ongoing, TournamentStatus::Finished, TournamentStatus::Cancelled] {
+            assert_eq!(status.to_string().parse::<TournamentStatus>().unwrap(), status);
+            assert_eq!(serde_json::to_string(&status).unwrap(), format!("\"{}\"", status));
+        }
+        assert_eq!(" Cancelled ".parse::<TournamentStatus>().unwrap(), TournamentStatus::Cancelled);
+        assert!("postponed".parse::<TournamentStatus>().is_err());
+        assert!(TournamentStatus::Cancelled.is_final() && !TournamentStatus::Ongoing.is_final());
+    }
+
+    #[test]
+    fn test_race_event_overlaps() {
+        let year_end = event_from("2025-12-28", 6); // 2026-01-02まで
+        assert!(year_end.overlaps(&event_from("2026-01-02", 3)).unwrap());
+        assert!(!year_end.overlaps(&event_from("2026-01-03", 3)).unwrap());
+        assert!(year_end.overlaps(&event_from("2025-12-20", 9)).unwrap());
+        assert!(!year_end.overlaps(&event_from("2025-12-20", 8)).unwrap());
+        assert!(year_end.overlaps(&event_from("2025-12-30", 1)).unwrap());
+        assert!(event_from("2025-12-30", 1).overlaps(&year_end).unwrap());
+        assert!(year_end.overlaps(&event_from("2025-12-30", 0)).is_err());
+    }
+
+    #[test]
+    fn test_race_event_months_covered() {
+        assert_eq!(event_from("2025-09-10", 7).months_covered().unwrap(), vec![202509]);
+        assert_eq!(event_from("2025-09-30", 1).months_covered().unwrap(), vec![202509]);
+        assert_eq!(event_from("2025-09-30", 2).months_covered().unwrap(), vec![202509, 202510]);
+        assert_eq!(event_from("2025-12-31", 2).months_covered().unwrap(), vec![202512, 202601]);
+        assert_eq!(event_from("2025-11-30", 30).months_covered().unwrap(), vec![202511, 202512]);
+        assert_eq!(event_from("2025-11-30", 33).months_covered().unwrap(), vec![202511, 202512, 202601]);
+        assert_eq!(event_from("2024-02-29", 1).months_covered().unwrap(), vec![202402]);
+    }
+
+    fn valid_builder() -> RaceEventBuilder {
+        RaceEvent::builder()
+            .venue_id(4)
+            .venue_name("平和島")
+            .event_name("トーキョー・ベイ・カップ")
+            .grade(Grade::G1)
+            .start_date("2025-09-10")
+            .duration_days(7)
+    }
+
+    fn invalid_field<T: std::fmt::Debug>(result: Result<T>) -> String {
+        match result {
+            Err(StoreError::ValidationError { field, .. }) => field,
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_race_event_builder() {
+        let event = valid_builder().build().unwrap();
+        let mut expected = sample_data().events[1].clone();
+        expected.event_name = "トーキョー・ベイ・カップ".to_string();
+        assert_eq!(event, expected);
+
+        let defaults = RaceEvent::builder()
+            .venue_id(1)
+            .venue_name("桐生")
+            .event_name("一般戦")
+            .start(date("2025-09-11"))
+            .build()
+            .unwrap();
+        assert_eq!(defaults.grade, Grade::Ippan);
+        assert_eq!(defaults.duration_days, 1);
+        assert_eq!(defaults.start_date, "2025-09-11");
+    }
+
+    #[test]
+    fn test_race_event_builder_validation() {
+        assert_eq!(invalid_field(valid_builder().event_name("").build()), "event_name");
+        assert_eq!(invalid_field(valid_builder().event_name("  ").build()), "event_name");
+        assert_eq!(invalid_field(valid_builder().venue_name("").build()), "venue_name");
+        assert_eq!(invalid_field(valid_builder().start_date("2025-09-31").build()), "start_date");
+        assert_eq!(invalid_field(valid_builder().start_date("2025/09/10").build()), "start_date");
+        assert_eq!(invalid_field(valid_builder().duration_days(0).build()), "duration_days");
+        assert_eq!(invalid_field(valid_builder().duration_days(31).build()), "duration_days");
+        assert_eq!(invalid_field(valid_builder().venue_id(0).build()), "venue_id");
+        assert_eq!(invalid_field(valid_builder().venue_id(25).build()), "venue_id");
+        // 必須項目を設定しない場合
+        assert_eq!(invalid_field(RaceEvent::builder().event_name("a").venue_name("b").build()), "start_date");
+        assert_eq!(invalid_field(RaceEvent::builder().build()), "event_name");
+        assert_eq!(
+            invalid_field(RaceEvent::builder().event_name("a").venue_name("b").start_date("2025-09-10").build()),
+            "venue_id"
+        );
+    }
+
+    #[test]
+    fn test_monthly_schedule_builder() {
+        let schedule = MonthlySchedule::builder(202509).events(sample_data().events).build().unwrap();
+        assert_eq!(schedule, sample_data());
+        assert_eq!(MonthlySchedule::builder(202512).build().unwrap().year_month, "2025-12");
+
+        // 前月から続く大会は含められるが、重ならない大会は含められない
+        let year_end = valid_builder().start_date("2025-12-28").build().unwrap();
+        assert!(MonthlySchedule::builder(202601).event(year_end.clone()).build().is_ok());
+        assert_eq!(invalid_field(MonthlySchedule::builder(202602).event(year_end.clone()).build()), "start_date");
+        assert_eq!(invalid_field(MonthlySchedule::builder(202511).event(year_end).build()), "start_date");
+
+        assert_eq!(invalid_field(MonthlySchedule::builder(202513).build()), "year_month");
+        assert_eq!(invalid_field(MonthlySchedule::builder(202500).build()), "year_month");
+        let mut unchecked = sample_data().events[0].clone();
+        unchecked.venue_id = 99;
+        assert_eq!(invalid_field(MonthlySchedule::builder(202509).event(unchecked).build()), "venue_id");
+    }
+
+    #[test]
+    fn test_monthly_schedule_helpers() {
+        let mut schedule = sample_data();
+        schedule.sort_by_start_date();
+        let starts: Vec<&str> = schedule.events.iter().map(|event| event.start_date.as_str()).collect();
+        assert_eq!(starts, ["2025-09-10", "2025-09-11", "2025-09-13"]);
+
+        let g1 = schedule.filter_grade(Grade::G1);
+        assert_eq!(g1.year_month, "2025-09");
+        assert_eq!(g1.events.len(), 2);
+        assert!(schedule.filter_grade(Grade::SG).events.is_empty());
+
+        let heiwajima = schedule.events_at_venue(4);
+        assert_eq!(heiwajima.len(), 1);
+        assert_eq!(heiwajima[0].venue_name, "平和島");
+        assert!(schedule.events_at_venue(24).is_empty());
+
+        assert_eq!(schedule.date_span(), Some((date("2025-09-10"), date("2025-09-18"))));
+        schedule.events.push(event_from("2025-09-29", 4));
+        schedule.events.push(event_from("2025-13-01", 3));
+        schedule.events.push(event_from("2025-09-01", 0));
+        assert_eq!(schedule.date_span(), Some((date("2025-09-10"), date("2025-10-02"))));
+        assert_eq!(MonthlySchedule::builder(202509).build().unwrap().date_span(), None);
+    }
+
+    #[test]
+    fn test_monthly_schedule_merge() {
+        let ours = sample_data();
+        let mut theirs = MonthlySchedule { year_month: "2025-09".to_string(), events: vec![sample_data().events[1].clone()] };
+        theirs.events.push(event_from("2025-09-20", 3));
+
+        // 同一のイベントは1件にまとめ、新しいイベントは後ろに追加する
+        let report = ours.merge_report(&theirs).unwrap();
+        assert_eq!(report.duplicates, 1);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(report.schedule.events.len(), 4);
+        assert_eq!(report.schedule.events[..3], ours.events[..]);
+        assert_eq!(report.schedule.events[3], theirs.events[1]);
+        assert_eq!(ours.merge(&theirs).unwrap(), report.schedule);
+        assert_eq!(ours.merge(&ours).unwrap(), ours);
+
+        // 同じ大会で内容が異なる場合は両方を残して報告する
+        let mut conflicting = sample_data();
+        conflicting.events.truncate(2);
+        conflicting.events[1].duration_days = 6;
+        conflicting.events[1].grade = Grade::G2;
+        let report = ours.merge_report(&conflicting).unwrap();
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.schedule.events.len(), 4);
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.tournament_id, generate_tournament_id("平和島", "開設７１周年記念トーキョー・ベイ・カップ"));
+        assert_eq!(conflict.kept, ours.events[1]);
+        assert_eq!(conflict.incoming, conflicting.events[1]);
+        assert!(report.schedule.events.contains(&conflict.kept) && report.schedule.events.contains(&conflict.incoming));
+
+        // 3つ目の版は既存の2件それぞれと衝突する
+        let mut third = conflicting.clone();
+        third.events = vec![RaceEvent { duration_days: 5, ..ours.events[1].clone() }];
+        let report = report.schedule.merge_report(&third).unwrap();
+        assert_eq!(report.conflicts.len(), 3);
+        assert_eq!(report.schedule.events.len(), 5);
+
+        let other_month = MonthlySchedule { year_month: "2025-10".to_string(), events: Vec::new() };
+        assert_eq!(invalid_field(ours.merge(&other_month)), "year_month");
+    }
+
+    #[test]
+    fn test_race_event_display() {
+        let events = sample_data().events;
+        assert_eq!(events[1].to_string(), "『G1』開設７１周年記念トーキョー・ベイ・カップ @ 平和島 2025-09-10〜09-16");
+        assert_eq!(events[0].to_string(), "『一般』バスケで群馬を熱くする群馬クレインサンダーズカップ @ 桐生 2025-09-11〜09-16");
+        assert_eq!(events[2].short_label(), "住之江 G1 第５３回高松宮記念特別競走");
+
+        let year_end = RaceEvent { event_name: "年末年始特別競走".to_string(), ..event_from("2025-12-28", 8) };
+        assert_eq!(year_end.to_string(), "『G1』年末年始特別競走 @ 平和島 2025-12-28〜2026-01-04");
+        assert_eq!(event_from("2025-09-10", 1).to_string(), "『G1』2025-09-10 から 1 日間 @ 平和島 2025-09-10");
+        assert_eq!(event_from("2025-09-31", 3).to_string(), "『G1』2025-09-31 から 3 日間 @ 平和島 2025-09-31 (3 days)");
+    }
+
+    #[test]
+    fn test_monthly_schedule_summary() {
+        assert_eq!(
+            sample_data().summary(),
+            concat!(
+                "2025-09: 3 events\n",
+                "2025-09-10〜09-16  平和島  G1    開設７１周年記念トーキョー・ベイ・カップ\n",
+                "2025-09-11〜09-16  桐生    一般  バスケで群馬を熱くする群馬クレインサンダーズカップ\n",
+                "2025-09-13〜09-18  住之江  G1    第５３回高松宮記念特別競走\n",
+            )
+        );
+
+        let mut schedule = sample_data();
+        schedule.events.truncate(1);
+        schedule.events.push(RaceEvent { venue_name: "大村".to_string(), ..event_from("2025-09-29", 4) });
+        assert_eq!(
+            schedule.summary(),
+            concat!(
+                "2025-09: 2 events\n",
+                "2025-09-11〜09-16  桐生  一般  バスケで群馬を熱くする群馬クレインサンダーズカップ\n",
+                "2025-09-29〜10-02  大村  G1    2025-09-29 から 4 日間\n",
+            )
+        );
+        assert_eq!(MonthlySchedule::builder(202510).build().unwrap().summary(), "2025-10: no events\n");
+    }
+
+    #[test]
+    fn test_normalize_venue() {
+        let normalized = |venue_id: u32, venue_name: &str| {
+            let mut event = RaceEvent { venue_id, venue_name: venue_name.to_string(), ..event_from("2025-09-10", 3) };
+            event.normalize_venue().map(|_| (event.venue_id, event.venue_name))
+        };
+        assert_eq!(normalized(4, "平和島").unwrap(), (4, "平和島".to_string()));
+        // 別表記は正式名称に、欠けている側は補う
+        assert_eq!(normalized(11, "琵琶湖").unwrap(), (11, "びわこ".to_string()));
+        assert_eq!(normalized(0, "ボートレース住之江").unwrap(), (12, "住之江".to_string()));
+        assert_eq!(normalized(99, "大村").unwrap(), (24, "大村".to_string()));
+        assert_eq!(normalized(1, "").unwrap(), (1, "桐生".to_string()));
+        assert_eq!(normalized(1, "キリュウ").unwrap(), (1, "桐生".to_string()));
+
+        // どちらが誤りか判断できない場合と、どちらも会場を特定できない場合はエラー
+        assert!(matches!(normalized(5, "平和島"), Err(StoreError::InvalidVenue { ref field, .. }) if field == "venue_name"));
+        assert!(matches!(normalized(0, "東京"), Err(StoreError::InvalidVenue { ref field, ref value }) if field == "venue_id" && value == "0"));
+    }
+
     #[test]
     fn test_memory_store_basic_operations() {
         let mut store = MemoryStore::new();
@@ -141,6 +1628,7 @@ mod tests {
         assert_eq!(store.keys().unwrap().len(), 0);
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn test_file_store_basic_operations() {
         let test_file = "test_db.json";
@@ -163,6 +1651,7 @@ mod tests {
         fs::remove_file(test_file).ok();
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn test_file_store_persistence() {
         let test_file = "test_persistence.json";
@@ -218,6 +1707,7 @@ mod tests {
         assert!(!values.iter().any(|v| v.contains("秋季大会")));
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn test_file_store_scan_with_sample_data() {
         let test_file = "test_scan_sample.json";
@@ -247,6 +1737,81 @@ mod tests {
         fs::remove_file(test_file).ok();
     }
 
+    #[test]
+    fn test_delete_many() {
+        let mut store = MemoryStore::new();
+        store.put("key1".to_string(), "value1".to_string()).unwrap();
+        store.put("key2".to_string(), "value2".to_string()).unwrap();
+        store.put("key3".to_string(), "value3".to_string()).unwrap();
+
+        let keys = vec!["key1".to_string(), "key2".to_string(), "missing".to_string()];
+        assert_eq!(store.delete_many(&keys).unwrap(), 2);
+        assert_eq!(store.keys().unwrap(), vec!["key3".to_string()]);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_delete_many() {
+        let test_file = "test_delete_many.json";
+
+        {
+            let mut store = FileStore::new(test_file).unwrap();
+            store.put("key1".to_string(), "value1".to_string()).unwrap();
+            store.put("key2".to_string(), "value2".to_string()).unwrap();
+            store.put("key3".to_string(), "value3".to_string()).unwrap();
+
+            let keys = vec!["key1".to_string(), "key3".to_string()];
+            assert_eq!(store.delete_many(&keys).unwrap(), 2);
+            assert!(store.delete_many(&["".to_string()]).is_err());
+        }
+
+        {
+            let store = FileStore::new(test_file).unwrap();
+            assert_eq!(store.keys().unwrap(), vec!["key2".to_string()]);
+        }
+
+        fs::remove_file(test_file).ok();
+    }
+
+    #[test]
+    fn test_apply_batch() {
+        let mut store = MemoryStore::new();
+        store.put("old".to_string(), "value".to_string()).unwrap();
+
+        store.apply_batch(vec![("new".to_string(), "value".to_string())], &["old".to_string()]).unwrap();
+        assert_eq!(store.keys().unwrap(), vec!["new".to_string()]);
+
+        // 不正なキーを含むバッチは何も変更しない
+        assert!(store.apply_batch(vec![("a".to_string(), "1".to_string())], &["".to_string()]).is_err());
+        assert_eq!(store.keys().unwrap(), vec!["new".to_string()]);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_file_store_apply_batch() {
+        let test_file = "test_apply_batch.json";
+
+        {
+            let mut store = FileStore::new(test_file).unwrap();
+            store.put("old".to_string(), "value".to_string()).unwrap();
+            store
+                .apply_batch(
+                    vec![("new1".to_string(), "a".to_string()), ("new2".to_string(), "b".to_string())],
+                    &["old".to_string()],
+                )
+                .unwrap();
+        }
+
+        {
+            let store = FileStore::new(test_file).unwrap();
+            let mut keys = store.keys().unwrap();
+            keys.sort();
+            assert_eq!(keys, vec!["new1".to_string(), "new2".to_string()]);
+        }
+
+        fs::remove_file(test_file).ok();
+    }
+
     #[test]
     fn test_scan_invalid_keys() {
         let mut store = MemoryStore::new();
@@ -257,3 +1822,20 @@ mod tests {
         assert!(store.scan("", "").is_err());
     }
 }
+
+/// wasm32 上でのスモークテスト (`wasm-pack test --node -- --no-default-features --features memory` で実行する)
+#[cfg(all(test, target_arch = "wasm32", feature = "memory"))]
+mod wasm_smoke_tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    include!("../testdata/sample.rs");
+
+    #[wasm_bindgen_test]
+    fn test_memory_engine_on_wasm() {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        let mut expected = sample_data();
+        expected.events.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap(), expected);
+    }
+}
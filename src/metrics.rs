@@ -0,0 +1,174 @@
+//! メトリクスモジュール
+//!
+//! エンジンのストア操作の回数・書き込みバイト数などのカウンタと、
+//! Prometheus のテキスト形式 (exposition format) への出力を提供する
+
+use crate::engine::StatisticsCounters;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// エンジンが操作ごとに加算するカウンタ
+///
+/// 読み取り系のメソッド (`&self`) からも加算するためアトミックにしている。
+/// 値の間の一貫性は必要ないため Relaxed で読み書きする
+#[derive(Debug, Default)]
+pub(crate) struct OperationCounters {
+    puts: AtomicU64,
+    gets: AtomicU64,
+    scans: AtomicU64,
+    deletes: AtomicU64,
+    bytes_written: AtomicU64,
+    serialization_failures: AtomicU64,
+    malformed_keys: AtomicU64,
+    hook_panics: AtomicU64,
+}
+
+impl OperationCounters {
+    /// ストアへの書き込み
+    ///
+    /// # Arguments
+    /// * `count` - 書き込んだキーの数
+    /// * `bytes` - キーと値の合計バイト数
+    pub(crate) fn record_puts(&self, count: usize, bytes: usize) {
+        self.puts.fetch_add(count as u64, Ordering::Relaxed);
+        self.bytes_written.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_get(&self) {
+        self.gets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_scan(&self) {
+        self.scans.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_deletes(&self, count: usize) {
+        self.deletes.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_serialization_failure(&self) {
+        self.serialization_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_malformed_key(&self) {
+        self.malformed_keys.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_hook_panic(&self) {
+        self.hook_panics.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 現在の値を取得
+    ///
+    /// # Arguments
+    /// * `counters` - エンジンの統計カウンタ (存在しない場合は None)
+    pub(crate) fn snapshot(&self, counters: Option<StatisticsCounters>) -> EngineMetrics {
+        EngineMetrics {
+            puts: self.puts.load(Ordering::Relaxed),
+            gets: self.gets.load(Ordering::Relaxed),
+            scans: self.scans.load(Ordering::Relaxed),
+            deletes: self.deletes.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            serialization_failures: self.serialization_failures.load(Ordering::Relaxed),
+            malformed_keys: self.malformed_keys.load(Ordering::Relaxed),
+            hook_panics: self.hook_panics.load(Ordering::Relaxed),
+            counters,
+        }
+    }
+}
+
+/// エンジンのメトリクス
+///
+/// 操作回数はエンジンの生成時から数え、ストアを差し替えても引き継がない
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EngineMetrics {
+    /// ストアに書き込んだキーの数
+    pub puts: u64,
+    /// ストアからの個別読み込みの回数
+    pub gets: u64,
+    /// 範囲スキャン・キー一覧の取得の回数
+    pub scans: u64,
+    /// ストアから削除したキーの数
+    pub deletes: u64,
+    /// 書き込んだキーと値の合計バイト数
+    pub bytes_written: u64,
+    /// 値のシリアライズ・デシリアライズに失敗した回数
+    pub serialization_failures: u64,
+    /// スキャンで読み飛ばした形式の不正なキーの数
+    pub malformed_keys: u64,
+    /// フック内で捕捉した panic の回数
+    pub hook_panics: u64,
+    /// 名前空間ごとのエントリ数（統計カウンタが存在しない場合は None）
+    pub counters: Option<StatisticsCounters>,
+}
+
+/// メトリクスを Prometheus のテキスト形式で出力
+///
+/// 操作回数は `norimaki_<name>_total` の counter、エントリ数は `namespace` ラベル付きの
+/// `norimaki_entries` gauge として出力する。統計カウンタがない場合はエントリ数を出力しない
+///
+/// # Arguments
+/// * `metrics` - `BoatRaceEngine::metrics` で取得したメトリクス
+///
+/// # Returns
+/// 改行で終わるテキスト
+pub fn render_prometheus(metrics: &EngineMetrics) -> String {
+    let mut output = String::new();
+    let totals = [
+        ("norimaki_puts_total", "Keys written to the store.", metrics.puts),
+        ("norimaki_gets_total", "Single-key reads from the store.", metrics.gets),
+        ("norimaki_scans_total", "Range scans and key listings.", metrics.scans),
+        ("norimaki_deletes_total", "Keys deleted from the store.", metrics.deletes),
+        ("norimaki_bytes_written_total", "Key and value bytes written to the store.", metrics.bytes_written),
+        (
+            "norimaki_serialization_failures_total",
+            "Values that failed to serialize or deserialize.",
+            metrics.serialization_failures,
+        ),
+        ("norimaki_malformed_keys_total", "Malformed keys skipped during scans.", metrics.malformed_keys),
+        ("norimaki_hook_panics_total", "Panics caught in the engine hook.", metrics.hook_panics),
+    ];
+    for (name, help, value) in totals {
+        let _ = writeln!(output, "# HELP {} {}", name, help);
+        let _ = writeln!(output, "# TYPE {} counter", name);
+        let _ = writeln!(output, "{} {}", name, value);
+    }
+
+    if let Some(counters) = metrics.counters {
+        let _ = writeln!(output, "# HELP norimaki_entries Entries stored per namespace.");
+        let _ = writeln!(output, "# TYPE norimaki_entries gauge");
+        for (namespace, value) in [("monthly", counters.monthly_entries), ("race", counters.races)] {
+            let _ = writeln!(output, "norimaki_entries{{namespace=\"{}\"}} {}", namespace, value);
+        }
+        let _ = writeln!(output, "# HELP norimaki_tournaments Tournaments registered in the monthly view.");
+        let _ = writeln!(output, "# TYPE norimaki_tournaments gauge");
+        let _ = writeln!(output, "norimaki_tournaments {}", counters.tournaments);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prometheus() {
+        let metrics = EngineMetrics {
+            puts: 3,
+            bytes_written: 120,
+            hook_panics: 2,
+            counters: Some(StatisticsCounters { monthly_entries: 2, races: 5, tournaments: 1 }),
+            ..EngineMetrics::default()
+        };
+        let text = render_prometheus(&metrics);
+        assert!(text.starts_with("# HELP norimaki_puts_total Keys written to the store.\n# TYPE norimaki_puts_total counter\nnorimaki_puts_total 3\n"));
+        assert!(text.contains("\nnorimaki_bytes_written_total 120\n"));
+        assert!(text.contains("\nnorimaki_hook_panics_total 2\n"));
+        assert!(text.contains("\nnorimaki_entries{namespace=\"monthly\"} 2\nnorimaki_entries{namespace=\"race\"} 5\n"));
+        assert!(text.ends_with("norimaki_tournaments 1\n"));
+
+        let without_counters = render_prometheus(&EngineMetrics::default());
+        assert!(!without_counters.contains("norimaki_entries"));
+        assert!(without_counters.contains("\nnorimaki_gets_total 0\n"));
+    }
+}
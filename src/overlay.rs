@@ -0,0 +1,261 @@
+//! 重ね合わせストアモジュール
+//!
+//! 読み込み元のストアの上にメモリ上の変更を重ねるストアを提供する。
+//! 変更は読み込み元に反映せずに保持し、後から `into_changes` で取り出して `apply_changes` で適用できる
+
+use crate::{KeyValueStore, MemoryStore, Result, StoreError};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 読み込み元のストアに重ねた変更を保持するストア
+///
+/// 書き込みは重ねたストア (`overlay`) に、削除は読み込み元のキーを隠す削除済みの印 (tombstone) として保持する。
+/// 読み込み・スキャンは重ねた変更を読み込み元より優先し、スキャン結果はキー順で重複しない
+#[derive(Debug)]
+pub struct OverlayStore<'a, B: KeyValueStore> {
+    base: &'a B,
+    overlay: MemoryStore,
+    /// 削除したキー (読み込み元の同じキーを隠す)
+    tombstones: BTreeSet<String>,
+}
+
+/// 重ねたストアで行った変更
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// キーに値を書き込む
+    Put { key: String, value: String },
+    /// キーを削除する
+    Delete { key: String },
+}
+
+impl<'a, B: KeyValueStore> OverlayStore<'a, B> {
+    /// # Arguments
+    /// * `base` - 読み込み元のストア (変更しない)
+    /// * `overlay` - 変更を保持するストア (既に入っているエントリは書き込みとして扱う)
+    pub fn new(base: &'a B, overlay: MemoryStore) -> Self {
+        Self {
+            base,
+            overlay,
+            tombstones: BTreeSet::new(),
+        }
+    }
+
+    /// 読み込み元のストアを取得
+    pub fn base(&self) -> &'a B {
+        self.base
+    }
+
+    /// 保持している変更を取り出す
+    ///
+    /// 各キーの最終状態だけを返すため、同じキーへの変更は1つにまとまる。
+    /// 削除 (キー順) の後に書き込み (キー順) を並べる
+    pub fn into_changes(self) -> Vec<Change> {
+        let mut puts: Vec<(String, String)> = self.overlay.into_entries().collect();
+        puts.sort();
+        self.tombstones
+            .into_iter()
+            .map(|key| Change::Delete { key })
+            .chain(puts.into_iter().map(|(key, value)| Change::Put { key, value }))
+            .collect()
+    }
+
+    /// 読み込み元の範囲内のエントリ (削除済みのキーを除く)
+    fn base_range(&self, start: &str, end: &str) -> Result<BTreeMap<String, String>> {
+        let mut entries = BTreeMap::new();
+        for key in self.base.keys()? {
+            if key.as_str() >= start && key.as_str() < end && !self.tombstones.contains(&key) {
+                if let Some(value) = self.base.get(&key)? {
+                    entries.insert(key, value);
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl<B: KeyValueStore> KeyValueStore for OverlayStore<'_, B> {
+    fn put(&mut self, key: String, value: String) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        self.tombstones.remove(&key);
+        self.overlay.put(key, value)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        if self.tombstones.contains(key) {
+            return Ok(None);
+        }
+        match self.overlay.get(key)? {
+            Some(value) => Ok(Some(value)),
+            None => self.base.get(key),
+        }
+    }
+
+    fn contains_key(&self, key: &str) -> Result<bool> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        if self.tombstones.contains(key) {
+            return Ok(false);
+        }
+        Ok(self.overlay.contains_key(key)? || self.base.contains_key(key)?)
+    }
+
+    fn with_value<R>(&self, key: &str, f: impl FnOnce(&str) -> R) -> Result<Option<R>> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        if self.tombstones.contains(key) {
+            return Ok(None);
+        }
+        if self.overlay.contains_key(key)? {
+            return self.overlay.with_value(key, f);
+        }
+        self.base.with_value(key, f)
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        self.overlay.delete(key)?;
+        self.tombstones.insert(key.to_string());
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        let mut keys: BTreeSet<String> = self
+            .base
+            .keys()?
+            .into_iter()
+            .filter(|key| !self.tombstones.contains(key))
+            .collect();
+        keys.extend(self.overlay.keys()?);
+        Ok(keys.into_iter().collect())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.tombstones.extend(self.base.keys()?);
+        self.tombstones.extend(self.overlay.keys()?);
+        self.overlay.clear()
+    }
+
+    fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        let mut entries = self.base_range(start, end)?;
+        // 同じキーは重ねた値で上書きする
+        entries.extend(self.overlay.scan(start, end)?);
+        Ok(entries.into_iter().collect())
+    }
+}
+
+/// `OverlayStore::into_changes` で取り出した変更をストアに適用
+///
+/// 変更は1回の `apply_batch` で適用する (削除 → 書き込みの順)
+///
+/// # Arguments
+/// * `store` - 適用先のストア
+/// * `changes` - 適用する変更
+pub fn apply_changes(store: &mut impl KeyValueStore, changes: Vec<Change>) -> Result<()> {
+    let mut puts = Vec::new();
+    let mut deletes = Vec::new();
+    for change in changes {
+        match change {
+            Change::Put { key, value } => puts.push((key, value)),
+            Change::Delete { key } => deletes.push(key),
+        }
+    }
+    store.apply_batch(puts, &deletes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_store() -> MemoryStore {
+        let mut base = MemoryStore::new();
+        for key in ["a", "b", "c", "e"] {
+            base.put(key.to_string(), format!("base_{}", key)).unwrap();
+        }
+        base
+    }
+
+    fn entry(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn test_overlay_masks_deleted_base_key() {
+        let base = base_store();
+        let mut store = OverlayStore::new(&base, MemoryStore::new());
+        store.delete("b").unwrap();
+
+        assert_eq!(store.get("b").unwrap(), None);
+        assert!(!store.contains_key("b").unwrap());
+        assert_eq!(store.get("a").unwrap(), Some("base_a".to_string()));
+        assert!(store.contains_key("a").unwrap());
+        assert_eq!(store.keys().unwrap(), vec!["a", "c", "e"]);
+        assert_eq!(store.scan("a", "z").unwrap(), vec![entry("a", "base_a"), entry("c", "base_c"), entry("e", "base_e")]);
+
+        // 削除後に書き込めば再び見える
+        store.put("b".to_string(), "new_b".to_string()).unwrap();
+        assert_eq!(store.get("b").unwrap(), Some("new_b".to_string()));
+        assert!(store.contains_key("b").unwrap());
+        store.delete("b").unwrap();
+        assert_eq!(store.get("b").unwrap(), None);
+
+        assert_eq!(base.get("b").unwrap(), Some("base_b".to_string()));
+    }
+
+    #[test]
+    fn test_scan_straddles_both_layers() {
+        let base = base_store();
+        let mut overlay = MemoryStore::new();
+        overlay.put("d".to_string(), "staged_d".to_string()).unwrap();
+        let mut store = OverlayStore::new(&base, overlay);
+        store.put("c".to_string(), "new_c".to_string()).unwrap();
+        store.put("f".to_string(), "new_f".to_string()).unwrap();
+        store.delete("e").unwrap();
+
+        assert_eq!(
+            store.scan("b", "f").unwrap(),
+            vec![entry("b", "base_b"), entry("c", "new_c"), entry("d", "staged_d")]
+        );
+        assert_eq!(store.scan_keys("a", "g").unwrap(), vec!["a", "b", "c", "d", "f"]);
+        assert!(store.scan("", "z").is_err());
+    }
+
+    #[test]
+    fn test_into_changes_and_apply() {
+        let mut base = base_store();
+        let mut store = OverlayStore::new(&base, MemoryStore::new());
+        store.put("c".to_string(), "new_c".to_string()).unwrap();
+        store.put("x".to_string(), "temp".to_string()).unwrap();
+        store.delete("x").unwrap();
+        store.delete("a").unwrap();
+        store.put("b".to_string(), "new_b".to_string()).unwrap();
+
+        let changes = store.into_changes();
+        assert_eq!(
+            changes,
+            vec![
+                Change::Delete { key: "a".to_string() },
+                Change::Delete { key: "x".to_string() },
+                Change::Put { key: "b".to_string(), value: "new_b".to_string() },
+                Change::Put { key: "c".to_string(), value: "new_c".to_string() },
+            ]
+        );
+
+        apply_changes(&mut base, changes).unwrap();
+        let mut keys = base.keys().unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["b", "c", "e"]);
+        assert_eq!(base.get("b").unwrap(), Some("new_b".to_string()));
+        assert_eq!(base.get("c").unwrap(), Some("new_c".to_string()));
+    }
+}
@@ -0,0 +1,357 @@
+//! 容量制限モジュール
+//!
+//! 別のストアを包み、エントリ数とキー・値の合計バイト数に上限を設けるストアを提供する。
+//! 会場に置く小型機など、データベースの大きさを抑えたい環境で使う
+
+use crate::{KeyValueStore, Quota, Result, StoreError};
+use std::collections::BTreeMap;
+
+/// ストアの使用量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuotaUsage {
+    /// エントリ数
+    pub entries: usize,
+    /// キーと値の合計バイト数
+    pub bytes: usize,
+}
+
+/// エントリ数と合計バイト数に上限を設けるストア
+///
+/// 上限を超える書き込みは何も変更せずに `StoreError::QuotaExceeded` を返す。
+/// `apply_batch` は適用後の使用量を先に計算して検査するため、エンジンの一括書き込みも
+/// 何も書き込まずに失敗する。使用量を増やさない書き込み (上書きで値が縮む場合など) と削除は、
+/// 既に上限を超えている場合も受け付ける
+///
+/// ```
+/// use norimaki_db::quota::QuotaStore;
+/// use norimaki_db::{KeyValueStore, MemoryStore, StoreError};
+///
+/// let mut store = QuotaStore::new(MemoryStore::new())?.with_max_entries(2);
+/// store.put("Ua".to_string(), "1".to_string())?;
+/// store.put("Ub".to_string(), "2".to_string())?;
+/// assert!(matches!(store.put("Uc".to_string(), "3".to_string()), Err(StoreError::QuotaExceeded { limit: 2, attempted: 3, .. })));
+/// assert_eq!(store.usage().entries, 2);
+/// # Ok::<(), StoreError>(())
+/// ```
+#[derive(Debug)]
+pub struct QuotaStore<S: KeyValueStore> {
+    inner: S,
+    max_entries: Option<usize>,
+    max_total_bytes: Option<usize>,
+    usage: QuotaUsage,
+}
+
+/// エントリが使用するバイト数
+fn entry_bytes(key: &str, value: &str) -> usize {
+    key.len() + value.len()
+}
+
+impl<S: KeyValueStore> QuotaStore<S> {
+    /// 上限を設定しないストアを作成
+    ///
+    /// 包むストアに既にあるエントリを読み、現在の使用量を数える
+    ///
+    /// # Arguments
+    /// * `inner` - 包むストア
+    pub fn new(inner: S) -> Result<Self> {
+        let usage = Self::count(&inner)?;
+        Ok(Self {
+            inner,
+            max_entries: None,
+            max_total_bytes: None,
+            usage,
+        })
+    }
+
+    /// ストアの全エントリを読んで使用量を数える
+    fn count(inner: &S) -> Result<QuotaUsage> {
+        let mut usage = QuotaUsage::default();
+        for key in inner.keys()? {
+            if let Some(value) = inner.get(&key)? {
+                usage.entries += 1;
+                usage.bytes += entry_bytes(&key, &value);
+            }
+        }
+        Ok(usage)
+    }
+
+    /// 複数のキーを変更する操作が包んだストアで失敗した後、使用量を数え直す
+    ///
+    /// 一部だけ適用されている可能性があるため、控えておいた値ではなくストアの内容に合わせる
+    fn recount_after(&mut self, error: StoreError) -> StoreError {
+        if let Ok(usage) = Self::count(&self.inner) {
+            self.usage = usage;
+        }
+        error
+    }
+
+    /// エントリ数の上限を設定
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// キーと値の合計バイト数の上限を設定
+    pub fn with_max_total_bytes(mut self, max_total_bytes: usize) -> Self {
+        self.max_total_bytes = Some(max_total_bytes);
+        self
+    }
+
+    /// 現在の使用量
+    pub fn usage(&self) -> QuotaUsage {
+        self.usage
+    }
+
+    /// 包んでいるストアを取得
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// 包んでいるストアを取り出す
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// 書き込み後の使用量が上限内か検査
+    ///
+    /// 使用量が増えない場合は、既に上限を超えていても受け付ける
+    fn check(&self, after: QuotaUsage) -> Result<()> {
+        let checks = [
+            (Quota::Entries, self.max_entries, self.usage.entries, after.entries),
+            (Quota::Bytes, self.max_total_bytes, self.usage.bytes, after.bytes),
+        ];
+        for (quota, limit, current, attempted) in checks {
+            if let Some(limit) = limit {
+                if attempted > limit && attempted > current {
+                    return Err(StoreError::QuotaExceeded { quota, limit, attempted });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// キーの現在の値の大きさ (存在しない場合は None)
+    fn current_bytes(&self, key: &str) -> Result<Option<usize>> {
+        Ok(self.inner.get(key)?.map(|value| entry_bytes(key, &value)))
+    }
+
+    /// キーを削除した後の使用量
+    fn without(usage: QuotaUsage, bytes: Option<usize>) -> QuotaUsage {
+        match bytes {
+            Some(bytes) => QuotaUsage { entries: usage.entries - 1, bytes: usage.bytes - bytes },
+            None => usage,
+        }
+    }
+}
+
+impl<S: KeyValueStore> KeyValueStore for QuotaStore<S> {
+    fn put(&mut self, key: String, value: String) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        let mut after = Self::without(self.usage, self.current_bytes(&key)?);
+        after.entries += 1;
+        after.bytes += entry_bytes(&key, &value);
+        self.check(after)?;
+        self.inner.put(key, value)?;
+        self.usage = after;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        self.inner.get(key)
+    }
+
+    fn contains_key(&self, key: &str) -> Result<bool> {
+        self.inner.contains_key(key)
+    }
+
+    fn with_value<R>(&self, key: &str, f: impl FnOnce(&str) -> R) -> Result<Option<R>> {
+        self.inner.with_value(key, f)
+    }
+
+    fn scan_with(&mut self, start: &str, end: &str, f: impl FnMut(&str, &str) -> Result<()>) -> Result<()> {
+        self.inner.scan_with(start, end, f)
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        let after = Self::without(self.usage, self.current_bytes(key)?);
+        self.inner.delete(key)?;
+        self.usage = after;
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        self.inner.keys()
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.inner.clear()?;
+        self.usage = QuotaUsage::default();
+        Ok(())
+    }
+
+    fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+        self.inner.scan(start, end)
+    }
+
+    fn scan_keys(&mut self, start: &str, end: &str) -> Result<Vec<String>> {
+        self.inner.scan_keys(start, end)
+    }
+
+    fn delete_many(&mut self, keys: &[String]) -> Result<usize> {
+        if keys.iter().any(|key| key.is_empty()) {
+            return Err(StoreError::InvalidKey);
+        }
+        let mut existing: BTreeMap<&str, Option<usize>> = BTreeMap::new();
+        for key in keys {
+            if !existing.contains_key(key.as_str()) {
+                existing.insert(key, self.current_bytes(key)?);
+            }
+        }
+        let after = existing.into_values().fold(self.usage, Self::without);
+        let deleted = self.inner.delete_many(keys).map_err(|error| self.recount_after(error))?;
+        self.usage = after;
+        Ok(deleted)
+    }
+
+    fn apply_batch(&mut self, puts: Vec<(String, String)>, deletes: &[String]) -> Result<()> {
+        if deletes.iter().any(|key| key.is_empty()) || puts.iter().any(|(key, _)| key.is_empty()) {
+            return Err(StoreError::InvalidKey);
+        }
+        // 変更するキーの適用後の大きさ (削除 → 書き込みの順に適用し、同じキーへの後の書き込みが勝つ)
+        let mut sizes: BTreeMap<&str, Option<usize>> = BTreeMap::new();
+        for key in deletes {
+            sizes.insert(key, None);
+        }
+        for (key, value) in &puts {
+            sizes.insert(key, Some(entry_bytes(key, value)));
+        }
+        let mut after = self.usage;
+        for (key, size) in sizes {
+            after = Self::without(after, self.current_bytes(key)?);
+            if let Some(size) = size {
+                after.entries += 1;
+                after.bytes += size;
+            }
+        }
+        self.check(after)?;
+        self.inner.apply_batch(puts, deletes).map_err(|error| self.recount_after(error))?;
+        self.usage = after;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoatRaceEngine, Grade, MemoryStore, MonthlySchedule, RaceEvent};
+
+    include!("../testdata/sample.rs");
+
+    fn quota_error(result: Result<()>) -> (Quota, usize, usize) {
+        match result {
+            Err(StoreError::QuotaExceeded { quota, limit, attempted }) => (quota, limit, attempted),
+            other => panic!("expected QuotaExceeded, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_entry_limit_boundary() {
+        let mut store = QuotaStore::new(MemoryStore::new()).unwrap().with_max_entries(3);
+        for key in ["Ua", "Ub", "Uc"] {
+            store.put(key.to_string(), "v".to_string()).unwrap();
+        }
+        // ちょうど上限
+        assert_eq!(store.usage(), QuotaUsage { entries: 3, bytes: 9 });
+        assert_eq!(quota_error(store.put("Ud".to_string(), "v".to_string())), (Quota::Entries, 3, 4));
+        // 上書きはエントリ数を増やさない
+        store.put("Ua".to_string(), "longer".to_string()).unwrap();
+        assert_eq!(store.usage(), QuotaUsage { entries: 3, bytes: 14 });
+
+        store.delete("Ub").unwrap();
+        store.delete("Ub").unwrap();
+        assert_eq!(store.usage(), QuotaUsage { entries: 2, bytes: 11 });
+        store.put("Ud".to_string(), "v".to_string()).unwrap();
+        assert_eq!(store.usage().entries, 3);
+        assert_eq!(store.inner().keys().unwrap(), vec!["Ua", "Uc", "Ud"]);
+    }
+
+    #[test]
+    fn test_byte_limit_boundary() {
+        let mut store = QuotaStore::new(MemoryStore::new()).unwrap().with_max_total_bytes(10);
+        // "Ua" + "12345678" = 10 バイトでちょうど上限
+        store.put("Ua".to_string(), "12345678".to_string()).unwrap();
+        assert_eq!(store.usage().bytes, 10);
+        assert_eq!(quota_error(store.put("Ua".to_string(), "123456789".to_string())), (Quota::Bytes, 10, 11));
+        assert_eq!(store.get("Ua").unwrap(), Some("12345678".to_string()));
+
+        // 置き換えは差分だけ数える
+        store.put("Ua".to_string(), "1234".to_string()).unwrap();
+        assert_eq!(store.usage().bytes, 6);
+        store.put("Ub".to_string(), "12".to_string()).unwrap();
+        assert_eq!(store.usage(), QuotaUsage { entries: 2, bytes: 10 });
+        assert_eq!(quota_error(store.put("Uc".to_string(), String::new())), (Quota::Bytes, 10, 12));
+
+        assert_eq!(store.delete_many(&["Ua".to_string(), "Ua".to_string(), "Ux".to_string()]).unwrap(), 1);
+        assert_eq!(store.usage(), QuotaUsage { entries: 1, bytes: 4 });
+        store.clear().unwrap();
+        assert_eq!(store.usage(), QuotaUsage::default());
+    }
+
+    #[test]
+    fn test_batch_is_checked_up_front() {
+        let mut store = QuotaStore::new(MemoryStore::new()).unwrap().with_max_entries(3);
+        store.put("Ua".to_string(), "1".to_string()).unwrap();
+        store.put("Ub".to_string(), "2".to_string()).unwrap();
+
+        // 削除と書き込みを合わせてちょうど上限
+        let puts = vec![("Uc".to_string(), "3".to_string()), ("Ud".to_string(), "4".to_string())];
+        store.apply_batch(puts, &["Ua".to_string()]).unwrap();
+        assert_eq!(store.usage().entries, 3);
+
+        // 上限を超えるバッチは何も変更しない
+        let puts = vec![("Ub".to_string(), "changed".to_string()), ("Ue".to_string(), "5".to_string())];
+        assert_eq!(quota_error(store.apply_batch(puts, &[])), (Quota::Entries, 3, 4));
+        assert_eq!(store.get("Ub").unwrap(), Some("2".to_string()));
+        assert_eq!(store.usage().entries, 3);
+    }
+
+    #[test]
+    fn test_existing_entries_count_and_over_quota_stores_can_shrink() {
+        let mut inner = MemoryStore::new();
+        inner.put("Ua".to_string(), "1234".to_string()).unwrap();
+        inner.put("Ub".to_string(), "1234".to_string()).unwrap();
+        let mut store = QuotaStore::new(inner).unwrap().with_max_total_bytes(8);
+        assert_eq!(store.usage(), QuotaUsage { entries: 2, bytes: 12 });
+
+        // 使用量を増やさない書き込みは受け付ける
+        store.put("Ua".to_string(), "12".to_string()).unwrap();
+        assert_eq!(store.usage().bytes, 10);
+        assert_eq!(quota_error(store.put("Uc".to_string(), String::new())), (Quota::Bytes, 8, 12));
+        store.delete("Ub").unwrap();
+        store.put("Uc".to_string(), "12".to_string()).unwrap();
+        assert_eq!(store.usage(), QuotaUsage { entries: 2, bytes: 8 });
+    }
+
+    #[test]
+    fn test_engine_bulk_write_fails_before_writing() {
+        let store = QuotaStore::new(MemoryStore::new()).unwrap().with_max_entries(5);
+        let mut engine = BoatRaceEngine::new(store);
+        let result = engine.put_monthly_schedule(&sample_data());
+        assert!(matches!(result, Err(StoreError::QuotaExceeded { quota: Quota::Entries, limit: 5, .. })), "{:?}", result);
+        assert_eq!(engine.store().usage(), QuotaUsage::default());
+        assert!(engine.store().inner().keys().unwrap().is_empty());
+
+        let races: Vec<(u64, String)> = (1..=6).map(|n| (1000 + n, format!("race{}", n))).collect();
+        assert!(matches!(engine.put_races_bulk("tokyo_bay_cup", &races), Err(StoreError::QuotaExceeded { .. })));
+        assert!(engine.store().inner().keys().unwrap().is_empty());
+
+        // 上限内なら書き込める
+        engine.put_races_bulk("tokyo_bay_cup", &races[..4]).unwrap();
+        assert_eq!(engine.store().usage().entries, 5);
+    }
+}
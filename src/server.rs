@@ -0,0 +1,250 @@
+//! HTTP 読み取り API モジュール (`http` フィーチャー)
+//!
+//! tiny_http のサーバー上に実装する。エンドポイントはすべて GET のみで、レスポンスは serde_json で出力する
+//!
+//! * `GET /months/{yyyymm}` - 月別スケジュール (`MonthlySchedule`)
+//! * `GET /tournaments/{id}/races` - 大会のレース一覧 (タイムスタンプ・日時・値のバイト数)
+//! * `GET /stats` - データベース統計 (`DatabaseStatistics`)
+//!
+//! 接続の読み書きは tiny_http のスレッドが接続ごとに行うため、遅いクライアントが他の接続を待たせない。
+//! 読み終えたリクエストはエンジンを持つ1本のスレッドが受け取った順に処理する
+
+use std::io;
+use std::net::{SocketAddr, TcpListener};
+
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::{BoatRaceEngine, KeyValueStore, Result, StoreError};
+
+/// 読み取り専用の HTTP サーバー
+pub struct Server<K: KeyValueStore> {
+    engine: BoatRaceEngine<K>,
+    server: tiny_http::Server,
+}
+
+impl<K: KeyValueStore + Send + 'static> Server<K> {
+    /// アドレスにバインドしたサーバーを作成
+    ///
+    /// ポート 0 を指定した場合は空いているポートが割り当てられる (`local_addr` で確認できる)
+    ///
+    /// # Arguments
+    /// * `engine` - 公開するエンジン
+    /// * `addr` - 待ち受けアドレス
+    ///
+    /// # Returns
+    /// サーバー
+    pub fn bind(engine: BoatRaceEngine<K>, addr: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let server = tiny_http::Server::from_listener(listener, None).map_err(io::Error::other)?;
+        Ok(Self { engine, server })
+    }
+
+    /// 実際に待ち受けているアドレス
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| StoreError::from(io::Error::other("server is not listening on a TCP address")))
+    }
+
+    /// リクエストを受け取った順に処理し続ける
+    ///
+    /// 個々のリクエストへの応答に失敗した場合は、その接続だけを諦めて処理を続ける
+    pub fn run(mut self) -> Result<()> {
+        loop {
+            let request = self.server.recv()?;
+            let _ = handle_request(&mut self.engine, request);
+        }
+    }
+}
+
+/// エンジンを HTTP で公開する (戻らない)
+///
+/// # Arguments
+/// * `engine` - 公開するエンジン
+/// * `addr` - 待ち受けアドレス
+///
+/// # Returns
+/// バインドに失敗した場合のみエラーを返す
+pub fn serve<K: KeyValueStore + Send + 'static>(engine: BoatRaceEngine<K>, addr: SocketAddr) -> Result<()> {
+    Server::bind(engine, addr)?.run()
+}
+
+fn handle_request<K: KeyValueStore>(engine: &mut BoatRaceEngine<K>, request: Request) -> io::Result<()> {
+    let (status, body) = match request.method() {
+        Method::Get => route(engine, request.url()),
+        _ => (405, json!({ "error": "only GET is supported" }).to_string()),
+    };
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).expect("valid header");
+    request.respond(Response::from_string(body).with_status_code(status).with_header(content_type))
+}
+
+/// パスに対応するハンドラーを呼び、(ステータス, JSON 本文) を返す
+fn route<K: KeyValueStore>(engine: &mut BoatRaceEngine<K>, path: &str) -> (u16, String) {
+    let path = path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let result = match segments.as_slice() {
+        ["months", year_month] => year_month
+            .parse::<u32>()
+            .map_err(|_| StoreError::invalid_value(format!("year_month {:?} is not YYYYMM", year_month)))
+            .and_then(|year_month| crate::key::validate_year_month(year_month).map(|_| year_month))
+            .and_then(|year_month| engine.get_monthly_schedule(year_month))
+            .and_then(|schedule| to_json(&schedule)),
+        ["tournaments", tournament_id, "races"] => tournament_races(engine, &percent_decode(tournament_id)),
+        ["stats"] => engine.get_detailed_statistics().and_then(|stats| to_json(&stats)),
+        _ => return (404, json!({ "error": format!("no route for {}", path) }).to_string()),
+    };
+
+    match result {
+        Ok(body) => (200, body),
+        Err(error) => {
+            let status = if error.is_not_found() {
+                404
+            } else if error.is_invalid_input() {
+                400
+            } else {
+                500
+            };
+            (status, json!({ "error": error.to_string(), "code": error.code() }).to_string())
+        }
+    }
+}
+
+/// レースデータの型は呼び出し側依存のため、タイムスタンプと値の大きさだけを返す
+fn tournament_races<K: KeyValueStore>(engine: &mut BoatRaceEngine<K>, tournament_id: &str) -> Result<String> {
+    let sizes = engine.get_race_sizes(tournament_id)?;
+    if sizes.is_empty() {
+        return Err(StoreError::not_found(tournament_id));
+    }
+
+    let races: Vec<serde_json::Value> = sizes
+        .into_iter()
+        .map(|(timestamp, bytes)| json!({
+            "timestamp": timestamp,
+            "time": i64::try_from(timestamp)
+                .ok()
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|when| when.to_rfc3339()),
+            "bytes": bytes,
+        }))
+        .collect();
+    to_json(&json!({ "tournament_id": tournament_id, "races": races }))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string(value)?)
+}
+
+/// パス要素の %XX エスケープを戻す (不正なエスケープはそのまま残す)
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grade, MemoryStore, MonthlySchedule, RaceEvent};
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::time::Duration;
+
+    include!("../testdata/sample.rs");
+
+    /// 空きポートでサーバーを起動し、そのアドレスを返す
+    fn start() -> SocketAddr {
+        let mut engine = BoatRaceEngine::new(MemoryStore::new());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        engine.put_race_data("tokyo_bay_cup", 1757640600000, &"race1").unwrap();
+        // 大会IDが前方一致するだけの別の大会
+        engine.put_race_data("tokyo_bay_cup2", 1757640600000, &"other").unwrap();
+        // 大会の範囲内にある解析できないキーは読み飛ばす
+        engine.store_mut().put("Ttokyo_bay_cup\x00garbage".to_string(), "x".to_string()).unwrap();
+        let server = Server::bind(engine, "127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = server.local_addr().unwrap();
+        std::thread::spawn(move || server.run());
+        addr
+    }
+
+    /// 生のリクエストを送り、レスポンス全体を返す
+    fn send_raw(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    fn get(addr: SocketAddr, path: &str) -> (u16, serde_json::Value) {
+        let response = send_raw(addr, &format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path));
+        let status = response[9..12].parse().unwrap();
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn test_read_endpoints() {
+        let addr = start();
+
+        let (status, body) = get(addr, "/months/202509");
+        assert_eq!(status, 200);
+        let schedule: MonthlySchedule = serde_json::from_value(body).unwrap();
+        assert_eq!(schedule.events.len(), sample_data().events.len());
+
+        let (status, body) = get(addr, "/tournaments/tokyo_bay_cup/races");
+        assert_eq!(status, 200);
+        assert_eq!(body["races"][0]["timestamp"], 1757640600000u64);
+        assert_eq!(body["races"][0]["time"], "2025-09-12T01:30:00+00:00");
+        assert_eq!(body["races"].as_array().unwrap().len(), 1);
+
+        let (status, body) = get(addr, "/stats");
+        assert_eq!(status, 200);
+        assert_eq!(body["races"], 2);
+        assert_eq!(body["months"], json!([202509]));
+
+        let response = send_raw(addr, "GET /stats HTTP/1.1\r\nConnection: close\r\n\r\n");
+        assert!(response.to_ascii_lowercase().contains("content-type: application/json"), "{}", response);
+    }
+
+    #[test]
+    fn test_error_statuses() {
+        let addr = start();
+        assert_eq!(get(addr, "/months/202513").0, 400);
+        assert_eq!(get(addr, "/months/abc").0, 400);
+        assert_eq!(get(addr, "/tournaments/unknown_cup/races").0, 404);
+        assert_eq!(get(addr, "/tournaments/tokyo_bay/races").0, 404);
+        assert_eq!(get(addr, "/nowhere").0, 404);
+
+        let response = send_raw(addr, "DELETE /stats HTTP/1.1\r\nConnection: close\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405"), "{}", response);
+    }
+
+    #[test]
+    fn test_idle_client_does_not_block_others() {
+        let addr = start();
+
+        // リクエスト行を送り切らないまま待つクライアント
+        let mut idle = TcpStream::connect(addr).unwrap();
+        idle.write_all(b"GET /stats").unwrap();
+
+        let started = std::time::Instant::now();
+        assert_eq!(get(addr, "/stats").0, 200);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
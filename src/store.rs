@@ -1,30 +1,197 @@
 use crate::{Result, StoreError};
+#[cfg(feature = "fs")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "fs")]
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "fs")]
 use std::fs::{File, OpenOptions};
+#[cfg(feature = "fs")]
 use std::io::{Read, Write};
+#[cfg(feature = "fs")]
 use std::path::Path;
+#[cfg(feature = "fs")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+#[cfg(feature = "fs")]
+use std::time::SystemTime;
+#[cfg(all(feature = "fs", feature = "tracing"))]
+use std::time::{Duration, Instant};
 
 pub trait KeyValueStore {
     fn put(&mut self, key: String, value: String) -> Result<()>;
     fn get(&self, key: &str) -> Result<Option<String>>;
     fn delete(&mut self, key: &str) -> Result<()>;
+
+    /// キーが存在するか
+    ///
+    /// 既定の実装は `get` で値を読む。値を複製せずに確認できるバックエンドは上書きすること
+    fn contains_key(&self, key: &str) -> Result<bool> {
+        Ok(self.get(key)?.is_some())
+    }
+
+    /// 全てのキーを取得する
+    ///
+    /// どのバックエンドでもキーの辞書順 (バイト列の昇順) で返す。
+    /// ダンプ・エクスポート・バックアップの出力が実行ごとに変わらないよう、実装は必ずこの順序を守ること
     fn keys(&self) -> Result<Vec<String>>;
     fn clear(&mut self) -> Result<()>;
     fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>>;
+
+    /// キーの値を複製せずにクロージャに渡し、その結果を返す
+    ///
+    /// 既定の実装は `get` で読んだ値を渡す。メモリ上に値を持つバックエンドは上書きし、
+    /// デシリアライズするだけの読み込みで値を複製しないようにする
+    ///
+    /// # Returns
+    /// クロージャの戻り値（キーが存在しない場合は None）
+    fn with_value<R>(&self, key: &str, f: impl FnOnce(&str) -> R) -> Result<Option<R>> {
+        Ok(self.get(key)?.map(|value| f(&value)))
+    }
+
+    /// 範囲内の各エントリを、値を複製せずに順不同でクロージャに渡す
+    ///
+    /// クロージャがエラーを返した時点で打ち切り、そのエラーを返す。既定の実装は `scan` の結果を渡す
+    fn scan_with(&mut self, start: &str, end: &str, mut f: impl FnMut(&str, &str) -> Result<()>) -> Result<()> {
+        for (key, value) in self.scan(start, end)? {
+            f(&key, &value)?;
+        }
+        Ok(())
+    }
+
+    /// 範囲内のキーのみを取得する（値は複製しない実装を推奨）
+    fn scan_keys(&mut self, start: &str, end: &str) -> Result<Vec<String>> {
+        Ok(self.scan(start, end)?.into_iter().map(|(key, _)| key).collect())
+    }
+
+    /// 複数のキーをまとめて削除し、実際に存在したキーの数を返す
+    fn delete_many(&mut self, keys: &[String]) -> Result<usize> {
+        let mut deleted = 0;
+        for key in keys {
+            if self.contains_key(key)? {
+                deleted += 1;
+            }
+            self.delete(key)?;
+        }
+        Ok(deleted)
+    }
+
+    /// 削除と書き込みをまとめて適用する（削除 → 書き込みの順）
+    ///
+    /// いずれかのキーが不正な場合は何も変更せずにエラーを返す
+    fn apply_batch(&mut self, puts: Vec<(String, String)>, deletes: &[String]) -> Result<()> {
+        if deletes.iter().any(|key| key.is_empty()) || puts.iter().any(|(key, _)| key.is_empty()) {
+            return Err(StoreError::InvalidKey);
+        }
+        for key in deletes {
+            self.delete(key)?;
+        }
+        for (key, value) in puts {
+            self.put(key, value)?;
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MemoryStore {
     data: HashMap<String, String>,
 }
 
+/// キーを辞書順に並べて取得 (`KeyValueStore::keys` の順序保証)
+fn sorted_keys(data: &HashMap<String, String>) -> Vec<String> {
+    let mut keys: Vec<String> = data.keys().cloned().collect();
+    keys.sort_unstable();
+    keys
+}
+
+/// キーと値の合計バイト数 (HashMap のオーバーヘッドは含まない概算)
+fn approximate_bytes(data: &HashMap<String, String>) -> usize {
+    data.iter().map(|(key, value)| key.len() + value.len()).sum()
+}
+
+/// 範囲内のエントリを借用したままクロージャに渡す (MemoryStore と FileStore の `scan_with`)
+fn scan_entries_with(
+    data: &HashMap<String, String>,
+    start: &str,
+    end: &str,
+    mut f: impl FnMut(&str, &str) -> Result<()>,
+) -> Result<()> {
+    for (key, value) in data {
+        if key.as_str() >= start && key.as_str() < end {
+            f(key, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// エントリをキー順に1行1件で出力
+///
+/// キーと値は `{:?}` で出力するため、セパレータなどの制御文字もそのまま読める
+fn dump_entries(data: &HashMap<String, String>, limit: usize) -> String {
+    let mut keys: Vec<&String> = data.keys().collect();
+    keys.sort();
+    let mut output = String::new();
+    for key in keys.iter().take(limit) {
+        output.push_str(&format!("{:?} => {:?}\n", key, data[*key]));
+    }
+    if keys.len() > limit {
+        output.push_str(&format!("... {} more entries\n", keys.len() - limit));
+    }
+    output
+}
+
 impl MemoryStore {
     pub fn new() -> Self {
         Self {
             data: HashMap::new(),
         }
     }
+
+    /// ストアを破棄して全エントリを取り出す (順不同)
+    pub(crate) fn into_entries(self) -> impl Iterator<Item = (String, String)> {
+        self.data.into_iter()
+    }
+
+    /// 内容をキー順に出力
+    ///
+    /// `Debug` は件数とサイズだけを出力するため、中身を確認したい場合に使う
+    ///
+    /// # Arguments
+    /// * `limit` - 出力する最大エントリ数 (超えた分は件数のみ出力)
+    ///
+    /// # Returns
+    /// 1行1エントリの `"key" => "value"` 形式のテキスト
+    pub fn debug_dump(&self, limit: usize) -> String {
+        dump_entries(&self.data, limit)
+    }
+
+    /// ヒープ使用量の概算 (バイト)
+    ///
+    /// キーと値の確保済み容量と、ハッシュテーブルのバケットを数える。
+    /// `CompactMemoryStore::memory_usage` と同じ基準で比較するための値
+    ///
+    /// # Returns
+    /// 推定バイト数
+    pub fn memory_usage(&self) -> usize {
+        let strings: usize = self.data.iter().map(|(key, value)| key.capacity() + value.capacity()).sum();
+        strings + self.data.capacity() * std::mem::size_of::<(String, String)>()
+    }
+}
+
+impl fmt::Debug for MemoryStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryStore")
+            .field("entries", &self.data.len())
+            .field("approx_bytes", &approximate_bytes(&self.data))
+            .finish()
+    }
+}
+
+impl fmt::Display for MemoryStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "in-memory store ({} entries, {} bytes)", self.data.len(), approximate_bytes(&self.data))
+    }
 }
 
 impl Default for MemoryStore {
@@ -49,6 +216,27 @@ impl KeyValueStore for MemoryStore {
         Ok(self.data.get(key).cloned())
     }
 
+    fn contains_key(&self, key: &str) -> Result<bool> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(self.data.contains_key(key))
+    }
+
+    fn with_value<R>(&self, key: &str, f: impl FnOnce(&str) -> R) -> Result<Option<R>> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(self.data.get(key).map(|value| f(value)))
+    }
+
+    fn scan_with(&mut self, start: &str, end: &str, f: impl FnMut(&str, &str) -> Result<()>) -> Result<()> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        scan_entries_with(&self.data, start, end, f)
+    }
+
     fn delete(&mut self, key: &str) -> Result<()> {
         if key.is_empty() {
             return Err(StoreError::InvalidKey);
@@ -58,7 +246,7 @@ impl KeyValueStore for MemoryStore {
     }
 
     fn keys(&self) -> Result<Vec<String>> {
-        Ok(self.data.keys().cloned().collect())
+        Ok(sorted_keys(&self.data))
     }
 
     fn clear(&mut self) -> Result<()> {
@@ -78,72 +266,411 @@ impl KeyValueStore for MemoryStore {
         }
         Ok(result)
     }
+
+    fn scan_keys(&mut self, start: &str, end: &str) -> Result<Vec<String>> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(self
+            .data
+            .keys()
+            .filter(|key| key.as_str() >= start && key.as_str() < end)
+            .cloned()
+            .collect())
+    }
 }
 
+#[cfg(feature = "fs")]
 #[derive(Debug, Serialize, Deserialize)]
 struct FileData {
     data: HashMap<String, String>,
 }
 
-#[derive(Debug)]
+/// 書き出し用の FileData (キー順に並べ、同じ内容なら同じバイト列になるようにする)
+#[cfg(feature = "fs")]
+#[derive(Serialize)]
+struct SortedFileData<'a> {
+    data: BTreeMap<&'a str, &'a str>,
+}
+
+/// ファイルの更新時刻と長さ (外部からの変更検知に使う)
+#[cfg(feature = "fs")]
+type FileState = (Option<SystemTime>, u64);
+
+/// FileStore がメモリ上に持つ内容
+#[cfg(feature = "fs")]
+struct FileContents {
+    data: HashMap<String, String>,
+    /// 最後に読み込み/書き出ししたときのファイルの状態 (ファイルがなければ None)
+    file_state: Option<FileState>,
+    /// 書き出しに失敗し、メモリ上にファイルへ反映されていない変更がある
+    dirty: bool,
+}
+
+/// `FileStore::reload` で反映された変更の件数
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReloadStats {
+    /// ファイルにだけあったキーの数
+    pub added: usize,
+    /// 値が変わっていたキーの数
+    pub changed: usize,
+    /// ファイルからなくなっていたキーの数
+    pub removed: usize,
+}
+
+#[cfg(feature = "fs")]
+impl ReloadStats {
+    /// 変更がなかったか
+    pub fn is_unchanged(&self) -> bool {
+        self.added == 0 && self.changed == 0 && self.removed == 0
+    }
+}
+
+#[cfg(feature = "fs")]
+fn read_file_state(file_path: &str) -> Result<Option<FileState>> {
+    match std::fs::metadata(file_path) {
+        Ok(metadata) => Ok(Some((metadata.modified().ok(), metadata.len()))),
+        // 親ディレクトリがない・親の位置にファイルがある場合もファイルなしとして扱う (書き出し時にパス付きで報告する)
+        Err(error) if matches!(error.kind(), std::io::ErrorKind::NotFound | std::io::ErrorKind::NotADirectory) => Ok(None),
+        Err(error) => Err(StoreError::io_at(error, "cannot read file metadata", file_path)),
+    }
+}
+
+#[cfg(feature = "fs")]
+fn read_file_data(file_path: &str) -> Result<HashMap<String, String>> {
+    let path = Path::new(file_path);
+    if path.is_dir() {
+        let error = std::io::Error::new(std::io::ErrorKind::IsADirectory, "expected a file");
+        return Err(StoreError::io_at(error, "path is a directory", file_path));
+    }
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let mut contents = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|error| StoreError::io_at(error, "cannot read file", file_path))?;
+
+    if contents.trim().is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let file_data: FileData = serde_json::from_str(&contents)?;
+    Ok(file_data.data)
+}
+
+#[cfg(feature = "fs")]
+impl FileContents {
+    /// ファイルを読み直してメモリ上の内容を置き換える
+    ///
+    /// 未反映の変更がある場合は上書きせず `Conflict` を返す
+    fn reload(&mut self, file_path: &str) -> Result<ReloadStats> {
+        if self.dirty {
+            return Err(StoreError::Conflict(file_path.to_string()));
+        }
+        // 読み込み中に書き換えられても次の確認で読み直されるよう、状態を先に取得する
+        let file_state = read_file_state(file_path)?;
+        let data = read_file_data(file_path)?;
+
+        let mut stats = ReloadStats::default();
+        for (key, value) in &data {
+            match self.data.get(key) {
+                None => stats.added += 1,
+                Some(old) if old != value => stats.changed += 1,
+                Some(_) => {}
+            }
+        }
+        stats.removed = self.data.keys().filter(|key| !data.contains_key(*key)).count();
+
+        self.data = data;
+        self.file_state = file_state;
+        Ok(stats)
+    }
+
+    /// ファイルが前回の読み書きから変わっていれば読み直す
+    fn refresh(&mut self, file_path: &str) -> Result<()> {
+        let current = read_file_state(file_path)?;
+        if current != self.file_state {
+            let stats = self.reload(file_path)?;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(path = %file_path, added = stats.added, changed = stats.changed, removed = stats.removed, "FileStore reloaded");
+            #[cfg(not(feature = "tracing"))]
+            let _ = stats;
+        }
+        Ok(())
+    }
+}
+
+/// FileStore がファイルを書き出し fsync するタイミング (`fs` フィーチャー)
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncPolicy {
+    /// 書き込みごとにファイルを書き出して fsync する (既定。最も安全で最も遅い)
+    #[default]
+    Always,
+    /// 書き込みはメモリ上に溜め、`flush` (または Drop) でまとめて書き出して fsync する
+    OnFlush,
+    /// 書き込みごとにファイルを書き出すが fsync しない (ディスクへの反映は OS に任せる)
+    Never,
+}
+
+/// JSON ファイルに永続化するストア (`fs` フィーチャー)
+#[cfg(feature = "fs")]
 pub struct FileStore {
     file_path: String,
-    data: HashMap<String, String>,
+    /// `get` / `keys` (`&self`) からも自動再読み込みできるよう Mutex に入れる
+    contents: Mutex<FileContents>,
+    save_count: usize,
+    /// 書き出し時に親ディレクトリがなければ作成する
+    create_dirs: bool,
+    /// 操作の前にファイルの変更を確認し、変わっていれば読み直す
+    auto_reload: bool,
+    /// 書き出しと fsync のタイミング
+    sync_policy: SyncPolicy,
+    /// 書き出したが fsync していない内容がある (`SyncPolicy::Never`)
+    unsynced: bool,
+    /// この時間を超えた書き出しを warn として出力する
+    #[cfg(feature = "tracing")]
+    slow_save_threshold: Duration,
 }
 
+/// FileStore の書き出しを遅いとみなす既定の時間 (`tracing` フィーチャー)
+#[cfg(all(feature = "fs", feature = "tracing"))]
+pub const DEFAULT_SLOW_SAVE_THRESHOLD: Duration = Duration::from_millis(100);
+
+#[cfg(feature = "fs")]
 impl FileStore {
     pub fn new<P: AsRef<Path>>(file_path: P) -> Result<Self> {
         let file_path = file_path.as_ref().to_string_lossy().to_string();
-        let mut store = Self {
+        let mut contents = FileContents { data: HashMap::new(), file_state: None, dirty: false };
+        contents.reload(&file_path)?;
+        Ok(Self {
             file_path,
-            data: HashMap::new(),
-        };
-        store.load()?;
-        Ok(store)
+            contents: Mutex::new(contents),
+            save_count: 0,
+            create_dirs: true,
+            auto_reload: false,
+            sync_policy: SyncPolicy::Always,
+            unsynced: false,
+            #[cfg(feature = "tracing")]
+            slow_save_threshold: DEFAULT_SLOW_SAVE_THRESHOLD,
+        })
     }
 
-    fn load(&mut self) -> Result<()> {
-        if !Path::new(&self.file_path).exists() {
-            return Ok(());
-        }
+    /// 書き出しを遅いとみなす時間を設定（既定は `DEFAULT_SLOW_SAVE_THRESHOLD`、`tracing` フィーチャー）
+    ///
+    /// 書き出しにこの時間を超えてかかった場合、ファイルパス・エントリ数・所要時間を warn イベントとして出力する
+    #[cfg(feature = "tracing")]
+    pub fn with_slow_save_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_save_threshold = threshold;
+        self
+    }
+
+    /// 書き出し時に親ディレクトリがなければ作成するか設定 (既定は true)
+    ///
+    /// false の場合、親ディレクトリがないと最初の書き込みがパス付きの `IoError` になる
+    pub fn with_create_dirs(mut self, create_dirs: bool) -> Self {
+        self.create_dirs = create_dirs;
+        self
+    }
+
+    /// ファイルの変更を自動で取り込むか設定 (既定は false)
+    ///
+    /// true の場合、読み書きの前にファイルの更新時刻と長さを確認し、
+    /// 前回の読み書きから変わっていれば `reload` と同じように読み直す。
+    /// 未反映の変更がある状態でファイルが変わっていた場合は、その操作が `Conflict` になる
+    pub fn with_auto_reload(mut self, auto_reload: bool) -> Self {
+        self.auto_reload = auto_reload;
+        self
+    }
+
+    /// 書き出しと fsync のタイミングを設定 (既定は `SyncPolicy::Always`)
+    ///
+    /// 大量に書き込む場合は `SyncPolicy::OnFlush` にして最後に `flush` を呼ぶと、
+    /// 書き込みごとのファイル全体の書き出しと fsync を省ける
+    pub fn with_sync_policy(mut self, sync_policy: SyncPolicy) -> Self {
+        self.sync_policy = sync_policy;
+        self
+    }
+
+    /// ファイルを読み直し、メモリ上の内容をファイルの内容で置き換える
+    ///
+    /// 他のプロセスがファイルを書き換えた場合に使う
+    ///
+    /// # Returns
+    /// 追加・変更・削除されたキーの件数。書き出しに失敗した変更が残っている場合は
+    /// マージせずに `Conflict` (値: ファイルパス) を返す
+    pub fn reload(&mut self) -> Result<ReloadStats> {
+        let contents = self.contents.get_mut().unwrap_or_else(PoisonError::into_inner);
+        contents.reload(&self.file_path)
+    }
 
-        let mut file = File::open(&self.file_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
+    /// このインスタンスがファイルを書き出した回数
+    pub fn save_count(&self) -> usize {
+        self.save_count
+    }
+
+    /// 内容をキー順に出力
+    ///
+    /// `Debug` はパス・件数・サイズだけを出力するため、中身を確認したい場合に使う
+    ///
+    /// # Arguments
+    /// * `limit` - 出力する最大エントリ数 (超えた分は件数のみ出力)
+    ///
+    /// # Returns
+    /// 1行1エントリの `"key" => "value"` 形式のテキスト
+    pub fn debug_dump(&self, limit: usize) -> String {
+        dump_entries(&self.lock_contents().data, limit)
+    }
+
+    fn lock_contents(&self) -> MutexGuard<'_, FileContents> {
+        self.contents.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 読み取り用に内容を取得 (自動再読み込みが有効なら先に変更を取り込む)
+    fn read_contents(&self) -> Result<MutexGuard<'_, FileContents>> {
+        let mut contents = self.lock_contents();
+        if self.auto_reload {
+            contents.refresh(&self.file_path)?;
+        }
+        Ok(contents)
+    }
 
-        if contents.trim().is_empty() {
-            return Ok(());
+    /// 書き込み用に内容を取得 (自動再読み込みが有効なら先に変更を取り込む)
+    fn write_contents(&mut self) -> Result<&mut FileContents> {
+        let contents = self.contents.get_mut().unwrap_or_else(PoisonError::into_inner);
+        if self.auto_reload {
+            contents.refresh(&self.file_path)?;
         }
+        Ok(contents)
+    }
 
-        let file_data: FileData = serde_json::from_str(&contents)?;
-        self.data = file_data.data;
+    /// ファイルに反映されていない変更を書き出し、fsync する
+    ///
+    /// `SyncPolicy::OnFlush` で溜めた変更を永続化するときや、`SyncPolicy::Never` で
+    /// 書き出した内容を確実にディスクへ届けたいときに呼ぶ。`SyncPolicy::Always` では
+    /// 書き込みごとに fsync 済みのため何もしない。`OnFlush` / `Never` では Drop 時にも呼ばれる
+    pub fn flush(&mut self) -> Result<()> {
+        let contents = self.contents.get_mut().unwrap_or_else(PoisonError::into_inner);
+        if contents.dirty {
+            return self.write_file(true);
+        }
+        if self.unsynced {
+            File::open(&self.file_path)
+                .and_then(|file| file.sync_all())
+                .map_err(|error| StoreError::io_at(error, "cannot sync file", &self.file_path))?;
+            self.unsynced = false;
+        }
         Ok(())
     }
 
-    fn save(&self) -> Result<()> {
-        let file_data = FileData {
-            data: self.data.clone(),
+    /// ファイルに反映されていない変更があるか (`SyncPolicy::OnFlush` で溜まっている間や、書き出しに失敗した後は true)
+    pub fn has_pending_writes(&self) -> bool {
+        self.lock_contents().dirty
+    }
+
+    fn save(&mut self) -> Result<()> {
+        // 書き出しが終わるまではファイルに反映されていない変更として扱う
+        self.contents.get_mut().unwrap_or_else(PoisonError::into_inner).dirty = true;
+        match self.sync_policy {
+            SyncPolicy::Always => self.write_file(true),
+            SyncPolicy::OnFlush => Ok(()),
+            SyncPolicy::Never => self.write_file(false),
+        }
+    }
+
+    fn write_file(&mut self, sync: bool) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let started = Instant::now();
+        let contents = self.contents.get_mut().unwrap_or_else(PoisonError::into_inner);
+        let file_data = SortedFileData {
+            data: contents.data.iter().map(|(key, value)| (key.as_str(), value.as_str())).collect(),
         };
         let json = serde_json::to_string_pretty(&file_data)?;
 
-        let mut file = OpenOptions::new()
+        if self.create_dirs {
+            if let Some(parent) = Path::new(&self.file_path).parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)
+                    .map_err(|error| StoreError::io_at(error, "cannot create directory", parent.to_string_lossy()))?;
+            }
+        }
+        OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(&self.file_path)?;
+            .open(&self.file_path)
+            .and_then(|mut file| {
+                file.write_all(json.as_bytes())?;
+                if sync {
+                    file.sync_all()?;
+                }
+                Ok(())
+            })
+            .map_err(|error| StoreError::io_at(error, "cannot write file", &self.file_path))?;
+        contents.file_state = read_file_state(&self.file_path)?;
+        contents.dirty = false;
+        self.unsynced = !sync;
+        self.save_count += 1;
 
-        file.write_all(json.as_bytes())?;
-        file.sync_all()?;
+        #[cfg(feature = "tracing")]
+        {
+            let elapsed = started.elapsed();
+            tracing::debug!(path = %self.file_path, entries = contents.data.len(), bytes = json.len(), sync, ?elapsed, "FileStore saved");
+            if elapsed > self.slow_save_threshold {
+                tracing::warn!(path = %self.file_path, entries = contents.data.len(), ?elapsed, threshold = ?self.slow_save_threshold, "slow FileStore save");
+            }
+        }
         Ok(())
     }
 }
 
+#[cfg(feature = "fs")]
+impl Drop for FileStore {
+    fn drop(&mut self) {
+        if self.sync_policy == SyncPolicy::Always {
+            return;
+        }
+        // Drop ではエラーを返せないため、失敗はログに出すだけにする
+        let result = self.flush();
+        #[cfg(feature = "tracing")]
+        if let Err(error) = result {
+            tracing::warn!(path = %self.file_path, %error, "FileStore flush on drop failed");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = result;
+    }
+}
+
+#[cfg(feature = "fs")]
+impl fmt::Debug for FileStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let contents = self.lock_contents();
+        f.debug_struct("FileStore")
+            .field("path", &self.file_path)
+            .field("entries", &contents.data.len())
+            .field("approx_bytes", &approximate_bytes(&contents.data))
+            .field("save_count", &self.save_count)
+            .finish()
+    }
+}
+
+#[cfg(feature = "fs")]
+impl fmt::Display for FileStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let contents = self.lock_contents();
+        write!(f, "file store at {} ({} entries, {} bytes)", self.file_path, contents.data.len(), approximate_bytes(&contents.data))
+    }
+}
+
+#[cfg(feature = "fs")]
 impl KeyValueStore for FileStore {
     fn put(&mut self, key: String, value: String) -> Result<()> {
         if key.is_empty() {
             return Err(StoreError::InvalidKey);
         }
-        self.data.insert(key, value);
+        self.write_contents()?.data.insert(key, value);
         self.save()?;
         Ok(())
     }
@@ -152,24 +679,45 @@ impl KeyValueStore for FileStore {
         if key.is_empty() {
             return Err(StoreError::InvalidKey);
         }
-        Ok(self.data.get(key).cloned())
+        Ok(self.read_contents()?.data.get(key).cloned())
+    }
+
+    fn contains_key(&self, key: &str) -> Result<bool> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(self.read_contents()?.data.contains_key(key))
+    }
+
+    fn with_value<R>(&self, key: &str, f: impl FnOnce(&str) -> R) -> Result<Option<R>> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(self.read_contents()?.data.get(key).map(|value| f(value)))
+    }
+
+    fn scan_with(&mut self, start: &str, end: &str, f: impl FnMut(&str, &str) -> Result<()>) -> Result<()> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        scan_entries_with(&self.write_contents()?.data, start, end, f)
     }
 
     fn delete(&mut self, key: &str) -> Result<()> {
         if key.is_empty() {
             return Err(StoreError::InvalidKey);
         }
-        self.data.remove(key);
+        self.write_contents()?.data.remove(key);
         self.save()?;
         Ok(())
     }
 
     fn keys(&self) -> Result<Vec<String>> {
-        Ok(self.data.keys().cloned().collect())
+        Ok(sorted_keys(&self.read_contents()?.data))
     }
 
     fn clear(&mut self) -> Result<()> {
-        self.data.clear();
+        self.write_contents()?.data.clear();
         self.save()?;
         Ok(())
     }
@@ -179,11 +727,57 @@ impl KeyValueStore for FileStore {
             return Err(StoreError::InvalidKey);
         }
         let mut result = Vec::new();
-        for (key, value) in &self.data {
+        for (key, value) in &self.write_contents()?.data {
             if key.as_str() >= start && key.as_str() < end {
                 result.push((key.clone(), value.clone()));
             }
         }
         Ok(result)
     }
+
+    fn scan_keys(&mut self, start: &str, end: &str) -> Result<Vec<String>> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(self
+            .write_contents()?
+            .data
+            .keys()
+            .filter(|key| key.as_str() >= start && key.as_str() < end)
+            .cloned()
+            .collect())
+    }
+
+    fn delete_many(&mut self, keys: &[String]) -> Result<usize> {
+        if keys.iter().any(|key| key.is_empty()) {
+            return Err(StoreError::InvalidKey);
+        }
+        let contents = self.write_contents()?;
+        let mut deleted = 0;
+        for key in keys {
+            if contents.data.remove(key).is_some() {
+                deleted += 1;
+            }
+        }
+        // 削除があった場合のみ一度だけ書き出す
+        if deleted > 0 {
+            self.save()?;
+        }
+        Ok(deleted)
+    }
+
+    fn apply_batch(&mut self, puts: Vec<(String, String)>, deletes: &[String]) -> Result<()> {
+        if deletes.iter().any(|key| key.is_empty()) || puts.iter().any(|(key, _)| key.is_empty()) {
+            return Err(StoreError::InvalidKey);
+        }
+        let contents = self.write_contents()?;
+        for key in deletes {
+            contents.data.remove(key);
+        }
+        for (key, value) in puts {
+            contents.data.insert(key, value);
+        }
+        // まとめて一度だけ書き出す
+        self.save()
+    }
 }
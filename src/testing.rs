@@ -0,0 +1,528 @@
+//! テスト用ストアモジュール (`test-util` フィーチャー)
+//!
+//! `BoatRaceEngine<K>` を使うコードのテストで使えるストアを提供する。
+//! 何も保存しない `NullStore`、呼び出しを記録する `RecordingStore`、
+//! 指定した回の操作を失敗させる `FailingStore` の3つ
+
+use crate::{KeyValueStore, MemoryStore, Result, StoreError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// 書き込みを受け付けるが何も保存しないストア
+///
+/// 読み取りは常に `None` / 空を返す。書き込みの結果を読み返さないコードのテストや、
+/// エンジンの書き込み処理だけを動かしたい場合に使う
+///
+/// ```
+/// use norimaki_db::testing::NullStore;
+/// use norimaki_db::KeyValueStore;
+///
+/// let mut store = NullStore;
+/// store.put("Ukey".to_string(), "value".to_string())?;
+/// assert_eq!(store.get("Ukey")?, None);
+/// assert!(store.keys()?.is_empty());
+/// # Ok::<(), norimaki_db::StoreError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NullStore;
+
+impl KeyValueStore for NullStore {
+    fn put(&mut self, key: String, _value: String) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(None)
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(Vec::new())
+    }
+
+    fn delete_many(&mut self, keys: &[String]) -> Result<usize> {
+        if keys.iter().any(|key| key.is_empty()) {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(0)
+    }
+
+    fn apply_batch(&mut self, puts: Vec<(String, String)>, deletes: &[String]) -> Result<()> {
+        if deletes.iter().any(|key| key.is_empty()) || puts.iter().any(|(key, _)| key.is_empty()) {
+            return Err(StoreError::InvalidKey);
+        }
+        Ok(())
+    }
+}
+
+/// `RecordingStore` が記録するストアの呼び出し (引数つき)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreCall {
+    Put { key: String, value: String },
+    Get { key: String },
+    Delete { key: String },
+    Keys,
+    Clear,
+    Scan { start: String, end: String },
+    ScanKeys { start: String, end: String },
+    DeleteMany { keys: Vec<String> },
+    ApplyBatch { puts: Vec<(String, String)>, deletes: Vec<String> },
+}
+
+impl StoreCall {
+    /// この呼び出しで書き込まれるキー (`Put` と `ApplyBatch` の書き込み)
+    pub fn written_keys(&self) -> Vec<&str> {
+        match self {
+            StoreCall::Put { key, .. } => vec![key.as_str()],
+            StoreCall::ApplyBatch { puts, .. } => puts.iter().map(|(key, _)| key.as_str()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// この呼び出しで削除されるキー (`Delete` / `DeleteMany` と `ApplyBatch` の削除。`Clear` は含まない)
+    pub fn deleted_keys(&self) -> Vec<&str> {
+        match self {
+            StoreCall::Delete { key } => vec![key.as_str()],
+            StoreCall::DeleteMany { keys } | StoreCall::ApplyBatch { deletes: keys, .. } => {
+                keys.iter().map(String::as_str).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// 別のストアを包み、呼び出しを引数つきで順に記録するストア
+///
+/// 呼び出しはそのまま包んだストアに渡す (`scan_keys` / `delete_many` / `apply_batch` も
+/// 包んだストアの実装を呼び、1回の呼び出しとして記録する)。
+/// エンジンがストアに何をしたかをテストで確かめるために使う
+///
+/// ```
+/// use norimaki_db::testing::{RecordingStore, StoreCall};
+/// use norimaki_db::{classify_key, BoatRaceEngine, Grade, MemoryStore, MonthlySchedule, Namespace, RaceEvent};
+///
+/// let event = |venue_id, venue: &str, name: &str, start: &str| {
+///     RaceEvent::builder()
+///         .venue_id(venue_id)
+///         .venue_name(venue)
+///         .event_name(name)
+///         .grade(Grade::Ippan)
+///         .start_date(start)
+///         .duration_days(6)
+///         .build()
+/// };
+/// let schedule = MonthlySchedule::builder(202509)
+///     .event(event(4, "平和島", "トーキョー・ベイ・カップ", "2025-09-10")?)
+///     .event(event(1, "桐生", "群馬クレインサンダーズカップ", "2025-09-11")?)
+///     .event(event(24, "大村", "大村市長杯", "2025-09-20")?)
+///     .build()?;
+///
+/// let mut engine = BoatRaceEngine::new(RecordingStore::new(MemoryStore::new()));
+/// engine.put_monthly_schedule(&schedule)?;
+///
+/// // 書き込みは1回のバッチにまとまる
+/// let store = engine.store();
+/// assert_eq!(store.calls().iter().filter(|call| matches!(call, StoreCall::ApplyBatch { .. })).count(), 1);
+///
+/// // 日別インデックスや更新日時も同じバッチで書き込まれるため、月別スケジュールの名前空間で絞り込む:
+/// // 大会ごとに1件ずつ、ちょうど3件の書き込みで、削除はない
+/// let monthly = |keys: Vec<String>| keys.into_iter().filter(|key| classify_key(key) == Some(Namespace::Monthly)).count();
+/// assert_eq!(monthly(store.written_keys()), 3);
+/// assert_eq!(monthly(store.deleted_keys()), 0);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct RecordingStore<K: KeyValueStore> {
+    inner: K,
+    /// `get` / `keys` (`&self`) からも記録できるよう Mutex に入れる
+    calls: Mutex<Vec<StoreCall>>,
+}
+
+impl<K: KeyValueStore> RecordingStore<K> {
+    pub fn new(inner: K) -> Self {
+        Self {
+            inner,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 包んでいるストアを取得
+    pub fn inner(&self) -> &K {
+        &self.inner
+    }
+
+    /// 包んでいるストアを取り出す
+    pub fn into_inner(self) -> K {
+        self.inner
+    }
+
+    /// これまでの呼び出し (古い順)
+    pub fn calls(&self) -> Vec<StoreCall> {
+        self.lock_calls().clone()
+    }
+
+    /// これまでの呼び出しを取り出し、記録を空にする
+    ///
+    /// 準備段階の呼び出しを捨て、確かめたい操作の呼び出しだけを見たい場合に使う
+    pub fn take_calls(&mut self) -> Vec<StoreCall> {
+        std::mem::take(self.calls.get_mut().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// 書き込まれたキー (呼び出し順。`apply_batch` の書き込みも1件ずつ数える)
+    pub fn written_keys(&self) -> Vec<String> {
+        self.lock_calls()
+            .iter()
+            .flat_map(|call| call.written_keys().into_iter().map(str::to_string).collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// 削除されたキー (呼び出し順。`delete_many` / `apply_batch` の削除も1件ずつ数える)
+    pub fn deleted_keys(&self) -> Vec<String> {
+        self.lock_calls()
+            .iter()
+            .flat_map(|call| call.deleted_keys().into_iter().map(str::to_string).collect::<Vec<_>>())
+            .collect()
+    }
+
+    fn lock_calls(&self) -> std::sync::MutexGuard<'_, Vec<StoreCall>> {
+        self.calls.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn record(&self, call: StoreCall) {
+        self.lock_calls().push(call);
+    }
+}
+
+impl<K: KeyValueStore> KeyValueStore for RecordingStore<K> {
+    fn put(&mut self, key: String, value: String) -> Result<()> {
+        self.record(StoreCall::Put { key: key.clone(), value: value.clone() });
+        self.inner.put(key, value)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        self.record(StoreCall::Get { key: key.to_string() });
+        self.inner.get(key)
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        self.record(StoreCall::Delete { key: key.to_string() });
+        self.inner.delete(key)
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        self.record(StoreCall::Keys);
+        self.inner.keys()
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.record(StoreCall::Clear);
+        self.inner.clear()
+    }
+
+    fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+        self.record(StoreCall::Scan { start: start.to_string(), end: end.to_string() });
+        self.inner.scan(start, end)
+    }
+
+    fn scan_keys(&mut self, start: &str, end: &str) -> Result<Vec<String>> {
+        self.record(StoreCall::ScanKeys { start: start.to_string(), end: end.to_string() });
+        self.inner.scan_keys(start, end)
+    }
+
+    fn delete_many(&mut self, keys: &[String]) -> Result<usize> {
+        self.record(StoreCall::DeleteMany { keys: keys.to_vec() });
+        self.inner.delete_many(keys)
+    }
+
+    fn apply_batch(&mut self, puts: Vec<(String, String)>, deletes: &[String]) -> Result<()> {
+        self.record(StoreCall::ApplyBatch { puts: puts.clone(), deletes: deletes.to_vec() });
+        self.inner.apply_batch(puts, deletes)
+    }
+}
+
+/// 指定した回の操作を、指定したエラーで失敗させるストア
+///
+/// 操作 (トレイトのメソッド呼び出し) を1から数え、`fail_at` で指定した回だけ
+/// 包んだストアに渡さずにエラーを返す。それ以外の操作は包んだストアに渡す。
+/// `fail_put_at` を使うと、操作ではなく書き込みを1件ずつ数えて指定した回の書き込みを失敗させる。
+/// エラー処理の経路をテストするために使う
+///
+/// ```
+/// use norimaki_db::testing::FailingStore;
+/// use norimaki_db::{KeyValueStore, MemoryStore, StoreError};
+///
+/// let mut store = FailingStore::new(MemoryStore::new()).fail_at(2, StoreError::InvalidKey);
+/// store.put("Ua".to_string(), "1".to_string())?;
+/// assert!(matches!(store.put("Ub".to_string(), "2".to_string()), Err(StoreError::InvalidKey)));
+/// store.put("Uc".to_string(), "3".to_string())?;
+/// assert_eq!(store.inner().keys()?, vec!["Ua", "Uc"]);
+/// # Ok::<(), StoreError>(())
+/// ```
+#[derive(Debug)]
+pub struct FailingStore<K: KeyValueStore = MemoryStore> {
+    inner: K,
+    /// 失敗させる回 (1始まり) とそのときのエラー
+    failure: Option<(usize, StoreError)>,
+    /// 失敗させる書き込みの回 (1始まり) とそのときのエラー
+    put_failure: Option<(usize, StoreError)>,
+    /// これまでの操作回数 (`get` / `keys` (`&self`) からも数えられるようアトミックにする)
+    operations: AtomicUsize,
+    /// これまでの書き込み回数
+    puts: usize,
+}
+
+impl<K: KeyValueStore> FailingStore<K> {
+    /// 失敗させる回を指定しないストア (すべての操作を包んだストアに渡す)
+    pub fn new(inner: K) -> Self {
+        Self {
+            inner,
+            failure: None,
+            put_failure: None,
+            operations: AtomicUsize::new(0),
+            puts: 0,
+        }
+    }
+
+    /// 失敗させる操作を設定
+    ///
+    /// # Arguments
+    /// * `operation` - 失敗させる操作の回 (1始まり。これまでの操作も数える)
+    /// * `error` - その操作で返すエラー
+    pub fn fail_at(mut self, operation: usize, error: StoreError) -> Self {
+        self.failure = Some((operation, error));
+        self
+    }
+
+    /// 失敗させる書き込みを設定
+    ///
+    /// 設定すると `apply_batch` は包んだストアの実装を使わず、削除と書き込みを1件ずつ行う。
+    /// そのため、バッチの途中で失敗して一部だけ書き込まれたストアを再現できる
+    ///
+    /// # Arguments
+    /// * `put` - 失敗させる書き込みの回 (1始まり。`put` と `apply_batch` 内の書き込みを1件ずつ数える)
+    /// * `error` - その書き込みで返すエラー
+    pub fn fail_put_at(mut self, put: usize, error: StoreError) -> Self {
+        self.put_failure = Some((put, error));
+        self
+    }
+
+    /// これまでの書き込み回数 (失敗させた書き込みも含む)
+    pub fn puts(&self) -> usize {
+        self.puts
+    }
+
+    /// これまでの操作回数 (失敗させた操作も含む)
+    pub fn operations(&self) -> usize {
+        self.operations.load(Ordering::SeqCst)
+    }
+
+    /// 包んでいるストアを取得
+    pub fn inner(&self) -> &K {
+        &self.inner
+    }
+
+    /// 包んでいるストアを取り出す
+    pub fn into_inner(self) -> K {
+        self.inner
+    }
+
+    /// 操作を1回数え、失敗させる回ならエラーを返す
+    fn check(&self) -> Result<()> {
+        let operation = self.operations.fetch_add(1, Ordering::SeqCst) + 1;
+        match &self.failure {
+            Some((fail_at, error)) if *fail_at == operation => Err(error.clone()),
+            _ => Ok(()),
+        }
+    }
+
+    /// 書き込みを1回数え、失敗させる回ならエラーを返す
+    fn check_put(&mut self) -> Result<()> {
+        self.puts += 1;
+        match &self.put_failure {
+            Some((fail_at, error)) if *fail_at == self.puts => Err(error.clone()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<K: KeyValueStore> KeyValueStore for FailingStore<K> {
+    fn put(&mut self, key: String, value: String) -> Result<()> {
+        self.check()?;
+        self.check_put()?;
+        self.inner.put(key, value)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        self.check()?;
+        self.inner.get(key)
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        self.check()?;
+        self.inner.delete(key)
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        self.check()?;
+        self.inner.keys()
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.check()?;
+        self.inner.clear()
+    }
+
+    fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+        self.check()?;
+        self.inner.scan(start, end)
+    }
+
+    fn scan_keys(&mut self, start: &str, end: &str) -> Result<Vec<String>> {
+        self.check()?;
+        self.inner.scan_keys(start, end)
+    }
+
+    fn delete_many(&mut self, keys: &[String]) -> Result<usize> {
+        self.check()?;
+        self.inner.delete_many(keys)
+    }
+
+    fn apply_batch(&mut self, puts: Vec<(String, String)>, deletes: &[String]) -> Result<()> {
+        self.check()?;
+        if self.put_failure.is_none() {
+            self.puts += puts.len();
+            return self.inner.apply_batch(puts, deletes);
+        }
+        if deletes.iter().any(|key| key.is_empty()) || puts.iter().any(|(key, _)| key.is_empty()) {
+            return Err(StoreError::InvalidKey);
+        }
+        for key in deletes {
+            self.inner.delete(key)?;
+        }
+        for (key, value) in puts {
+            self.check_put()?;
+            self.inner.put(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoatRaceEngine, MonthlySchedule, RaceEvent};
+
+    fn sample_data() -> MonthlySchedule {
+        let event = |venue_id, venue: &str, name: &str, start: &str| {
+            RaceEvent::builder()
+                .venue_id(venue_id)
+                .venue_name(venue)
+                .event_name(name)
+                .grade(crate::Grade::Ippan)
+                .start_date(start)
+                .duration_days(6)
+                .build()
+                .unwrap()
+        };
+        MonthlySchedule::builder(202509)
+            .event(event(4, "平和島", "トーキョー・ベイ・カップ", "2025-09-10"))
+            .event(event(1, "桐生", "群馬クレインサンダーズカップ", "2025-09-11"))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_null_store() {
+        let mut engine = BoatRaceEngine::new(NullStore);
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert!(engine.get_monthly_schedule(202509).unwrap().events.is_empty());
+
+        let mut store = NullStore;
+        assert!(store.scan("A", "Z").unwrap().is_empty());
+        assert!(store.scan_keys("A", "Z").unwrap().is_empty());
+        assert_eq!(store.delete_many(&["Ua".to_string()]).unwrap(), 0);
+        assert!(matches!(store.put(String::new(), "x".to_string()), Err(StoreError::InvalidKey)));
+    }
+
+    #[test]
+    fn test_recording_store_records_calls_in_order() {
+        let mut store = RecordingStore::new(MemoryStore::new());
+        store.put("Ua".to_string(), "1".to_string()).unwrap();
+        assert_eq!(store.get("Ua").unwrap(), Some("1".to_string()));
+        assert_eq!(store.scan("U", "V").unwrap().len(), 1);
+        assert_eq!(store.scan_keys("U", "V").unwrap(), vec!["Ua"]);
+        store.apply_batch(vec![("Ub".to_string(), "2".to_string())], &["Ua".to_string()]).unwrap();
+        assert_eq!(store.delete_many(&["Ub".to_string(), "Uc".to_string()]).unwrap(), 1);
+        store.delete("Ud").unwrap();
+        store.keys().unwrap();
+        store.clear().unwrap();
+
+        assert_eq!(
+            store.calls(),
+            vec![
+                StoreCall::Put { key: "Ua".to_string(), value: "1".to_string() },
+                StoreCall::Get { key: "Ua".to_string() },
+                StoreCall::Scan { start: "U".to_string(), end: "V".to_string() },
+                StoreCall::ScanKeys { start: "U".to_string(), end: "V".to_string() },
+                StoreCall::ApplyBatch { puts: vec![("Ub".to_string(), "2".to_string())], deletes: vec!["Ua".to_string()] },
+                StoreCall::DeleteMany { keys: vec!["Ub".to_string(), "Uc".to_string()] },
+                StoreCall::Delete { key: "Ud".to_string() },
+                StoreCall::Keys,
+                StoreCall::Clear,
+            ]
+        );
+        assert_eq!(store.written_keys(), vec!["Ua", "Ub"]);
+        assert_eq!(store.deleted_keys(), vec!["Ua", "Ub", "Uc", "Ud"]);
+
+        assert_eq!(store.take_calls().len(), 9);
+        assert!(store.calls().is_empty());
+        assert!(store.into_inner().keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failing_store_fails_only_the_chosen_operation() {
+        let error = StoreError::ValidationError { field: "disk".to_string(), message: "full".to_string() };
+        let mut store = FailingStore::new(MemoryStore::new()).fail_at(3, error);
+        store.put("Ua".to_string(), "1".to_string()).unwrap();
+        assert_eq!(store.get("Ua").unwrap(), Some("1".to_string()));
+        match store.scan("U", "V") {
+            Err(StoreError::ValidationError { field, .. }) => assert_eq!(field, "disk"),
+            other => panic!("expected ValidationError, got {:?}", other),
+        }
+        assert_eq!(store.scan("U", "V").unwrap().len(), 1);
+        assert_eq!(store.operations(), 4);
+
+        // 書き込み (最後の操作) を失敗させると、エンジンはエラーを返し何も書き込まれない
+        let mut probe = BoatRaceEngine::new(FailingStore::new(MemoryStore::new()));
+        probe.put_monthly_schedule(&sample_data()).unwrap();
+        let operations = probe.store().operations();
+        let mut engine = BoatRaceEngine::new(FailingStore::new(MemoryStore::new()).fail_at(operations, StoreError::InvalidKey));
+        assert!(matches!(engine.put_monthly_schedule(&sample_data()), Err(StoreError::InvalidKey)));
+        assert!(engine.store().inner().keys().unwrap().is_empty());
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 2);
+    }
+}
@@ -0,0 +1,54 @@
+//! 表示幅処理モジュール
+//!
+//! 端末に表を出力する際の桁揃えに使う表示幅の計算を提供する。
+//! 東アジアの全角文字 (漢字・かな・全角英数など) を2桁、それ以外を1桁として数える簡易的な実装で、
+//! 結合文字や曖昧幅の文字 (①、○ など) は1桁として扱うため、端末によってはずれることがある
+
+/// 全角として表示される文字か (Unicode の East Asian Width が W / F の主な範囲)
+fn is_wide(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x115f
+            | 0x2e80..=0x303e
+            | 0x3041..=0x33ff
+            | 0x3400..=0x4dbf
+            | 0x4e00..=0x9fff
+            | 0xa000..=0xa4cf
+            | 0xac00..=0xd7a3
+            | 0xf900..=0xfaff
+            | 0xfe30..=0xfe4f
+            | 0xff00..=0xff60
+            | 0xffe0..=0xffe6
+            | 0x1f300..=0x1f64f
+            | 0x1f900..=0x1f9ff
+            | 0x20000..=0x3fffd
+    )
+}
+
+/// 文字列の表示幅 (桁数)
+pub(crate) fn display_width(s: &str) -> usize {
+    s.chars().map(|c| if is_wide(c) { 2 } else { 1 }).sum()
+}
+
+/// 表示幅が `width` になるよう末尾を空白で埋める (既に超えている場合はそのまま)
+pub(crate) fn pad(s: &str, width: usize) -> String {
+    format!("{}{}", s, " ".repeat(width.saturating_sub(display_width(s))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("G1"), 2);
+        assert_eq!(display_width("平和島"), 6);
+        assert_eq!(display_width("一般"), 4);
+        assert_eq!(display_width("トーキョー・ベイ・カップ"), 24);
+        assert_eq!(display_width("ﾄｰｷｮｰ"), 5);
+        assert_eq!(display_width("第５３回"), 8);
+        assert_eq!(display_width("2025-09-10〜09-16"), 17);
+        assert_eq!(pad("桐生", 6), "桐生  ");
+        assert_eq!(pad("住之江", 4), "住之江");
+    }
+}
@@ -1,9 +1,57 @@
-/// 構造体値処理モジュール
-/// 
-/// bincodeを使用した型安全なシリアライズ/デシリアライズ機能を提供
+//! 構造体値処理モジュール
+//! 
+//! bincodeを使用した型安全なシリアライズ/デシリアライズ機能を提供
 
 use crate::{Result, StoreError};
-use serde::{Deserialize, Serialize};
+use bincode::Options;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// 値のバイナリ形式のバージョン
+///
+/// `bincode_options` の設定や、保存している型のフィールド構成を変えると既存のデータベースが読めなくなる。
+/// 形式を変える場合はこの値を上げ、旧形式を読む処理を残すこと
+/// (testdata/golden の値が読めなくなった場合は互換性が失われている)
+///
+/// - 1: 接頭辞なしの bincode + Base64
+/// - 2: `BINCODE_V2_PREFIX` 付き。`RaceEvent` に `notes` を追加
+///
+/// bincode はフィールドを位置で読むため、型にフィールドを追加するとレイアウトが変わる。
+/// 旧レイアウトを持つ型は `Deserialize` 実装で `decoding_format_version` を見て旧形式を読む
+pub const FORMAT_VERSION: u32 = 2;
+
+/// 形式バージョン 2 の bincode 値の接頭辞 (Base64 に現れない ':' を含む)
+pub const BINCODE_V2_PREFIX: &str = "b2:";
+
+thread_local! {
+    static DECODING_VERSION: std::cell::Cell<u32> = const { std::cell::Cell::new(FORMAT_VERSION) };
+}
+
+/// デコード中の bincode 値の形式バージョン
+///
+/// 接頭辞のない旧形式の値を読んでいる間だけ 1 になる。JSON などの自己記述的な形式では参照しない
+pub(crate) fn decoding_format_version() -> u32 {
+    DECODING_VERSION.with(|version| version.get())
+}
+
+/// 指定した形式バージョンのレイアウトで bincode の値を読む
+fn deserialize_version<T: for<'de> Deserialize<'de>>(data: &[u8], version: u32) -> Result<T> {
+    let previous = DECODING_VERSION.with(|current| current.replace(version));
+    let result = deserialize(data);
+    DECODING_VERSION.with(|current| current.set(previous));
+    result
+}
+
+/// 形式バージョン 1 の bincode 設定 (固定長整数・リトルエンディアン・サイズ上限なし)
+///
+/// bincode 1 の `bincode::serialize` と同じ設定を明示的に固定したもの。
+/// bincode の既定値が変わっても保存形式が変わらないよう、必ずこの設定を経由する
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_little_endian()
+        .with_no_limit()
+        .allow_trailing_bytes()
+}
 
 /// 任意の構造体をバイナリ形式でシリアライズ
 /// 
@@ -13,10 +61,10 @@ use serde::{Deserialize, Serialize};
 /// # Returns
 /// バイナリデータ
 pub fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>> {
-    bincode::serialize(value).map_err(|e| StoreError::SerializationError(format!("Serialize error: {}", e)))
+    bincode_options().serialize(value).map_err(StoreError::serialization)
 }
 
-/// バイナリデータから構造体にデシリアライズ
+/// バイナリデータから構造体にデシリアライズ (現在の形式バージョンのレイアウト)
 /// 
 /// # Arguments
 /// * `data` - バイナリデータ
@@ -24,7 +72,7 @@ pub fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>> {
 /// # Returns
 /// デシリアライズされた構造体
 pub fn deserialize<T: for<'de> Deserialize<'de>>(data: &[u8]) -> Result<T> {
-    bincode::deserialize(data).map_err(|e| StoreError::SerializationError(format!("Deserialize error: {}", e)))
+    bincode_options().deserialize(data).map_err(StoreError::serialization)
 }
 
 /// 構造体をKeyValueStoreに格納するためのString形式に変換
@@ -33,28 +81,443 @@ pub fn deserialize<T: for<'de> Deserialize<'de>>(data: &[u8]) -> Result<T> {
 /// * `value` - シリアライズする構造体
 /// 
 /// # Returns
-/// Base64エンコードされた文字列
+/// `BINCODE_V2_PREFIX` に Base64 エンコードしたバイナリを続けた文字列
 pub fn serialize_to_string<T: Serialize>(value: &T) -> Result<String> {
-    use base64::{Engine as _, engine::general_purpose};
     let binary = serialize(value)?;
-    Ok(general_purpose::STANDARD.encode(binary))
+    Ok(prefixed_base64(BINCODE_V2_PREFIX, &binary))
+}
+
+/// 接頭辞に Base64 エンコードしたバイナリを続けた文字列を作る
+///
+/// 最終的な長さを先に計算して一度だけ確保し、中間の文字列を作らない
+fn prefixed_base64(prefix: &str, binary: &[u8]) -> String {
+    use base64::{Engine as _, engine::general_purpose};
+    let encoded_len = base64::encoded_len(binary.len(), true).unwrap_or(0);
+    let mut output = String::with_capacity(prefix.len() + encoded_len);
+    output.push_str(prefix);
+    general_purpose::STANDARD.encode_string(binary, &mut output);
+    output
+}
+
+/// MessagePack 値の接頭辞 (Base64 にも JSON にも現れないため形式の判別に使う)
+pub const MSGPACK_PREFIX: &str = "mp:";
+
+/// 構造体を MessagePack にシリアライズ (`rmp` フィーチャー)
+///
+/// 構造体はフィールド名をキーとするマップになり、Python などの msgpack ライブラリでそのまま読める
+///
+/// # Arguments
+/// * `value` - シリアライズする構造体
+///
+/// # Returns
+/// MessagePack のバイト列
+#[cfg(feature = "rmp")]
+pub fn serialize_msgpack<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    // human readable として扱い、JSON と同じく未設定の追加フィールドを書かない
+    let mut output = Vec::new();
+    let mut serializer = rmp_serde::Serializer::new(&mut output).with_struct_map().with_human_readable();
+    value.serialize(&mut serializer).map_err(StoreError::serialization)?;
+    Ok(output)
+}
+
+/// MessagePack から構造体にデシリアライズ (`rmp` フィーチャー)
+///
+/// # Arguments
+/// * `data` - MessagePack のバイト列
+///
+/// # Returns
+/// デシリアライズされた構造体
+#[cfg(feature = "rmp")]
+pub fn deserialize_msgpack<T: DeserializeOwned>(data: &[u8]) -> Result<T> {
+    let mut deserializer = rmp_serde::Deserializer::from_read_ref(data).with_human_readable();
+    T::deserialize(&mut deserializer).map_err(StoreError::serialization)
+}
+
+/// 圧縮された値の接頭辞
+pub const COMPRESSED_PREFIX: &str = "~";
+
+/// 圧縮前のバイト列の形式 (圧縮した値の2バイト目): そのまま格納する文字列 (JSON など)
+#[cfg(feature = "compression")]
+const PAYLOAD_TEXT: u8 = 0;
+/// 圧縮前のバイト列の形式: 形式バージョン 2 の bincode
+#[cfg(feature = "compression")]
+const PAYLOAD_BINCODE: u8 = 1;
+/// 圧縮前のバイト列の形式: MessagePack
+#[cfg(feature = "compression")]
+const PAYLOAD_MSGPACK: u8 = 2;
+
+/// 圧縮方式 (`compression` フィーチャー)
+#[cfg(feature = "compression")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    /// deflate (RFC 1951)。繰り返しの多い値に有効
+    #[default]
+    Deflate,
+}
+
+#[cfg(feature = "compression")]
+impl CompressionAlgorithm {
+    fn marker(self) -> u8 {
+        match self {
+            CompressionAlgorithm::Deflate => 1,
+        }
+    }
+}
+
+/// 大きな値の圧縮設定 (`BoatRaceEngine::with_compression`。`compression` フィーチャー)
+#[cfg(feature = "compression")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Compression {
+    pub algorithm: CompressionAlgorithm,
+    /// シリアライズしたバイト数 (Base64 にする前) がこれを超える値だけを圧縮する
+    pub threshold: usize,
+}
+
+#[cfg(feature = "compression")]
+impl Default for Compression {
+    fn default() -> Self {
+        Self {
+            algorithm: CompressionAlgorithm::default(),
+            threshold: 1024,
+        }
+    }
+}
+
+/// シリアライズしたバイト列を圧縮した格納用文字列にする
+///
+/// 圧縮した値は `COMPRESSED_PREFIX` + Base64([方式 1 バイト][形式 1 バイト][元の長さ u32 LE][圧縮データ]) となる
+#[cfg(feature = "compression")]
+fn compress_payload(kind: u8, bytes: &[u8], compression: &Compression) -> Result<String> {
+    let size = u32::try_from(bytes.len())
+        .map_err(|_| StoreError::serialization(format!("value of {} bytes is too large to compress", bytes.len())))?;
+    let mut payload = vec![compression.algorithm.marker(), kind];
+    payload.extend_from_slice(&size.to_le_bytes());
+    payload.extend(crate::compress::compress(bytes));
+    Ok(prefixed_base64(COMPRESSED_PREFIX, &payload))
+}
+
+/// `COMPRESSED_PREFIX` を除いた圧縮値を展開し、圧縮前の形式とバイト列を返す
+#[cfg(feature = "compression")]
+fn decompress_payload(data: &str) -> Result<(u8, Vec<u8>)> {
+    use base64::{Engine as _, engine::general_purpose};
+    let payload = general_purpose::STANDARD.decode(data)
+        .map_err(StoreError::serialization)?;
+    if payload.len() < 6 {
+        return Err(StoreError::serialization("truncated compressed value"));
+    }
+    let size = u32::from_le_bytes(payload[2..6].try_into().expect("4 bytes")) as usize;
+    let decompressed = match payload[0] {
+        1 => crate::compress::decompress(&payload[6..], size)?,
+        marker => return Err(StoreError::serialization(format!("unknown compression marker {}", marker))),
+    };
+    Ok((payload[1], decompressed))
+}
+
+/// 圧縮しない場合の格納用文字列の長さ
+#[cfg(feature = "compression")]
+fn plain_payload_len(kind: u8, size: usize) -> usize {
+    let base64_len = || base64::encoded_len(size, true).unwrap_or(usize::MAX);
+    match kind {
+        PAYLOAD_BINCODE => BINCODE_V2_PREFIX.len().saturating_add(base64_len()),
+        PAYLOAD_MSGPACK => MSGPACK_PREFIX.len().saturating_add(base64_len()),
+        _ => size,
+    }
+}
+
+/// 圧縮しない場合の格納用文字列 (`ValueCodec::encode` と同じ文字列)
+#[cfg(feature = "compression")]
+fn plain_payload(kind: u8, bytes: Vec<u8>) -> Result<String> {
+    match kind {
+        PAYLOAD_BINCODE => Ok(prefixed_base64(BINCODE_V2_PREFIX, &bytes)),
+        PAYLOAD_MSGPACK => Ok(prefixed_base64(MSGPACK_PREFIX, &bytes)),
+        _ => String::from_utf8(bytes).map_err(StoreError::serialization),
+    }
+}
+
+/// 展開したバイト列を圧縮前の形式に従ってデシリアライズ
+#[cfg(feature = "compression")]
+fn deserialize_payload<T: for<'de> Deserialize<'de>>(kind: u8, bytes: &[u8]) -> Result<T> {
+    match kind {
+        PAYLOAD_TEXT => {
+            let text = std::str::from_utf8(bytes).map_err(StoreError::serialization)?;
+            if text.starts_with(COMPRESSED_PREFIX) || split_type_tag(text).0.is_some() {
+                return Err(StoreError::serialization("nested compressed value"));
+            }
+            deserialize_plain(text)
+        }
+        PAYLOAD_BINCODE => deserialize_version(bytes, 2),
+        PAYLOAD_MSGPACK => deserialize_msgpack_payload(bytes),
+        kind => Err(StoreError::serialization(format!("unknown compressed payload kind {}", kind))),
+    }
+}
+
+/// 構造体を格納用の文字列に変換し、閾値を超える場合は圧縮する (`compression` フィーチャー)
+///
+/// # Arguments
+/// * `value` - シリアライズする構造体
+/// * `compression` - 圧縮設定
+///
+/// # Returns
+/// Base64エンコードされた (圧縮された) 文字列
+#[cfg(feature = "compression")]
+pub fn serialize_to_string_compressed<T: Serialize>(value: &T, compression: &Compression) -> Result<String> {
+    Codec::BincodeBase64.encode_compressed(value, compression)
 }
 
 /// String形式から構造体にデシリアライズ
+///
+/// 形式は自動判別する。'{' '[' '"' で始まる値は JSON (Base64 には現れない文字) として、
+/// `MSGPACK_PREFIX` で始まる値は MessagePack + Base64 として (`rmp` フィーチャーが必要)、
+/// `TYPE_TAG_PREFIX` で始まる値は型名を除いて読み (型名は確かめない)、
+/// `COMPRESSED_PREFIX` で始まる値は展開してから読み、`BINCODE_V2_PREFIX` で始まる値は形式バージョン 2 の
+/// bincode + Base64 として、それ以外は形式バージョン 1 の bincode + Base64 として読み、
+/// 失敗した場合は JSON の数値・真偽値として読む
+/// (型名と圧縮は外側の1層ずつだけで、入れ子になった値は `StoreError::SerializationError`)
 /// 
 /// # Arguments
-/// * `data` - Base64エンコードされた文字列
+/// * `data` - Base64エンコードされた文字列、または JSON
 /// 
 /// # Returns
 /// デシリアライズされた構造体
 pub fn deserialize_from_string<T: for<'de> Deserialize<'de>>(data: &str) -> Result<T> {
+    deserialize_untagged(split_type_tag(data).1)
+}
+
+/// 型名を除いた値をデシリアライズ
+///
+/// 型名と圧縮はそれぞれ外側の1層だけを受け付け、入れ子になった値は壊れているものとして拒否する
+/// (細工された値で再帰が深くならないよう、展開は繰り返さない)
+fn deserialize_untagged<T: for<'de> Deserialize<'de>>(data: &str) -> Result<T> {
+    if split_type_tag(data).0.is_some() {
+        return Err(StoreError::serialization("nested type tag"));
+    }
+    let Some(compressed) = data.strip_prefix(COMPRESSED_PREFIX) else {
+        return deserialize_plain(data);
+    };
+    #[cfg(feature = "compression")]
+    {
+        let (kind, decompressed) = decompress_payload(compressed)?;
+        deserialize_payload(kind, &decompressed)
+    }
+    #[cfg(not(feature = "compression"))]
+    {
+        Err(StoreError::serialization(format!(
+            "{}-character compressed value requires the compression feature",
+            compressed.len()
+        )))
+    }
+}
+
+/// 型名も圧縮もない値を、形式を自動判別してデシリアライズ
+fn deserialize_plain<T: for<'de> Deserialize<'de>>(data: &str) -> Result<T> {
     use base64::{Engine as _, engine::general_purpose};
-    let binary = general_purpose::STANDARD.decode(data)
-        .map_err(|e| StoreError::SerializationError(format!("Base64 decode error: {}", e)))?;
-    deserialize(&binary)
+    if data.starts_with(['{', '[', '"']) {
+        return Ok(serde_json::from_str(data)?);
+    }
+    if let Some(encoded) = data.strip_prefix(MSGPACK_PREFIX) {
+        let binary = general_purpose::STANDARD.decode(encoded)
+            .map_err(StoreError::serialization)?;
+        return deserialize_msgpack_payload(&binary);
+    }
+    if let Some(encoded) = data.strip_prefix(BINCODE_V2_PREFIX) {
+        let binary = general_purpose::STANDARD.decode(encoded)
+            .map_err(StoreError::serialization)?;
+        return deserialize_version(&binary, 2);
+    }
+    let decoded = general_purpose::STANDARD.decode(data)
+        .map_err(StoreError::serialization)
+        .and_then(|binary| deserialize_version(&binary, 1));
+    decoded.or_else(|error| serde_json::from_str(data).map_err(|_| error))
+}
+
+/// MessagePack のバイト列をデシリアライズ (`rmp` フィーチャーがなければエラー)
+fn deserialize_msgpack_payload<T: for<'de> Deserialize<'de>>(binary: &[u8]) -> Result<T> {
+    #[cfg(feature = "rmp")]
+    {
+        deserialize_msgpack(binary)
+    }
+    #[cfg(not(feature = "rmp"))]
+    {
+        Err(StoreError::serialization(format!(
+            "{}-byte MessagePack value requires the rmp feature",
+            binary.len()
+        )))
+    }
+}
+
+/// 型名を記録した値の接頭辞 (`t:型名:` に続けてエンコード済みの値を置く)
+pub const TYPE_TAG_PREFIX: &str = "t:";
+
+/// エンコード済みの値の先頭に型名を付ける
+///
+/// 型名を付けた値も `deserialize_from_string` でそのまま読める。
+/// `deserialize_tagged` で読むと、別の型名が付いた値を型の取り違えとして拒否する
+///
+/// # Arguments
+/// * `type_name` - 型名 (空でなく ':' を含まないこと)
+/// * `encoded` - エンコード済みの値
+///
+/// # Returns
+/// 型名付きの値。型名が不正な場合は `StoreError::InvalidValue`
+pub fn tag_value(type_name: &str, encoded: &str) -> Result<String> {
+    if type_name.is_empty() || type_name.contains(':') {
+        return Err(StoreError::invalid_value(format!("invalid type tag {:?}", type_name)));
+    }
+    let mut tagged = String::with_capacity(TYPE_TAG_PREFIX.len() + type_name.len() + 1 + encoded.len());
+    tagged.push_str(TYPE_TAG_PREFIX);
+    tagged.push_str(type_name);
+    tagged.push(':');
+    tagged.push_str(encoded);
+    Ok(tagged)
+}
+
+/// 値から型名を取り出す
+///
+/// # Returns
+/// (型名, 型名を除いた値)。型名のない値は (None, 値そのもの)
+pub fn split_type_tag(data: &str) -> (Option<&str>, &str) {
+    data.strip_prefix(TYPE_TAG_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+        .map_or((None, data), |(type_name, encoded)| (Some(type_name), encoded))
+}
+
+/// 型名を確かめてから値をデシリアライズ
+///
+/// 型名のない値 (型名を記録する前に書いた値など) はそのまま読む
+///
+/// # Arguments
+/// * `data` - ストアの値
+/// * `expected` - 期待する型名
+///
+/// # Returns
+/// デシリアライズされた値。別の型名が付いている場合は
+/// `expected type 期待する型名, found 記録された型名` の `StoreError::SerializationError`
+pub fn deserialize_tagged<T: DeserializeOwned>(data: &str, expected: &str) -> Result<T> {
+    match split_type_tag(data) {
+        (Some(found), _) if found != expected => Err(StoreError::serialization(format!(
+            "expected type {}, found {}",
+            expected, found
+        ))),
+        (_, encoded) => deserialize_untagged(encoded),
+    }
+}
+
+/// 値をストアに格納する文字列形式
+///
+/// 読み込みはどの実装でも形式を自動判別する (`deserialize_from_string`) ため、
+/// 形式を切り替えても既存の値はそのまま読める
+pub trait ValueCodec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<String>;
+    fn decode<T: DeserializeOwned>(&self, data: &str) -> Result<T>;
+}
+
+/// bincode + Base64 (既定。コンパクト)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BincodeBase64Codec;
+
+impl ValueCodec for BincodeBase64Codec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<String> {
+        serialize_to_string(value)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, data: &str) -> Result<T> {
+        deserialize_from_string(data)
+    }
+}
+
+/// JSON (FileStore のファイルを人が読んだり手で修正したりできる)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonCodec;
+
+impl ValueCodec for JsonCodec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<String> {
+        Ok(serde_json::to_string(value)?)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, data: &str) -> Result<T> {
+        deserialize_from_string(data)
+    }
+}
+
+/// `MSGPACK_PREFIX` + MessagePack + Base64 (`rmp` フィーチャー。他言語から読める)
+///
+/// 他言語では接頭辞を取り除いて Base64 デコードした後、msgpack としてデコードする
+#[cfg(feature = "rmp")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessagePackCodec;
+
+#[cfg(feature = "rmp")]
+impl ValueCodec for MessagePackCodec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<String> {
+        let binary = serialize_msgpack(value)?;
+        Ok(prefixed_base64(MSGPACK_PREFIX, &binary))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, data: &str) -> Result<T> {
+        deserialize_from_string(data)
+    }
+}
+
+/// エンジンで選択できる値の形式 (`BoatRaceEngine::with_codec`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Codec {
+    /// `BincodeBase64Codec`
+    #[default]
+    BincodeBase64,
+    /// `JsonCodec`
+    Json,
+    /// `MessagePackCodec`
+    #[cfg(feature = "rmp")]
+    MessagePack,
+}
+
+impl Codec {
+    /// 値をシリアライズしたバイト列 (Base64 にする前) と、その形式
+    #[cfg(feature = "compression")]
+    fn serialize_payload<T: Serialize>(&self, value: &T) -> Result<(u8, Vec<u8>)> {
+        match self {
+            Codec::BincodeBase64 => Ok((PAYLOAD_BINCODE, serialize(value)?)),
+            Codec::Json => Ok((PAYLOAD_TEXT, serde_json::to_vec(value)?)),
+            #[cfg(feature = "rmp")]
+            Codec::MessagePack => Ok((PAYLOAD_MSGPACK, serialize_msgpack(value)?)),
+        }
+    }
+
+    /// 値をエンコードし、シリアライズしたバイト列が閾値を超える場合は圧縮する (`compression` フィーチャー)
+    ///
+    /// 圧縮は Base64 にする前のバイト列に対して行う。
+    /// 閾値以下の値や、圧縮しても短くならない値は `encode` と同じ文字列になる
+    #[cfg(feature = "compression")]
+    pub fn encode_compressed<T: Serialize>(&self, value: &T, compression: &Compression) -> Result<String> {
+        let (kind, bytes) = self.serialize_payload(value)?;
+        if bytes.len() > compression.threshold {
+            let compressed = compress_payload(kind, &bytes, compression)?;
+            if compressed.len() < plain_payload_len(kind, bytes.len()) {
+                return Ok(compressed);
+            }
+        }
+        plain_payload(kind, bytes)
+    }
+}
+
+impl ValueCodec for Codec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<String> {
+        match self {
+            Codec::BincodeBase64 => BincodeBase64Codec.encode(value),
+            Codec::Json => JsonCodec.encode(value),
+            #[cfg(feature = "rmp")]
+            Codec::MessagePack => MessagePackCodec.encode(value),
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(&self, data: &str) -> Result<T> {
+        deserialize_from_string(data)
+    }
 }
 
 /// 構造体の大きさを効率的に計算
+///
+/// バイト列は作らず、シリアライズ後の大きさだけを数える
 /// 
 /// # Arguments
 /// * `value` - 計算対象の構造体
@@ -62,14 +525,69 @@ pub fn deserialize_from_string<T: for<'de> Deserialize<'de>>(data: &str) -> Resu
 /// # Returns
 /// シリアライズ後のバイト数
 pub fn calculate_size<T: Serialize>(value: &T) -> Result<usize> {
-    let binary = serialize(value)?;
-    Ok(binary.len())
+    let size = bincode_options().serialized_size(value).map_err(StoreError::serialization)?;
+    usize::try_from(size).map_err(StoreError::serialization)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{MonthlySchedule, RaceEvent};
+    use crate::{Grade, MonthlySchedule, RaceEvent};
+
+    #[test]
+    fn test_type_tag() {
+        let encoded = serialize_to_string(&42u32).unwrap();
+        let tagged = tag_value("Count", &encoded).unwrap();
+        assert_eq!(tagged, format!("t:Count:{}", encoded));
+        assert_eq!(split_type_tag(&tagged), (Some("Count"), encoded.as_str()));
+        assert_eq!(split_type_tag(&encoded), (None, encoded.as_str()));
+
+        assert_eq!(deserialize_from_string::<u32>(&tagged).unwrap(), 42);
+        assert_eq!(deserialize_tagged::<u32>(&tagged, "Count").unwrap(), 42);
+        assert_eq!(deserialize_tagged::<u32>(&encoded, "Count").unwrap(), 42);
+        let error = deserialize_tagged::<u32>(&tagged, "Total").unwrap_err();
+        assert!(error.to_string().contains("expected type Total, found Count"), "{}", error);
+
+        // 圧縮した値や JSON にも型名を付けられる
+        let json = tag_value("Count", "42").unwrap();
+        assert_eq!(deserialize_tagged::<u32>(&json, "Count").unwrap(), 42);
+        #[cfg(feature = "compression")]
+        {
+            let compressed = serialize_to_string_compressed(&"x".repeat(2000), &Compression::default()).unwrap();
+            assert!(compressed.starts_with(COMPRESSED_PREFIX));
+            let tagged = tag_value("Text", &compressed).unwrap();
+            assert_eq!(deserialize_tagged::<String>(&tagged, "Text").unwrap(), "x".repeat(2000));
+        }
+
+        assert!(matches!(tag_value("", &encoded), Err(StoreError::InvalidValue { .. })));
+        assert!(matches!(tag_value("a:b", &encoded), Err(StoreError::InvalidValue { .. })));
+    }
+
+    #[test]
+    fn test_nested_envelopes_rejected() {
+        let encoded = serialize_to_string(&42u32).unwrap();
+
+        // 深く入れ子にした型名でもスタックを使い切らずにエラーを返す
+        let nested = format!("{}{}", "t:a:".repeat(200_000), encoded);
+        assert!(matches!(deserialize_from_string::<u32>(&nested), Err(StoreError::SerializationError { .. })));
+        assert!(matches!(deserialize_tagged::<u32>(&nested, "a"), Err(StoreError::SerializationError { .. })));
+        let twice = tag_value("a", &tag_value("a", &encoded).unwrap()).unwrap();
+        assert!(deserialize_from_string::<u32>(&twice).is_err());
+
+        // 圧縮の入れ子も1層までしか展開しない (閾値を見ずに直接組み立てる)
+        #[cfg(feature = "compression")]
+        {
+            let compress = |inner: &str| compress_payload(PAYLOAD_TEXT, inner.as_bytes(), &Compression::default()).unwrap();
+            assert_eq!(deserialize_from_string::<u32>(&compress(&encoded)).unwrap(), 42);
+            let mut nested = encoded.clone();
+            for _ in 0..3 {
+                nested = compress(&nested);
+            }
+            assert!(matches!(deserialize_from_string::<u32>(&nested), Err(StoreError::SerializationError { .. })));
+            let tagged_inside = compress(&tag_value("a", &encoded).unwrap());
+            assert!(deserialize_from_string::<u32>(&tagged_inside).is_err());
+        }
+    }
 
     #[test]
     fn test_serialize_deserialize() {
@@ -77,9 +595,10 @@ mod tests {
             venue_id: 4,
             venue_name: "平和島".to_string(),
             event_name: "トーキョー・ベイ・カップ".to_string(),
-            grade: "G1".to_string(),
+            grade: Grade::G1,
             start_date: "2025-09-10".to_string(),
             duration_days: 7,
+            notes: None,
         };
 
         // シリアライズ
@@ -102,9 +621,10 @@ mod tests {
             venue_id: 1,
             venue_name: "桐生".to_string(),
             event_name: "群馬クレインサンダーズカップ".to_string(),
-            grade: "一般".to_string(),
+            grade: Grade::Ippan,
             start_date: "2025-09-11".to_string(),
             duration_days: 6,
+            notes: None,
         };
 
         // String形式でシリアライズ
@@ -114,7 +634,7 @@ mod tests {
         // String形式からデシリアライズ
         let restored: RaceEvent = deserialize_from_string(&encoded).unwrap();
         assert_eq!(restored.venue_name, "桐生");
-        assert_eq!(restored.grade, "一般");
+        assert_eq!(restored.grade, Grade::Ippan);
     }
 
     #[test]
@@ -127,17 +647,19 @@ mod tests {
                     venue_id: 1,
                     venue_name: "桐生".to_string(),
                     event_name: "群馬クレインサンダーズカップ".to_string(),
-                    grade: "一般".to_string(),
+                    grade: Grade::Ippan,
                     start_date: "2025-09-11".to_string(),
                     duration_days: 6,
+                    notes: None,
                 },
                 RaceEvent {
                     venue_id: 4,
                     venue_name: "平和島".to_string(),
                     event_name: "トーキョー・ベイ・カップ".to_string(),
-                    grade: "G1".to_string(),
+                    grade: Grade::G1,
                     start_date: "2025-09-10".to_string(),
                     duration_days: 7,
+                    notes: None,
                 },
             ],
         };
@@ -149,7 +671,7 @@ mod tests {
         assert_eq!(restored.year_month, schedule.year_month);
         assert_eq!(restored.events.len(), schedule.events.len());
         assert_eq!(restored.events[0].venue_name, schedule.events[0].venue_name);
-        assert_eq!(restored.events[1].grade, "G1");
+        assert_eq!(restored.events[1].grade, Grade::G1);
     }
 
     #[test]
@@ -158,9 +680,10 @@ mod tests {
             venue_id: 4,
             venue_name: "平和島".to_string(),
             event_name: "トーキョー・ベイ・カップ".to_string(),
-            grade: "G1".to_string(),
+            grade: Grade::G1,
             start_date: "2025-09-10".to_string(),
             duration_days: 7,
+            notes: None,
         };
 
         let size = calculate_size(&event).unwrap();
@@ -182,4 +705,246 @@ mod tests {
         let result: Result<RaceEvent> = deserialize(&invalid_binary);
         assert!(result.is_err());
     }
+
+    #[cfg(feature = "rmp")]
+    #[test]
+    fn test_msgpack_golden_bytes() {
+        let event = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "Cup".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-09-10".to_string(),
+            duration_days: 7,
+            notes: None,
+        };
+        // 他言語のデコーダーとの互換性を保つため、バイト列を固定する
+        let mut expected = vec![0x86];
+        expected.extend(b"\xa8venue_id\x04");
+        expected.extend(b"\xaavenue_name\xa9");
+        expected.extend("平和島".as_bytes());
+        expected.extend(b"\xaaevent_name\xa3Cup");
+        expected.extend(b"\xa5grade\xa2G1");
+        expected.extend(b"\xaastart_date\xaa2025-09-10");
+        expected.extend(b"\xadduration_days\x07");
+        assert_eq!(serialize_msgpack(&event).unwrap(), expected);
+        assert_eq!(deserialize_msgpack::<RaceEvent>(&expected).unwrap(), event);
+
+        let encoded = MessagePackCodec.encode(&event).unwrap();
+        assert!(encoded.starts_with(MSGPACK_PREFIX));
+        assert_eq!(deserialize_from_string::<RaceEvent>(&encoded).unwrap(), event);
+        assert_eq!(Codec::MessagePack.decode::<RaceEvent>(&encoded).unwrap(), event);
+        assert_eq!(Codec::BincodeBase64.decode::<RaceEvent>(&encoded).unwrap(), event);
+    }
+
+    #[cfg(not(feature = "rmp"))]
+    #[test]
+    fn test_msgpack_value_without_feature() {
+        let result: Result<RaceEvent> = deserialize_from_string("mp:gA==");
+        assert!(matches!(result, Err(error @ StoreError::SerializationError { .. }) if error.to_string().contains("rmp")));
+    }
+
+    #[test]
+    fn test_golden_values() {
+        // 形式バージョン 1 で保存した値。読めなくなった場合は既存のデータベースとの互換性が失われている
+        let event: RaceEvent = deserialize_from_string(include_str!("../testdata/golden/race_event.b64").trim()).unwrap();
+        assert_eq!(event, RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "開設７１周年記念トーキョー・ベイ・カップ".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-09-10".to_string(),
+            duration_days: 7,
+            notes: None,
+        });
+
+        let encoded = include_str!("../testdata/golden/monthly_schedule.b64").trim();
+        let schedule: MonthlySchedule = deserialize_from_string(encoded).unwrap();
+        assert_eq!(schedule.year_month, "2025-09");
+        assert_eq!(schedule.events.len(), 3);
+        assert_eq!(schedule.events[1], event);
+        assert_eq!(schedule.events[2].event_name, "第５３回高松宮記念特別競走");
+
+        // 形式バージョン 2 の値 (現在の書き込み形式)
+        let encoded_v2 = include_str!("../testdata/golden/monthly_schedule_v2.b64").trim();
+        assert_eq!(deserialize_from_string::<MonthlySchedule>(encoded_v2).unwrap(), schedule);
+        let event_v2: RaceEvent = deserialize_from_string(include_str!("../testdata/golden/race_event_v2.b64").trim()).unwrap();
+        assert_eq!(event_v2, RaceEvent { notes: Some("優勝戦 9月16日 16:40".to_string()), ..event.clone() });
+
+        // 書き込み側も同じバイト列になる
+        assert_eq!(serialize_to_string(&schedule).unwrap(), encoded_v2);
+        assert_eq!(serialize_to_string(&event_v2).unwrap(), include_str!("../testdata/golden/race_event_v2.b64").trim());
+        assert_eq!(serialize(&1757640600000u64).unwrap(), 1757640600000u64.to_le_bytes());
+        assert_eq!(FORMAT_VERSION, 2);
+    }
+
+    #[test]
+    fn test_forward_compatible_race_event() {
+        let event = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "開設７１周年記念トーキョー・ベイ・カップ".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-09-10".to_string(),
+            duration_days: 7,
+            notes: None,
+        };
+
+        // 追加フィールドのない古い JSON と、未知のフィールドを含む新しい JSON のどちらも読める
+        let old_json = r#"{"venue_id":4,"venue_name":"平和島","event_name":"開設７１周年記念トーキョー・ベイ・カップ","grade":"G1","start_date":"2025-09-10","duration_days":7}"#;
+        assert_eq!(deserialize_from_string::<RaceEvent>(old_json).unwrap(), event);
+        let newer_json = r#"{"venue_id":4,"venue_name":"平和島","event_name":"開設７１周年記念トーキョー・ベイ・カップ","grade":"G1","start_date":"2025-09-10","duration_days":7,"notes":"初日中止","timetable_url":"https://example.com","broadcast":{"tv":true}}"#;
+        let read = deserialize_from_string::<RaceEvent>(newer_json).unwrap();
+        assert_eq!(read, RaceEvent { notes: Some("初日中止".to_string()), ..event.clone() });
+
+        // 未設定の追加フィールドは JSON に書かない (古いバージョンの出力と同じ)
+        assert_eq!(JsonCodec.encode(&event).unwrap(), old_json);
+        let schedule = MonthlySchedule { year_month: "2025-09".to_string(), events: vec![read.clone(), event.clone()] };
+        for codec in [Codec::Json, Codec::BincodeBase64] {
+            let encoded = codec.encode(&schedule).unwrap();
+            assert_eq!(codec.decode::<MonthlySchedule>(&encoded).unwrap(), schedule, "{:?}", codec);
+        }
+
+        // bincode は旧レイアウト (接頭辞なし) と現在のレイアウトの両方を読む
+        use base64::{Engine as _, engine::general_purpose};
+        let old_bincode = include_str!("../testdata/golden/race_event.b64").trim();
+        assert!(!old_bincode.starts_with(BINCODE_V2_PREFIX));
+        assert_eq!(deserialize_from_string::<RaceEvent>(old_bincode).unwrap(), event);
+        let current = serialize_to_string(&read).unwrap();
+        assert!(current.starts_with(BINCODE_V2_PREFIX));
+        assert_eq!(deserialize_from_string::<RaceEvent>(&current).unwrap(), read);
+        // 旧レイアウトの値の後ろに別の値が続いても、追加フィールドとして読み込まない
+        let mut binary = general_purpose::STANDARD.decode(old_bincode).unwrap();
+        binary.extend_from_slice(&[1, 4, 0, 0, 0, 0, 0, 0, 0]);
+        let legacy: RaceEvent = deserialize_from_string(&general_purpose::STANDARD.encode(binary)).unwrap();
+        assert_eq!(legacy.notes, None);
+    }
+
+    #[cfg(feature = "rmp")]
+    #[test]
+    fn test_forward_compatible_msgpack() {
+        let mut event = RaceEvent {
+            venue_id: 4,
+            venue_name: "平和島".to_string(),
+            event_name: "トーキョー・ベイ・カップ".to_string(),
+            grade: Grade::G1,
+            start_date: "2025-09-10".to_string(),
+            duration_days: 7,
+            notes: None,
+        };
+        // 未設定の追加フィールドはマップに含めない
+        let without_notes = serialize_msgpack(&event).unwrap();
+        assert_eq!(without_notes[0], 0x86);
+        assert_eq!(deserialize_msgpack::<RaceEvent>(&without_notes).unwrap(), event);
+
+        event.notes = Some("ナイター".to_string());
+        let with_notes = serialize_msgpack(&event).unwrap();
+        assert_eq!(with_notes[0], 0x87);
+        assert_eq!(deserialize_msgpack::<RaceEvent>(&with_notes).unwrap(), event);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compression() {
+        use base64::{Engine as _, engine::general_purpose};
+        let payload: Vec<String> = (0..500).map(|i| format!("racer-{:03}", i % 10)).collect();
+        let compression = Compression::default();
+        let plain = serialize_to_string(&payload).unwrap();
+        let compressed = serialize_to_string_compressed(&payload, &compression).unwrap();
+        assert!(plain.len() > 10_000);
+        assert!(compressed.starts_with(COMPRESSED_PREFIX));
+        assert!(compressed.len() * 5 < plain.len());
+        assert_eq!(deserialize_from_string::<Vec<String>>(&compressed).unwrap(), payload);
+
+        // Base64 にする前の bincode を圧縮している
+        let envelope = general_purpose::STANDARD.decode(&compressed[COMPRESSED_PREFIX.len()..]).unwrap();
+        assert_eq!(envelope[..2], [CompressionAlgorithm::Deflate.marker(), PAYLOAD_BINCODE]);
+        assert_eq!(crate::compress::decompress(&envelope[6..], serialize(&payload).unwrap().len()).unwrap(), serialize(&payload).unwrap());
+
+        // 閾値以下の値と既存の非圧縮の値はそのまま
+        assert_eq!(serialize_to_string_compressed(&"race1", &compression).unwrap(), serialize_to_string(&"race1").unwrap());
+        assert_eq!(deserialize_from_string::<Vec<String>>(&plain).unwrap(), payload);
+        // 圧縮しても短くならない値もそのまま
+        let mut state = 1u32;
+        let noise: Vec<u8> = (0..4000)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                (state >> 16) as u8
+            })
+            .collect();
+        assert_eq!(serialize_to_string_compressed(&noise, &compression).unwrap(), serialize_to_string(&noise).unwrap());
+
+        // JSON も圧縮できる
+        let json = Codec::Json.encode_compressed(&payload, &compression).unwrap();
+        assert!(json.starts_with(COMPRESSED_PREFIX));
+        assert_eq!(deserialize_from_string::<Vec<String>>(&json).unwrap(), payload);
+        assert_eq!(Codec::Json.encode_compressed(&"race1", &compression).unwrap(), "\"race1\"");
+        #[cfg(feature = "rmp")]
+        {
+            let msgpack = Codec::MessagePack.encode_compressed(&payload, &compression).unwrap();
+            assert!(msgpack.starts_with(COMPRESSED_PREFIX));
+            assert_eq!(deserialize_from_string::<Vec<String>>(&msgpack).unwrap(), payload);
+        }
+
+        assert!(deserialize_from_string::<Vec<String>>("~AQ==").is_err());
+        assert!(deserialize_from_string::<Vec<String>>(&compressed[..compressed.len() - 4]).is_err());
+        let unknown_kind = compress_payload(9, &serialize(&payload).unwrap(), &compression).unwrap();
+        assert!(deserialize_from_string::<Vec<String>>(&unknown_kind).is_err());
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn test_compressed_value_requires_feature() {
+        let error = deserialize_from_string::<u32>("~AQAEAAAA").unwrap_err();
+        assert!(error.to_string().contains("compression feature"), "{}", error);
+    }
+
+    fn japanese_schedule() -> MonthlySchedule {
+        MonthlySchedule {
+            year_month: "2025-09".to_string(),
+            events: vec![RaceEvent {
+                venue_id: 4,
+                venue_name: "平和島".to_string(),
+                event_name: "開設７１周年記念トーキョー・ベイ・カップ".to_string(),
+                grade: Grade::G1,
+                start_date: "2025-09-10".to_string(),
+                duration_days: 7,
+                notes: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_codec_round_trip() {
+        let schedule = japanese_schedule();
+        for codec in [Codec::BincodeBase64, Codec::Json] {
+            let encoded = codec.encode(&schedule).unwrap();
+            let restored: MonthlySchedule = codec.decode(&encoded).unwrap();
+            assert_eq!(restored, schedule, "{:?}", codec);
+        }
+
+        // JSON は人が読める
+        let json = JsonCodec.encode(&schedule).unwrap();
+        assert!(json.starts_with('{'));
+        assert!(json.contains("開設７１周年記念トーキョー・ベイ・カップ"));
+        assert_eq!(BincodeBase64Codec.encode(&schedule).unwrap(), serialize_to_string(&schedule).unwrap());
+    }
+
+    #[test]
+    fn test_decode_auto_detects_format() {
+        let schedule = japanese_schedule();
+        let json = JsonCodec.encode(&schedule).unwrap();
+        let binary = BincodeBase64Codec.encode(&schedule).unwrap();
+        // どちらの方式でも両方の形式を読める
+        for codec in [Codec::BincodeBase64, Codec::Json] {
+            assert_eq!(codec.decode::<MonthlySchedule>(&json).unwrap(), schedule);
+            assert_eq!(codec.decode::<MonthlySchedule>(&binary).unwrap(), schedule);
+        }
+
+        // JSON のスカラー値
+        assert_eq!(deserialize_from_string::<String>(&JsonCodec.encode(&"race1").unwrap()).unwrap(), "race1");
+        assert_eq!(deserialize_from_string::<u64>(&JsonCodec.encode(&5u64).unwrap()).unwrap(), 5);
+        assert_eq!(deserialize_from_string::<u64>(&BincodeBase64Codec.encode(&5u64).unwrap()).unwrap(), 5);
+        assert!(deserialize_from_string::<Vec<u32>>("[1, 2").is_err());
+    }
 }
\ No newline at end of file
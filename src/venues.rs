@@ -0,0 +1,122 @@
+//! 会場マスタモジュール
+//!
+//! 全国24場の会場ID (1=桐生 … 24=大村) と正式名称の対応表、および表記ゆれのある会場名の解決を提供する
+
+/// (会場ID, 正式名称, 都道府県, 水質) の一覧 (会場ID順)
+pub(crate) const STANDARD_VENUES: [(u32, &str, &str, &str); 24] = [
+    (1, "桐生", "群馬県", "淡水"),
+    (2, "戸田", "埼玉県", "淡水"),
+    (3, "江戸川", "東京都", "汽水"),
+    (4, "平和島", "東京都", "海水"),
+    (5, "多摩川", "東京都", "淡水"),
+    (6, "浜名湖", "静岡県", "汽水"),
+    (7, "蒲郡", "愛知県", "汽水"),
+    (8, "常滑", "愛知県", "海水"),
+    (9, "津", "三重県", "汽水"),
+    (10, "三国", "福井県", "淡水"),
+    (11, "びわこ", "滋賀県", "淡水"),
+    (12, "住之江", "大阪府", "淡水"),
+    (13, "尼崎", "兵庫県", "淡水"),
+    (14, "鳴門", "徳島県", "海水"),
+    (15, "丸亀", "香川県", "海水"),
+    (16, "児島", "岡山県", "海水"),
+    (17, "宮島", "広島県", "海水"),
+    (18, "徳山", "山口県", "海水"),
+    (19, "下関", "山口県", "海水"),
+    (20, "若松", "福岡県", "海水"),
+    (21, "芦屋", "福岡県", "淡水"),
+    (22, "福岡", "福岡県", "汽水"),
+    (23, "唐津", "佐賀県", "淡水"),
+    (24, "大村", "長崎県", "海水"),
+];
+
+/// 正式名称以外で使われる会場名 (別表記・読み)
+const ALIASES: [(u32, &str); 10] = [
+    (2, "とだ"),
+    (7, "がまごおり"),
+    (8, "とこなめ"),
+    (11, "琵琶湖"),
+    (11, "ビワコ"),
+    (13, "あまがさき"),
+    (14, "なると"),
+    (16, "こじま"),
+    (21, "あしや"),
+    (23, "からつ"),
+];
+
+/// 会場名の前後に付くことがある語 ("ボートレース平和島"、"平和島競艇場" など)
+const PREFIXES: [&str; 3] = ["ボートレース", "BOAT RACE ", "BOATRACE"];
+const SUFFIXES: [&str; 3] = ["ボートレース場", "競艇場", "競艇"];
+
+/// 会場IDから正式名称を取得
+///
+/// # Arguments
+/// * `id` - 会場ID (1..=24)
+///
+/// # Returns
+/// 正式名称 (範囲外の ID は None)
+pub fn venue_name(id: u32) -> Option<&'static str> {
+    STANDARD_VENUES.iter().find(|venue| venue.0 == id).map(|venue| venue.1)
+}
+
+/// 会場名から会場IDを取得
+///
+/// 正式名称のほか、別表記 ("琵琶湖" など)・ひらがなの読み・"ボートレース" や "競艇場" が付いた名称も受け付ける。
+/// 前後の空白は会場名の誤りとして扱い、取り除かない
+///
+/// # Arguments
+/// * `name` - 会場名 (例: "平和島"、"ボートレースびわこ")
+///
+/// # Returns
+/// 会場ID (該当する会場がない場合は None)
+pub fn venue_id(name: &str) -> Option<u32> {
+    let mut name = name;
+    if let Some(stripped) = PREFIXES.iter().find_map(|prefix| name.strip_prefix(prefix)) {
+        name = stripped;
+    }
+    if let Some(stripped) = SUFFIXES.iter().find_map(|suffix| name.strip_suffix(suffix)) {
+        name = stripped;
+    }
+    STANDARD_VENUES
+        .iter()
+        .map(|venue| (venue.0, venue.1))
+        .chain(ALIASES)
+        .find(|(_, candidate)| *candidate == name)
+        .map(|(id, _)| id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_venue_table() {
+        let names = [
+            "桐生", "戸田", "江戸川", "平和島", "多摩川", "浜名湖", "蒲郡", "常滑", "津", "三国", "びわこ", "住之江",
+            "尼崎", "鳴門", "丸亀", "児島", "宮島", "徳山", "下関", "若松", "芦屋", "福岡", "唐津", "大村",
+        ];
+        for (index, name) in names.iter().enumerate() {
+            let id = index as u32 + 1;
+            assert_eq!(venue_name(id), Some(*name));
+            assert_eq!(venue_id(name), Some(id));
+        }
+        assert_eq!(venue_name(0), None);
+        assert_eq!(venue_name(25), None);
+    }
+
+    #[test]
+    fn test_venue_aliases() {
+        assert_eq!(venue_id("琵琶湖"), Some(11));
+        assert_eq!(venue_id("ビワコ"), Some(11));
+        assert_eq!(venue_id("ボートレースびわこ"), Some(11));
+        assert_eq!(venue_id("BOAT RACE 平和島"), Some(4));
+        assert_eq!(venue_id("住之江競艇場"), Some(12));
+        assert_eq!(venue_id("とこなめ"), Some(8));
+        assert_eq!(venue_id("ボートレース津"), Some(9));
+
+        assert_eq!(venue_id("平和島 "), None);
+        assert_eq!(venue_id("ボートレース"), None);
+        assert_eq!(venue_id(""), None);
+        assert_eq!(venue_id("東京"), None);
+    }
+}
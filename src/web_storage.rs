@@ -0,0 +1,219 @@
+//! ブラウザストレージモジュール (`wasm` フィーチャー)
+//!
+//! Web Storage API (localStorage / sessionStorage) 上の KeyValueStore を提供する。
+//! ストレージ操作は `WebStorage` トレイトで抽象化し、`web_sys::Storage` に実装している
+//! (テストではメモリ上の実装に差し替えられる)。
+//!
+//! ```ignore
+//! let store = norimaki_db::LocalStorageStore::local("norimaki:")?;
+//! let engine = norimaki_db::BoatRaceEngine::new(store);
+//! ```
+
+use wasm_bindgen::JsValue;
+
+use crate::{KeyValueStore, Result, StoreError};
+
+/// Web Storage API の操作 (メソッド名は `web_sys::Storage` に合わせている)
+pub trait WebStorage {
+    fn get_item(&self, key: &str) -> Result<Option<String>>;
+    fn set_item(&self, key: &str, value: &str) -> Result<()>;
+    fn remove_item(&self, key: &str) -> Result<()>;
+    /// index 番目のキー (範囲外は None)
+    fn key(&self, index: u32) -> Result<Option<String>>;
+    fn length(&self) -> Result<u32>;
+}
+
+/// ブラウザの Storage (容量超過の `QuotaExceededError` などは `StoreError::IoError` になる)
+impl WebStorage for web_sys::Storage {
+    fn get_item(&self, key: &str) -> Result<Option<String>> {
+        web_sys::Storage::get_item(self, key).map_err(js_error)
+    }
+
+    fn set_item(&self, key: &str, value: &str) -> Result<()> {
+        web_sys::Storage::set_item(self, key, value).map_err(js_error)
+    }
+
+    fn remove_item(&self, key: &str) -> Result<()> {
+        web_sys::Storage::remove_item(self, key).map_err(js_error)
+    }
+
+    fn key(&self, index: u32) -> Result<Option<String>> {
+        web_sys::Storage::key(self, index).map_err(js_error)
+    }
+
+    fn length(&self) -> Result<u32> {
+        web_sys::Storage::length(self).map_err(js_error)
+    }
+}
+
+/// JS の例外を StoreError に変換する
+fn js_error(error: JsValue) -> StoreError {
+    std::io::Error::other(format!("web storage error: {:?}", error)).into()
+}
+
+/// Web Storage に保存するストア
+///
+/// 同じオリジンの他のデータと混ざらないよう、すべてのキーにプレフィックスを付けて保存する。
+/// `clear` はこのプレフィックスのキーのみを削除する
+#[derive(Debug)]
+pub struct LocalStorageStore<S: WebStorage = web_sys::Storage> {
+    storage: S,
+    prefix: String,
+}
+
+impl<S: WebStorage> LocalStorageStore<S> {
+    /// # Arguments
+    /// * `storage` - 保存先のストレージ
+    /// * `prefix` - キーに付けるプレフィックス (例: "norimaki:")
+    pub fn new(storage: S, prefix: impl Into<String>) -> Self {
+        Self {
+            storage,
+            prefix: prefix.into(),
+        }
+    }
+
+    fn storage_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+impl LocalStorageStore {
+    /// ブラウザの localStorage に保存するストアを作成
+    ///
+    /// # Arguments
+    /// * `prefix` - キーに付けるプレフィックス (例: "norimaki:")
+    ///
+    /// # Returns
+    /// ストア。window がない環境 (Web Worker など) や、localStorage が無効な場合はエラー
+    pub fn local(prefix: impl Into<String>) -> Result<Self> {
+        let storage = window()?.local_storage().map_err(js_error)?;
+        Ok(Self::new(storage.ok_or_else(|| unavailable("localStorage"))?, prefix))
+    }
+
+    /// ブラウザの sessionStorage に保存するストアを作成 (タブを閉じると消える)
+    ///
+    /// # Arguments
+    /// * `prefix` - キーに付けるプレフィックス
+    pub fn session(prefix: impl Into<String>) -> Result<Self> {
+        let storage = window()?.session_storage().map_err(js_error)?;
+        Ok(Self::new(storage.ok_or_else(|| unavailable("sessionStorage"))?, prefix))
+    }
+}
+
+fn window() -> Result<web_sys::Window> {
+    web_sys::window().ok_or_else(|| unavailable("window"))
+}
+
+fn unavailable(what: &str) -> StoreError {
+    std::io::Error::new(std::io::ErrorKind::Unsupported, format!("{} is not available", what)).into()
+}
+
+impl<S: WebStorage> KeyValueStore for LocalStorageStore<S> {
+    fn put(&mut self, key: String, value: String) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        self.storage.set_item(&self.storage_key(&key), &value)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        self.storage.get_item(&self.storage_key(key))
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        if key.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        self.storage.remove_item(&self.storage_key(key))
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        for index in 0..self.storage.length()? {
+            if let Some(key) = self.storage.key(index)? {
+                if let Some(key) = key.strip_prefix(&self.prefix) {
+                    keys.push(key.to_string());
+                }
+            }
+        }
+        // Web Storage の列挙順は実装依存のため並べ替える
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        // 列挙中に削除するとインデックスがずれるため、先にキーを集める
+        for key in self.keys()? {
+            self.storage.remove_item(&self.storage_key(&key))?;
+        }
+        Ok(())
+    }
+
+    fn scan(&mut self, start: &str, end: &str) -> Result<Vec<(String, String)>> {
+        if start.is_empty() || end.is_empty() {
+            return Err(StoreError::InvalidKey);
+        }
+        let mut result = Vec::new();
+        for key in self.keys()? {
+            if key.as_str() >= start && key.as_str() < end {
+                if let Some(value) = self.get(&key)? {
+                    result.push((key, value));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoatRaceEngine, Grade, MonthlySchedule, RaceEvent};
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    include!("../testdata/sample.rs");
+
+    /// localStorage の代わりに使うメモリ上のストレージ
+    #[derive(Default)]
+    struct FakeStorage(RefCell<BTreeMap<String, String>>);
+
+    impl WebStorage for FakeStorage {
+        fn get_item(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.0.borrow().get(key).cloned())
+        }
+        fn set_item(&self, key: &str, value: &str) -> Result<()> {
+            self.0.borrow_mut().insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+        fn remove_item(&self, key: &str) -> Result<()> {
+            self.0.borrow_mut().remove(key);
+            Ok(())
+        }
+        fn key(&self, index: u32) -> Result<Option<String>> {
+            Ok(self.0.borrow().keys().nth(index as usize).cloned())
+        }
+        fn length(&self) -> Result<u32> {
+            Ok(self.0.borrow().len() as u32)
+        }
+    }
+
+    #[test]
+    fn test_local_storage_store_with_engine() {
+        let storage = FakeStorage::default();
+        storage.set_item("other_app", "keep").unwrap();
+
+        let mut engine = BoatRaceEngine::new(LocalStorageStore::new(storage, "norimaki:"));
+        engine.put_monthly_schedule(&sample_data()).unwrap();
+        assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), sample_data().events.len());
+
+        let mut store = engine.into_store();
+        assert!(store.keys().unwrap().iter().all(|key| !key.starts_with("norimaki:")));
+        store.clear().unwrap();
+        assert!(store.keys().unwrap().is_empty());
+        assert_eq!(store.storage.get_item("other_app").unwrap(), Some("keep".to_string()));
+    }
+}
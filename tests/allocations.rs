@@ -0,0 +1,93 @@
+//! 書き込み経路のメモリ確保回数のテスト
+//!
+//! 確保回数を数えるアロケータを差し込み、値のエンコードと月別スケジュールの取り込みで
+//! 余分な確保 (二重のシリアライズや中間の文字列、存在確認のための値の複製) が増えていないことを確かめる
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use norimaki_db::value::serialize_to_string;
+use norimaki_db::{BoatRaceEngine, Grade, MemoryStore, MonthlySchedule, RaceEvent};
+
+/// 計測中のスレッドでの確保 (alloc / realloc) だけを数えるアロケータ
+struct CountingAllocator;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn record_allocation() {
+    // スレッド終了後の確保などで thread_local にアクセスできない場合は数えない
+    let _ = COUNTING.try_with(|counting| {
+        if counting.get() {
+            let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation();
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_allocation();
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// クロージャの実行中にこのスレッドで行われた確保の回数
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    ALLOCATIONS.with(|allocations| allocations.set(0));
+    COUNTING.with(|counting| counting.set(true));
+    let result = f();
+    COUNTING.with(|counting| counting.set(false));
+    (result, ALLOCATIONS.with(Cell::get))
+}
+
+/// 大会IDがすべて異なる1日開催のイベントを並べた月別スケジュール
+fn large_schedule(events: usize) -> MonthlySchedule {
+    MonthlySchedule {
+        year_month: "2025-09".to_string(),
+        events: (0..events)
+            .map(|i| RaceEvent {
+                venue_id: (i % 24) as u32 + 1,
+                venue_name: format!("Venue {}", i % 24 + 1),
+                event_name: format!("Synthetic Cup {}", i),
+                grade: Grade::Ippan,
+                start_date: format!("2025-09-{:02}", i % 28 + 1),
+                duration_days: 1,
+                notes: None,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_serialize_to_string_allocations() {
+    let event = &large_schedule(1).events[0];
+    let (encoded, allocations) = count_allocations(|| serialize_to_string(event).unwrap());
+    assert!(encoded.starts_with("b2:"));
+    // bincode のバイト列と、接頭辞付きの Base64 文字列 (長さを先に計算して一度で確保) の2回
+    assert_eq!(allocations, 2);
+}
+
+#[test]
+fn test_schedule_import_allocations() {
+    let schedule = large_schedule(1000);
+    let mut engine = BoatRaceEngine::new(MemoryStore::new());
+    let ((), allocations) = count_allocations(|| engine.put_monthly_schedule(&schedule).unwrap());
+    assert_eq!(engine.get_monthly_schedule(202509).unwrap().events.len(), 1000);
+    // 書き込み経路を見直す前は約 34,000 回 (1イベントあたり約34回) だった
+    assert!(allocations < 25_000, "{} allocations", allocations);
+}
+
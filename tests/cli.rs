@@ -0,0 +1,106 @@
+//! norimaki-cli の結合テスト
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use norimaki_db::{BoatRaceEngine, FileStore, Grade, MonthlySchedule, RaceEvent};
+
+include!("../testdata/sample.rs");
+
+/// テストごとに別ファイルのフィクスチャDBを作る
+fn fixture(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("norimaki_cli_{}_{}.json", name, std::process::id()));
+    std::fs::remove_file(&path).ok();
+    let mut engine = BoatRaceEngine::new(FileStore::new(&path).unwrap());
+    engine.put_monthly_schedule(&sample_data()).unwrap();
+    let tournament_id = norimaki_db::generate_tournament_id("平和島", "開設７１周年記念トーキョー・ベイ・カップ");
+    engine.put_race_data(&tournament_id, 1757640600000, &"race1").unwrap();
+    path
+}
+
+fn cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_norimaki-cli")).args(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_read_commands() {
+    let db = fixture("read");
+    let db = db.to_str().unwrap();
+
+    let output = cli(&["stats", db]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("monthly entries"));
+
+    let output = cli(&["months", db]);
+    assert_eq!(stdout(&output), "202509\n");
+
+    let output = cli(&["show", db, "2025-09"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.starts_with("start_date"));
+    assert!(text.contains("開設７１周年記念トーキョー・ベイ・カップ"));
+    assert_eq!(text.lines().count(), 2 + sample_data().events.len());
+
+    let output = cli(&["year", db, "2025"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert_eq!(text.lines().count(), 13);
+    assert!(text.lines().any(|line| line.starts_with("2025-09       3") && line.ends_with("1,4,12")));
+
+    let output = cli(&["tournaments", db]);
+    assert!(stdout(&output).lines().any(|line| line.contains("202509") && line.contains("  1  ")));
+
+    let tournament_id = norimaki_db::generate_tournament_id("平和島", "開設７１周年記念トーキョー・ベイ・カップ");
+    let output = cli(&["races", db, &tournament_id]);
+    assert!(stdout(&output).contains("1757640600000  2025-09-12T01:30:00+00:00"));
+
+    std::fs::remove_file(db).ok();
+}
+
+#[test]
+fn test_write_commands() {
+    let db = fixture("write");
+    let db = db.to_str().unwrap();
+    let csv = std::env::temp_dir().join(format!("norimaki_cli_{}.csv", std::process::id()));
+
+    let output = cli(&["export-csv", db, "202509", csv.to_str().unwrap()]);
+    assert!(output.status.success());
+    let text = std::fs::read_to_string(&csv).unwrap();
+    assert_eq!(text.lines().count(), 1 + sample_data().events.len());
+
+    let output = cli(&["delete-month", db, "202509"]);
+    assert!(output.status.success());
+    let engine = BoatRaceEngine::new(FileStore::new(db).unwrap());
+    assert!(engine.get_detailed_statistics().unwrap().months.is_empty());
+
+    std::fs::remove_file(db).ok();
+    std::fs::remove_file(csv).ok();
+}
+
+#[test]
+fn test_argument_errors() {
+    let db = fixture("errors");
+    let db = db.to_str().unwrap();
+
+    for year_month in ["2025-13", "2025", "abcdef", "2025-9"] {
+        let output = cli(&["show", db, year_month]);
+        assert_eq!(output.status.code(), Some(2), "{}", year_month);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("invalid year_month"));
+    }
+    assert_eq!(cli(&["show", db]).status.code(), Some(2));
+    assert_eq!(cli(&["year", db, "twenty"]).status.code(), Some(2));
+    assert_eq!(cli(&["frobnicate", db]).status.code(), Some(2));
+    assert_eq!(cli(&[]).status.code(), Some(2));
+
+    let missing = std::env::temp_dir().join("norimaki_cli_missing.json");
+    let output = cli(&["stats", missing.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("does not exist"));
+    assert!(!missing.exists());
+
+    std::fs::remove_file(db).ok();
+}